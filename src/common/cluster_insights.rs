@@ -1,30 +1,75 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use futures::StreamExt;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use once_cell::sync::{Lazy, OnceCell};
+use kube::api::{ApiResource, DynamicObject, GroupVersionKind, ListParams};
 use kube::{Api, Client};
-use k8s_openapi::api::core::v1::{Node, Pod, Namespace};
+use k8s_openapi::api::apps::v1::{Deployment, ReplicaSet};
+use k8s_openapi::api::batch::v1::{CronJob, Job};
+use k8s_openapi::api::core::v1::{Container, Event, Node, NodeSelectorRequirement, NodeSelectorTerm, Pod, PodAffinityTerm, PodSpec, Namespace, ResourceQuota, ResourceRequirements, Taint, Toleration};
+use k8s_openapi::api::scheduling::v1::PriorityClass;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, LabelSelectorRequirement, ObjectMeta, OwnerReference};
 use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 
+use super::config::{self, ServerConfig};
+use super::diagnostics::{self, Diagnostic};
+use super::messages::{self, ExplanationDetail, Lang};
 use super::metrics::{increment_requests, increment_errors, RequestTimer};
+use super::progress::Progress;
+use super::watch::{self, WatchStores};
 
 use rmcp::{
-    ServerHandler,
+    RoleServer, ServerHandler,
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
-    model::{ServerCapabilities, ServerInfo, CallToolResult, Content},
+    handler::server::tool::ToolCallContext,
+    model::{ServerCapabilities, ServerInfo, CallToolRequestParam, CallToolResult, Content, ListToolsResult, PaginatedRequestParam},
+    service::RequestContext,
     ErrorData as McpError,
-    schemars, tool, tool_handler, tool_router,
+    schemars, tool, tool_router,
 };
 
 // =================== DATA STRUCTURES ===================
 
+/// Cache control shared by every tool that reads the cluster, flattened into its parameters
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct CacheParams {
+    #[serde(default)]
+    #[schemars(description = "Bypass the list cache and re-query the cluster (slower; use right after changes)")]
+    pub fresh: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct ClusterCapacityParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "What pods are counted by: Requests (default), Limits or Usage (live usage from metrics-server)")]
+    pub basis: ResourceBasis,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+    #[serde(default)]
+    #[schemars(description = "Also report the change in allocation since the previous get_cluster_capacity call of this session (default: false)")]
+    pub include_delta: bool,
+    #[serde(default)]
+    #[schemars(description = "Compute capacity as if the pods of these namespaces did not exist, e.g. to see what evacuating them frees up")]
+    pub exclude_namespaces: Option<Vec<String>>,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
 pub struct ClusterCapacityResponse {
     #[schemars(description = "Total CPU in cores")]
     pub total_cpu_cores: f64,
     #[schemars(description = "Total memory in GB")]
     pub total_memory_gb: f64,
-    #[schemars(description = "Allocated CPU (requests) in cores")]
+    #[schemars(description = "Allocated CPU (per basis, of pods bound to a node) in cores")]
     pub allocated_cpu_cores: f64,
-    #[schemars(description = "Allocated memory (requests) in GB")]
+    #[schemars(description = "Allocated memory (per basis, of pods bound to a node) in GB")]
     pub allocated_memory_gb: f64,
     #[schemars(description = "Available CPU in cores")]
     pub available_cpu_cores: f64,
@@ -32,22 +77,189 @@ pub struct ClusterCapacityResponse {
     pub available_memory_gb: f64,
     #[schemars(description = "Number of nodes")]
     pub node_count: usize,
+    #[serde(default)]
+    #[schemars(description = "CPU requests of pending pods not yet bound to a node, in cores (incoming demand, not allocated)")]
+    pub pending_cpu_cores: f64,
+    #[serde(default)]
+    #[schemars(description = "Memory requests of pending pods not yet bound to a node, in GB (incoming demand, not allocated)")]
+    pub pending_memory_gb: f64,
+    #[serde(default)]
+    #[schemars(description = "Number of pending pods not yet bound to a node")]
+    pub pending_pod_count: usize,
+    #[serde(default)]
+    #[schemars(description = "Unbound pods held back by spec.schedulingGates, left out of the pending demand (see find_gated_pods)")]
+    pub gated_pod_count: usize,
+    #[serde(default)]
+    #[schemars(description = "What pods were counted by: Requests, Limits or Usage")]
+    pub basis: ResourceBasis,
     #[schemars(description = "Explanation of capacity calculation")]
     pub explanation: String,
+    #[serde(default)]
+    #[schemars(description = "Whether the node or pod list came from the TTL list cache instead of the API server")]
+    pub from_cache: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Age in seconds of the oldest cached list used (only when from_cache)")]
+    pub cache_age_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Allocated CPU change in cores since the previous call of this session by the same basis (only with include_delta)")]
+    pub delta_allocated_cpu_cores: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Allocated memory change in GB since the previous call of this session by the same basis (only with include_delta)")]
+    pub delta_allocated_memory_gb: Option<f64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+impl ClusterCapacityResponse {
+    /// Reduce the available capacity by the requests of the Pending pods, which will take it as soon as they schedule
+    fn subtract_pending_demand(&mut self) {
+        self.available_cpu_cores -= self.pending_cpu_cores;
+        self.available_memory_gb -= self.pending_memory_gb;
+    }
+
+    /// Cap the available capacity at `target_percent` of the total: what remains before allocation would exceed the target
+    fn cap_utilization(&mut self, target_percent: f64) {
+        let fraction = target_percent / 100.0;
+        self.available_cpu_cores = self.total_cpu_cores * fraction - self.allocated_cpu_cores;
+        self.available_memory_gb = self.total_memory_gb * fraction - self.allocated_memory_gb;
+    }
+
+    /// Count the capacity held by lower-priority pods as available, since a request that may preempt can take it
+    fn reclaim_preemptible(&mut self, preemption: &PreemptibleCapacity) {
+        self.available_cpu_cores += preemption.reclaimable_cpu_cores;
+        self.available_memory_gb += preemption.reclaimable_memory_gb;
+    }
+}
+
+/// What a pod is taken to hold on its node when resources are aggregated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub enum ResourceBasis {
+    /// Effective requests (init containers, native sidecars and pod overhead included), as the scheduler counts them
+    #[default]
+    #[serde(alias = "requests")]
+    Requests,
+    /// Limits (the request where a container sets no limit), a conservative no-overcommit view
+    #[serde(alias = "limits")]
+    Limits,
+    /// Live usage from metrics-server; pods without metrics (e.g. Pending) count as zero
+    #[serde(alias = "usage")]
+    Usage,
+}
+
+impl ResourceBasis {
+    /// CPU and memory a pod holds under this basis. `usage` is the live usage the Usage basis
+    /// reads from; it is ignored by the other bases.
+    fn pod_amounts(self, pod: &Pod, usage: Option<&PodUsage>) -> Resources {
+        match self {
+            ResourceBasis::Requests => pod_effective_request_resources(pod),
+            ResourceBasis::Limits => pod_limits_or_requests(pod),
+            ResourceBasis::Usage => {
+                let key = (
+                    pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                    pod.metadata.name.clone().unwrap_or_default(),
+                );
+                let (cpu_cores, memory_gb) = usage.and_then(|usage| usage.get(&key)).copied().unwrap_or_default();
+                Resources::from_cores_and_gb(cpu_cores, memory_gb)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
 pub struct CheckResourceFitParams {
     #[schemars(description = "Required CPU in cores")]
     pub cpu_cores: f64,
     #[schemars(description = "Required memory in GB")]
     pub memory_gb: f64,
+    #[serde(default)]
+    #[schemars(description = "Allocatable CPU in cores of a representative node for scale-up math (default: median of existing nodes)")]
+    pub node_cpu_cores: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Allocatable memory in GB of a representative node for scale-up math (default: median of existing nodes)")]
+    pub node_memory_gb: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Check whether the request fits after the autoscaler grows the node groups (default: false)")]
+    pub consider_autoscaling: bool,
+    #[serde(default)]
+    #[schemars(description = "Node group max-size config, required with consider_autoscaling (Cluster Autoscaler node groups or Karpenter provisioners)")]
+    pub node_groups: Option<Vec<NodeGroupLimit>>,
+    #[serde(default)]
+    #[schemars(description = "Cluster totals to check against instead of the live cluster (no API calls; for planning without a cluster)")]
+    pub cluster_override: Option<ClusterTotals>,
+    #[serde(default)]
+    #[schemars(description = "Other resources to check, by name and quantity (e.g. {\"ephemeral-storage\": \"20Gi\", \"nvidia.com/gpu\": \"1\"})")]
+    pub extra_resources: Option<BTreeMap<String, String>>,
+    #[serde(default)]
+    #[schemars(description = "What existing pods hold: Requests (default), Limits for a conservative answer without overcommit, or Usage (live usage from metrics-server)")]
+    pub basis: ResourceBasis,
+    #[serde(default)]
+    #[schemars(description = "Subtract the requests of Pending pods waiting for a node from the available capacity before deciding (default: false)")]
+    pub subtract_pending_demand: bool,
+    #[serde(default)]
+    #[schemars(description = "PriorityClass the request runs at: capacity held by lower-priority pods counts as available, as the scheduler can preempt them")]
+    pub priority_class: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Check as if the pods of these namespaces did not exist, e.g. whether the request fits once they are evacuated")]
+    pub exclude_namespaces: Option<Vec<String>>,
+    #[serde(default)]
+    #[schemars(description = "Allocation policy cap in percent of the total: available is total * target% - allocated (default: 100)")]
+    pub target_utilization_percent: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct ClusterTotals {
+    #[schemars(description = "Total CPU capacity in cores")]
+    pub total_cpu_cores: f64,
+    #[schemars(description = "Total memory capacity in GB")]
+    pub total_memory_gb: f64,
+    #[schemars(description = "Allocated CPU (requests) in cores")]
+    pub allocated_cpu_cores: f64,
+    #[schemars(description = "Allocated memory (requests) in GB")]
+    pub allocated_memory_gb: f64,
+    #[serde(default)]
+    #[schemars(description = "Number of nodes (informational, default: 0)")]
+    pub node_count: usize,
+}
+
+impl ClusterTotals {
+    /// Capacity as computed from a live cluster with these totals
+    fn to_capacity(&self) -> ClusterCapacityResponse {
+        ClusterCapacityResponse {
+            total_cpu_cores: self.total_cpu_cores,
+            total_memory_gb: self.total_memory_gb,
+            allocated_cpu_cores: self.allocated_cpu_cores,
+            allocated_memory_gb: self.allocated_memory_gb,
+            available_cpu_cores: self.total_cpu_cores - self.allocated_cpu_cores,
+            available_memory_gb: self.total_memory_gb - self.allocated_memory_gb,
+            node_count: self.node_count,
+            pending_cpu_cores: 0.0,
+            pending_memory_gb: 0.0,
+            pending_pod_count: 0,
+            gated_pod_count: 0,
+            basis: ResourceBasis::Requests,
+            explanation: String::new(),
+            from_cache: false,
+            cache_age_seconds: None,
+            delta_allocated_cpu_cores: None,
+            delta_allocated_memory_gb: None,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
 pub struct CheckResourceFitResponse {
     #[schemars(description = "Whether resources fit in cluster")]
     pub fits: bool,
+    #[schemars(description = "Whether the requested CPU fits in the available CPU")]
+    pub cpu_fits: bool,
+    #[schemars(description = "Whether the requested memory fits in the available memory")]
+    pub memory_fits: bool,
     #[schemars(description = "Available CPU in cores")]
     pub available_cpu_cores: f64,
     #[schemars(description = "Available memory in GB")]
@@ -56,8 +268,91 @@ pub struct CheckResourceFitResponse {
     pub cpu_utilization_percent: f64,
     #[schemars(description = "Memory utilization percentage")]
     pub memory_utilization_percent: f64,
+    #[serde(default)]
+    #[schemars(description = "What existing pods were counted by: Requests, Limits or Usage")]
+    pub basis: ResourceBasis,
     #[schemars(description = "Explanation of fit check")]
     pub explanation: String,
+    #[schemars(description = "Scale-up recommendation when resources do not fit")]
+    pub scale_up: Option<ScaleUpRecommendation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Whether the request fits once node groups scale up (only with consider_autoscaling when it does not fit now)")]
+    pub fits_after_scaleup: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Number of nodes the autoscaler must add for the request to fit")]
+    pub autoscale_nodes_needed: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Fit of each requested extra resource (only with extra_resources)")]
+    pub extra_resources: Option<Vec<ExtraResourceFit>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Capacity held by lower-priority pods (only with priority_class)")]
+    pub preemption: Option<PreemptibleCapacity>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct PreemptibleCapacity {
+    #[schemars(description = "PriorityClass the request runs at")]
+    pub priority_class: String,
+    #[schemars(description = "Priority value of the PriorityClass")]
+    pub priority: i32,
+    #[schemars(description = "Whether the PriorityClass may preempt (its preemptionPolicy is not Never); if not, nothing is reclaimed")]
+    pub preempts: bool,
+    #[schemars(description = "CPU in cores held by lower-priority pods")]
+    pub reclaimable_cpu_cores: f64,
+    #[schemars(description = "Memory in GB held by lower-priority pods")]
+    pub reclaimable_memory_gb: f64,
+    #[schemars(description = "Number of running pods with a lower priority")]
+    pub preemptible_pod_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct ExtraResourceFit {
+    #[schemars(description = "Resource name")]
+    pub resource: String,
+    #[schemars(description = "Requested amount in the resource's base unit (bytes for storage, devices for device plugins)")]
+    pub requested: f64,
+    #[schemars(description = "Summed node allocatable minus pod requests, in the base unit")]
+    pub available: f64,
+    #[schemars(description = "Whether the requested amount fits in the available amount")]
+    pub fits: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct ScaleUpRecommendation {
+    #[schemars(description = "CPU shortfall in cores")]
+    pub cpu_shortfall_cores: f64,
+    #[schemars(description = "Memory shortfall in GB")]
+    pub memory_shortfall_gb: f64,
+    #[schemars(description = "Allocatable CPU in cores of the node size used for the recommendation")]
+    pub node_cpu_cores: f64,
+    #[schemars(description = "Allocatable memory in GB of the node size used for the recommendation")]
+    pub node_memory_gb: f64,
+    #[schemars(description = "Where the node size comes from (parameter or median of existing nodes)")]
+    pub node_size_source: String,
+    #[schemars(description = "CPU in cores reserved on every node by DaemonSet pods")]
+    pub daemonset_cpu_overhead_cores: f64,
+    #[schemars(description = "Memory in GB reserved on every node by DaemonSet pods")]
+    pub daemonset_memory_overhead_gb: f64,
+    #[schemars(description = "Number of nodes to add (absent when a node cannot cover its DaemonSet overhead)")]
+    pub nodes_needed: Option<u32>,
+    #[schemars(description = "Explanation of the scale-up recommendation")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodeGroupLimit {
+    #[schemars(description = "Node group or provisioner name")]
+    pub name: String,
+    #[schemars(description = "Current number of nodes in the group")]
+    pub current_nodes: u32,
+    #[schemars(description = "Maximum number of nodes the autoscaler may scale the group to")]
+    pub max_nodes: u32,
+    #[serde(default)]
+    #[schemars(description = "Allocatable CPU in cores of the group's nodes (default: node size of the scale-up recommendation)")]
+    pub node_cpu_cores: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Allocatable memory in GB of the group's nodes (default: node size of the scale-up recommendation)")]
+    pub node_memory_gb: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
@@ -68,6 +363,10 @@ pub struct NodeInfo {
     pub total_cpu_cores: f64,
     #[schemars(description = "Total memory in GB")]
     pub total_memory_gb: f64,
+    #[schemars(description = "Allocatable CPU in cores (capacity minus system reservations)")]
+    pub allocatable_cpu_cores: f64,
+    #[schemars(description = "Allocatable memory in GB (capacity minus system reservations)")]
+    pub allocatable_memory_gb: f64,
     #[schemars(description = "Allocated CPU (requests) in cores")]
     pub allocated_cpu_cores: f64,
     #[schemars(description = "Allocated memory (requests) in GB")]
@@ -80,12 +379,174 @@ pub struct NodeInfo {
     pub pod_count: usize,
 }
 
+/// Ordering used by get_node_breakdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub enum NodeSortBy {
+    /// Node name, ascending
+    #[default]
+    #[serde(alias = "name")]
+    Name,
+    /// Available CPU, ascending (fullest nodes first)
+    #[serde(alias = "least_available_cpu")]
+    LeastAvailableCpu,
+    /// Available memory, ascending (fullest nodes first)
+    #[serde(alias = "least_available_memory")]
+    LeastAvailableMemory,
+    /// Pod count, descending
+    #[serde(alias = "most_pods")]
+    MostPods,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct NodeBreakdownParams {
+    #[serde(default)]
+    #[schemars(description = "Order of the nodes: Name (default), LeastAvailableCpu, LeastAvailableMemory or MostPods")]
+    pub sort_by: NodeSortBy,
+    #[serde(default)]
+    #[schemars(description = "Maximum number of nodes to return (default: all)")]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    #[schemars(description = "Number of sorted nodes to skip, for paging (default: 0)")]
+    pub offset: Option<usize>,
+    #[serde(default)]
+    #[schemars(description = "Opaque next_cursor of the previous page: continue after its last node, stable as nodes come and go (not with offset)")]
+    pub cursor: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "What pods are counted by: Requests (default), Limits or Usage (live usage from metrics-server)")]
+    pub basis: ResourceBasis,
+    #[serde(default)]
+    #[schemars(description = "Collapse nodes with the same instance type, allocatable and utilization band into groups instead of listing them (default: false; not with limit/offset)")]
+    pub group_identical: bool,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+/// Position in a get_node_breakdown listing: the sort order and the sort key and name of the last
+/// node returned. Paging resumes after that position rather than at a count, so nodes added or
+/// removed before it neither repeat nor skip nodes. The sort key is kept as its bit pattern
+/// (f64::to_bits), so it survives the JSON round trip exactly, NaN and -0.0 included.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct NodeCursor {
+    sort_by: NodeSortBy,
+    key_bits: u64,
+    name: String,
+}
+
+impl NodeCursor {
+    /// Cursor after `info` in a listing sorted by `sort_by`
+    fn after(info: &NodeInfo, sort_by: NodeSortBy) -> Self {
+        NodeCursor { sort_by, key_bits: node_sort_key(info, sort_by).to_bits(), name: info.name.clone() }
+    }
+
+    /// Sort key of the node the cursor points after
+    fn key(&self) -> f64 {
+        f64::from_bits(self.key_bits)
+    }
+
+    /// Opaque form handed to clients: the JSON, hex-encoded
+    fn encode(&self) -> String {
+        serde_json::to_vec(self).unwrap_or_default().iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn decode(cursor: &str) -> Result<Self, String> {
+        let bytes = (0..cursor.len()).step_by(2)
+            .map(|i| cursor.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| "Invalid cursor".to_string())?;
+        serde_json::from_slice(&bytes).map_err(|_| "Invalid cursor".to_string())
+    }
+}
+
+/// Sort key of a node under `sort_by`; nodes are ordered by it, then by name
+fn node_sort_key(info: &NodeInfo, sort_by: NodeSortBy) -> f64 {
+    match sort_by {
+        NodeSortBy::Name => 0.0,
+        NodeSortBy::LeastAvailableCpu => info.available_cpu_cores,
+        NodeSortBy::LeastAvailableMemory => info.available_memory_gb,
+        NodeSortBy::MostPods => -(info.pod_count as f64),
+    }
+}
+
+/// Sort node infos by `sort_by`, ties by name
+fn sort_node_infos(node_infos: &mut [NodeInfo], sort_by: NodeSortBy) {
+    node_infos.sort_by(|a, b| node_sort_key(a, sort_by).total_cmp(&node_sort_key(b, sort_by)).then_with(|| a.name.cmp(&b.name)));
+}
+
+/// Offset in `node_infos` at which the page after `cursor` starts; sorts the node infos
+fn cursor_offset(node_infos: &mut [NodeInfo], sort_by: NodeSortBy, cursor: &str) -> Result<usize, String> {
+    let cursor = NodeCursor::decode(cursor)?;
+    if cursor.sort_by != sort_by {
+        return Err(format!("The cursor was issued for sort_by {:?}; pass the same sort_by to continue", cursor.sort_by));
+    }
+
+    sort_node_infos(node_infos, sort_by);
+    Ok(node_infos.partition_point(|info| match node_sort_key(info, sort_by).total_cmp(&cursor.key()) {
+        std::cmp::Ordering::Less => true,
+        std::cmp::Ordering::Equal => info.name <= cursor.name,
+        std::cmp::Ordering::Greater => false,
+    }))
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, schemars::JsonSchema)]
+pub struct AvailabilityRange {
+    #[schemars(description = "Least available on a node of the group")]
+    pub min: f64,
+    #[schemars(description = "Most available on a node of the group")]
+    pub max: f64,
+    #[schemars(description = "Average available per node of the group")]
+    pub avg: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodeGroup {
+    #[schemars(description = "Instance type label of the nodes (node.kubernetes.io/instance-type), 'unknown' without one")]
+    pub instance_type: String,
+    #[schemars(description = "Number of nodes in the group")]
+    pub node_count: usize,
+    #[schemars(description = "Names of the nodes in the group")]
+    pub nodes: Vec<String>,
+    #[schemars(description = "Allocatable CPU in cores of each node")]
+    pub allocatable_cpu_cores: f64,
+    #[schemars(description = "Allocatable memory in GB of each node")]
+    pub allocatable_memory_gb: f64,
+    #[schemars(description = "Available CPU in cores across the nodes of the group")]
+    pub available_cpu_cores: AvailabilityRange,
+    #[schemars(description = "Available memory in GB across the nodes of the group")]
+    pub available_memory_gb: AvailabilityRange,
+    #[schemars(description = "Number of pods on all nodes of the group")]
+    pub pod_count: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
 pub struct NodeBreakdownResponse {
-    #[schemars(description = "List of nodes with their resource information")]
+    #[schemars(description = "List of nodes with their resource information (one page with limit/offset)")]
     pub nodes: Vec<NodeInfo>,
     #[schemars(description = "Total number of nodes")]
     pub total_nodes: usize,
+    #[serde(default)]
+    #[schemars(description = "Number of sorted nodes skipped before this page")]
+    pub offset: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Offset of the next page, absent on the last page")]
+    pub next_offset: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Opaque cursor to pass as cursor for the next page, absent on the last page")]
+    pub next_cursor: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "What the allocated and available figures count pods by: Requests, Limits or Usage")]
+    pub basis: ResourceBasis,
+    #[serde(default)]
+    #[schemars(description = "Pods bound to a node that no longer exists, left out of every node (see find_orphaned_pods)")]
+    pub orphaned_pod_count: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "With group_identical, the node groups (largest first) replacing the empty nodes list")]
+    pub node_groups: Option<Vec<NodeGroup>>,
     #[schemars(description = "Explanation of node breakdown")]
     pub explanation: String,
 }
@@ -106,1033 +567,13988 @@ pub struct NamespaceUsage {
     pub pod_count: usize,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct NamespaceUsageParams {
+    #[serde(default)]
+    #[schemars(description = "List Succeeded and Failed pods in terminal_pods; they never count towards usage (default: false)")]
+    pub include_terminal: bool,
+    #[serde(default)]
+    #[schemars(description = "Only report these namespaces and add their combined total (default: all namespaces; each must exist)")]
+    pub namespaces: Option<Vec<String>>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
 pub struct NamespaceUsageResponse {
     #[schemars(description = "List of namespaces with their resource usage")]
     pub namespaces: Vec<NamespaceUsage>,
     #[schemars(description = "Total number of namespaces")]
     pub total_namespaces: usize,
+    #[schemars(description = "Order of namespaces: the key field, order and basis")]
+    pub sort: SortSpec,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Combined usage of the requested namespaces, named by joining them with ', ' (only with namespaces)")]
+    pub combined: Option<NamespaceUsage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Succeeded and Failed pods, excluded from the usage (only with include_terminal)")]
+    pub terminal_pods: Option<Vec<TerminalPod>>,
     #[schemars(description = "Explanation of namespace usage")]
     pub explanation: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
-pub struct PodResourceInfo {
+pub struct TerminalPod {
     #[schemars(description = "Pod name")]
     pub name: String,
     #[schemars(description = "Namespace")]
     pub namespace: String,
-    #[schemars(description = "CPU requests in millicores")]
-    pub cpu_requests_millicores: i64,
-    #[schemars(description = "Memory requests in MB")]
-    pub memory_requests_mb: i64,
-    #[schemars(description = "CPU limits in millicores")]
-    pub cpu_limits_millicores: i64,
-    #[schemars(description = "Memory limits in MB")]
-    pub memory_limits_mb: i64,
+    #[schemars(description = "Pod phase (Succeeded or Failed)")]
+    pub phase: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct BurstRiskParams {
+    #[serde(default)]
+    #[schemars(description = "Limit/request ratio above which a namespace or pod is flagged (default: 2.0)")]
+    pub ratio_threshold: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Also report flagged pods (default: false)")]
+    pub include_pods: bool,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct BurstRisk {
+    #[schemars(description = "Namespace")]
+    pub namespace: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Pod name (only for pod entries)")]
+    pub pod: Option<String>,
+    #[schemars(description = "CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "CPU limits in cores")]
+    pub cpu_limits_cores: f64,
+    #[schemars(description = "CPU limit/request ratio (absent without CPU requests)")]
+    pub cpu_limit_ratio: Option<f64>,
+    #[schemars(description = "Memory requests in GB")]
+    pub memory_requests_gb: f64,
+    #[schemars(description = "Memory limits in GB")]
+    pub memory_limits_gb: f64,
+    #[schemars(description = "Memory limit/request ratio (absent without memory requests)")]
+    pub memory_limit_ratio: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct BurstRiskResponse {
+    #[schemars(description = "Limit/request ratio threshold used")]
+    pub ratio_threshold: f64,
+    #[schemars(description = "Flagged namespaces, sorted by highest ratio (descending)")]
+    pub namespaces: Vec<BurstRisk>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Flagged pods, sorted by highest ratio (only with include_pods)")]
+    pub pods: Option<Vec<BurstRisk>>,
+    #[schemars(description = "Number of namespaces checked")]
+    pub total_namespaces_checked: usize,
+    #[schemars(description = "Explanation of the burst risk check")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct NamespacesWithoutLimitsParams {
+    #[serde(default)]
+    #[schemars(description = "Fraction of pods without a memory limit at which a namespace is flagged, between 0 and 1 (default: 0.5)")]
+    pub min_unlimited_fraction: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NamespaceLimitGap {
+    #[schemars(description = "Namespace name")]
+    pub namespace: String,
+    #[schemars(description = "Number of pods in namespace")]
+    pub pod_count: usize,
+    #[schemars(description = "Number of pods with at least one container without a memory limit")]
+    pub pods_without_memory_limit: usize,
+    #[schemars(description = "Fraction of the namespace pods without a memory limit")]
+    pub unlimited_fraction: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct NamespacesWithoutLimitsResponse {
+    #[schemars(description = "Fraction threshold used")]
+    pub min_unlimited_fraction: f64,
+    #[schemars(description = "Flagged namespaces, highest unlimited fraction first")]
+    pub namespaces: Vec<NamespaceLimitGap>,
+    #[schemars(description = "Number of namespaces with pods that were checked")]
+    pub total_namespaces_checked: usize,
+    #[schemars(description = "Explanation of the limit check")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct PodSize {
+    #[schemars(description = "Name of the pod size (e.g. small, large)")]
+    pub name: String,
+    #[schemars(description = "CPU request in cores")]
+    pub cpu_cores: f64,
+    #[schemars(description = "Memory request in GB")]
+    pub memory_gb: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct NodeAcceptanceMatrixParams {
+    #[schemars(description = "Candidate pod sizes to check against every node")]
+    pub sizes: Vec<PodSize>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodeAcceptance {
     #[schemars(description = "Node name")]
     pub node: String,
+    #[schemars(description = "Available CPU in cores")]
+    pub available_cpu_cores: f64,
+    #[schemars(description = "Available memory in GB")]
+    pub available_memory_gb: f64,
+    #[schemars(description = "Whether each size still fits on the node, in the order of sizes")]
+    pub accepts: Vec<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
-pub struct PodResourceStatsResponse {
-    #[schemars(description = "Top pods by resource consumption")]
-    pub top_pods: Vec<PodResourceInfo>,
-    #[schemars(description = "Total number of pods")]
-    pub total_pods: usize,
-    #[schemars(description = "Sort criteria used")]
-    pub sorted_by: String,
-    #[schemars(description = "Explanation of pod resource stats")]
+pub struct NodeAcceptanceMatrixResponse {
+    #[schemars(description = "Size names, the columns of the matrix")]
+    pub sizes: Vec<String>,
+    #[schemars(description = "One row per node, sorted by name")]
+    pub nodes: Vec<NodeAcceptance>,
+    #[schemars(description = "Number of nodes accepting each size, in the order of sizes")]
+    pub accepting_nodes: Vec<usize>,
+    #[schemars(description = "Explanation of the acceptance matrix")]
     pub explanation: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
-pub struct CheckReplicaCapacityParams {
-    #[schemars(description = "Application or pod name pattern to find")]
-    pub app_name: String,
-    #[schemars(description = "Namespace to search in")]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct LargestPodPerNodeParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct LargestPod {
+    #[schemars(description = "Pod name")]
+    pub name: String,
+    #[schemars(description = "Namespace")]
     pub namespace: String,
-    #[schemars(description = "Number of additional replicas needed")]
-    pub replica_count: i32,
+    #[schemars(description = "CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Memory requests in GB")]
+    pub memory_requests_gb: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodeLargestPods {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Number of running pods on the node")]
+    pub pod_count: usize,
+    #[schemars(description = "Pod with the highest CPU request")]
+    pub largest_by_cpu: LargestPod,
+    #[schemars(description = "Pod with the highest memory request")]
+    pub largest_by_memory: LargestPod,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
-pub struct CheckReplicaCapacityResponse {
-    #[schemars(description = "Whether replicas can fit in cluster")]
-    pub fits: bool,
-    #[schemars(description = "Name of the reference pod used for calculations")]
-    pub reference_pod: String,
-    #[schemars(description = "CPU required per replica in cores")]
-    pub cpu_per_replica_cores: f64,
-    #[schemars(description = "Memory required per replica in GB")]
-    pub memory_per_replica_gb: f64,
-    #[schemars(description = "Total CPU required for all replicas in cores")]
-    pub total_cpu_required_cores: f64,
-    #[schemars(description = "Total memory required for all replicas in GB")]
-    pub total_memory_required_gb: f64,
-    #[schemars(description = "Available CPU in cluster in cores")]
+pub struct LargestPodPerNodeResponse {
+    #[schemars(description = "Nodes with pods, sorted by name")]
+    pub nodes: Vec<NodeLargestPods>,
+    #[schemars(description = "Explanation of the largest pods")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct StrandedCapacityParams {
+    #[serde(default)]
+    #[schemars(description = "CPU cores per GB of memory of a typical pod (default: the cluster's average request ratio)")]
+    pub cpu_to_memory_ratio: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodeStrandedCapacity {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Available CPU in cores")]
     pub available_cpu_cores: f64,
-    #[schemars(description = "Available memory in cluster in GB")]
+    #[schemars(description = "Available memory in GB")]
     pub available_memory_gb: f64,
-    #[schemars(description = "Current number of matching pods")]
-    pub current_pod_count: usize,
-    #[schemars(description = "CPU utilization percentage after adding replicas")]
-    pub projected_cpu_utilization_percent: f64,
-    #[schemars(description = "Memory utilization percentage after adding replicas")]
-    pub projected_memory_utilization_percent: f64,
-    #[schemars(description = "Detailed explanation of capacity check")]
+    #[schemars(description = "Available CPU that balanced pods can still use, in cores")]
+    pub usable_cpu_cores: f64,
+    #[schemars(description = "Available memory that balanced pods can still use, in GB")]
+    pub usable_memory_gb: f64,
+    #[schemars(description = "Available CPU without matching free memory, in cores")]
+    pub stranded_cpu_cores: f64,
+    #[schemars(description = "Available memory without matching free CPU, in GB")]
+    pub stranded_memory_gb: f64,
+    #[schemars(description = "Stranded share of the node's allocatable CPU or memory, whichever is stranded")]
+    pub stranded_percent: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct StrandedCapacityResponse {
+    #[schemars(description = "CPU cores per GB of memory of the reference pod used")]
+    pub cpu_to_memory_ratio: f64,
+    #[schemars(description = "Nodes sorted by stranded_percent (descending)")]
+    pub nodes: Vec<NodeStrandedCapacity>,
+    #[schemars(description = "Stranded CPU across all nodes in cores")]
+    pub total_stranded_cpu_cores: f64,
+    #[schemars(description = "Stranded memory across all nodes in GB")]
+    pub total_stranded_memory_gb: f64,
+    #[schemars(description = "Explanation of the stranded capacity")]
     pub explanation: String,
 }
 
-// =================== HELPER FUNCTIONS ===================
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct OverpackedNodesParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
 
-/// Parse Kubernetes quantity to cores (CPU)
-fn quantity_to_cores(quantity: &Quantity) -> f64 {
-    let s = &quantity.0;
-    if s.is_empty() {
-        return 0.0;
-    }
-    
-    // Handle millicores (e.g., "100m")
-    if s.ends_with('m') {
-        if let Ok(millicores) = s[..s.len() - 1].parse::<f64>() {
-            return millicores / 1000.0;
-        }
-    }
-    
-    // Handle cores (e.g., "2", "0.5")
-    if let Ok(cores) = s.parse::<f64>() {
-        return cores;
-    }
-    
-    0.0
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct OverpackedNode {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Number of running pods on the node")]
+    pub pod_count: usize,
+    #[schemars(description = "Allocatable CPU in cores")]
+    pub allocatable_cpu_cores: f64,
+    #[schemars(description = "Allocatable memory in GB")]
+    pub allocatable_memory_gb: f64,
+    #[schemars(description = "Summed effective CPU requests of the running pods in cores")]
+    pub requested_cpu_cores: f64,
+    #[schemars(description = "Summed effective memory requests of the running pods in GB")]
+    pub requested_memory_gb: f64,
+    #[schemars(description = "CPU requested beyond allocatable in cores (0 when within)")]
+    pub cpu_overage_cores: f64,
+    #[schemars(description = "Memory requested beyond allocatable in GB (0 when within)")]
+    pub memory_overage_gb: f64,
 }
 
-/// Parse Kubernetes quantity to GB (memory)
-fn quantity_to_gb(quantity: &Quantity) -> f64 {
-    let s = &quantity.0;
-    if s.is_empty() {
-        return 0.0;
-    }
-    
-    // Handle various memory units
-    let (value, unit) = if s.ends_with("Ki") {
-        (s[..s.len() - 2].parse::<f64>().ok(), 1024.0)
-    } else if s.ends_with("Mi") {
-        (s[..s.len() - 2].parse::<f64>().ok(), 1024.0 * 1024.0)
-    } else if s.ends_with("Gi") {
-        (s[..s.len() - 2].parse::<f64>().ok(), 1024.0 * 1024.0 * 1024.0)
-    } else if s.ends_with("Ti") {
-        (s[..s.len() - 2].parse::<f64>().ok(), 1024.0 * 1024.0 * 1024.0 * 1024.0)
-    } else if s.ends_with("K") {
-        (s[..s.len() - 1].parse::<f64>().ok(), 1000.0)
-    } else if s.ends_with("M") {
-        (s[..s.len() - 1].parse::<f64>().ok(), 1000.0 * 1000.0)
-    } else if s.ends_with("G") {
-        (s[..s.len() - 1].parse::<f64>().ok(), 1000.0 * 1000.0 * 1000.0)
-    } else if s.ends_with("T") {
-        (s[..s.len() - 1].parse::<f64>().ok(), 1000.0 * 1000.0 * 1000.0 * 1000.0)
-    } else {
-        // Assume bytes
-        (s.parse::<f64>().ok(), 1.0)
-    };
-    
-    if let Some(v) = value {
-        v * unit / (1024.0 * 1024.0 * 1024.0) // Convert to GB
-    } else {
-        0.0
-    }
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct OverpackedNodesResponse {
+    #[schemars(description = "Nodes whose pod requests exceed their allocatable CPU or memory, largest relative overage first")]
+    pub nodes: Vec<OverpackedNode>,
+    #[schemars(description = "Number of nodes checked")]
+    pub total_nodes_checked: usize,
+    #[schemars(description = "Explanation of the overpacked nodes")]
+    pub explanation: String,
 }
 
-/// Parse Kubernetes quantity to MB (memory)
-fn quantity_to_mb(quantity: &Quantity) -> i64 {
-    (quantity_to_gb(quantity) * 1024.0) as i64
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct BalanceReportParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
 }
 
-/// Parse Kubernetes quantity to millicores (CPU)
-fn quantity_to_millicores(quantity: &Quantity) -> i64 {
-    (quantity_to_cores(quantity) * 1000.0) as i64
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodeUtilization {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Number of pods on the node")]
+    pub pod_count: usize,
+    #[schemars(description = "CPU requests as a percentage of allocatable CPU")]
+    pub cpu_utilization_percent: f64,
+    #[schemars(description = "Memory requests as a percentage of allocatable memory")]
+    pub memory_utilization_percent: f64,
 }
 
-// =================== CLUSTER INSIGHTS ===================
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, schemars::JsonSchema)]
+pub struct UtilizationSpread {
+    #[schemars(description = "Mean utilization across nodes in percent")]
+    pub mean_percent: f64,
+    #[schemars(description = "Population standard deviation of the utilization in percentage points")]
+    pub std_dev_percent: f64,
+    #[schemars(description = "Standard deviation divided by the mean (0 when the mean is 0)")]
+    pub coefficient_of_variation: f64,
+}
 
-#[derive(Debug, Clone)]
-pub struct ClusterInsights {
-    tool_router: ToolRouter<Self>,
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct BalanceReportResponse {
+    #[schemars(description = "Spread of CPU utilization across nodes")]
+    pub cpu: UtilizationSpread,
+    #[schemars(description = "Spread of memory utilization across nodes")]
+    pub memory: UtilizationSpread,
+    #[schemars(description = "Whether the CPU or memory coefficient of variation exceeds the imbalance threshold")]
+    pub imbalanced: bool,
+    #[schemars(description = "Node with the highest utilization (CPU or memory, whichever is higher)")]
+    pub most_utilized: Option<NodeUtilization>,
+    #[schemars(description = "Node with the lowest utilization (CPU or memory, whichever is higher)")]
+    pub least_utilized: Option<NodeUtilization>,
+    #[schemars(description = "All nodes, most utilized first")]
+    pub nodes: Vec<NodeUtilization>,
+    #[schemars(description = "Explanation of the balance, with a rebalancing suggestion when imbalanced")]
+    pub explanation: String,
 }
 
-impl ClusterInsights {
-    /// Get cluster capacity
-    async fn get_cluster_capacity_internal() -> Result<ClusterCapacityResponse, String> {
-        let client = Client::try_default().await
-            .map_err(|e| format!("Failed to create Kubernetes client: {}", e))?;
-        
-        let nodes_api: Api<Node> = Api::all(client.clone());
-        let pods_api: Api<Pod> = Api::all(client.clone());
-        
-        let nodes = nodes_api.list(&Default::default()).await
-            .map_err(|e| format!("Failed to list nodes: {}", e))?;
-        
-        let pods = pods_api.list(&Default::default()).await
-            .map_err(|e| format!("Failed to list pods: {}", e))?;
-        
-        let mut total_cpu_cores = 0.0;
-        let mut total_memory_gb = 0.0;
-        
-        for node in &nodes.items {
-            if let Some(status) = &node.status {
-                if let Some(capacity) = &status.capacity {
-                    if let Some(cpu) = capacity.get("cpu") {
-                        total_cpu_cores += quantity_to_cores(cpu);
-                    }
-                    if let Some(memory) = capacity.get("memory") {
-                        total_memory_gb += quantity_to_gb(memory);
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct PodChurnParams {
+    #[serde(default)]
+    #[schemars(description = "Length of the look-back window in minutes (default: 60)")]
+    pub window_minutes: Option<u64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NamespaceChurn {
+    #[schemars(description = "Namespace")]
+    pub namespace: String,
+    #[schemars(description = "Pods created within the window (from creationTimestamp)")]
+    pub created_pods: usize,
+    #[schemars(description = "Pods deleted by controllers within the window (from SuccessfulDelete events)")]
+    pub deleted_pods: usize,
+    #[schemars(description = "Created plus deleted pods per hour over the window")]
+    pub churn_per_hour: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct PodChurnResponse {
+    #[schemars(description = "Length of the look-back window in minutes")]
+    pub window_minutes: u64,
+    #[schemars(description = "Pods created within the window across all namespaces")]
+    pub created_pods: usize,
+    #[schemars(description = "Pods deleted by controllers within the window across all namespaces")]
+    pub deleted_pods: usize,
+    #[schemars(description = "Namespaces with churn in the window, highest churn rate first")]
+    pub namespaces: Vec<NamespaceChurn>,
+    #[schemars(description = "Whether Events could be listed; when false, deletions are not counted")]
+    pub events_available: bool,
+    #[schemars(description = "Explanation of the churn")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct GpuReportParams {
+    #[serde(default)]
+    #[schemars(description = "Also list every pod that requests GPUs (default: false)")]
+    pub include_pods: bool,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodeGpus {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Allocatable GPUs on the node")]
+    pub total_gpus: u64,
+    #[schemars(description = "GPUs requested by the running pods on the node")]
+    pub requested_gpus: u64,
+    #[schemars(description = "GPUs not requested by any pod (total minus requested)")]
+    pub idle_gpus: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NamespaceGpus {
+    #[schemars(description = "Namespace")]
+    pub namespace: String,
+    #[schemars(description = "GPUs requested by the namespace's running pods")]
+    pub requested_gpus: u64,
+    #[schemars(description = "Number of running pods requesting GPUs")]
+    pub pod_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct PodGpus {
+    #[schemars(description = "Pod name")]
+    pub name: String,
+    #[schemars(description = "Namespace")]
+    pub namespace: String,
+    #[schemars(description = "Node name (or 'unscheduled')")]
+    pub node: String,
+    #[schemars(description = "GPUs requested by the pod")]
+    pub gpus: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct GpuReportResponse {
+    #[schemars(description = "Allocatable GPUs across all nodes")]
+    pub total_gpus: u64,
+    #[schemars(description = "GPUs requested by running pods")]
+    pub requested_gpus: u64,
+    #[schemars(description = "GPUs not requested by any pod")]
+    pub idle_gpus: u64,
+    #[schemars(description = "GPUs requested by pods still waiting for a node")]
+    pub pending_gpus: u64,
+    #[schemars(description = "Nodes with GPUs, most idle GPUs first")]
+    pub nodes: Vec<NodeGpus>,
+    #[schemars(description = "Namespaces requesting GPUs, most GPUs first")]
+    pub namespaces: Vec<NamespaceGpus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Pods requesting GPUs, most GPUs first (only with include_pods)")]
+    pub pods: Option<Vec<PodGpus>>,
+    #[schemars(description = "Explanation of the GPU usage")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct MaxPodsOfSizeParams {
+    #[schemars(description = "CPU request of one pod in cores")]
+    pub cpu_cores: f64,
+    #[schemars(description = "Memory request of one pod in GB")]
+    pub memory_gb: f64,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodePodCount {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Number of pods of the size that fit on the node")]
+    pub pods: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct MaxPodsOfSizeResponse {
+    #[schemars(description = "CPU request of one pod in cores")]
+    pub cpu_cores: f64,
+    #[schemars(description = "Memory request of one pod in GB")]
+    pub memory_gb: f64,
+    #[schemars(description = "Pods that fit when packed node by node (the realistic answer)")]
+    pub max_pods: u64,
+    #[schemars(description = "Pods that fit when dividing the cluster-wide available resources, ignoring fragmentation")]
+    pub naive_max_pods: u64,
+    #[schemars(description = "Resource that runs out first across the nodes: cpu or memory")]
+    pub limiting_resource: String,
+    #[schemars(description = "Pods that fit per node, most first")]
+    pub nodes: Vec<NodePodCount>,
+    #[schemars(description = "Explanation of the estimate")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct UsageByLabelParams {
+    #[schemars(description = "Pod label key to group by (e.g. team, cost-center)")]
+    pub label_key: String,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct LabelUsage {
+    #[schemars(description = "Label value ('unlabeled' for pods without the label)")]
+    pub value: String,
+    #[schemars(description = "CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Memory requests in GB")]
+    pub memory_requests_gb: f64,
+    #[schemars(description = "CPU limits in cores")]
+    pub cpu_limits_cores: f64,
+    #[schemars(description = "Memory limits in GB")]
+    pub memory_limits_gb: f64,
+    #[schemars(description = "Number of pods with this label value")]
+    pub pod_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct UsageByLabelResponse {
+    #[schemars(description = "Label key used for grouping")]
+    pub label_key: String,
+    #[schemars(description = "Resource usage per label value, sorted by CPU requests (descending)")]
+    pub groups: Vec<LabelUsage>,
+    #[schemars(description = "Number of label values, including the unlabeled bucket")]
+    pub total_groups: usize,
+    #[schemars(description = "Explanation of the label usage")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct UsageForSelectorParams {
+    #[schemars(description = "Label selector matched against pods of all namespaces (e.g. 'app.kubernetes.io/part-of=monitoring')")]
+    pub label_selector: String,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct UsageForSelectorResponse {
+    #[schemars(description = "Label selector used")]
+    pub label_selector: String,
+    #[schemars(description = "CPU requests of the matching pods in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Memory requests of the matching pods in GB")]
+    pub memory_requests_gb: f64,
+    #[schemars(description = "CPU limits of the matching pods in cores")]
+    pub cpu_limits_cores: f64,
+    #[schemars(description = "Memory limits of the matching pods in GB")]
+    pub memory_limits_gb: f64,
+    #[schemars(description = "Number of matching pods (Succeeded and Failed pods excluded)")]
+    pub pod_count: usize,
+    #[schemars(description = "Namespaces of the matching pods, sorted")]
+    pub namespaces: Vec<String>,
+    #[schemars(description = "Explanation of the usage")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct UsageByWorkloadKindParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct WorkloadKindUsage {
+    #[schemars(description = "Kind of the top-level owner (e.g. Deployment, StatefulSet, DaemonSet, CronJob; 'Pod' for pods without an owner)")]
+    pub kind: String,
+    #[schemars(description = "CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Memory requests in GB")]
+    pub memory_requests_gb: f64,
+    #[schemars(description = "CPU limits in cores")]
+    pub cpu_limits_cores: f64,
+    #[schemars(description = "Memory limits in GB")]
+    pub memory_limits_gb: f64,
+    #[schemars(description = "Number of pods owned by this kind")]
+    pub pod_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct UsageByWorkloadKindResponse {
+    #[schemars(description = "Resource usage per top-level owner kind, sorted by CPU requests (descending)")]
+    pub kinds: Vec<WorkloadKindUsage>,
+    #[schemars(description = "Total number of pods")]
+    pub total_pods: usize,
+    #[schemars(description = "Explanation of the workload kind usage")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct ReplicaConsistencyParams {
+    #[schemars(description = "Only check workloads in this namespace (default: all namespaces; blank means the server's DEFAULT_NAMESPACE)")]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct ReplicaResourceValue {
+    #[schemars(description = "Quantity as written in the pod spec, or 'unset'")]
+    pub value: String,
+    #[schemars(description = "Replicas using this value, sorted")]
+    pub pods: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct ReplicaResourceDifference {
+    #[schemars(description = "Container name")]
+    pub container: String,
+    #[schemars(description = "'request' or 'limit'")]
+    pub kind: String,
+    #[schemars(description = "Resource name (e.g. cpu, memory)")]
+    pub resource: String,
+    #[schemars(description = "Distinct values across the replicas, most common first")]
+    pub values: Vec<ReplicaResourceValue>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct WorkloadConsistency {
+    #[schemars(description = "Namespace of the workload")]
+    pub namespace: String,
+    #[schemars(description = "Kind of the top-level owner (e.g. Deployment, StatefulSet, DaemonSet)")]
+    pub owner_kind: String,
+    #[schemars(description = "Name of the top-level owner")]
+    pub owner_name: String,
+    #[schemars(description = "Number of running or pending replicas compared")]
+    pub replica_count: usize,
+    #[schemars(description = "Whether all replicas share identical requests and limits")]
+    pub consistent: bool,
+    #[schemars(description = "Requests and limits that differ between replicas (empty when consistent)")]
+    pub differences: Vec<ReplicaResourceDifference>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct ReplicaConsistencyResponse {
+    #[schemars(description = "Namespace the workloads were taken from, or None for all namespaces")]
+    pub namespace: Option<String>,
+    #[schemars(description = "Workloads with at least two replicas, inconsistent ones first, then by namespace and name")]
+    pub workloads: Vec<WorkloadConsistency>,
+    #[schemars(description = "Number of workloads compared")]
+    pub workloads_checked: usize,
+    #[schemars(description = "Number of workloads whose replicas differ")]
+    pub inconsistent_workloads: usize,
+    #[schemars(description = "Explanation of the replica consistency")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct DeploymentScaleParams {
+    #[schemars(description = "Name of the Deployment")]
+    pub deployment: String,
+    #[serde(default)]
+    #[schemars(description = "Namespace of the Deployment (default: the server's DEFAULT_NAMESPACE, if configured)")]
+    pub namespace: String,
+    #[schemars(description = "Total number of replicas wanted (not the number to add)")]
+    pub target_replicas: i32,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct ReplicaPlacement {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Number of additional replicas placed on the node")]
+    pub replicas: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct ExcludedNode {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Why the replicas cannot run there (cordoned, nodeSelector, node affinity or an untolerated taint)")]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct DeploymentScaleResponse {
+    #[schemars(description = "Deployment name")]
+    pub deployment: String,
+    #[schemars(description = "Namespace of the Deployment")]
+    pub namespace: String,
+    #[schemars(description = "Replicas the Deployment currently has")]
+    pub current_replicas: i32,
+    #[schemars(description = "Total number of replicas wanted")]
+    pub target_replicas: i32,
+    #[schemars(description = "Replicas to add to reach the target (0 when already there)")]
+    pub additional_replicas: i32,
+    #[schemars(description = "Effective CPU request of one replica in cores, from the pod template")]
+    pub cpu_per_replica_cores: f64,
+    #[schemars(description = "Effective memory request of one replica in GB, from the pod template")]
+    pub memory_per_replica_gb: f64,
+    #[schemars(description = "Whether every additional replica can be scheduled")]
+    pub fits: bool,
+    #[schemars(description = "Additional replicas that fit on the eligible nodes")]
+    pub schedulable_replicas: i32,
+    #[schemars(description = "Additional replicas that would stay Pending")]
+    pub unschedulable_replicas: i32,
+    #[schemars(description = "Where the schedulable replicas would go, most replicas first")]
+    pub placements: Vec<ReplicaPlacement>,
+    #[schemars(description = "Nodes the pod template cannot run on, sorted by name")]
+    pub excluded_nodes: Vec<ExcludedNode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Most additional replicas the template's required pod anti-affinity allows (one per free topology domain)")]
+    pub anti_affinity_limit: Option<i32>,
+    #[schemars(description = "Explanation of the scale check")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct CronJobCapacityParams {
+    #[schemars(description = "Name of the CronJob")]
+    pub cronjob: String,
+    #[serde(default)]
+    #[schemars(description = "Namespace of the CronJob (default: the server's DEFAULT_NAMESPACE, if configured)")]
+    pub namespace: String,
+    #[schemars(description = "Number of the CronJob's Jobs assumed to run at the same time at peak")]
+    pub concurrency: i32,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct CronJobCapacityResponse {
+    #[schemars(description = "CronJob name")]
+    pub cronjob: String,
+    #[schemars(description = "Namespace of the CronJob")]
+    pub namespace: String,
+    #[schemars(description = "Cron schedule of the CronJob")]
+    pub schedule: String,
+    #[schemars(description = "concurrencyPolicy of the CronJob: Allow, Forbid or Replace")]
+    pub concurrency_policy: String,
+    #[schemars(description = "Number of concurrent Jobs assumed")]
+    pub concurrency: i32,
+    #[schemars(description = "Pods each Job runs at once: the job template's parallelism, capped by its completions")]
+    pub pods_per_job: i32,
+    #[schemars(description = "Pods to place: concurrency times pods_per_job")]
+    pub pods_needed: i32,
+    #[schemars(description = "Effective CPU request of one pod in cores, from the job template")]
+    pub cpu_per_pod_cores: f64,
+    #[schemars(description = "Effective memory request of one pod in GB, from the job template")]
+    pub memory_per_pod_gb: f64,
+    #[schemars(description = "CPU in cores all the pods request together")]
+    pub total_cpu_cores: f64,
+    #[schemars(description = "Memory in GB all the pods request together")]
+    pub total_memory_gb: f64,
+    #[schemars(description = "Whether every pod can be scheduled alongside the current allocation")]
+    pub fits: bool,
+    #[schemars(description = "Pods that fit on the eligible nodes")]
+    pub schedulable_pods: i32,
+    #[schemars(description = "Pods that would stay Pending")]
+    pub unschedulable_pods: i32,
+    #[schemars(description = "Where the schedulable pods would go, most pods first")]
+    pub placements: Vec<ReplicaPlacement>,
+    #[schemars(description = "Nodes the job template cannot run on, sorted by name")]
+    pub excluded_nodes: Vec<ExcludedNode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Most pods the job template's required pod anti-affinity allows (one per free topology domain)")]
+    pub anti_affinity_limit: Option<i32>,
+    #[schemars(description = "Explanation of the CronJob capacity check")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct EvictionRiskParams {
+    #[serde(default)]
+    #[schemars(description = "Flag a node when its usage is within this percent of allocatable (default: 10)")]
+    pub margin_percent: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodeEvictionRisk {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Memory working set in GB (absent without node metrics)")]
+    pub memory_usage_gb: Option<f64>,
+    #[schemars(description = "Allocatable memory in GB")]
+    pub memory_allocatable_gb: f64,
+    #[schemars(description = "Memory working set as a percentage of allocatable (absent without node metrics)")]
+    pub memory_usage_percent: Option<f64>,
+    #[schemars(description = "Ephemeral storage used on the node filesystem in GB (absent without kubelet stats)")]
+    pub storage_usage_gb: Option<f64>,
+    #[schemars(description = "Allocatable ephemeral storage in GB")]
+    pub storage_allocatable_gb: f64,
+    #[schemars(description = "Ephemeral storage used as a percentage of allocatable (absent without kubelet stats)")]
+    pub storage_usage_percent: Option<f64>,
+    #[schemars(description = "Resources within the margin of allocatable: memory and/or ephemeral-storage")]
+    pub resources_at_risk: Vec<String>,
+    #[schemars(description = "MemoryPressure/DiskPressure conditions the kubelet currently reports as True")]
+    pub pressure_conditions: Vec<String>,
+    #[schemars(description = "Whether a resource is within the margin or the kubelet reports pressure")]
+    pub at_risk: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct EvictionRiskResponse {
+    #[schemars(description = "Margin of allocatable used to flag nodes, in percent")]
+    pub margin_percent: f64,
+    #[schemars(description = "Nodes sorted by risk: at-risk nodes first, then by the highest usage percentage (descending)")]
+    pub nodes: Vec<NodeEvictionRisk>,
+    #[schemars(description = "Number of nodes at risk")]
+    pub at_risk_nodes: usize,
+    #[schemars(description = "Whether node memory usage from metrics.k8s.io was available")]
+    pub memory_metrics_available: bool,
+    #[schemars(description = "Whether ephemeral storage usage from the kubelet stats was available")]
+    pub storage_metrics_available: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(description = "Why usage data is missing; the node pressure conditions are still reported")]
+    pub warnings: Vec<String>,
+    #[schemars(description = "Explanation of the eviction risk")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct NamespaceShareParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NamespaceShare {
+    #[schemars(description = "Namespace name")]
+    pub namespace: String,
+    #[schemars(description = "CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Memory requests in GB")]
+    pub memory_requests_gb: f64,
+    #[schemars(description = "Share of the cluster's CPU requests, in percent")]
+    pub cpu_share_percent: f64,
+    #[schemars(description = "Share of the cluster's memory requests, in percent")]
+    pub memory_share_percent: f64,
+    #[schemars(description = "Blended share: the average of the CPU and memory shares, in percent")]
+    pub resource_share_percent: f64,
+    #[schemars(description = "Number of pods in namespace")]
+    pub pod_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct NamespaceShareResponse {
+    #[schemars(description = "Namespaces sorted by blended share (descending)")]
+    pub namespaces: Vec<NamespaceShare>,
+    #[schemars(description = "CPU requests of all namespaces in cores, the base of the CPU shares")]
+    pub total_cpu_requests_cores: f64,
+    #[schemars(description = "Memory requests of all namespaces in GB, the base of the memory shares")]
+    pub total_memory_requests_gb: f64,
+    #[schemars(description = "Explanation of the namespace shares")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct RequestOutliersParams {
+    #[serde(default)]
+    #[schemars(description = "Flag a pod whose effective CPU or memory request is at least this multiple of the median pod's (default: 10)")]
+    pub median_multiple: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct ContainerRequest {
+    #[schemars(description = "Container name")]
+    pub name: String,
+    #[schemars(description = "Container kind: container, init or sidecar (init container with restartPolicy Always)")]
+    pub kind: String,
+    #[schemars(description = "CPU request in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Memory request in GB")]
+    pub memory_requests_gb: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct RequestOutlier {
+    #[schemars(description = "Pod name")]
+    pub name: String,
+    #[schemars(description = "Namespace")]
+    pub namespace: String,
+    #[schemars(description = "Node name (or 'unscheduled')")]
+    pub node: String,
+    #[schemars(description = "Summed CPU requests of the app containers in cores")]
+    pub containers_cpu_cores: f64,
+    #[schemars(description = "Summed memory requests of the app containers in GB")]
+    pub containers_memory_gb: f64,
+    #[schemars(description = "Effective CPU request in cores (init containers, sidecars and pod overhead included)")]
+    pub effective_cpu_cores: f64,
+    #[schemars(description = "Effective memory request in GB (init containers, sidecars and pod overhead included)")]
+    pub effective_memory_gb: f64,
+    #[schemars(description = "Effective CPU request as a multiple of the median pod's")]
+    pub cpu_median_multiple: f64,
+    #[schemars(description = "Effective memory request as a multiple of the median pod's")]
+    pub memory_median_multiple: f64,
+    #[schemars(description = "Why the pod is flagged")]
+    pub reasons: Vec<String>,
+    #[schemars(description = "Requests of every container, init container and sidecar")]
+    pub containers: Vec<ContainerRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct RequestOutliersResponse {
+    #[schemars(description = "Multiple of the median used to flag pods")]
+    pub median_multiple: f64,
+    #[schemars(description = "Median effective CPU request of the checked pods in cores")]
+    pub median_cpu_cores: f64,
+    #[schemars(description = "Median effective memory request of the checked pods in GB")]
+    pub median_memory_gb: f64,
+    #[schemars(description = "Flagged pods, largest multiple of the median first")]
+    pub pods: Vec<RequestOutlier>,
+    #[schemars(description = "Number of non-terminal pods checked")]
+    pub total_pods_checked: usize,
+    #[schemars(description = "Explanation of the outlier check")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct CapacityByNodeStateParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodeStateCapacity {
+    #[schemars(description = "Node state: Ready (schedulable and Ready), Cordoned (unschedulable, whatever its readiness) or NotReady (schedulable but not Ready)")]
+    pub state: String,
+    #[schemars(description = "Number of nodes in this state")]
+    pub node_count: usize,
+    #[schemars(description = "Names of the nodes in this state, sorted")]
+    pub nodes: Vec<String>,
+    #[schemars(description = "Total CPU capacity in cores")]
+    pub cpu_cores: f64,
+    #[schemars(description = "Total memory capacity in GB")]
+    pub memory_gb: f64,
+    #[schemars(description = "Allocatable CPU in cores")]
+    pub allocatable_cpu_cores: f64,
+    #[schemars(description = "Allocatable memory in GB")]
+    pub allocatable_memory_gb: f64,
+    #[schemars(description = "Share of the cluster's CPU capacity, in percent")]
+    pub cpu_percent: f64,
+    #[schemars(description = "Share of the cluster's memory capacity, in percent")]
+    pub memory_percent: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct CapacityByNodeStateResponse {
+    #[schemars(description = "Capacity per node state, always Ready, Cordoned and NotReady in this order")]
+    pub states: Vec<NodeStateCapacity>,
+    #[schemars(description = "Total CPU capacity of all nodes in cores (as in get_cluster_capacity)")]
+    pub total_cpu_cores: f64,
+    #[schemars(description = "Total memory capacity of all nodes in GB (as in get_cluster_capacity)")]
+    pub total_memory_gb: f64,
+    #[schemars(description = "CPU capacity of cordoned and not-ready nodes, which takes no new pods, in cores")]
+    pub stuck_cpu_cores: f64,
+    #[schemars(description = "Memory capacity of cordoned and not-ready nodes, which takes no new pods, in GB")]
+    pub stuck_memory_gb: f64,
+    #[schemars(description = "Explanation of the capacity by node state")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct NamespaceHeadroomParams {
+    #[serde(default)]
+    #[schemars(description = "Weight of each namespace in the fair share, by name (default: 1 for every namespace; each must exist)")]
+    pub weights: Option<BTreeMap<String, f64>>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NamespaceHeadroom {
+    #[schemars(description = "Namespace name")]
+    pub namespace: String,
+    #[schemars(description = "Weight of the namespace in the fair share")]
+    pub weight: f64,
+    #[schemars(description = "Fair share of the cluster CPU in cores: total CPU times weight over the summed weights")]
+    pub fair_share_cpu_cores: f64,
+    #[schemars(description = "Fair share of the cluster memory in GB: total memory times weight over the summed weights")]
+    pub fair_share_memory_gb: f64,
+    #[schemars(description = "CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Memory requests in GB")]
+    pub memory_requests_gb: f64,
+    #[schemars(description = "Fair share minus CPU requests in cores; negative when over the fair share")]
+    pub cpu_headroom_cores: f64,
+    #[schemars(description = "Fair share minus memory requests in GB; negative when over the fair share")]
+    pub memory_headroom_gb: f64,
+    #[schemars(description = "Whether CPU or memory requests exceed the fair share")]
+    pub over_fair_share: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct NamespaceHeadroomResponse {
+    #[schemars(description = "Namespaces sorted by the smaller fraction of fair share left (ascending), most constrained first")]
+    pub namespaces: Vec<NamespaceHeadroom>,
+    #[schemars(description = "Total CPU of all nodes in cores, divided into the fair shares (as in get_cluster_capacity)")]
+    pub total_cpu_cores: f64,
+    #[schemars(description = "Total memory of all nodes in GB, divided into the fair shares (as in get_cluster_capacity)")]
+    pub total_memory_gb: f64,
+    #[schemars(description = "Sum of the namespace weights")]
+    pub total_weight: f64,
+    #[schemars(description = "Number of namespaces over their fair share")]
+    pub over_fair_share_count: usize,
+    #[schemars(description = "Explanation of the namespace headroom")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct RecommendNodeSizeParams {
+    #[serde(default)]
+    #[schemars(description = "Pod spec to host, as in a Pod's spec field; its effective requests count init containers, sidecars and overhead")]
+    pub pod_spec: Option<serde_json::Value>,
+    #[serde(default)]
+    #[schemars(description = "CPU in cores to host, instead of pod_spec (requires memory_gb)")]
+    pub cpu_cores: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Memory in GB to host, instead of pod_spec (requires cpu_cores)")]
+    pub memory_gb: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct RecommendNodeSizeResponse {
+    #[schemars(description = "CPU in cores of the workload (effective requests of pod_spec, or cpu_cores)")]
+    pub workload_cpu_cores: f64,
+    #[schemars(description = "Memory in GB of the workload (effective requests of pod_spec, or memory_gb)")]
+    pub workload_memory_gb: f64,
+    #[schemars(description = "CPU in cores DaemonSet pods reserve on every node, sampled from the cluster")]
+    pub daemonset_cpu_overhead_cores: f64,
+    #[schemars(description = "Memory in GB DaemonSet pods reserve on every node, sampled from the cluster")]
+    pub daemonset_memory_overhead_gb: f64,
+    #[schemars(description = "Minimum allocatable CPU in cores: workload plus DaemonSet overhead")]
+    pub min_allocatable_cpu_cores: f64,
+    #[schemars(description = "Minimum allocatable memory in GB: workload plus DaemonSet overhead")]
+    pub min_allocatable_memory_gb: f64,
+    #[schemars(description = "CPU in cores each node keeps for the kubelet and OS (node_reserved_cpu_cores setting)")]
+    pub reserved_cpu_cores: f64,
+    #[schemars(description = "Memory in GB each node keeps for the kubelet and OS (node_reserved_memory_gb setting)")]
+    pub reserved_memory_gb: f64,
+    #[schemars(description = "Minimum node CPU capacity in cores to provision: minimum allocatable plus the reserve")]
+    pub min_capacity_cpu_cores: f64,
+    #[schemars(description = "Minimum node memory capacity in GB to provision: minimum allocatable plus the reserve")]
+    pub min_capacity_memory_gb: f64,
+    #[schemars(description = "Existing nodes whose allocatable is at least the minimum allocatable")]
+    pub large_enough_nodes: Vec<String>,
+    #[schemars(description = "Explanation of the node size recommendation")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct OrphanedPodsParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct OrphanedPod {
+    #[schemars(description = "Pod name")]
+    pub name: String,
+    #[schemars(description = "Pod namespace")]
+    pub namespace: String,
+    #[schemars(description = "Missing node named in spec.nodeName")]
+    pub node: String,
+    #[schemars(description = "Pod phase as last reported (often stale, since no kubelet updates it)")]
+    pub phase: String,
+    #[schemars(description = "CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Memory requests in GB")]
+    pub memory_requests_gb: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct OrphanedPodsResponse {
+    #[schemars(description = "Non-terminal pods bound to a node missing from the node list, by node then namespace and name")]
+    pub pods: Vec<OrphanedPod>,
+    #[schemars(description = "Distinct missing nodes the pods point at")]
+    pub missing_nodes: Vec<String>,
+    #[schemars(description = "Summed CPU requests of the orphaned pods in cores")]
+    pub total_cpu_requests_cores: f64,
+    #[schemars(description = "Summed memory requests of the orphaned pods in GB")]
+    pub total_memory_requests_gb: f64,
+    #[schemars(description = "Explanation of the orphaned pods")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct NamespaceDownsizeParams {
+    #[serde(default)]
+    #[schemars(description = "Only recommend for this namespace (default: every namespace holding pods)")]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Headroom in percent kept above observed usage (default: 20)")]
+    pub headroom_percent: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+/// Where the usage a downsize recommendation keeps headroom over comes from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, schemars::JsonSchema)]
+pub enum UsageSource {
+    /// Live usage from metrics-server
+    Metrics,
+    /// Limits (requests where no limit is set) as the ceiling usage cannot exceed, without metrics
+    Limits,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NamespaceDownsize {
+    #[schemars(description = "Namespace name")]
+    pub namespace: String,
+    #[schemars(description = "Number of non-terminal pods")]
+    pub pod_count: usize,
+    #[schemars(description = "CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Memory requests in GB")]
+    pub memory_requests_gb: f64,
+    #[schemars(description = "Observed CPU usage in cores, or the limit ceiling without metrics")]
+    pub cpu_usage_cores: f64,
+    #[schemars(description = "Observed memory usage in GB, or the limit ceiling without metrics")]
+    pub memory_usage_gb: f64,
+    #[schemars(description = "CPU requests that keep the headroom over each pod's usage, never above its current requests")]
+    pub recommended_cpu_requests_cores: f64,
+    #[schemars(description = "Memory requests that keep the headroom over each pod's usage, never above its current requests")]
+    pub recommended_memory_requests_gb: f64,
+    #[schemars(description = "CPU in cores freed by the recommendation")]
+    pub cpu_reducible_cores: f64,
+    #[schemars(description = "Memory in GB freed by the recommendation")]
+    pub memory_reducible_gb: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct NamespaceDownsizeResponse {
+    #[schemars(description = "Namespaces by CPU reducible (descending), then memory reducible")]
+    pub namespaces: Vec<NamespaceDownsize>,
+    #[schemars(description = "Headroom in percent kept above observed usage")]
+    pub headroom_percent: f64,
+    #[schemars(description = "Usage source: Metrics, or Limits when metrics-server is unavailable")]
+    pub usage_source: UsageSource,
+    #[schemars(description = "CPU in cores freed across the namespaces")]
+    pub total_cpu_reducible_cores: f64,
+    #[schemars(description = "Memory in GB freed across the namespaces")]
+    pub total_memory_reducible_gb: f64,
+    #[schemars(description = "Explanation of the downsize recommendation")]
+    pub explanation: String,
+}
+
+/// Part of the report built by get_full_report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, schemars::JsonSchema)]
+pub enum ReportSection {
+    /// Cluster capacity, as get_cluster_capacity
+    #[serde(alias = "capacity")]
+    Capacity,
+    /// Per-node breakdown, as get_node_breakdown
+    #[serde(alias = "nodes")]
+    Nodes,
+    /// Per-namespace usage, as get_namespace_usage
+    #[serde(alias = "namespaces")]
+    Namespaces,
+    /// Largest pods by CPU requests, as get_pod_resource_stats
+    #[serde(alias = "top_pods")]
+    TopPods,
+    /// Counts of pending, orphaned and stuck pods and of overpacked, cordoned and NotReady nodes
+    #[serde(alias = "health")]
+    Health,
+}
+
+impl ReportSection {
+    const ALL: [ReportSection; 5] = [
+        ReportSection::Capacity,
+        ReportSection::Nodes,
+        ReportSection::Namespaces,
+        ReportSection::TopPods,
+        ReportSection::Health,
+    ];
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct FullReportParams {
+    #[serde(default)]
+    #[schemars(description = "Sections to include: Capacity, Nodes, Namespaces, TopPods, Health (default: all)")]
+    pub sections: Option<Vec<ReportSection>>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanations: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct HealthSignals {
+    #[schemars(description = "Pods waiting for a node (gated pods excluded)")]
+    pub pending_pod_count: usize,
+    #[schemars(description = "Non-terminal pods bound to a node that no longer exists")]
+    pub orphaned_pod_count: usize,
+    #[schemars(description = "Pods terminating for longer than the default grace period of find_stuck_pods")]
+    pub stuck_pod_count: usize,
+    #[schemars(description = "Nodes whose pod requests exceed their allocatable CPU or memory")]
+    pub overpacked_node_count: usize,
+    #[schemars(description = "Cordoned (unschedulable) nodes")]
+    pub cordoned_node_count: usize,
+    #[schemars(description = "Nodes whose Ready condition is not True")]
+    pub not_ready_node_count: usize,
+    #[schemars(description = "Explanation of the health signals")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct FullReportResponse {
+    #[schemars(description = "Sections included, in report order")]
+    pub sections: Vec<ReportSection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Cluster capacity (Capacity section)")]
+    pub capacity: Option<ClusterCapacityResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Per-node breakdown sorted by name (Nodes section)")]
+    pub nodes: Option<NodeBreakdownResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Per-namespace usage (Namespaces section)")]
+    pub namespaces: Option<NamespaceUsageResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Largest pods by CPU requests (TopPods section)")]
+    pub top_pods: Option<PodResourceStatsResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Health signals (Health section)")]
+    pub health: Option<HealthSignals>,
+    #[schemars(description = "One line per included section")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct GatedPodsParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct GatedPod {
+    #[schemars(description = "Pod name")]
+    pub name: String,
+    #[schemars(description = "Pod namespace")]
+    pub namespace: String,
+    #[schemars(description = "Names of the scheduling gates still set, in spec order")]
+    pub gates: Vec<String>,
+    #[schemars(description = "CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Memory requests in GB")]
+    pub memory_requests_gb: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct GatedPodsResponse {
+    #[schemars(description = "Unbound, non-terminal pods with scheduling gates, by namespace then name")]
+    pub pods: Vec<GatedPod>,
+    #[schemars(description = "Number of gated pods per gate name")]
+    pub gates: BTreeMap<String, usize>,
+    #[schemars(description = "Summed CPU requests of the gated pods in cores")]
+    pub total_cpu_requests_cores: f64,
+    #[schemars(description = "Summed memory requests of the gated pods in GB")]
+    pub total_memory_requests_gb: f64,
+    #[schemars(description = "Explanation of the gated pods")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct BurstProjectionParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodeBurstProjection {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Allocatable CPU in cores")]
+    pub allocatable_cpu_cores: f64,
+    #[schemars(description = "Allocatable memory in GB")]
+    pub allocatable_memory_gb: f64,
+    #[schemars(description = "Summed CPU limits of the pods bound to the node in cores (the request where a container sets no limit)")]
+    pub cpu_limits_cores: f64,
+    #[schemars(description = "Summed memory limits of the pods bound to the node in GB (the request where a container sets no limit)")]
+    pub memory_limits_gb: f64,
+    #[schemars(description = "CPU limits as a percentage of allocatable")]
+    pub cpu_burst_percent: f64,
+    #[schemars(description = "Memory limits as a percentage of allocatable")]
+    pub memory_burst_percent: f64,
+    #[schemars(description = "CPU the pods would use at full burst, capped at allocatable, in cores")]
+    pub projected_cpu_cores: f64,
+    #[schemars(description = "Memory the pods would use at full burst, capped at allocatable, in GB")]
+    pub projected_memory_gb: f64,
+    #[schemars(description = "CPU limits beyond allocatable in cores (0 when they fit)")]
+    pub cpu_excess_cores: f64,
+    #[schemars(description = "Memory limits beyond allocatable in GB (0 when they fit)")]
+    pub memory_excess_gb: f64,
+    #[schemars(description = "Whether full burst would exceed allocatable CPU or memory")]
+    pub oversubscribed: bool,
+    #[schemars(description = "Non-terminal pods bound to the node")]
+    pub pod_count: usize,
+    #[schemars(description = "Pods with a container lacking a CPU or memory limit; they can burst beyond the projection")]
+    pub unlimited_pod_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct BurstProjectionResponse {
+    #[schemars(description = "Nodes by highest CPU or memory burst percentage (descending)")]
+    pub nodes: Vec<NodeBurstProjection>,
+    #[schemars(description = "Number of nodes whose limits exceed allocatable CPU or memory")]
+    pub oversubscribed_node_count: usize,
+    #[schemars(description = "Cluster CPU use at full burst, each node capped at its allocatable, in cores")]
+    pub projected_cpu_cores: f64,
+    #[schemars(description = "Cluster memory use at full burst, each node capped at its allocatable, in GB")]
+    pub projected_memory_gb: f64,
+    #[schemars(description = "Summed CPU limits beyond allocatable over all nodes in cores")]
+    pub total_cpu_excess_cores: f64,
+    #[schemars(description = "Summed memory limits beyond allocatable over all nodes in GB")]
+    pub total_memory_excess_gb: f64,
+    #[schemars(description = "Explanation of the burst projection")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct QuotaUsageParams {
+    #[schemars(description = "Only report the quotas of this namespace (default: all namespaces; blank means the server's DEFAULT_NAMESPACE)")]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct QuotaResourceUsage {
+    #[schemars(description = "Quota resource name, e.g. requests.cpu, limits.memory or pods")]
+    pub resource: String,
+    #[schemars(description = "Hard limit (cores for CPU, GB for memory, a count for pods)")]
+    pub hard: f64,
+    #[schemars(description = "Amount used by the pods the quota's scopes select, in the same unit")]
+    pub used: f64,
+    #[schemars(description = "Hard limit minus used (0 when exhausted)")]
+    pub remaining: f64,
+    #[schemars(description = "Used as a percentage of the hard limit")]
+    pub utilization_percent: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Used amount in the quota's status as last computed by the API server, for comparison")]
+    pub reported_used: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NamespaceQuota {
+    #[schemars(description = "Namespace of the quota")]
+    pub namespace: String,
+    #[schemars(description = "ResourceQuota name")]
+    pub name: String,
+    #[schemars(description = "Scopes from spec.scopes and spec.scopeSelector restricting which pods count, e.g. 'BestEffort' or 'PriorityClass In (high)'; empty when every pod counts")]
+    pub scopes: Vec<String>,
+    #[schemars(description = "Non-terminal pods of the namespace the scopes select")]
+    pub matching_pod_count: usize,
+    #[schemars(description = "Non-terminal pods of the namespace")]
+    pub namespace_pod_count: usize,
+    #[schemars(description = "CPU, memory and pod count resources of spec.hard with their usage, by highest utilization")]
+    pub resources: Vec<QuotaResourceUsage>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct QuotaUsageResponse {
+    #[schemars(description = "ResourceQuotas by highest utilization of any resource (descending)")]
+    pub quotas: Vec<NamespaceQuota>,
+    #[schemars(description = "Explanation of the quota usage")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct NodePackingParams {
+    #[schemars(description = "Flag nodes whose CPU or memory utilization reaches this percentage of allocatable as over-packed (default: 85)")]
+    pub over_packed_percent: Option<f64>,
+    #[schemars(description = "Flag nodes whose CPU and memory utilization both stay below this percentage of allocatable as under-packed (default: 30)")]
+    pub under_packed_percent: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+/// How full a node is packed relative to the get_node_packing thresholds
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, schemars::JsonSchema)]
+pub enum PackingState {
+    /// CPU or memory utilization at or above the over-packed threshold
+    OverPacked,
+    /// Between the thresholds
+    Normal,
+    /// CPU and memory utilization both below the under-packed threshold
+    UnderPacked,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodePacking {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Non-terminal pods bound to the node")]
+    pub pod_count: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Pods the node allows (allocatable pods), when it reports one")]
+    pub max_pods: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Pod count as a percentage of max_pods")]
+    pub pod_density_percent: Option<f64>,
+    #[schemars(description = "Average effective CPU requests per pod in cores (0 without pods)")]
+    pub avg_pod_cpu_cores: f64,
+    #[schemars(description = "Average effective memory requests per pod in GB (0 without pods)")]
+    pub avg_pod_memory_gb: f64,
+    #[schemars(description = "Effective CPU requests as a percentage of allocatable CPU")]
+    pub cpu_utilization_percent: f64,
+    #[schemars(description = "Effective memory requests as a percentage of allocatable memory")]
+    pub memory_utilization_percent: f64,
+    #[schemars(description = "Mean of the CPU and memory utilization; a node is packed efficiently when both are high")]
+    pub packing_efficiency_percent: f64,
+    #[schemars(description = "Gap between CPU and memory utilization in percentage points; a large gap strands the less used resource")]
+    pub imbalance_percent: f64,
+    #[schemars(description = "OverPacked, Normal or UnderPacked")]
+    pub packing: PackingState,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct NodePackingResponse {
+    #[schemars(description = "Nodes by packing efficiency (descending)")]
+    pub nodes: Vec<NodePacking>,
+    #[schemars(description = "Over-packed threshold used, in percent")]
+    pub over_packed_percent: f64,
+    #[schemars(description = "Under-packed threshold used, in percent")]
+    pub under_packed_percent: f64,
+    #[schemars(description = "Names of the over-packed nodes")]
+    pub over_packed_nodes: Vec<String>,
+    #[schemars(description = "Names of the under-packed nodes")]
+    pub under_packed_nodes: Vec<String>,
+    #[schemars(description = "Packing efficiency of the whole cluster: summed requests over summed allocatable, averaged across CPU and memory")]
+    pub cluster_packing_efficiency_percent: f64,
+    #[schemars(description = "Explanation of the node packing")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct StrandedGpusParams {
+    #[schemars(description = "CPU cores a GPU pod requests per GPU (default: the median of the running GPU pods, else each node's allocatable CPU divided by its GPUs)")]
+    pub cpu_cores_per_gpu: Option<f64>,
+    #[schemars(description = "Memory in GB a GPU pod requests per GPU (default: the median of the running GPU pods, else each node's allocatable memory divided by its GPUs)")]
+    pub memory_gb_per_gpu: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct StrandedGpuNode {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Allocatable GPUs on the node")]
+    pub total_gpus: u64,
+    #[schemars(description = "GPUs requested by the running pods on the node")]
+    pub requested_gpus: u64,
+    #[schemars(description = "GPUs not requested by any pod (total minus requested)")]
+    pub idle_gpus: u64,
+    #[schemars(description = "Effective CPU requests as a percentage of allocatable CPU")]
+    pub cpu_utilization_percent: f64,
+    #[schemars(description = "Effective memory requests as a percentage of allocatable memory")]
+    pub memory_utilization_percent: f64,
+    #[schemars(description = "Allocatable minus requested CPU in cores")]
+    pub available_cpu_cores: f64,
+    #[schemars(description = "Allocatable minus requested memory in GB")]
+    pub available_memory_gb: f64,
+    #[schemars(description = "CPU cores assumed per GPU")]
+    pub cpu_cores_per_gpu: f64,
+    #[schemars(description = "Memory in GB assumed per GPU")]
+    pub memory_gb_per_gpu: f64,
+    #[schemars(description = "Idle GPUs that the free CPU and memory can still feed")]
+    pub schedulable_idle_gpus: u64,
+    #[schemars(description = "Idle GPUs that cannot be used because CPU or memory ran out first")]
+    pub stranded_gpus: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Resource that strands the GPUs ('cpu' or 'memory'), when any are stranded")]
+    pub limiting_resource: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct StrandedGpusResponse {
+    #[schemars(description = "GPU nodes, most stranded GPUs first")]
+    pub nodes: Vec<StrandedGpuNode>,
+    #[schemars(description = "Names of the nodes with stranded GPUs")]
+    pub stranded_nodes: Vec<String>,
+    #[schemars(description = "Idle GPUs across all nodes")]
+    pub idle_gpus: u64,
+    #[schemars(description = "Idle GPUs that cannot be used because their node ran out of CPU or memory")]
+    pub stranded_gpus: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "CPU cores per GPU used for every node (absent when each node's own share is used)")]
+    pub cpu_cores_per_gpu: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Memory in GB per GPU used for every node (absent when each node's own share is used)")]
+    pub memory_gb_per_gpu: Option<f64>,
+    #[schemars(description = "Explanation of the stranded GPUs")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct RequestCoverageParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NamespaceRequestCoverage {
+    #[schemars(description = "Namespace")]
+    pub namespace: String,
+    #[schemars(description = "Non-terminal pods in the namespace")]
+    pub pod_count: usize,
+    #[schemars(description = "Pods whose every container requests both CPU and memory")]
+    pub compliant_pod_count: usize,
+    #[schemars(description = "Pods with at least one container missing a CPU or memory request")]
+    pub non_compliant_pod_count: usize,
+    #[schemars(description = "Compliant pods as a percentage of all pods")]
+    pub coverage_percent: f64,
+    #[schemars(description = "Pods whose every container requests CPU, as a percentage")]
+    pub cpu_coverage_percent: f64,
+    #[schemars(description = "Pods whose every container requests memory, as a percentage")]
+    pub memory_coverage_percent: f64,
+    #[schemars(description = "Names of the non-compliant pods")]
+    pub non_compliant_pods: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct RequestCoverageResponse {
+    #[schemars(description = "Non-terminal pods in the cluster")]
+    pub pod_count: usize,
+    #[schemars(description = "Pods whose every container requests both CPU and memory")]
+    pub compliant_pod_count: usize,
+    #[schemars(description = "Pods with at least one container missing a CPU or memory request")]
+    pub non_compliant_pod_count: usize,
+    #[schemars(description = "Compliant pods as a percentage of all pods (100 without pods)")]
+    pub coverage_percent: f64,
+    #[schemars(description = "Pods whose every container requests CPU, as a percentage")]
+    pub cpu_coverage_percent: f64,
+    #[schemars(description = "Pods whose every container requests memory, as a percentage")]
+    pub memory_coverage_percent: f64,
+    #[schemars(description = "Namespaces by coverage (ascending), so the least compliant come first")]
+    pub namespaces: Vec<NamespaceRequestCoverage>,
+    #[schemars(description = "Explanation of the request coverage")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct RequestRatioMismatchParams {
+    #[schemars(description = "Deviation in percent between the pod and node memory-per-core ratios tolerated before flagging a mismatch (default: 25)")]
+    pub tolerance_percent: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct RequestRatioMismatchResponse {
+    #[schemars(description = "CPU requested by the running pods in cores")]
+    pub requested_cpu_cores: f64,
+    #[schemars(description = "Memory requested by the running pods in GB")]
+    pub requested_memory_gb: f64,
+    #[schemars(description = "Allocatable CPU of all nodes in cores")]
+    pub allocatable_cpu_cores: f64,
+    #[schemars(description = "Allocatable memory of all nodes in GB")]
+    pub allocatable_memory_gb: f64,
+    #[schemars(description = "GB of memory requested per requested core")]
+    pub pod_memory_gb_per_core: f64,
+    #[schemars(description = "GB of allocatable memory per allocatable core")]
+    pub node_memory_gb_per_core: f64,
+    #[schemars(description = "Pod ratio divided by node ratio: above 1 the pods are memory-heavy for the nodes, below 1 CPU-heavy")]
+    pub mismatch_factor: f64,
+    #[schemars(description = "Whether the ratios deviate by more than tolerance_percent")]
+    pub mismatched: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Resource left over once pods of this shape fill the cluster ('cpu' or 'memory'), when mismatched")]
+    pub stranded_resource: Option<String>,
+    #[schemars(description = "Share of the stranded resource's allocatable left unused once the other runs out, in percent")]
+    pub stranded_percent: f64,
+    #[schemars(description = "Common instance family closest to the pod ratio: compute-optimized (2 GB/core), general-purpose (4) or memory-optimized (8)")]
+    pub recommended_node_family: String,
+    #[schemars(description = "What to change: the node shape or the pod requests")]
+    pub recommendation: String,
+    #[schemars(description = "Explanation of the ratio comparison")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct CapacityInPodUnitsParams {
+    #[schemars(description = "CPU request of the reference pod in cores")]
+    pub cpu_cores: f64,
+    #[schemars(description = "Memory request of the reference pod in GB")]
+    pub memory_gb: f64,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodePodUnits {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Reference pods the node's allocatable holds, by the binding resource")]
+    pub total_units: f64,
+    #[schemars(description = "Reference pods' worth of requests already placed, by the resource they use most of")]
+    pub allocated_units: f64,
+    #[schemars(description = "Whole reference pods that still fit")]
+    pub available_units: u64,
+    #[schemars(description = "Resource that limits the available units: cpu or memory")]
+    pub binding_resource: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct CapacityInPodUnitsResponse {
+    #[schemars(description = "CPU request of the reference pod in cores")]
+    pub cpu_cores: f64,
+    #[schemars(description = "Memory request of the reference pod in GB")]
+    pub memory_gb: f64,
+    #[schemars(description = "Reference pods the cluster's allocatable holds, summed node by node")]
+    pub total_units: f64,
+    #[schemars(description = "Reference pods' worth of requests already placed")]
+    pub allocated_units: f64,
+    #[schemars(description = "Whole reference pods that still fit, summed node by node")]
+    pub available_units: u64,
+    #[schemars(description = "Nodes where CPU limits the available units")]
+    pub cpu_bound_nodes: usize,
+    #[schemars(description = "Nodes where memory limits the available units")]
+    pub memory_bound_nodes: usize,
+    #[schemars(description = "Per node, most available units first")]
+    pub nodes: Vec<NodePodUnits>,
+    #[schemars(description = "Explanation of the capacity in reference pods")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct FindLargePodsParams {
+    #[schemars(description = "List pods requesting at least this many CPU cores")]
+    pub min_cpu_cores: Option<f64>,
+    #[schemars(description = "List pods requesting at least this much memory in GB")]
+    pub min_memory_gb: Option<f64>,
+    #[schemars(description = "Only search this namespace (default: all namespaces; blank means the server's DEFAULT_NAMESPACE)")]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct LargePod {
+    #[schemars(description = "Pod name")]
+    pub name: String,
+    #[schemars(description = "Namespace")]
+    pub namespace: String,
+    #[schemars(description = "Node name (or 'unscheduled')")]
+    pub node: String,
+    #[schemars(description = "Effective CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Effective memory requests in GB")]
+    pub memory_requests_gb: f64,
+    #[schemars(description = "Thresholds the pod reaches: cpu, memory or both")]
+    pub exceeds: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct FindLargePodsResponse {
+    #[schemars(description = "Every pod at or above a threshold, by CPU requests and then memory requests (descending)")]
+    pub pods: Vec<LargePod>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "CPU threshold used, in cores")]
+    pub min_cpu_cores: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Memory threshold used, in GB")]
+    pub min_memory_gb: Option<f64>,
+    #[schemars(description = "Non-terminal pods searched")]
+    pub searched_pod_count: usize,
+    #[schemars(description = "Explanation of the large pods")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct PodAccountingParams {
+    #[schemars(description = "Pod name")]
+    pub name: String,
+    #[serde(default)]
+    #[schemars(description = "Namespace of the pod (default: the server's DEFAULT_NAMESPACE, if configured)")]
+    pub namespace: String,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+/// How a container takes part in the effective request of its pod
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, schemars::JsonSchema)]
+pub enum ContainerRole {
+    /// App container: requests are summed
+    App,
+    /// Native sidecar (init container with restartPolicy Always): runs alongside the app containers,
+    /// so its requests add to theirs and to every later init container
+    Sidecar,
+    /// Ordinary init container: runs alone before the app containers, so only the largest counts
+    Init,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct ContainerAccounting {
+    #[schemars(description = "Container name")]
+    pub name: String,
+    #[schemars(description = "App, Sidecar or Init")]
+    pub role: ContainerRole,
+    #[schemars(description = "Requests as written, by resource name")]
+    pub raw_requests: BTreeMap<String, String>,
+    #[schemars(description = "Limits as written, by resource name")]
+    pub raw_limits: BTreeMap<String, String>,
+    #[schemars(description = "Whether the container status reports resources after an in-place resize; its requests and limits each win over the spec's")]
+    pub from_resize_status: bool,
+    #[schemars(description = "Parsed CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Parsed memory requests in GB")]
+    pub memory_requests_gb: f64,
+    #[schemars(description = "Parsed CPU limits in cores (0 when unset)")]
+    pub cpu_limits_cores: f64,
+    #[schemars(description = "Parsed memory limits in GB (0 when unset)")]
+    pub memory_limits_gb: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Init containers only: CPU while it runs, its requests plus the sidecars started before it")]
+    pub init_phase_cpu_cores: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Init containers only: memory while it runs, its requests plus the sidecars started before it")]
+    pub init_phase_memory_gb: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct PodAccountingResponse {
+    #[schemars(description = "Pod name")]
+    pub name: String,
+    #[schemars(description = "Namespace")]
+    pub namespace: String,
+    #[schemars(description = "Node name (or 'unscheduled')")]
+    pub node: String,
+    #[schemars(description = "How get_cluster_capacity counts the pod: Allocated (bound to a node), Pending, Gated or Ignored (Succeeded or Failed)")]
+    pub counted_as: String,
+    #[schemars(description = "Containers in spec order: app containers, then init containers")]
+    pub containers: Vec<ContainerAccounting>,
+    #[schemars(description = "Summed requests of the app containers in cores")]
+    pub app_cpu_cores: f64,
+    #[schemars(description = "Summed requests of the app containers in GB")]
+    pub app_memory_gb: f64,
+    #[schemars(description = "Summed requests of the native sidecars in cores")]
+    pub sidecar_cpu_cores: f64,
+    #[schemars(description = "Summed requests of the native sidecars in GB")]
+    pub sidecar_memory_gb: f64,
+    #[schemars(description = "Largest init phase CPU in cores (0 without init containers)")]
+    pub init_max_cpu_cores: f64,
+    #[schemars(description = "Largest init phase memory in GB (0 without init containers)")]
+    pub init_max_memory_gb: f64,
+    #[schemars(description = "Pod overhead (spec.overhead, from the RuntimeClass) in cores")]
+    pub overhead_cpu_cores: f64,
+    #[schemars(description = "Pod overhead (spec.overhead, from the RuntimeClass) in GB")]
+    pub overhead_memory_gb: f64,
+    #[schemars(description = "Effective CPU request used in aggregation: max(app + sidecars, init max) + overhead, in cores")]
+    pub effective_cpu_cores: f64,
+    #[schemars(description = "Effective memory request used in aggregation: max(app + sidecars, init max) + overhead, in GB")]
+    pub effective_memory_gb: f64,
+    #[schemars(description = "The calculation, one step per line")]
+    pub steps: Vec<String>,
+    #[schemars(description = "Explanation of the pod's accounting")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct ImageDistributionParams {
+    #[schemars(description = "Only count pods in this namespace (default: all namespaces; blank means the server's DEFAULT_NAMESPACE)")]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct ImageUsage {
+    #[schemars(description = "Container image reference as written in the pod spec")]
+    pub image: String,
+    #[schemars(description = "Number of pods with at least one container (or init container) using this image")]
+    pub pod_count: usize,
+    #[schemars(description = "Namespaces of those pods, sorted")]
+    pub namespaces: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct ImageDistributionResponse {
+    #[schemars(description = "Namespace the pods were taken from, or None for all namespaces")]
+    pub namespace: Option<String>,
+    #[schemars(description = "Distinct images sorted by pod count (descending)")]
+    pub images: Vec<ImageUsage>,
+    #[schemars(description = "Number of distinct images")]
+    pub total_images: usize,
+    #[schemars(description = "Number of pods scanned")]
+    pub total_pods: usize,
+    #[schemars(description = "Explanation of the image distribution")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct OverheadBreakdownParams {
+    #[schemars(description = "Namespaces counted as system overhead (default: kube-system, kube-public, kube-node-lease, monitoring)")]
+    pub system_namespaces: Option<Vec<String>>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, schemars::JsonSchema)]
+pub struct ResourceShare {
+    #[schemars(description = "CPU requests in cores")]
+    pub cpu_cores: f64,
+    #[schemars(description = "Memory requests in GB")]
+    pub memory_gb: f64,
+    #[schemars(description = "CPU requests as a percentage of total cluster CPU")]
+    pub cpu_percent: f64,
+    #[schemars(description = "Memory requests as a percentage of total cluster memory")]
+    pub memory_percent: f64,
+    #[schemars(description = "Number of pods")]
+    pub pod_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct OverheadBreakdownResponse {
+    #[schemars(description = "Total cluster CPU in cores")]
+    pub total_cpu_cores: f64,
+    #[schemars(description = "Total cluster memory in GB")]
+    pub total_memory_gb: f64,
+    #[schemars(description = "Requests of pods in system namespaces or managed by DaemonSets")]
+    pub system: ResourceShare,
+    #[schemars(description = "Requests of all other (application) pods")]
+    pub workloads: ResourceShare,
+    #[schemars(description = "Namespaces counted as system overhead")]
+    pub system_namespaces: Vec<String>,
+    #[schemars(description = "Explanation of the overhead breakdown")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct StuckPodsParams {
+    #[schemars(description = "Seconds a pod may stay terminating before it counts as stuck (default: 300)")]
+    pub grace_period_seconds: Option<u64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct StuckPod {
+    #[schemars(description = "Pod name")]
+    pub name: String,
+    #[schemars(description = "Namespace")]
+    pub namespace: String,
+    #[schemars(description = "Node name (or 'unscheduled')")]
+    pub node: String,
+    #[schemars(description = "Pod phase")]
+    pub phase: String,
+    #[schemars(description = "When deletion was requested (RFC 3339)")]
+    pub deletion_timestamp: String,
+    #[schemars(description = "Seconds since deletion was requested")]
+    pub terminating_seconds: i64,
+    #[schemars(description = "Finalizers still blocking deletion")]
+    pub finalizers: Vec<String>,
+    #[schemars(description = "Effective CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Effective memory requests in GB")]
+    pub memory_requests_gb: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct StuckPodsResponse {
+    #[schemars(description = "Pods terminating for longer than the grace period, longest first")]
+    pub pods: Vec<StuckPod>,
+    #[schemars(description = "Number of stuck pods")]
+    pub total_stuck: usize,
+    #[schemars(description = "CPU requests in cores still held by stuck pods")]
+    pub held_cpu_cores: f64,
+    #[schemars(description = "Memory requests in GB still held by stuck pods")]
+    pub held_memory_gb: f64,
+    #[schemars(description = "Grace period in seconds used")]
+    pub grace_period_seconds: u64,
+    #[schemars(description = "Explanation of the stuck pods")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct NMinusOneParams {
+    #[serde(default)]
+    #[schemars(description = "Number of largest nodes to lose (default: 1, i.e. N-1)")]
+    pub nodes_lost: Option<u32>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct NMinusOneResponse {
+    #[schemars(description = "Number of nodes lost")]
+    pub nodes_lost: usize,
+    #[schemars(description = "Names of the lost nodes (the largest by allocatable CPU, then memory)")]
+    pub lost_nodes: Vec<String>,
+    #[schemars(description = "Whether every pod of the lost nodes fits on the remaining schedulable nodes")]
+    pub survives: bool,
+    #[schemars(description = "Number of remaining nodes")]
+    pub remaining_nodes: usize,
+    #[schemars(description = "Allocatable CPU of the remaining nodes in cores")]
+    pub remaining_cpu_cores: f64,
+    #[schemars(description = "Allocatable memory of the remaining nodes in GB")]
+    pub remaining_memory_gb: f64,
+    #[schemars(description = "CPU requests of all pods that must run after the loss in cores")]
+    pub requested_cpu_cores: f64,
+    #[schemars(description = "Memory requests of all pods that must run after the loss in GB")]
+    pub requested_memory_gb: f64,
+    #[schemars(description = "CPU requests as a percentage of the remaining allocatable CPU")]
+    pub cpu_utilization_percent: f64,
+    #[schemars(description = "Memory requests as a percentage of the remaining allocatable memory")]
+    pub memory_utilization_percent: f64,
+    #[schemars(description = "Number of non-DaemonSet pods that ran on the lost nodes")]
+    pub displaced_pods: usize,
+    #[schemars(description = "Displaced pods (namespace/name) that fit on no remaining node")]
+    pub unplaceable_pods: Vec<String>,
+    #[schemars(description = "Explanation of the node loss check")]
+    pub explanation: String,
+}
+
+/// Ranking used by get_pod_resource_stats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub enum PodSortBy {
+    /// Sum of container CPU requests
+    #[default]
+    #[serde(alias = "cpu_requests")]
+    CpuRequests,
+    /// Live CPU usage from metrics.k8s.io
+    #[serde(alias = "cpu_usage")]
+    CpuUsage,
+    /// Live memory usage from metrics.k8s.io
+    #[serde(alias = "memory_usage")]
+    MemoryUsage,
+}
+
+impl PodSortBy {
+    fn uses_metrics(self) -> bool {
+        self != PodSortBy::CpuRequests
+    }
+
+    /// Structured form of the ranking, always descending
+    fn sort_spec(self) -> SortSpec {
+        let (key, basis) = match self {
+            PodSortBy::CpuRequests => ("cpu_requests_millicores", ResourceBasis::Requests),
+            PodSortBy::CpuUsage => ("cpu_usage_millicores", ResourceBasis::Usage),
+            PodSortBy::MemoryUsage => ("memory_usage_mb", ResourceBasis::Usage),
+        };
+        SortSpec { key: key.to_string(), order: SortOrder::Descending, basis }
+    }
+}
+
+/// Direction of a sort
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct SortSpec {
+    #[schemars(description = "Field of each item the list is ordered by; its name carries the unit (e.g. cpu_requests_millicores)")]
+    pub key: String,
+    #[schemars(description = "Ascending or Descending")]
+    pub order: SortOrder,
+    #[schemars(description = "What the key measures: Requests, Limits or Usage (live usage from metrics-server)")]
+    pub basis: ResourceBasis,
+}
+
+/// Output format of get_pod_resource_stats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub enum PodOutputFormat {
+    /// The `{status, data}` envelope with the full response
+    #[default]
+    #[serde(alias = "json")]
+    Json,
+    /// One JSON object per pod per line, without the envelope or any field but the pods
+    #[serde(alias = "json_lines", alias = "jsonl")]
+    JsonLines,
+}
+
+/// Live CPU (cores) and memory (GB) usage per pod, keyed by (namespace, name)
+type PodUsage = HashMap<(String, String), (f64, f64)>;
+
+/// Resource sets to fetch together with fetch_cluster_state
+#[derive(Clone, Copy, Default)]
+struct ClusterStateOptions<'a> {
+    nodes: bool,
+    pods: bool,
+    namespaces: bool,
+    pod_usage: bool,
+    /// Record a pod metrics failure in `metrics_error` instead of failing the fetch
+    metrics_optional: bool,
+    /// Bypass the watch and list caches
+    fresh: bool,
+    /// Reports the pods listed so far when they are paged from the API server
+    progress: Option<&'a Progress>,
+}
+
+/// Resource sets fetched by fetch_cluster_state; the sets not requested stay empty
+#[derive(Debug, Default)]
+struct ClusterState {
+    nodes: Vec<Node>,
+    pods: Vec<Pod>,
+    namespaces: Vec<String>,
+    pod_usage: PodUsage,
+    /// Why pod metrics could not be listed, with `metrics_optional`
+    metrics_error: Option<String>,
+    /// Age of the oldest TTL list cache entry the nodes and pods came from; None when they were listed
+    /// live or came from the always-current watch caches
+    cache_age: Option<Duration>,
+}
+
+impl ClusterState {
+    /// The pod usage, or why pod metrics could not be listed
+    fn take_pod_usage(&mut self) -> Result<PodUsage, String> {
+        match self.metrics_error.take() {
+            Some(e) => Err(e),
+            None => Ok(std::mem::take(&mut self.pod_usage)),
+        }
+    }
+}
+
+/// Live usage in bytes per node name
+type NodeUsageBytes = HashMap<String, i64>;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct PodResourceInfo {
+    #[schemars(description = "Pod name")]
+    pub name: String,
+    #[schemars(description = "Namespace")]
+    pub namespace: String,
+    #[schemars(description = "CPU requests in millicores")]
+    pub cpu_requests_millicores: i64,
+    #[schemars(description = "Memory requests in MB")]
+    pub memory_requests_mb: i64,
+    #[schemars(description = "CPU limits in millicores")]
+    pub cpu_limits_millicores: i64,
+    #[schemars(description = "Memory limits in MB")]
+    pub memory_limits_mb: i64,
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Pod phase, only set for terminal (Succeeded or Failed) pods listed with include_terminal")]
+    pub phase: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Original CPU request quantities of the containers (only with include_raw)")]
+    pub raw_cpu_request: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Original memory request quantities of the containers (only with include_raw)")]
+    pub raw_memory_request: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Original CPU limit quantities of the containers (only with include_raw)")]
+    pub raw_cpu_limit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Original memory limit quantities of the containers (only with include_raw)")]
+    pub raw_memory_limit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Live CPU usage in millicores (only when sorting by usage)")]
+    pub cpu_usage_millicores: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Live memory usage in MB (only when sorting by usage)")]
+    pub memory_usage_mb: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct PodResourceStatsParams {
+    #[serde(default)]
+    #[schemars(description = "Include the original Kubernetes quantity strings for each pod (default: false)")]
+    pub include_raw: bool,
+    #[serde(default)]
+    #[schemars(description = "Also list Succeeded and Failed pods, marked with their phase (default: false)")]
+    pub include_terminal: bool,
+    #[serde(default)]
+    #[schemars(description = "Sort by CpuRequests (default), CpuUsage or MemoryUsage; usage comes from metrics.k8s.io")]
+    pub sort_by: PodSortBy,
+    #[serde(default)]
+    #[schemars(description = "Json (default) for the full response, or JsonLines for one JSON object per pod per line (top_pods only: totals, sort, warnings and explanation are left out)")]
+    pub format: PodOutputFormat,
+    #[serde(default)]
+    #[schemars(description = "Warn about memory quantities without a unit that are implausibly small, e.g. '512' (bytes) meant as '512Mi' (default: false)")]
+    pub strict_quantities: bool,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct PodResourceStatsResponse {
+    #[schemars(description = "Top pods by resource consumption")]
+    pub top_pods: Vec<PodResourceInfo>,
+    #[schemars(description = "Total number of pods")]
+    pub total_pods: usize,
+    #[schemars(description = "Sort criteria used")]
+    pub sorted_by: String,
+    #[schemars(description = "Sort criteria used, structured: the key field of top_pods, order and basis")]
+    pub sort: SortSpec,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Whether live usage from metrics.k8s.io was available (only when sorting by usage)")]
+    pub metrics_available: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Why the requested usage sort fell back to CPU requests")]
+    pub warning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Memory quantities that look like a missing unit suffix (only with strict_quantities)")]
+    pub warnings: Option<Vec<String>>,
+    #[schemars(description = "Explanation of pod resource stats")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct CheckReplicaCapacityParams {
+    #[schemars(description = "Application or pod name pattern to find")]
+    pub app_name: String,
+    #[serde(default)]
+    #[schemars(description = "Namespace to search in (default: the server's DEFAULT_NAMESPACE, if configured)")]
+    pub namespace: String,
+    #[schemars(description = "Number of additional replicas needed")]
+    pub replica_count: i32,
+    #[serde(default)]
+    #[schemars(description = "Allocatable CPU in cores of a representative node for scale-up math (default: median of existing nodes)")]
+    pub node_cpu_cores: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Allocatable memory in GB of a representative node for scale-up math (default: median of existing nodes)")]
+    pub node_memory_gb: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Check whether the request fits after the autoscaler grows the node groups (default: false)")]
+    pub consider_autoscaling: bool,
+    #[serde(default)]
+    #[schemars(description = "Node group max-size config, required with consider_autoscaling (Cluster Autoscaler node groups or Karpenter provisioners)")]
+    pub node_groups: Option<Vec<NodeGroupLimit>>,
+    #[serde(default)]
+    #[schemars(description = "Subtract the requests of Pending pods waiting for a node from the available capacity before deciding (default: false)")]
+    pub subtract_pending_demand: bool,
+    #[serde(default)]
+    #[schemars(description = "PriorityClass the request runs at: capacity held by lower-priority pods counts as available, as the scheduler can preempt them")]
+    pub priority_class: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Match app_name against pod names with the generated ReplicaSet/pod hash suffixes stripped, exactly instead of as a substring (default: false)")]
+    pub normalize_names: bool,
+    #[serde(default)]
+    #[schemars(description = "Allocation policy cap in percent of the total: available is total * target% - allocated (default: 100)")]
+    pub target_utilization_percent: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "What existing pods hold: Requests (default), Limits for a conservative answer without overcommit, or Usage (live usage from metrics-server)")]
+    pub basis: ResourceBasis,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct CheckReplicaCapacityResponse {
+    #[schemars(description = "Whether replicas can fit in cluster")]
+    pub fits: bool,
+    #[schemars(description = "Name of the reference pod used for calculations")]
+    pub reference_pod: String,
+    #[schemars(description = "CPU required per replica in cores")]
+    pub cpu_per_replica_cores: f64,
+    #[schemars(description = "Memory required per replica in GB")]
+    pub memory_per_replica_gb: f64,
+    #[schemars(description = "Total CPU required for all replicas in cores")]
+    pub total_cpu_required_cores: f64,
+    #[schemars(description = "Total memory required for all replicas in GB")]
+    pub total_memory_required_gb: f64,
+    #[schemars(description = "Available CPU in cluster in cores")]
+    pub available_cpu_cores: f64,
+    #[schemars(description = "Available memory in cluster in GB")]
+    pub available_memory_gb: f64,
+    #[schemars(description = "Current number of matching pods")]
+    pub current_pod_count: usize,
+    #[schemars(description = "CPU utilization percentage after adding replicas")]
+    pub projected_cpu_utilization_percent: f64,
+    #[schemars(description = "Memory utilization percentage after adding replicas")]
+    pub projected_memory_utilization_percent: f64,
+    #[serde(default)]
+    #[schemars(description = "What existing pods were counted by: Requests, Limits or Usage")]
+    pub basis: ResourceBasis,
+    #[schemars(description = "Detailed explanation of capacity check")]
+    pub explanation: String,
+    #[schemars(description = "Scale-up recommendation when replicas do not fit")]
+    pub scale_up: Option<ScaleUpRecommendation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Whether the request fits once node groups scale up (only with consider_autoscaling when it does not fit now)")]
+    pub fits_after_scaleup: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Number of nodes the autoscaler must add for the request to fit")]
+    pub autoscale_nodes_needed: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Set when the reference pod has no CPU or memory requests (BestEffort) and capacity cannot be estimated")]
+    pub warning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Capacity held by lower-priority pods (only with priority_class)")]
+    pub preemption: Option<PreemptibleCapacity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Most additional replicas the reference pod's required pod anti-affinity allows (one per free topology domain)")]
+    pub anti_affinity_limit: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, schemars::JsonSchema)]
+pub struct NodePlacement {
+    #[schemars(description = "Number of pods of the namespace on this node")]
+    pub pod_count: usize,
+    #[schemars(description = "CPU requests on this node in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Memory requests on this node in GB")]
+    pub memory_requests_gb: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NamespaceNodeDistribution {
+    #[schemars(description = "Namespace name")]
+    pub namespace: String,
+    #[schemars(description = "Pods, CPU and memory requests of the namespace per node")]
+    pub nodes: BTreeMap<String, NodePlacement>,
+    #[schemars(description = "Number of pods in namespace")]
+    pub pod_count: usize,
+    #[schemars(description = "Percentage of the namespace pods running on its busiest node")]
+    pub max_node_share_percent: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct NamespaceNodeDistributionParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct NamespaceNodeDistributionResponse {
+    #[schemars(description = "List of namespaces with their per-node distribution")]
+    pub namespaces: Vec<NamespaceNodeDistribution>,
+    #[schemars(description = "Total number of namespaces")]
+    pub total_namespaces: usize,
+    #[schemars(description = "Explanation of namespace node distribution")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct OversizedPod {
+    #[schemars(description = "Pod name")]
+    pub name: String,
+    #[schemars(description = "Namespace")]
+    pub namespace: String,
+    #[schemars(description = "Node name (or 'unscheduled')")]
+    pub node: String,
+    #[schemars(description = "Pod phase")]
+    pub phase: String,
+    #[schemars(description = "Effective CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Effective memory requests in GB")]
+    pub memory_requests_gb: f64,
+    #[schemars(description = "Whether the CPU request exceeds the largest node's allocatable CPU")]
+    pub exceeds_cpu: bool,
+    #[schemars(description = "Whether the memory request exceeds the largest node's allocatable memory")]
+    pub exceeds_memory: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct UnschedulableBySizeParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct UnschedulableBySizeResponse {
+    #[schemars(description = "Pods that no single node can host")]
+    pub pods: Vec<OversizedPod>,
+    #[schemars(description = "Number of oversized pods")]
+    pub total_oversized: usize,
+    #[schemars(description = "Number of non-terminal pods checked")]
+    pub total_pods_checked: usize,
+    #[schemars(description = "Largest allocatable CPU of any node in cores")]
+    pub largest_node_cpu_cores: f64,
+    #[schemars(description = "Largest allocatable memory of any node in GB")]
+    pub largest_node_memory_gb: f64,
+    #[schemars(description = "Explanation of the size check")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct PodsOnNodeParams {
+    #[schemars(description = "Name of the node to inspect")]
+    pub node_name: String,
+    #[serde(default)]
+    #[schemars(description = "Check whether the pods can be rescheduled on the other schedulable nodes (default: false)")]
+    pub check_reschedulable: bool,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct NodePod {
+    #[schemars(description = "Pod name")]
+    pub name: String,
+    #[schemars(description = "Pod namespace")]
+    pub namespace: String,
+    #[schemars(description = "Pod phase")]
+    pub phase: String,
+    #[schemars(description = "Effective CPU requests in cores")]
+    pub cpu_requests_cores: f64,
+    #[schemars(description = "Effective memory requests in GB")]
+    pub memory_requests_gb: f64,
+    #[schemars(description = "Whether the pod belongs to a DaemonSet (not rescheduled elsewhere)")]
+    pub daemonset: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Whether the pod fits on another schedulable node (only with check_reschedulable)")]
+    pub reschedulable: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Node the pod would be placed on by the bin-packing check")]
+    pub target_node: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct PodsOnNodeResponse {
+    #[schemars(description = "Node name")]
+    pub node: String,
+    #[schemars(description = "Whether the node is cordoned (unschedulable)")]
+    pub cordoned: bool,
+    #[schemars(description = "Non-terminal pods running on the node")]
+    pub pods: Vec<NodePod>,
+    #[schemars(description = "Number of pods on the node")]
+    pub total_pods: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Number of non-DaemonSet pods that fit on no other node (only with check_reschedulable)")]
+    pub unreschedulable_pods: Option<usize>,
+    #[schemars(description = "Explanation of the node's pods")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, schemars::JsonSchema)]
+pub struct ToolCapability {
+    #[schemars(description = "Tool name")]
+    pub name: String,
+    #[schemars(description = "Tool description")]
+    pub description: String,
+    #[schemars(description = "JSON schema of the tool input parameters")]
+    pub input_schema: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct ListCapabilitiesParams {
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct ListCapabilitiesResponse {
+    #[schemars(description = "List of tools exposed by this server")]
+    pub tools: Vec<ToolCapability>,
+    #[schemars(description = "Total number of tools")]
+    pub total_tools: usize,
+    #[schemars(description = "Explanation of capabilities listing")]
+    pub explanation: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct CapacitySnapshot {
+    #[schemars(description = "Time of the snapshot (RFC 3339, e.g. 2025-01-31T12:00:00Z)")]
+    pub timestamp: String,
+    #[schemars(description = "Total CPU in cores")]
+    pub total_cpu_cores: f64,
+    #[schemars(description = "Total memory in GB")]
+    pub total_memory_gb: f64,
+    #[schemars(description = "Allocated CPU (requests) in cores")]
+    pub allocated_cpu_cores: f64,
+    #[schemars(description = "Allocated memory (requests) in GB")]
+    pub allocated_memory_gb: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, schemars::JsonSchema)]
+pub struct ForecastCapacityParams {
+    #[serde(default)]
+    #[schemars(description = "Stored capacity snapshots to extrapolate from (default: this session's trend buffer)")]
+    pub snapshots: Option<Vec<CapacitySnapshot>>,
+    #[serde(default)]
+    #[schemars(description = "Utilization threshold in percent considered full (default: 90)")]
+    pub threshold_percent: Option<f64>,
+    #[serde(default)]
+    #[schemars(description = "Language of the explanation: 'en' or 'es' (default: en)")]
+    pub lang: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Explanation detail: None, Brief (one-line summary) or Full (default)")]
+    pub explanation_detail: ExplanationDetail,
+    #[serde(flatten)]
+    pub cache: CacheParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
+pub struct ForecastCapacityResponse {
+    #[schemars(description = "Utilization threshold in percent used for the forecast")]
+    pub threshold_percent: f64,
+    #[schemars(description = "Number of snapshots used")]
+    pub snapshots_used: usize,
+    #[schemars(description = "Allocated CPU growth rate in cores per day")]
+    pub cpu_growth_cores_per_day: f64,
+    #[schemars(description = "Allocated memory growth rate in GB per day")]
+    pub memory_growth_gb_per_day: f64,
+    #[schemars(description = "Days until CPU allocation reaches the threshold (absent if not growing)")]
+    pub days_until_cpu_threshold: Option<f64>,
+    #[schemars(description = "Days until memory allocation reaches the threshold (absent if not growing)")]
+    pub days_until_memory_threshold: Option<f64>,
+    #[schemars(description = "Days until the first resource reaches the threshold (absent if none is growing)")]
+    pub days_until_full: Option<f64>,
+    #[schemars(description = "Resource that reaches the threshold first: 'cpu' or 'memory'")]
+    pub limiting_resource: Option<String>,
+    #[schemars(description = "Explanation of the forecast")]
+    pub explanation: String,
+}
+
+/// Envelope serialized into the content of every tool result
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ToolEnvelope<T> {
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<T>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Data the tool skipped or degraded on while answering
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<Diagnostic>,
+    /// Whether lists in `data` were cut to MAX_RESPONSE_ITEMS / MAX_RESPONSE_BYTES
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+    /// Which lists were cut and how to narrow the query (only when truncated)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncation_note: Option<String>,
+}
+
+// =================== HELPER FUNCTIONS ===================
+
+/// Successful tool result whose lists keep at most `max_items` items and are halved, longest first,
+/// until the response fits `max_bytes` (0 disables either limit)
+fn success_result_within<T: Serialize>(data: &T, max_items: usize, max_bytes: usize) -> CallToolResult {
+    let diagnostics = diagnostics::take();
+    let envelope = ToolEnvelope {
+        status: "ok".to_string(),
+        data: Some(data),
+        error: None,
+        diagnostics: diagnostics.clone(),
+        truncated: false,
+        truncation_note: None,
+    };
+    let untruncated = match serde_json::to_string_pretty(&envelope) {
+        Ok(json_str) => json_str,
+        Err(e) => return error_result(format!("Error serializing response: {}", e)),
+    };
+    if max_items == 0 && (max_bytes == 0 || untruncated.len() <= max_bytes) {
+        return CallToolResult::success(vec![Content::text(untruncated)]);
+    }
+
+    // Only a response over a limit goes through a JSON value, so the others keep their field order
+    let mut value = match serde_json::to_value(data) {
+        Ok(value) => value,
+        Err(e) => return error_result(format!("Error serializing response: {}", e)),
+    };
+    truncated_result(&mut value, untruncated, max_items, max_bytes, |value, cut| truncated_envelope(value, cut, &diagnostics))
+}
+
+/// Whether `data` fits a success envelope of at most `max_bytes` (0 disables the limit), diagnostics aside
+fn fits_response_bytes<T: Serialize>(data: &T, max_bytes: usize) -> bool {
+    let envelope = ToolEnvelope {
+        status: "ok".to_string(),
+        data: Some(data),
+        error: None,
+        diagnostics: Vec::new(),
+        truncated: false,
+        truncation_note: None,
+    };
+    max_bytes == 0 || serde_json::to_string_pretty(&envelope).is_ok_and(|json| json.len() <= max_bytes)
+}
+
+/// Cut the lists in `value` to `max_items` items, then halve the longest list until `render` fits
+/// `max_bytes` (0 disables either limit). `render` serializes `value` after the cuts, given as JSON
+/// pointer to display path and original length. Without cuts the result is `untruncated`.
+fn truncated_result(
+    value: &mut serde_json::Value,
+    untruncated: String,
+    max_items: usize,
+    max_bytes: usize,
+    render: impl Fn(&serde_json::Value, &BTreeMap<String, (String, usize)>) -> Result<String, serde_json::Error>,
+) -> CallToolResult {
+    let mut cut: BTreeMap<String, (String, usize)> = BTreeMap::new();
+    if max_items > 0 {
+        truncate_lists(value, "", "", max_items, &mut cut);
+    }
+    let mut rendered = render(value, &cut);
+    while max_bytes > 0 && rendered.as_ref().is_ok_and(|text| text.len() > max_bytes) {
+        let mut longest = None;
+        find_longest_list(value, "", "", &mut longest);
+        let Some((_, pointer, path)) = longest else { break };
+        if let Some(serde_json::Value::Array(items)) = value.pointer_mut(&pointer) {
+            cut.entry(pointer).or_insert((path, items.len()));
+            items.truncate(items.len() / 2);
+        }
+        rendered = render(value, &cut);
+    }
+    if cut.is_empty() {
+        return CallToolResult::success(vec![Content::text(untruncated)]);
+    }
+
+    match rendered {
+        Ok(text) => CallToolResult::success(vec![Content::text(text)]),
+        Err(e) => error_result(format!("Error serializing response: {}", e)),
+    }
+}
+
+/// Serialized envelope of `value` after the cuts in `cut` (JSON pointer to display path and original
+/// length), marked as truncated with a note naming the cut lists when there are any
+fn truncated_envelope(value: &serde_json::Value, cut: &BTreeMap<String, (String, usize)>, diagnostics: &[Diagnostic]) -> Result<String, serde_json::Error> {
+    let truncation_note = truncation_note(value, cut);
+    serde_json::to_string_pretty(&ToolEnvelope {
+        status: "ok".to_string(),
+        data: Some(value),
+        error: None,
+        diagnostics: diagnostics.to_vec(),
+        truncated: truncation_note.is_some(),
+        truncation_note,
+    })
+}
+
+/// Note naming the lists cut in `value` with their original and kept lengths, None without cuts
+fn truncation_note(value: &serde_json::Value, cut: &BTreeMap<String, (String, usize)>) -> Option<String> {
+    // Lists inside dropped items are gone; the others add up per path
+    let mut lists: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for (pointer, (path, total)) in cut {
+        if let Some(kept) = value.pointer(pointer).and_then(|v| v.as_array()).map(Vec::len) {
+            let entry = lists.entry(path.as_str()).or_default();
+            entry.0 += total;
+            entry.1 += kept;
+        }
+    }
+
+    (!lists.is_empty()).then(|| {
+        let lists: Vec<String> = lists.iter().map(|(path, (total, kept))| format!("{} ({} → {})", path, total, kept)).collect();
+        format!(
+            "Response truncated to stay within MAX_RESPONSE_ITEMS/MAX_RESPONSE_BYTES: {}. Narrow the query with the tool's \
+             filters (namespace, label_selector, limit and offset, thresholds), or raise the limits.",
+            lists.join(", ")
+        )
+    })
+}
+
+/// JSON pointer of `key` inside the value at `pointer`
+fn child_pointer(pointer: &str, key: &str) -> String {
+    format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Cut every list in `value` to `max_items`, inner lists first, recording the cuts by pointer
+fn truncate_lists(value: &mut serde_json::Value, pointer: &str, path: &str, max_items: usize, cut: &mut BTreeMap<String, (String, usize)>) {
+    match value {
+        serde_json::Value::Array(items) => {
+            let item_path = format!("{}[]", path);
+            for (index, item) in items.iter_mut().enumerate() {
+                truncate_lists(item, &child_pointer(pointer, &index.to_string()), &item_path, max_items, cut);
+            }
+            if items.len() > max_items {
+                cut.insert(pointer.to_string(), (display_path(path), items.len()));
+                items.truncate(max_items);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for (key, field) in fields.iter_mut() {
+                truncate_lists(field, &child_pointer(pointer, key), &join_path(path, key), max_items, cut);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The non-empty list with the most items in `value`: its length, JSON pointer and display path
+fn find_longest_list(value: &serde_json::Value, pointer: &str, path: &str, longest: &mut Option<(usize, String, String)>) {
+    match value {
+        serde_json::Value::Array(items) => {
+            if !items.is_empty() && longest.as_ref().is_none_or(|(len, _, _)| items.len() > *len) {
+                *longest = Some((items.len(), pointer.to_string(), display_path(path)));
+            }
+            let item_path = format!("{}[]", path);
+            for (index, item) in items.iter().enumerate() {
+                find_longest_list(item, &child_pointer(pointer, &index.to_string()), &item_path, longest);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for (key, field) in fields {
+                find_longest_list(field, &child_pointer(pointer, key), &join_path(path, key), longest);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `path.key`, or `key` at the top level
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) }
+}
+
+/// A list path for the truncation note; the top-level list of a tool returning one is `data`
+fn display_path(path: &str) -> String {
+    if path.is_empty() { "data".to_string() } else { path.to_string() }
+}
+
+/// Serialize items as JSON Lines: one compact JSON object per line
+fn to_jsonl<T: Serialize>(items: &[T]) -> Result<String, serde_json::Error> {
+    let mut jsonl = String::new();
+    for item in items {
+        jsonl.push_str(&serde_json::to_string(item)?);
+        jsonl.push('\n');
+    }
+    Ok(jsonl)
+}
+
+/// Successful tool result with the items as JSON Lines, without the envelope (for jq and streaming consumers).
+/// Truncated like success_result_within; a cut ends with a `{"truncated": true, "truncation_note": ...}` line.
+fn jsonl_result_within<T: Serialize>(items: &[T], max_items: usize, max_bytes: usize) -> CallToolResult {
+    let untruncated = match to_jsonl(items) {
+        Ok(jsonl) => jsonl,
+        Err(e) => return error_result(format!("Error serializing response: {}", e)),
+    };
+    if max_items == 0 && (max_bytes == 0 || untruncated.len() <= max_bytes) {
+        return CallToolResult::success(vec![Content::text(untruncated)]);
+    }
+
+    let mut value = match serde_json::to_value(items) {
+        Ok(value) => value,
+        Err(e) => return error_result(format!("Error serializing response: {}", e)),
+    };
+    truncated_result(&mut value, untruncated, max_items, max_bytes, |value, cut| {
+        let mut jsonl = to_jsonl(value.as_array().map_or(&[][..], Vec::as_slice))?;
+        if let Some(note) = truncation_note(value, cut) {
+            jsonl.push_str(&serde_json::to_string(&serde_json::json!({ "truncated": true, "truncation_note": note }))?);
+            jsonl.push('\n');
+        }
+        Ok(jsonl)
+    })
+}
+
+/// Error tool result with the `{status: "error", error, diagnostics}` envelope, counted in the error metric
+fn error_result(message: String) -> CallToolResult {
+    increment_errors();
+    let envelope: ToolEnvelope<()> = ToolEnvelope {
+        status: "error".to_string(),
+        data: None,
+        error: Some(message.clone()),
+        diagnostics: diagnostics::take(),
+        truncated: false,
+        truncation_note: None,
+    };
+    let json_str = serde_json::to_string_pretty(&envelope).unwrap_or(message);
+    CallToolResult::error(vec![Content::text(json_str)])
+}
+
+/// Limit/request ratio, None when nothing is requested
+fn limit_ratio(requests: f64, limits: f64) -> Option<f64> {
+    (requests > 0.0).then_some(limits / requests)
+}
+
+impl BurstRisk {
+    fn new(namespace: String, pod: Option<String>, requests: (f64, f64), limits: (f64, f64)) -> Self {
+        BurstRisk {
+            namespace,
+            pod,
+            cpu_requests_cores: requests.0,
+            cpu_limits_cores: limits.0,
+            cpu_limit_ratio: limit_ratio(requests.0, limits.0),
+            memory_requests_gb: requests.1,
+            memory_limits_gb: limits.1,
+            memory_limit_ratio: limit_ratio(requests.1, limits.1),
+        }
+    }
+
+    /// Highest of the CPU and memory ratios
+    fn max_ratio(&self) -> f64 {
+        self.cpu_limit_ratio.unwrap_or(0.0).max(self.memory_limit_ratio.unwrap_or(0.0))
+    }
+}
+
+/// Check the node groups when consider_autoscaling is set: at least one, none shrinking, positive node sizes
+fn validate_node_groups(consider_autoscaling: bool, node_groups: Option<&[NodeGroupLimit]>) -> Result<(), String> {
+    if !consider_autoscaling {
+        return Ok(());
+    }
+
+    let node_groups = node_groups.unwrap_or_default();
+    if node_groups.is_empty() {
+        return Err("node_groups is required when consider_autoscaling is true".to_string());
+    }
+
+    if node_groups.iter().any(|g| g.max_nodes < g.current_nodes) {
+        return Err("Node group max_nodes must be at least current_nodes".to_string());
+    }
+
+    if node_groups.iter().any(|g| g.node_cpu_cores.is_some_and(|v| v <= 0.0) || g.node_memory_gb.is_some_and(|v| v <= 0.0)) {
+        return Err("Node size must be positive".to_string());
+    }
+
+    Ok(())
+}
+
+/// Check that replica_count is positive and at most `max` (max_replica_count / MAX_REPLICA_COUNT)
+fn validate_replica_count(replica_count: i32, max: i32) -> Result<(), String> {
+    if replica_count <= 0 {
+        return Err("Replica count must be positive".to_string());
+    }
+
+    if replica_count > max {
+        return Err(format!(
+            "Replica count {} exceeds the maximum of {} (set MAX_REPLICA_COUNT to change it)",
+            replica_count, max
+        ));
+    }
+
+    Ok(())
+}
+
+/// The given namespace, or `default` when it is blank. Blank without a default is an error.
+fn resolve_namespace(namespace: &str, default: Option<String>) -> Result<String, String> {
+    if !namespace.trim().is_empty() {
+        return Ok(namespace.to_string());
+    }
+
+    default.ok_or_else(|| "Namespace cannot be empty (set DEFAULT_NAMESPACE to provide a default)".to_string())
+}
+
+/// Maximum number of snapshots kept in a session's trend buffer
+const TREND_BUFFER_CAPACITY: usize = 1000;
+
+/// Extended resources counted as GPUs by get_gpu_report
+const GPU_RESOURCES: [&str; 2] = ["nvidia.com/gpu", "amd.com/gpu"];
+
+/// Look-back window of get_pod_churn when the caller does not give one
+const DEFAULT_CHURN_WINDOW_MINUTES: u64 = 60;
+
+/// Coefficient of variation of per-node utilization above which get_balance_report flags the cluster as imbalanced
+const IMBALANCE_CV_THRESHOLD: f64 = 0.3;
+
+/// Pods requested per page when listing all pods from the API server
+const POD_PAGE_SIZE: u32 = 500;
+
+/// Attempts at the metrics.k8s.io API before falling back to requests; metrics-server
+/// fails transiently between scrapes more often than the core API
+const METRICS_ATTEMPTS: usize = 3;
+
+/// Pause between metrics.k8s.io attempts
+const METRICS_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// Default multiple of the median pod's effective request at which find_request_outliers flags a pod
+const DEFAULT_OUTLIER_MEDIAN_MULTIPLE: f64 = 10.0;
+
+/// Default margin of allocatable, in percent, within which get_eviction_risk flags a node
+const DEFAULT_EVICTION_MARGIN_PERCENT: f64 = 10.0;
+
+/// Default CPU or memory utilization in percent at which get_node_packing flags a node as over-packed
+const DEFAULT_OVER_PACKED_PERCENT: f64 = 85.0;
+
+/// Default CPU and memory utilization in percent below which get_node_packing flags a node as under-packed
+const DEFAULT_UNDER_PACKED_PERCENT: f64 = 30.0;
+
+/// Default deviation in percent between the pod and node memory-per-core ratios that
+/// get_request_ratio_mismatch tolerates
+const DEFAULT_RATIO_TOLERANCE_PERCENT: f64 = 25.0;
+
+/// GB of memory per core of the common instance families, as recommended by get_request_ratio_mismatch
+const NODE_FAMILIES: [(f64, &str); 3] = [(2.0, "compute-optimized"), (4.0, "general-purpose"), (8.0, "memory-optimized")];
+
+/// Default headroom in percent recommend_namespace_downsize keeps above observed usage
+const DEFAULT_DOWNSIZE_HEADROOM_PERCENT: f64 = 20.0;
+
+/// Width in percentage points of the utilization bands nodes must share to be grouped by get_node_breakdown
+const NODE_GROUP_BAND_PERCENT: f64 = 10.0;
+
+/// Node labels naming the instance type, current first
+const INSTANCE_TYPE_LABELS: [&str; 2] = ["node.kubernetes.io/instance-type", "beta.kubernetes.io/instance-type"];
+
+/// One cached list result and when it was fetched
+#[derive(Debug)]
+struct TtlSlot<T> {
+    ttl: Duration,
+    entry: Mutex<Option<(Instant, Vec<T>)>>,
+}
+
+impl<T: Clone> TtlSlot<T> {
+    fn new(ttl: Duration) -> Self {
+        Self { ttl, entry: Mutex::new(None) }
+    }
+
+    /// The cached items, unless they are older than the TTL
+    fn get(&self) -> Option<Vec<T>> {
+        self.get_with_age().map(|(items, _)| items)
+    }
+
+    /// The cached items and how long ago they were fetched, unless they are older than the TTL
+    fn get_with_age(&self) -> Option<(Vec<T>, Duration)> {
+        let entry = self.entry.lock().unwrap_or_else(|e| e.into_inner());
+        entry.as_ref()
+            .map(|(fetched_at, items)| (fetched_at.elapsed(), items))
+            .filter(|(age, _)| *age < self.ttl)
+            .map(|(age, items)| (items.clone(), age))
+    }
+
+    fn put(&self, items: &[T]) {
+        if !self.ttl.is_zero() {
+            *self.entry.lock().unwrap_or_else(|e| e.into_inner()) = Some((Instant::now(), items.to_vec()));
+        }
+    }
+}
+
+/// Most tool results kept in the result cache; inserting beyond it evicts the entry closest to expiry
+const RESULT_CACHE_CAPACITY: usize = 256;
+
+/// Tools whose results are never cached: the trend forecast reads the snapshot buffer every call
+/// appends to, and the capability list is static
+const UNCACHED_TOOLS: [&str; 2] = ["forecast_capacity", "list_capabilities"];
+
+/// Shards of the result cache: a lookup or insert locks only the shard its key hashes to
+const RESULT_CACHE_SHARDS: usize = 16;
+
+/// Results keyed by tool and normalized parameters, each entry expiring on its own. A concurrent map:
+/// entries are spread over RwLock-guarded shards, so calls with different keys rarely contend.
+#[derive(Debug)]
+struct ResultCache<T> {
+    ttl: Duration,
+    capacity: usize,
+    /// Entries by key, with when they were cached
+    shards: Vec<RwLock<HashMap<String, (Instant, T)>>>,
+}
+
+impl<T: Clone> ResultCache<T> {
+    fn new(ttl: Duration, capacity: usize) -> Self {
+        Self { ttl, capacity, shards: (0..RESULT_CACHE_SHARDS).map(|_| RwLock::default()).collect() }
+    }
+
+    fn shard(&self, key: &str) -> &RwLock<HashMap<String, (Instant, T)>> {
+        use std::hash::{BuildHasher, RandomState};
+        static HASHER: Lazy<RandomState> = Lazy::new(RandomState::new);
+        &self.shards[HASHER.hash_one(key) as usize % self.shards.len()]
+    }
+
+    /// The cached result for `key`, unless it has expired
+    #[cfg(test)]
+    fn get(&self, key: &str) -> Option<T> {
+        self.get_with_age(key).map(|(value, _)| value)
+    }
+
+    /// The cached result for `key` and how long ago it was cached, unless it has expired
+    fn get_with_age(&self, key: &str) -> Option<(T, Duration)> {
+        let shard = self.shard(key).read().unwrap_or_else(|e| e.into_inner());
+        shard.get(key)
+            .map(|(cached_at, value)| (value, cached_at.elapsed()))
+            .filter(|(_, age)| *age < self.ttl)
+            .map(|(value, age)| (value.clone(), age))
+    }
+
+    /// Cache `value` under `key` for the TTL. A full cache first drops its expired entries, then
+    /// the one closest to expiry.
+    fn put(&self, key: String, value: &T) {
+        if self.ttl.is_zero() || self.capacity == 0 {
+            return;
+        }
+
+        let is_new = !self.shard(&key).read().unwrap_or_else(|e| e.into_inner()).contains_key(&key);
+        if is_new && self.len() >= self.capacity {
+            for shard in &self.shards {
+                shard.write().unwrap_or_else(|e| e.into_inner()).retain(|_, (cached_at, _)| cached_at.elapsed() < self.ttl);
+            }
+        }
+        if is_new && self.len() >= self.capacity {
+            let oldest = self.shards.iter()
+                .filter_map(|shard| {
+                    let entries = shard.read().unwrap_or_else(|e| e.into_inner());
+                    entries.iter().min_by_key(|(_, (cached_at, _))| *cached_at).map(|(key, (cached_at, _))| (*cached_at, key.clone()))
+                })
+                .min();
+            if let Some((_, oldest)) = oldest {
+                self.shard(&oldest).write().unwrap_or_else(|e| e.into_inner()).remove(&oldest);
+            }
+        }
+        self.shard(&key).write().unwrap_or_else(|e| e.into_inner()).insert(key, (Instant::now(), value.clone()));
+    }
+
+    /// Number of entries, expired ones included
+    fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.read().unwrap_or_else(|e| e.into_inner()).len()).sum()
+    }
+}
+
+/// A cached tool result as returned on a hit `age` after it was cached: a result reporting its
+/// freshness (`from_cache`, `cache_age_seconds`) is marked as cached, its age counting from when
+/// its data was listed
+fn cached_result(result: CallToolResult, age: Duration) -> CallToolResult {
+    let Some(text) = result.content.first().and_then(|content| content.as_text()) else {
+        return result;
+    };
+    let Ok(mut envelope) = serde_json::from_str::<serde_json::Value>(&text.text) else {
+        return result;
+    };
+    let Some(data) = envelope.get_mut("data").and_then(|data| data.as_object_mut()).filter(|data| data.contains_key("from_cache")) else {
+        return result;
+    };
+
+    let listed_age = data.get("cache_age_seconds").and_then(|age| age.as_u64()).unwrap_or(0);
+    data.insert("from_cache".to_string(), serde_json::Value::Bool(true));
+    data.insert("cache_age_seconds".to_string(), serde_json::Value::from(listed_age + age.as_secs()));
+    match serde_json::to_string_pretty(&envelope) {
+        Ok(json_str) => CallToolResult::success(vec![Content::text(json_str)]),
+        Err(_) => result,
+    }
+}
+
+/// Copy of a JSON value with object keys sorted and null members dropped, so equivalent
+/// parameters serialize identically
+fn normalize_json(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let sorted: BTreeMap<&String, serde_json::Value> = map.iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key, normalize_json(value)))
+                .collect();
+            serde_json::Value::Object(sorted.into_iter().map(|(key, value)| (key.clone(), value)).collect())
+        }
+        serde_json::Value::Array(items) => serde_json::Value::Array(items.iter().map(normalize_json).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Result cache key of a tool call: the tool name and its normalized parameters without `fresh`,
+/// which changes where the data comes from but not what is asked. None for uncached tools and
+/// for calls with `include_delta`, whose result depends on the session's previous call.
+fn result_cache_key(tool: &str, arguments: Option<&serde_json::Map<String, serde_json::Value>>) -> Option<String> {
+    let include_delta = arguments.and_then(|a| a.get("include_delta")).and_then(|v| v.as_bool()).unwrap_or(false);
+    if UNCACHED_TOOLS.contains(&tool) || include_delta {
+        return None;
+    }
+
+    let mut arguments = arguments.cloned().unwrap_or_default();
+    arguments.remove("fresh");
+    Some(format!("{}:{}", tool, normalize_json(&serde_json::Value::Object(arguments))))
+}
+
+/// List results reused across tool calls until they expire or a tool asks for fresh data
+#[derive(Debug)]
+struct ListCache {
+    nodes: TtlSlot<Node>,
+    pods: TtlSlot<Pod>,
+    namespaces: TtlSlot<String>,
+}
+
+impl ListCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            nodes: TtlSlot::new(ttl),
+            pods: TtlSlot::new(ttl),
+            namespaces: TtlSlot::new(ttl),
+        }
+    }
+}
+
+/// Parse Kubernetes quantity to cores (CPU) through its exact millicore count, so the micro- and
+/// nanocores reported by metrics-server (e.g. "2500u", "1234567n") go through the same parser as requests
+fn quantity_to_cores(quantity: &Quantity) -> f64 {
+    quantity_to_millicores(quantity) as f64 / 1000.0
+}
+
+/// Bytes in one GB as reported in responses. Every `_gb` field is in binary gigabytes (GiB, 2^30 bytes),
+/// the unit of Kubernetes "Gi" quantities: "1Gi" is 1.0 GB, while the decimal "1G" (10^9 bytes) is about 0.931 GB.
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Unit suffixes of quantities counted in whole units (bytes, devices, pods) and their size as
+/// `multiplier / divisor`: binary (Ki = 2^10 ... Ei = 2^60) before decimal (k = 10^3 ... E = 10^18),
+/// so "Mi" is never read as "M" followed by junk, and milli (m = 10^-3) as Kubernetes accepts it anywhere
+const MEMORY_UNITS_BYTES: [(&str, i128, i128); 13] = [
+    ("Ki", 1 << 10, 1),
+    ("Mi", 1 << 20, 1),
+    ("Gi", 1 << 30, 1),
+    ("Ti", 1 << 40, 1),
+    ("Pi", 1 << 50, 1),
+    ("Ei", 1 << 60, 1),
+    ("k", 1_000, 1),
+    ("M", 1_000_000, 1),
+    ("G", 1_000_000_000, 1),
+    ("T", 1_000_000_000_000, 1),
+    ("P", 1_000_000_000_000_000, 1),
+    ("E", 1_000_000_000_000_000_000, 1),
+    ("m", 1, 1_000),
+];
+
+/// Split a quantity into its number and the `multiplier / divisor` of its unit; no suffix means whole units
+fn split_memory_unit(s: &str) -> (&str, i128, i128) {
+    MEMORY_UNITS_BYTES.iter()
+        .find_map(|(suffix, multiplier, divisor)| s.strip_suffix(suffix).map(|number| (number, *multiplier, *divisor)))
+        .unwrap_or((s, 1, 1))
+}
+
+/// Parse a quantity counted in whole units (e.g. "2", "20Gi", "1500m") exactly, rounded up to a whole
+/// unit like Kubernetes does; None when malformed or negative
+fn parse_quantity_units(s: &str) -> Option<i64> {
+    let (number, multiplier, divisor) = split_memory_unit(s.trim());
+    scale_quantity_number(number, multiplier, divisor)
+}
+
+/// Parse a memory quantity string (e.g. "1.5Gi", "512M", "1500000000") to bytes, the canonical memory
+/// representation every other conversion goes through. Exact, rounded up to a whole byte like Kubernetes
+/// does; malformed and negative values are rejected.
+fn parse_memory_bytes(s: &str) -> Result<i64, String> {
+    let s = s.trim();
+    if s.starts_with('-') {
+        return Err(format!("Negative memory quantity '{}'", s));
+    }
+
+    parse_quantity_units(s).ok_or_else(|| format!("Invalid memory quantity '{}'", s))
+}
+
+/// Bare memory quantities (no unit suffix) below this many bytes are almost certainly a missing suffix
+const MIN_PLAUSIBLE_BARE_MEMORY_BYTES: i64 = 1024 * 1024;
+
+/// Whether a memory quantity has no unit suffix and is too small to be meant as bytes ("512" for "512Mi")
+fn is_implausible_bare_memory(s: &str) -> bool {
+    let s = s.trim();
+    split_memory_unit(s).0 == s
+        && parse_memory_bytes(s).is_ok_and(|bytes| bytes > 0 && bytes < MIN_PLAUSIBLE_BARE_MEMORY_BYTES)
+}
+
+/// Warnings for the memory requests and limits of all containers (init containers included)
+/// that are implausible bare numbers
+fn memory_quantity_warnings(pods: &[Pod]) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    for pod in pods {
+        let Some(spec) = &pod.spec else { continue };
+        let name = pod.metadata.name.as_deref().unwrap_or_default();
+        let namespace = pod.metadata.namespace.as_deref().unwrap_or("default");
+        for container in spec.init_containers.iter().flatten().chain(&spec.containers) {
+            let Some(resources) = &container.resources else { continue };
+            for (kind, quantities) in [("request", &resources.requests), ("limit", &resources.limits)] {
+                let Some(memory) = quantities.as_ref().and_then(|q| q.get("memory")) else { continue };
+                if is_implausible_bare_memory(&memory.0) {
+                    warnings.push(format!(
+                        "Pod '{}/{}' container '{}' has memory {} '{}' without a unit, which means {} bytes; did you mean '{}Mi'?",
+                        namespace, name, container.name, kind, memory.0, memory.0, memory.0
+                    ));
+                }
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Parse Kubernetes quantity to GB (GiB, see BYTES_PER_GB) through its exact byte count;
+/// empty, malformed and negative quantities count as 0
+fn quantity_to_gb(quantity: &Quantity) -> f64 {
+    quantity_to_bytes(quantity) as f64 / BYTES_PER_GB
+}
+
+/// Bytes in one MB (MiB)
+const BYTES_PER_MB: i64 = 1024 * 1024;
+
+/// Exactly scale the number of a quantity ("2", "0.5", ".25", "1e3") by `multiplier / divisor`,
+/// rounding up to a whole unit like Kubernetes does. None when malformed, negative or out of range.
+fn scale_quantity_number(number: &str, multiplier: i128, divisor: i128) -> Option<i64> {
+    let (mantissa, exponent) = match number.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().ok()?),
+        None => (number, 0),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+        return None;
+    }
+
+    // value = digits * 10^power
+    let digits: i128 = format!("{}{}", whole, fraction).parse().ok()?;
+    let power = exponent.checked_sub(i32::try_from(fraction.len()).ok()?)?;
+    let pow10 = 10i128.checked_pow(power.unsigned_abs())?;
+    let (numerator, denominator) = if power >= 0 {
+        (digits.checked_mul(multiplier)?.checked_mul(pow10)?, divisor)
+    } else {
+        (digits.checked_mul(multiplier)?, divisor.checked_mul(pow10)?)
+    };
+
+    i64::try_from((numerator + denominator - 1) / denominator).ok()
+}
+
+/// Parse Kubernetes quantity to millicores (CPU), exactly
+fn quantity_to_millicores(quantity: &Quantity) -> i64 {
+    let s = quantity.0.trim();
+    if s.is_empty() {
+        return 0;
+    }
+
+    let (number, multiplier, divisor) = [('m', 1, 1), ('u', 1, 1_000), ('n', 1, 1_000_000)].into_iter()
+        .find_map(|(suffix, multiplier, divisor)| s.strip_suffix(suffix).map(|number| (number, multiplier, divisor)))
+        .unwrap_or((s, 1_000, 1));
+
+    scale_quantity_number(number, multiplier, divisor).unwrap_or_else(|| {
+        diagnostics::warn("invalid_quantity", format!("Invalid CPU quantity '{}', counted as 0", s));
+        0
+    })
+}
+
+/// Parse Kubernetes quantity to bytes (memory), exactly; empty, malformed and negative quantities count as 0
+fn quantity_to_bytes(quantity: &Quantity) -> i64 {
+    if quantity.0.trim().is_empty() {
+        return 0;
+    }
+
+    parse_memory_bytes(&quantity.0).unwrap_or_else(|e| {
+        diagnostics::warn("invalid_quantity", format!("{}, counted as 0", e));
+        0
+    })
+}
+
+/// Parse Kubernetes quantity of a counted resource (pods, devices, extended resources), exactly;
+/// empty, malformed and negative quantities count as 0
+fn quantity_to_count(quantity: &Quantity) -> i64 {
+    let s = quantity.0.trim();
+    if s.is_empty() {
+        return 0;
+    }
+
+    parse_quantity_units(s).unwrap_or_else(|| {
+        diagnostics::warn("invalid_quantity", format!("Invalid quantity '{}', counted as 0", s));
+        0
+    })
+}
+
+/// CPU in millicores and memory in bytes. Sums of these stay exact however many pods are added;
+/// cores and GB are derived only for output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Resources {
+    millicores: i64,
+    bytes: i64,
+}
+
+impl Resources {
+    /// CPU and memory of a resource list (requests, limits, capacity, allocatable or overhead)
+    fn of(list: Option<&BTreeMap<String, Quantity>>) -> Self {
+        Resources {
+            millicores: list.and_then(|l| l.get("cpu")).map(quantity_to_millicores).unwrap_or(0),
+            bytes: list.and_then(|l| l.get("memory")).map(quantity_to_bytes).unwrap_or(0),
+        }
+    }
+
+    /// CPU in cores
+    fn cores(self) -> f64 {
+        self.millicores as f64 / 1000.0
+    }
+
+    /// Memory in GB (GiB)
+    fn gb(self) -> f64 {
+        self.bytes as f64 / BYTES_PER_GB
+    }
+
+    /// CPU (cores) and memory (GB), the shape of the f64 helpers
+    fn cores_and_gb(self) -> (f64, f64) {
+        (self.cores(), self.gb())
+    }
+
+    /// Nearest millicores and bytes of CPU (cores) and memory (GB) measured as f64, such as metrics
+    fn from_cores_and_gb(cpu_cores: f64, memory_gb: f64) -> Self {
+        Resources { millicores: (cpu_cores * 1000.0).round() as i64, bytes: (memory_gb * BYTES_PER_GB).round() as i64 }
+    }
+
+    /// Per-resource maximum
+    fn max(self, other: Self) -> Self {
+        Resources { millicores: self.millicores.max(other.millicores), bytes: self.bytes.max(other.bytes) }
+    }
+
+    /// Per-resource minimum
+    fn min(self, other: Self) -> Self {
+        Resources { millicores: self.millicores.min(other.millicores), bytes: self.bytes.min(other.bytes) }
+    }
+}
+
+impl std::ops::Add for Resources {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Resources { millicores: self.millicores + other.millicores, bytes: self.bytes + other.bytes }
+    }
+}
+
+impl std::ops::AddAssign for Resources {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl std::ops::Sub for Resources {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Resources { millicores: self.millicores - other.millicores, bytes: self.bytes - other.bytes }
+    }
+}
+
+impl std::ops::SubAssign for Resources {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl std::iter::Sum for Resources {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Resources::default(), |total, r| total + r)
+    }
+}
+
+/// Exact requests, limits and count of a group of pods (a namespace, a label value, ...)
+#[derive(Debug, Clone, Copy, Default)]
+struct PodTotals {
+    requests: Resources,
+    limits: Resources,
+    pod_count: usize,
+}
+
+impl PodTotals {
+    /// Add the requests and limits of one pod
+    fn add(&mut self, pod: &Pod) {
+        self.requests += pod_request_resources(pod);
+        self.limits += pod_limit_resources(pod);
+        self.pod_count += 1;
+    }
+}
+
+/// Format CPU for explanations (millicores below one core, cores otherwise)
+pub(crate) fn format_cpu(cores: f64) -> String {
+    if cores != 0.0 && cores.abs() < 1.0 {
+        format!("{:.0}m", cores * 1000.0)
+    } else {
+        format!("{:.2} cores", cores)
+    }
+}
+
+/// Format memory for explanations (MiB below one GB, GB otherwise)
+pub(crate) fn format_memory_gb(gb: f64) -> String {
+    if gb != 0.0 && gb.abs() < 1.0 {
+        format!("{:.0} MiB", gb * 1024.0)
+    } else {
+        format!("{:.2} GB", gb)
+    }
+}
+
+/// Requests a container of `pod` runs with. After an in-place resize (KEP-1287) the kubelet reports
+/// the applied values in the container status, which can differ from the spec and win over it. Requests
+/// and limits are merged separately, so a status that only reports requests keeps the spec's limits.
+fn container_request_quantities<'a>(pod: &'a Pod, container: &'a Container) -> Option<&'a BTreeMap<String, Quantity>> {
+    container_status_resources(pod, container).and_then(|r| r.requests.as_ref())
+        .or_else(|| container.resources.as_ref().and_then(|r| r.requests.as_ref()))
+}
+
+/// Limits a container of `pod` runs with, merged from its status like container_request_quantities
+fn container_limit_quantities<'a>(pod: &'a Pod, container: &'a Container) -> Option<&'a BTreeMap<String, Quantity>> {
+    container_status_resources(pod, container).and_then(|r| r.limits.as_ref())
+        .or_else(|| container.resources.as_ref().and_then(|r| r.limits.as_ref()))
+}
+
+/// Resources the kubelet reports in the status of a container of `pod`, if any
+fn container_status_resources<'a>(pod: &'a Pod, container: &Container) -> Option<&'a ResourceRequirements> {
+    let status = pod.status.as_ref().and_then(|status| {
+        status.container_statuses.iter().flatten()
+            .chain(status.init_container_statuses.iter().flatten())
+            .find(|status| status.name == container.name)
+    });
+
+    status.and_then(|status| status.resources.as_ref())
+}
+
+/// CPU and memory requests of a single container of `pod`
+fn container_requests(pod: &Pod, container: &Container) -> Resources {
+    Resources::of(container_request_quantities(pod, container))
+}
+
+/// CPU and memory limits of a single container of `pod`
+fn container_limits(pod: &Pod, container: &Container) -> Resources {
+    Resources::of(container_limit_quantities(pod, container))
+}
+
+/// Parse a device count such as a GPU quantity. Device plugins only advertise whole devices,
+/// so anything but a non-negative integer is logged and counted as 0.
+fn parse_device_count(quantity: &Quantity) -> u64 {
+    quantity.0.trim().parse().unwrap_or_else(|_| {
+        diagnostics::warn("invalid_quantity", format!("Invalid device quantity '{}', counted as 0", quantity.0));
+        0
+    })
+}
+
+/// GPUs (any of GPU_RESOURCES) on a node's allocatable
+fn node_gpus(node: &Node) -> u64 {
+    let allocatable = node.status.as_ref().and_then(|s| s.allocatable.as_ref());
+    GPU_RESOURCES.iter()
+        .filter_map(|resource| allocatable?.get(*resource))
+        .map(parse_device_count)
+        .sum()
+}
+
+/// GPUs requested by the app containers of a pod. A limit without a request counts,
+/// as the API server defaults extended resource requests to their limits.
+fn pod_gpus(pod: &Pod) -> u64 {
+    pod.spec.iter()
+        .flat_map(|spec| &spec.containers)
+        .filter_map(|container| container.resources.as_ref())
+        .flat_map(|resources| GPU_RESOURCES.iter().filter_map(move |resource| {
+            resources.requests.as_ref().and_then(|r| r.get(*resource))
+                .or_else(|| resources.limits.as_ref().and_then(|l| l.get(*resource)))
+        }))
+        .map(parse_device_count)
+        .sum()
+}
+
+/// Node reported for pods that are not bound to a node (including pods without a spec)
+const UNSCHEDULED_NODE: &str = "unscheduled";
+
+/// Node a pod is bound to. Pods without a spec count as unscheduled.
+fn pod_node_name(pod: &Pod) -> Option<&str> {
+    pod.spec.as_ref().and_then(|s| s.node_name.as_deref())
+}
+
+/// Node a pod is bound to, or `unscheduled`
+fn pod_node(pod: &Pod) -> String {
+    pod_node_name(pod).unwrap_or(UNSCHEDULED_NODE).to_string()
+}
+
+/// Whether `required` fits in `available`, forgiving a shortfall of up to `tolerance` (floating-point rounding)
+fn fits_within(available: f64, required: f64, tolerance: f64) -> bool {
+    available + tolerance >= required
+}
+
+/// How many copies of `per_copy` fit into `available`; None when nothing is requested (no bound).
+/// The small tolerance keeps exact multiples like 1.2 / 0.4 from rounding down.
+fn copies_that_fit(available: f64, per_copy: f64) -> Option<u64> {
+    (per_copy > 0.0).then(|| (available.max(0.0) / per_copy + 1e-9).floor() as u64)
+}
+
+/// Whether a segment of a pod name looks generated: lowercase alphanumeric with a digit, or
+/// without vowels (the alphabet Kubernetes draws random suffixes from has none)
+fn is_generated_segment(segment: &str) -> bool {
+    segment.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        && (segment.chars().any(|c| c.is_ascii_digit()) || !segment.chars().any(|c| "aeiou".contains(c)))
+}
+
+/// Stable workload name of a pod name: strips the random 5-character pod suffix and the
+/// ReplicaSet (or Job) hash before it, e.g. `payments-7d9f8b-abcde` → `payments` and
+/// `web-xk2pd` → `web`. A 5-character word such as the `redis` of `my-redis` is kept unless a
+/// hash precedes it; StatefulSet ordinals (`db-0`) are kept, as they are not generated.
+fn normalize_workload_name(name: &str) -> &str {
+    let is_hash = |segment: &str| (6..=10).contains(&segment.len()) && is_generated_segment(segment);
+    let mut name = name;
+    if let Some((rest, suffix)) = name.rsplit_once('-') {
+        let hash_before = rest.rsplit_once('-').is_some_and(|(_, hash)| is_hash(hash));
+        if suffix.len() == 5 && suffix.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+            && (hash_before || is_generated_segment(suffix))
+        {
+            name = rest;
+        }
+    }
+    match name.rsplit_once('-') {
+        Some((rest, hash)) if is_hash(hash) => rest,
+        _ => name,
+    }
+}
+
+/// Whether a pod belongs to `app_name`: its normalized workload name equals it with
+/// `normalize_names`, otherwise its name contains it
+fn pod_matches_app(pod: &Pod, app_name: &str, normalize_names: bool) -> bool {
+    pod.metadata.name.as_deref().is_some_and(|name| if normalize_names {
+        normalize_workload_name(name) == app_name
+    } else {
+        name.contains(app_name)
+    })
+}
+
+/// Drop the pods of `namespaces` for what-if capacity, returning what those bound to a node held
+/// by `basis` (the capacity their eviction frees)
+fn exclude_namespace_pods(pods: &mut Vec<Pod>, namespaces: &[String], basis: ResourceBasis, usage: Option<&PodUsage>) -> Resources {
+    let mut freed = Resources::default();
+    pods.retain(|pod| {
+        let namespace = pod.metadata.namespace.as_deref().unwrap_or("default");
+        if !namespaces.iter().any(|excluded| excluded == namespace) {
+            return true;
+        }
+        if pod_node_name(pod).is_some() && !pod_is_terminal(pod) {
+            freed += basis.pod_amounts(pod, usage);
+        }
+        false
+    });
+    freed
+}
+
+/// Whether a pod has finished (Succeeded or Failed) and no longer holds its requests
+fn pod_is_terminal(pod: &Pod) -> bool {
+    matches!(pod.status.as_ref().and_then(|s| s.phase.as_deref()), Some("Succeeded" | "Failed"))
+}
+
+/// Whether a pod is held back from scheduling by spec.schedulingGates; until the gates are removed
+/// it is not scheduling demand
+fn pod_is_gated(pod: &Pod) -> bool {
+    pod.spec.as_ref()
+        .and_then(|spec| spec.scheduling_gates.as_ref())
+        .is_some_and(|gates| !gates.is_empty())
+}
+
+/// Whether a pod is BestEffort: no container sets a CPU or memory request or limit
+fn pod_is_best_effort(pod: &Pod) -> bool {
+    pod.spec.iter()
+        .flat_map(|spec| spec.containers.iter().chain(spec.init_containers.iter().flatten()))
+        .filter_map(|container| container.resources.as_ref())
+        .flat_map(|resources| resources.requests.iter().chain(resources.limits.iter()))
+        .all(|list| !list.contains_key("cpu") && !list.contains_key("memory"))
+}
+
+/// Whether a pod has a pod affinity or anti-affinity term reaching into other namespaces
+fn pod_has_cross_namespace_affinity(pod: &Pod) -> bool {
+    let Some(affinity) = pod.spec.as_ref().and_then(|spec| spec.affinity.as_ref()) else {
+        return false;
+    };
+
+    let affinity_terms = affinity.pod_affinity.iter().flat_map(|a| {
+        a.required_during_scheduling_ignored_during_execution.iter().flatten()
+            .chain(a.preferred_during_scheduling_ignored_during_execution.iter().flatten().map(|p| &p.pod_affinity_term))
+    });
+    let anti_affinity_terms = affinity.pod_anti_affinity.iter().flat_map(|a| {
+        a.required_during_scheduling_ignored_during_execution.iter().flatten()
+            .chain(a.preferred_during_scheduling_ignored_during_execution.iter().flatten().map(|p| &p.pod_affinity_term))
+    });
+
+    affinity_terms.chain(anti_affinity_terms)
+        .any(|term| term.namespace_selector.is_some() || term.namespaces.as_ref().is_some_and(|n| !n.is_empty()))
+}
+
+/// Whether a pod falls in a ResourceQuota scope. `operator` and `values` only matter for
+/// PriorityClass; the other pod scopes only take Exists. Scopes that select no pods
+/// (VolumeAttributesClass) or are unknown never match.
+fn quota_scope_matches(scope: &str, operator: &str, values: &[String], pod: &Pod) -> bool {
+    let spec = pod.spec.as_ref();
+    match scope {
+        "Terminating" => spec.and_then(|s| s.active_deadline_seconds).is_some_and(|seconds| seconds >= 0),
+        "NotTerminating" => !spec.and_then(|s| s.active_deadline_seconds).is_some_and(|seconds| seconds >= 0),
+        "BestEffort" => pod_is_best_effort(pod),
+        "NotBestEffort" => !pod_is_best_effort(pod),
+        "CrossNamespacePodAffinity" => pod_has_cross_namespace_affinity(pod),
+        "PriorityClass" => {
+            let class = spec.and_then(|s| s.priority_class_name.as_deref()).filter(|class| !class.is_empty());
+            match operator {
+                "In" => class.is_some_and(|class| values.iter().any(|v| v == class)),
+                "NotIn" => !class.is_some_and(|class| values.iter().any(|v| v == class)),
+                "DoesNotExist" => class.is_none(),
+                _ => class.is_some(),
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Whether a ResourceQuota counts a pod: every scope of spec.scopes and every expression of
+/// spec.scopeSelector must match
+fn quota_selects_pod(quota: &ResourceQuota, pod: &Pod) -> bool {
+    let Some(spec) = quota.spec.as_ref() else {
+        return true;
+    };
+
+    spec.scopes.iter().flatten().all(|scope| quota_scope_matches(scope, "Exists", &[], pod))
+        && spec.scope_selector.iter()
+            .flat_map(|selector| selector.match_expressions.iter().flatten())
+            .all(|requirement| {
+                quota_scope_matches(&requirement.scope_name, &requirement.operator, requirement.values.as_deref().unwrap_or_default(), pod)
+            })
+}
+
+/// Readable scopes of a ResourceQuota, e.g. `BestEffort` or `PriorityClass In (high, critical)`
+fn quota_scopes(quota: &ResourceQuota) -> Vec<String> {
+    let Some(spec) = quota.spec.as_ref() else {
+        return Vec::new();
+    };
+
+    let expressions = spec.scope_selector.iter()
+        .flat_map(|selector| selector.match_expressions.iter().flatten())
+        .map(|requirement| match requirement.values.as_deref() {
+            Some(values) if !values.is_empty() => format!("{} {} ({})", requirement.scope_name, requirement.operator, values.join(", ")),
+            _ if requirement.operator == "Exists" => requirement.scope_name.clone(),
+            _ => format!("{} {}", requirement.scope_name, requirement.operator),
+        });
+    spec.scopes.iter().flatten().cloned().chain(expressions).collect()
+}
+
+/// A quota quantity in the unit get_quota_usage reports `resource` in (cores, GB or a count);
+/// None for resources it does not track
+fn quota_amount(resource: &str, quantity: &Quantity) -> Option<f64> {
+    match resource {
+        "cpu" | "requests.cpu" | "limits.cpu" => Some(quantity_to_cores(quantity)),
+        "memory" | "requests.memory" | "limits.memory" => Some(quantity_to_gb(quantity)),
+        "pods" => Some(quantity_to_count(quantity) as f64),
+        _ => None,
+    }
+}
+
+/// How much of a quota resource a pod uses, the way the quota admission counts it: effective
+/// requests (init containers and overhead included) and summed container limits
+fn pod_quota_usage(resource: &str, pod: &Pod) -> f64 {
+    match resource {
+        "cpu" | "requests.cpu" => pod_effective_request_resources(pod).cores(),
+        "memory" | "requests.memory" => pod_effective_request_resources(pod).gb(),
+        "limits.cpu" => pod_limit_resources(pod).cores(),
+        "limits.memory" => pod_limit_resources(pod).gb(),
+        "pods" => 1.0,
+        _ => 0.0,
+    }
+}
+
+/// Non-terminal pods bound to a node that is not in `nodes`, e.g. after the node object was deleted
+/// before the pod garbage collector caught up
+fn orphaned_pods<'a>(nodes: &[Node], pods: &'a [Pod]) -> Vec<&'a Pod> {
+    let node_names: BTreeSet<&str> = nodes.iter().filter_map(|node| node.metadata.name.as_deref()).collect();
+
+    pods.iter()
+        .filter(|pod| !pod_is_terminal(pod))
+        .filter(|pod| pod_node_name(pod).is_some_and(|node| !node_names.contains(node)))
+        .collect()
+}
+
+/// Whether every container of a pod sets a memory limit (pods without containers have none)
+fn pod_has_memory_limit(pod: &Pod) -> bool {
+    pod.spec.as_ref().is_some_and(|spec| {
+        !spec.containers.is_empty() && spec.containers.iter().all(|container| {
+            container.resources.as_ref()
+                .and_then(|r| r.limits.as_ref())
+                .is_some_and(|limits| limits.contains_key("memory"))
+        })
+    })
+}
+
+/// Whether every container of a pod limits both CPU and memory, so its burst is bounded
+fn pod_has_cpu_and_memory_limits(pod: &Pod) -> bool {
+    pod.spec.iter().flat_map(|spec| &spec.containers).all(|container| {
+        container_limit_quantities(pod, container)
+            .is_some_and(|limits| limits.contains_key("cpu") && limits.contains_key("memory"))
+    })
+}
+
+/// Whether every container of a pod requests `resource` ("cpu" or "memory"). Native sidecars
+/// run alongside the app containers and count; ordinary init containers do not.
+fn pod_requests_resource(pod: &Pod, resource: &str) -> bool {
+    pod.spec.as_ref().is_some_and(|spec| {
+        let sidecars = spec.init_containers.iter().flatten()
+            .filter(|container| container.restart_policy.as_deref() == Some("Always"));
+        !spec.containers.is_empty() && spec.containers.iter().chain(sidecars).all(|container| {
+            container_request_quantities(pod, container)
+                .is_some_and(|requests| requests.contains_key(resource))
+        })
+    })
+}
+
+/// Sum the limits of all containers in a pod
+fn pod_limit_resources(pod: &Pod) -> Resources {
+    pod.spec.iter().flat_map(|spec| &spec.containers).map(|container| container_limits(pod, container)).sum()
+}
+
+/// Sum the CPU (cores) and memory (GB) limits of all containers in a pod
+fn pod_limits(pod: &Pod) -> (f64, f64) {
+    pod_limit_resources(pod).cores_and_gb()
+}
+
+/// Sum the limits of all containers in a pod, taking the request for a resource a container does not limit
+fn pod_limits_or_requests(pod: &Pod) -> Resources {
+    pod.spec.iter()
+        .flat_map(|spec| &spec.containers)
+        .map(|container| {
+            let limits = container_limit_quantities(pod, container);
+            let requests = container_requests(pod, container);
+            Resources {
+                millicores: limits.and_then(|l| l.get("cpu")).map(quantity_to_millicores).unwrap_or(requests.millicores),
+                bytes: limits.and_then(|l| l.get("memory")).map(quantity_to_bytes).unwrap_or(requests.bytes),
+            }
+        })
+        .sum()
+}
+
+/// Sum the requests of all containers in a pod
+fn pod_request_resources(pod: &Pod) -> Resources {
+    pod.spec.iter().flat_map(|spec| &spec.containers).map(|container| container_requests(pod, container)).sum()
+}
+
+/// Sum the CPU (cores) and memory (GB) requests of all containers in a pod
+fn pod_requests(pod: &Pod) -> (f64, f64) {
+    pod_request_resources(pod).cores_and_gb()
+}
+
+/// Effective requests of a pod as the scheduler sees them: the larger of the app containers plus
+/// native sidecars (init containers with restartPolicy Always) and each init container plus the
+/// sidecars started before it, plus the pod overhead
+fn pod_effective_request_resources(pod: &Pod) -> Resources {
+    let spec = match &pod.spec {
+        Some(spec) => spec,
+        None => return Resources::default(),
+    };
+
+    let mut sidecars = Resources::default();
+    let mut init = Resources::default();
+
+    for init_container in spec.init_containers.iter().flatten() {
+        let requests = container_requests(pod, init_container);
+        if init_container.restart_policy.as_deref() == Some("Always") {
+            sidecars += requests;
+        } else {
+            init = init.max(sidecars + requests);
+        }
+    }
+
+    (pod_request_resources(pod) + sidecars).max(init) + Resources::of(spec.overhead.as_ref())
+}
+
+/// Effective request of a pod for any `resource` (e.g. "ephemeral-storage", "nvidia.com/gpu") in its
+/// base unit, counted like pod_effective_request_resources
+fn pod_effective_request_count(pod: &Pod, resource: &str) -> i64 {
+    let Some(spec) = &pod.spec else { return 0 };
+    let request = |container: &Container| container_request_quantities(pod, container)
+        .and_then(|requests| requests.get(resource))
+        .map_or(0, quantity_to_count);
+
+    let mut sidecars = 0;
+    let mut init = 0;
+
+    for init_container in spec.init_containers.iter().flatten() {
+        if init_container.restart_policy.as_deref() == Some("Always") {
+            sidecars += request(init_container);
+        } else {
+            init = init.max(sidecars + request(init_container));
+        }
+    }
+
+    let overhead = spec.overhead.as_ref().and_then(|overhead| overhead.get(resource)).map_or(0, quantity_to_count);
+    (spec.containers.iter().map(request).sum::<i64>() + sidecars).max(init) + overhead
+}
+
+/// Effective CPU (cores) and memory (GB) requests of a pod (see pod_effective_request_resources)
+fn pod_effective_requests(pod: &Pod) -> (f64, f64) {
+    pod_effective_request_resources(pod).cores_and_gb()
+}
+
+/// A node's allocatable CPU and memory, falling back to its capacity
+fn node_allocatable_resources(node: &Node) -> Resources {
+    Resources::of(node.status.as_ref().and_then(|status| status.allocatable.as_ref().or(status.capacity.as_ref())))
+}
+
+/// Read a node's allocatable CPU (cores) and memory (GB), falling back to its capacity
+fn node_allocatable(node: &Node) -> (f64, f64) {
+    node_allocatable_resources(node).cores_and_gb()
+}
+
+/// A node's total CPU and memory capacity
+fn node_capacity(node: &Node) -> Resources {
+    Resources::of(node.status.as_ref().and_then(|status| status.capacity.as_ref()))
+}
+
+/// Median of a list of values (0 when empty)
+fn median(mut values: Vec<f64>) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Per-node DaemonSet overhead in CPU (cores) and memory (GB): the effective requests of one pod of every DaemonSet
+fn daemonset_overhead(pods: &[Pod]) -> (f64, f64) {
+    let mut per_daemonset: HashMap<(String, String), (f64, f64)> = HashMap::new();
+
+    for pod in pods {
+        if let Some(owner) = daemonset_owner(pod) {
+            let key = (pod.metadata.namespace.clone().unwrap_or_default(), owner.name.clone());
+            let (cpu_cores, memory_gb) = pod_effective_requests(pod);
+            let entry = per_daemonset.entry(key).or_insert((0.0, 0.0));
+            entry.0 = entry.0.max(cpu_cores);
+            entry.1 = entry.1.max(memory_gb);
+        }
+    }
+
+    per_daemonset.values().fold((0.0, 0.0), |acc, v| (acc.0 + v.0, acc.1 + v.1))
+}
+
+/// Number of nodes needed to cover a shortfall given the usable CPU/memory of one node.
+/// Returns None when a node has no usable capacity for a resource that is short.
+fn nodes_needed_for_shortfall(
+    cpu_shortfall_cores: f64,
+    memory_shortfall_gb: f64,
+    usable_cpu_cores: f64,
+    usable_memory_gb: f64,
+) -> Option<u32> {
+    let cpu_nodes = if cpu_shortfall_cores > 0.0 {
+        if usable_cpu_cores <= 0.0 {
+            return None;
+        }
+        (cpu_shortfall_cores / usable_cpu_cores).ceil()
+    } else {
+        0.0
+    };
+
+    let memory_nodes = if memory_shortfall_gb > 0.0 {
+        if usable_memory_gb <= 0.0 {
+            return None;
+        }
+        (memory_shortfall_gb / usable_memory_gb).ceil()
+    } else {
+        0.0
+    };
+
+    Some(cpu_nodes.max(memory_nodes) as u32)
+}
+
+/// Seconds a pod may stay terminating before find_stuck_pods reports it
+const DEFAULT_STUCK_GRACE_PERIOD_SECONDS: u64 = 300;
+
+/// Namespaces counted as system overhead when the caller does not name them
+const DEFAULT_SYSTEM_NAMESPACES: [&str; 4] = ["kube-system", "kube-public", "kube-node-lease", "monitoring"];
+
+/// Summed container CPU (cores) and memory (GB) usage of a PodMetrics object's data, added up
+/// exactly in millicores and bytes like requests
+fn pod_metrics_usage(data: &serde_json::Value) -> (f64, f64) {
+    let containers = data["containers"].as_array().map(Vec::as_slice).unwrap_or_default();
+    let usage: Resources = containers.iter()
+        .map(|container| {
+            let quantity = |resource: &str| Quantity(container["usage"][resource].as_str().unwrap_or_default().to_string());
+            Resources { millicores: quantity_to_millicores(&quantity("cpu")), bytes: quantity_to_bytes(&quantity("memory")) }
+        })
+        .sum();
+    usage.cores_and_gb()
+}
+
+/// Memory usage (working set) in bytes of a NodeMetrics object's data
+fn node_metrics_memory_bytes(data: &serde_json::Value) -> i64 {
+    quantity_to_bytes(&Quantity(data["usage"]["memory"].as_str().unwrap_or_default().to_string()))
+}
+
+/// Bytes used on the node filesystem (which holds ephemeral storage) from a kubelet stats summary
+fn kubelet_fs_used_bytes(summary: &serde_json::Value) -> Option<i64> {
+    summary["node"]["fs"]["usedBytes"].as_i64()
+}
+
+/// MemoryPressure and DiskPressure conditions a node reports as True
+fn node_pressure_conditions(node: &Node) -> Vec<String> {
+    node.status.iter()
+        .flat_map(|status| status.conditions.iter().flatten())
+        .filter(|c| (c.type_ == "MemoryPressure" || c.type_ == "DiskPressure") && c.status == "True")
+        .map(|c| c.type_.clone())
+        .collect()
+}
+
+/// Controlling owner of intermediate controller objects, keyed by (namespace, kind, name)
+type OwnerIndex = HashMap<(String, String, String), OwnerReference>;
+
+/// Owner references followed at most when resolving a pod's top-level owner (guards against cycles)
+const MAX_OWNER_DEPTH: usize = 8;
+
+/// Controlling owner reference of an object (the first owner when none is marked as controller)
+fn controller_owner(meta: &ObjectMeta) -> Option<&OwnerReference> {
+    let refs = meta.owner_references.as_ref()?;
+    refs.iter().find(|r| r.controller == Some(true)).or_else(|| refs.first())
+}
+
+/// Index the controlling owner of each (kind, metadata) object
+fn owner_index<'a>(objects: impl IntoIterator<Item = (&'a str, &'a ObjectMeta)>) -> OwnerIndex {
+    objects.into_iter()
+        .filter_map(|(kind, meta)| {
+            let owner = controller_owner(meta)?;
+            let key = (
+                meta.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                kind.to_string(),
+                meta.name.clone().unwrap_or_default(),
+            );
+            Some((key, owner.clone()))
+        })
+        .collect()
+}
+
+/// A pod's top-level owner, following owner references through `owners`
+/// (e.g. ReplicaSet → Deployment, Job → CronJob). None for pods without an owner.
+fn top_level_owner<'a>(pod: &'a Pod, owners: &'a OwnerIndex) -> Option<&'a OwnerReference> {
+    let mut owner = controller_owner(&pod.metadata)?;
+    let namespace = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+
+    for _ in 0..MAX_OWNER_DEPTH {
+        match owners.get(&(namespace.clone(), owner.kind.clone(), owner.name.clone())) {
+            Some(parent) => owner = parent,
+            None => break,
+        }
+    }
+
+    Some(owner)
+}
+
+/// Kind of a pod's top-level owner (see top_level_owner). Pods without an owner are reported as 'Pod'.
+fn top_level_owner_kind(pod: &Pod, owners: &OwnerIndex) -> String {
+    top_level_owner(pod, owners).map(|owner| owner.kind.clone()).unwrap_or_else(|| "Pod".to_string())
+}
+
+/// Workload a pod is grouped under: its top-level owner's kind and name. A ReplicaSet or Job whose own
+/// owner is not indexed keeps its generated hash, so its name is normalized (normalize_workload_name)
+/// and the ReplicaSets of one Deployment still group together. None for pods without an owner.
+fn pod_workload(pod: &Pod, owners: &OwnerIndex) -> Option<(String, String)> {
+    let owner = top_level_owner(pod, owners)?;
+    let name = match owner.kind.as_str() {
+        "ReplicaSet" | "Job" => normalize_workload_name(&owner.name),
+        _ => owner.name.as_str(),
+    };
+    Some((owner.kind.clone(), name.to_string()))
+}
+
+/// Comparable form of a quantity, so "1Gi" and "1024Mi" match: millicores for CPU, bytes for memory,
+/// the raw string for other resources
+fn normalized_quantity(resource: &str, quantity: &Quantity) -> String {
+    match resource {
+        "cpu" => quantity_to_millicores(quantity).to_string(),
+        "memory" => quantity_to_bytes(quantity).to_string(),
+        _ => quantity.0.clone(),
+    }
+}
+
+/// Normalized and raw quantity of each (container, "request"/"limit", resource) of a pod
+type ContainerResourceValues = BTreeMap<(String, &'static str, String), (String, String)>;
+
+/// Requests and limits of every container of a pod
+fn container_resource_values(pod: &Pod) -> ContainerResourceValues {
+    let mut values = BTreeMap::new();
+
+    for container in pod.spec.iter().flat_map(|spec| &spec.containers) {
+        let requests = container_request_quantities(pod, container);
+        let limits = container_limit_quantities(pod, container);
+        for (kind, quantities) in [("request", requests), ("limit", limits)] {
+            for (resource, quantity) in quantities.into_iter().flatten() {
+                values.insert(
+                    (container.name.clone(), kind, resource.clone()),
+                    (normalized_quantity(resource, quantity), quantity.0.clone()),
+                );
+            }
+        }
+    }
+
+    values
+}
+
+/// Requests of every container, init container and native sidecar of a pod, in spec order
+fn container_request_breakdown(pod: &Pod) -> Vec<ContainerRequest> {
+    let Some(spec) = &pod.spec else { return Vec::new() };
+    let init_containers = spec.init_containers.iter().flatten().map(|container| {
+        let kind = if container.restart_policy.as_deref() == Some("Always") { "sidecar" } else { "init" };
+        (kind, container)
+    });
+    let containers = spec.containers.iter().map(|container| ("container", container));
+
+    init_containers.chain(containers)
+        .map(|(kind, container)| {
+            let requests = container_requests(pod, container);
+            ContainerRequest {
+                name: container.name.clone(),
+                kind: kind.to_string(),
+                cpu_requests_cores: requests.cores(),
+                memory_requests_gb: requests.gb(),
+            }
+        })
+        .collect()
+}
+
+/// Init containers (with the sidecars started before them) whose requests exceed the app containers
+/// plus all sidecars, per resource: these set the pod's effective request instead of the app containers
+fn dominating_init_containers(pod: &Pod) -> Vec<(String, &'static str)> {
+    let Some(spec) = &pod.spec else { return Vec::new() };
+    let all_sidecars: Resources = spec.init_containers.iter().flatten()
+        .filter(|container| container.restart_policy.as_deref() == Some("Always"))
+        .map(|container| container_requests(pod, container))
+        .sum();
+    let running = pod_request_resources(pod) + all_sidecars;
+
+    let mut sidecars = Resources::default();
+    let mut dominating = Vec::new();
+    for container in spec.init_containers.iter().flatten() {
+        let requests = container_requests(pod, container);
+        if container.restart_policy.as_deref() == Some("Always") {
+            sidecars += requests;
+            continue;
+        }
+        let during_init = sidecars + requests;
+        if during_init.millicores > running.millicores {
+            dominating.push((container.name.clone(), "cpu"));
+        }
+        if during_init.bytes > running.bytes {
+            dominating.push((container.name.clone(), "memory"));
+        }
+    }
+
+    dominating
+}
+
+/// Owner reference of a DaemonSet-managed pod
+fn daemonset_owner(pod: &Pod) -> Option<&OwnerReference> {
+    pod.metadata.owner_references.as_ref()
+        .and_then(|refs| refs.iter().find(|r| r.kind == "DaemonSet"))
+}
+
+/// Whether a node selector requirement holds for a value (a node label, or the node name for matchFields)
+fn requirement_matches(requirement: &NodeSelectorRequirement, value: Option<&str>) -> bool {
+    let values = requirement.values.as_deref().unwrap_or_default();
+    let compare = |ordering: std::cmp::Ordering| {
+        let value = value.and_then(|v| v.parse::<i64>().ok());
+        let bound = values.first().and_then(|v| v.parse::<i64>().ok());
+        matches!((value, bound), (Some(value), Some(bound)) if value.cmp(&bound) == ordering)
+    };
+
+    match requirement.operator.as_str() {
+        "In" => value.is_some_and(|v| values.iter().any(|candidate| candidate == v)),
+        "NotIn" => value.is_none_or(|v| !values.iter().any(|candidate| candidate == v)),
+        "Exists" => value.is_some(),
+        "DoesNotExist" => value.is_none(),
+        "Gt" => compare(std::cmp::Ordering::Greater),
+        "Lt" => compare(std::cmp::Ordering::Less),
+        _ => false,
+    }
+}
+
+/// Whether a toleration tolerates a taint
+fn tolerates(toleration: &Toleration, taint: &Taint) -> bool {
+    if toleration.effect.as_deref().is_some_and(|effect| !effect.is_empty() && effect != taint.effect) {
+        return false;
+    }
+
+    let key = toleration.key.as_deref().unwrap_or_default();
+    match toleration.operator.as_deref().unwrap_or("Equal") {
+        // An empty key with Exists tolerates every taint
+        "Exists" => key.is_empty() || key == taint.key,
+        _ => key == taint.key && toleration.value.as_deref().unwrap_or_default() == taint.value.as_deref().unwrap_or_default(),
+    }
+}
+
+/// Whether a node is cordoned (spec.unschedulable)
+fn node_is_cordoned(node: &Node) -> bool {
+    node.spec.as_ref().and_then(|s| s.unschedulable).unwrap_or(false)
+}
+
+/// Whether a node reports the Ready condition as True
+fn node_is_ready(node: &Node) -> bool {
+    node.status.iter()
+        .flat_map(|status| status.conditions.iter().flatten())
+        .any(|condition| condition.type_ == "Ready" && condition.status == "True")
+}
+
+/// Why a pod with this spec cannot be scheduled on the node, following the scheduler's filters for
+/// cordons, nodeSelector, required node affinity and NoSchedule/NoExecute taints. None when it can.
+fn node_exclusion_reason(spec: &PodSpec, node: &Node) -> Option<String> {
+    let labels = node.metadata.labels.clone().unwrap_or_default();
+    let node_name = node.metadata.name.as_deref().unwrap_or_default();
+
+    if node_is_cordoned(node) {
+        return Some("cordoned".to_string());
+    }
+
+    if let Some((key, value)) = spec.node_selector.iter().flatten().find(|(key, value)| labels.get(*key) != Some(*value)) {
+        return Some(format!("nodeSelector {}={} does not match", key, value));
+    }
+
+    let required = spec.affinity.as_ref()
+        .and_then(|a| a.node_affinity.as_ref())
+        .and_then(|a| a.required_during_scheduling_ignored_during_execution.as_ref());
+    if let Some(required) = required {
+        // Terms are ORed, the requirements of a term are ANDed, and an empty term matches nothing
+        let term_matches = |term: &NodeSelectorTerm| {
+            let expressions = term.match_expressions.as_deref().unwrap_or_default();
+            let fields = term.match_fields.as_deref().unwrap_or_default();
+            (!expressions.is_empty() || !fields.is_empty())
+                && expressions.iter().all(|r| requirement_matches(r, labels.get(&r.key).map(String::as_str)))
+                && fields.iter().all(|r| r.key == "metadata.name" && requirement_matches(r, Some(node_name)))
+        };
+        if !required.node_selector_terms.iter().any(term_matches) {
+            return Some("required node affinity does not match".to_string());
+        }
+    }
+
+    let tolerations = spec.tolerations.as_deref().unwrap_or_default();
+    let untolerated = node.spec.iter()
+        .flat_map(|s| s.taints.iter().flatten())
+        .filter(|taint| taint.effect == "NoSchedule" || taint.effect == "NoExecute")
+        .find(|taint| !tolerations.iter().any(|toleration| tolerates(toleration, taint)));
+    if let Some(taint) = untolerated {
+        return Some(match taint.value.as_deref() {
+            Some(value) if !value.is_empty() => format!("untolerated taint {}={}:{}", taint.key, value, taint.effect),
+            _ => format!("untolerated taint {}:{}", taint.key, taint.effect),
+        });
+    }
+
+    None
+}
+
+/// Copies of one pod template bin-packed onto the nodes it can run on
+struct ReplicaPacking {
+    /// Where the schedulable copies go, most copies first
+    placements: Vec<ReplicaPlacement>,
+    /// Nodes the template cannot run on, sorted by name
+    excluded_nodes: Vec<ExcludedNode>,
+    eligible_nodes: usize,
+    schedulable_replicas: i32,
+}
+
+/// What the non-terminal pods bound to a node below `priority` hold under `basis`, as a request at a
+/// PriorityClass could preempt them. Pods without a priority count as 0, the default without a PriorityClass.
+fn preemptible_capacity(class: &PriorityClass, pods: &[Pod], basis: ResourceBasis, usage: Option<&PodUsage>) -> PreemptibleCapacity {
+    let preemptible: Vec<&Pod> = pods.iter()
+        .filter(|pod| !pod_is_terminal(pod) && pod_node_name(pod).is_some())
+        .filter(|pod| pod.spec.as_ref().and_then(|spec| spec.priority).unwrap_or(0) < class.value)
+        .collect();
+    let reclaimable: Resources = preemptible.iter().map(|pod| basis.pod_amounts(pod, usage)).sum();
+
+    PreemptibleCapacity {
+        priority_class: class.metadata.name.clone().unwrap_or_default(),
+        priority: class.value,
+        preempts: class.preemption_policy.as_deref() != Some("Never"),
+        reclaimable_cpu_cores: reclaimable.cores(),
+        reclaimable_memory_gb: reclaimable.gb(),
+        preemptible_pod_count: preemptible.len(),
+    }
+}
+
+/// Whether labels satisfy a label selector: every matchLabels entry and every matchExpressions requirement
+fn label_selector_matches(selector: &LabelSelector, labels: &BTreeMap<String, String>) -> bool {
+    selector.match_labels.iter().flatten().all(|(key, value)| labels.get(key) == Some(value))
+        && selector.match_expressions.iter().flatten().all(|requirement| {
+            let value = labels.get(&requirement.key);
+            let values = requirement.values.as_deref().unwrap_or_default();
+            match requirement.operator.as_str() {
+                "In" => value.is_some_and(|v| values.contains(v)),
+                "NotIn" => value.is_none_or(|v| !values.contains(v)),
+                "Exists" => value.is_some(),
+                "DoesNotExist" => value.is_none(),
+                _ => false,
+            }
+        })
+}
+
+/// Parse a label selector string the way the API server reads it ("app=web", "env!=prod", "tier in (cache,queue)",
+/// "track notin (canary)", "gpu", "!spot"; comma-separated requirements all apply) into match expressions
+fn parse_label_selector(selector: &str) -> Result<LabelSelector, String> {
+    let invalid = || format!("Invalid label selector '{}'", selector);
+
+    // Commas inside a value set do not separate requirements
+    let mut requirements = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (index, c) in selector.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                requirements.push(&selector[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    requirements.push(&selector[start..]);
+
+    let mut match_expressions = Vec::new();
+    for requirement in requirements.into_iter().map(str::trim) {
+        let (key, operator, values) = if let Some(key) = requirement.strip_prefix('!') {
+            (key, "DoesNotExist", Vec::new())
+        } else if let Some((key, value)) = requirement.split_once("!=") {
+            (key, "NotIn", vec![value])
+        } else if let Some((key, value)) = requirement.split_once('=') {
+            (key, "In", vec![value.strip_prefix('=').unwrap_or(value)])
+        } else if let Some((key, set)) = requirement.split_once(char::is_whitespace) {
+            let set = set.trim();
+            let (operator, values) = match (set.strip_prefix("in"), set.strip_prefix("notin")) {
+                (Some(values), _) => ("In", values),
+                (_, Some(values)) => ("NotIn", values),
+                _ => return Err(invalid()),
+            };
+            let values = values.trim().strip_prefix('(').and_then(|v| v.strip_suffix(')')).ok_or_else(invalid)?;
+            (key, operator, values.split(',').collect())
+        } else {
+            (requirement, "Exists", Vec::new())
+        };
+
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(invalid());
+        }
+        match_expressions.push(LabelSelectorRequirement {
+            key: key.to_string(),
+            operator: operator.to_string(),
+            values: (!values.is_empty()).then(|| values.into_iter().map(|v| v.trim().to_string()).collect()),
+        });
+    }
+
+    Ok(LabelSelector { match_expressions: Some(match_expressions), match_labels: None })
+}
+
+/// The requiredDuringSchedulingIgnoredDuringExecution pod anti-affinity terms of a pod spec
+fn required_anti_affinity_terms(spec: &PodSpec) -> &[PodAffinityTerm] {
+    spec.affinity.as_ref()
+        .and_then(|affinity| affinity.pod_anti_affinity.as_ref())
+        .and_then(|anti_affinity| anti_affinity.required_during_scheduling_ignored_during_execution.as_deref())
+        .unwrap_or_default()
+}
+
+/// Whether a pod is selected by an anti-affinity term of a pod in `namespace`. The term covers its listed
+/// namespaces, else every namespace when it has a namespaceSelector (not evaluated), else `namespace`;
+/// a term without a labelSelector selects no pod.
+fn anti_affinity_term_selects(term: &PodAffinityTerm, namespace: &str, pod: &Pod) -> bool {
+    let pod_namespace = pod.metadata.namespace.as_deref().unwrap_or("default");
+    let in_scope = match term.namespaces.as_deref() {
+        Some(namespaces) if !namespaces.is_empty() => namespaces.iter().any(|ns| ns == pod_namespace),
+        _ => term.namespace_selector.is_some() || pod_namespace == namespace,
+    };
+    let labels = pod.metadata.labels.clone().unwrap_or_default();
+
+    in_scope && term.label_selector.as_ref().is_some_and(|selector| label_selector_matches(selector, &labels))
+}
+
+/// The topology domain of a node for an anti-affinity term: its value of the term's topology key
+fn topology_domain<'a>(term: &PodAffinityTerm, node: &'a Node) -> Option<&'a str> {
+    node.metadata.labels.as_ref()?.get(&term.topology_key).map(String::as_str)
+}
+
+/// Per required anti-affinity term of `template`, the topology domains already holding a non-terminal
+/// pod the term selects; a copy of the template cannot land in any of them
+fn anti_affinity_blocked_domains(template: &Pod, nodes: &[Node], pods: &[Pod]) -> Vec<BTreeSet<String>> {
+    let namespace = template.metadata.namespace.as_deref().unwrap_or("default");
+    let terms = template.spec.as_ref().map(required_anti_affinity_terms).unwrap_or_default();
+
+    terms.iter()
+        .map(|term| {
+            pods.iter()
+                .filter(|pod| !pod_is_terminal(pod) && anti_affinity_term_selects(term, namespace, pod))
+                .filter_map(|pod| {
+                    let node = nodes.iter().find(|node| node.metadata.name.as_deref() == pod_node_name(pod))?;
+                    topology_domain(term, node).map(str::to_string)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Most copies of `template` its required pod anti-affinity allows on the eligible nodes, ignoring resources:
+/// each term whose selector matches the template itself admits one copy per free topology domain. None when
+/// no such term exists or an eligible node outside every domain of the term leaves it unbounded.
+fn anti_affinity_limit(template: &Pod, nodes: &[Node], pods: &[Pod]) -> Option<i32> {
+    let namespace = template.metadata.namespace.as_deref().unwrap_or("default");
+    let spec = template.spec.as_ref()?;
+    let terms = required_anti_affinity_terms(spec);
+    let blocked = anti_affinity_blocked_domains(template, nodes, pods);
+    let eligible: Vec<&Node> = nodes.iter().filter(|node| node_exclusion_reason(spec, node).is_none()).collect();
+
+    terms.iter().zip(&blocked)
+        .filter(|(term, _)| anti_affinity_term_selects(term, namespace, template))
+        .filter_map(|(term, blocked)| {
+            let domains: Option<BTreeSet<&str>> = eligible.iter().map(|node| topology_domain(term, node)).collect();
+            domains.map(|domains| domains.iter().filter(|domain| !blocked.contains(**domain)).count() as i32)
+        })
+        .min()
+}
+
+/// Place `replicas` copies of a pod template, one at a time, on the eligible node with the most free CPU
+/// (then memory) where the template's effective requests `per_replica` fit. Free resources are allocatable
+/// minus the effective requests of the non-terminal pods bound to each node; free pod slots are respected.
+/// Required pod anti-affinity is enforced: a copy never lands in a topology domain that already holds a pod
+/// its terms select, including copies placed earlier in the pass.
+fn pack_replicas(template: &Pod, per_replica: Resources, replicas: i32, nodes: &[Node], pods: &[Pod]) -> ReplicaPacking {
+    let template_spec = template.spec.clone().unwrap_or_default();
+    let namespace = template.metadata.namespace.as_deref().unwrap_or("default");
+    let terms = required_anti_affinity_terms(&template_spec);
+    let mut blocked = anti_affinity_blocked_domains(template, nodes, pods);
+    let mut excluded_nodes = Vec::new();
+    // (name, free resources, free pod slots, node)
+    let mut free: Vec<(String, Resources, Option<u64>, &Node)> = Vec::new();
+    for node in nodes {
+        let node_name = node.metadata.name.clone().unwrap_or_default();
+        if let Some(reason) = node_exclusion_reason(&template_spec, node) {
+            excluded_nodes.push(ExcludedNode { node: node_name, reason });
+            continue;
+        }
+
+        let bound: Vec<&Pod> = pods.iter()
+            .filter(|pod| !pod_is_terminal(pod) && pod_node_name(pod) == Some(node_name.as_str()))
+            .collect();
+        let used: Resources = bound.iter().map(|pod| pod_effective_request_resources(pod)).sum();
+        let pod_slots = node.status.as_ref()
+            .and_then(|status| status.allocatable.as_ref())
+            .and_then(|allocatable| allocatable.get("pods"))
+            .map(|max_pods| parse_device_count(max_pods).saturating_sub(bound.len() as u64));
+        free.push((node_name, node_allocatable_resources(node) - used, pod_slots, node));
+    }
+    excluded_nodes.sort_by(|a, b| a.node.cmp(&b.node));
+    let eligible_nodes = free.len();
+
+    let mut placed: BTreeMap<String, i32> = BTreeMap::new();
+    let mut schedulable_replicas = 0;
+    while schedulable_replicas < replicas {
+        let slot = free.iter_mut()
+            .filter(|(_, resources, slots, node)| {
+                resources.millicores >= per_replica.millicores && resources.bytes >= per_replica.bytes && *slots != Some(0)
+                    && terms.iter().zip(&blocked)
+                        .all(|(term, blocked)| topology_domain(term, node).is_none_or(|domain| !blocked.contains(domain)))
+            })
+            .max_by(|a, b| a.1.millicores.cmp(&b.1.millicores)
+                .then_with(|| a.1.bytes.cmp(&b.1.bytes))
+                .then_with(|| b.0.cmp(&a.0)));
+        let Some((node_name, resources, slots, node)) = slot else { break };
+
+        *resources -= per_replica;
+        if let Some(slots) = slots {
+            *slots -= 1;
+        }
+        for (term, blocked) in terms.iter().zip(blocked.iter_mut()) {
+            if let Some(domain) = topology_domain(term, node).filter(|_| anti_affinity_term_selects(term, namespace, template)) {
+                blocked.insert(domain.to_string());
+            }
+        }
+        *placed.entry(node_name.clone()).or_default() += 1;
+        schedulable_replicas += 1;
+    }
+
+    let mut placements: Vec<ReplicaPlacement> = placed.into_iter()
+        .map(|(node, replicas)| ReplicaPlacement { node, replicas })
+        .collect();
+    placements.sort_by(|a, b| b.replicas.cmp(&a.replicas).then_with(|| a.node.cmp(&b.node)));
+
+    ReplicaPacking { placements, excluded_nodes, eligible_nodes, schedulable_replicas }
+}
+
+/// Nodes the autoscaler must add, taking node groups in order up to their max size,
+/// to cover the shortfall of a scale-up recommendation. None when the groups run out of room.
+fn autoscale_nodes_needed(scale_up: &ScaleUpRecommendation, node_groups: &[NodeGroupLimit]) -> Option<u32> {
+    let mut cpu_shortfall = scale_up.cpu_shortfall_cores;
+    let mut memory_shortfall = scale_up.memory_shortfall_gb;
+    let mut added = 0;
+
+    for group in node_groups {
+        let usable_cpu = group.node_cpu_cores.unwrap_or(scale_up.node_cpu_cores) - scale_up.daemonset_cpu_overhead_cores;
+        let usable_memory = group.node_memory_gb.unwrap_or(scale_up.node_memory_gb) - scale_up.daemonset_memory_overhead_gb;
+
+        for _ in group.current_nodes..group.max_nodes {
+            let helps = (cpu_shortfall > 0.0 && usable_cpu > 0.0) || (memory_shortfall > 0.0 && usable_memory > 0.0);
+            if !helps {
+                break;
+            }
+            cpu_shortfall -= usable_cpu.max(0.0);
+            memory_shortfall -= usable_memory.max(0.0);
+            added += 1;
+        }
+    }
+
+    (cpu_shortfall <= 0.0 && memory_shortfall <= 0.0).then_some(added)
+}
+
+/// Record a capacity snapshot in a trend buffer, dropping the oldest one when full
+fn record_snapshot(trend: &Mutex<VecDeque<CapacitySnapshot>>, capacity: &ClusterCapacityResponse) {
+    let mut buffer = trend.lock().unwrap_or_else(|e| e.into_inner());
+    if buffer.len() >= TREND_BUFFER_CAPACITY {
+        buffer.pop_front();
+    }
+    buffer.push_back(CapacitySnapshot {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        total_cpu_cores: capacity.total_cpu_cores,
+        total_memory_gb: capacity.total_memory_gb,
+        allocated_cpu_cores: capacity.allocated_cpu_cores,
+        allocated_memory_gb: capacity.allocated_memory_gb,
+    });
+}
+
+/// Least-squares slope of `values` over `days`
+fn linear_slope(days: &[f64], values: &[f64]) -> f64 {
+    let n = days.len() as f64;
+    let mean_day = days.iter().sum::<f64>() / n;
+    let mean_value = values.iter().sum::<f64>() / n;
+
+    let mut numerator = 0.0;
+    let mut denominator = 0.0;
+    for (day, value) in days.iter().zip(values) {
+        numerator += (day - mean_day) * (value - mean_value);
+        denominator += (day - mean_day) * (day - mean_day);
+    }
+
+    if denominator > 0.0 { numerator / denominator } else { 0.0 }
+}
+
+/// Days until `allocated` growing at `rate` per day reaches `threshold_percent` of `total`
+fn days_until_threshold(allocated: f64, total: f64, rate: f64, threshold_percent: f64) -> Option<f64> {
+    let target = total * threshold_percent / 100.0;
+    if allocated >= target {
+        Some(0.0)
+    } else if rate > 0.0 {
+        Some((target - allocated) / rate)
+    } else {
+        None
+    }
+}
+
+// =================== CLUSTER INSIGHTS ===================
+
+/// Error of every tool call that needs the cluster when no Kubernetes configuration can be found
+const NO_CLUSTER_MESSAGE: &str = "No Kubernetes configuration found: set KUBECONFIG, create ~/.kube/config \
+                                  or run the server in-cluster. check_resource_fit with cluster_override works without a cluster.";
+
+/// Whether detect_cluster_config() found a Kubernetes configuration; unset when it was not called (tests)
+static CLUSTER_CONFIGURED: OnceCell<bool> = OnceCell::new();
+
+/// Check once at startup whether a kubeconfig or in-cluster configuration can be found. Without one
+/// the server still starts, and tools that need the cluster answer with NO_CLUSTER_MESSAGE until restarted.
+pub async fn detect_cluster_config() {
+    let configured = match kube::Config::infer().await {
+        Ok(_) => true,
+        Err(e) => {
+            tracing::warn!("{} ({})", NO_CLUSTER_MESSAGE, e);
+            false
+        }
+    };
+    CLUSTER_CONFIGURED.get_or_init(|| configured);
+}
+
+#[derive(Clone)]
+pub struct ClusterInsights {
+    tool_router: ToolRouter<Self>,
+    /// Client used for API calls; None means `Client::try_default()` per request
+    client: Option<Client>,
+    /// Node, pod and namespace lists, shared by the sessions of one builder
+    cache: Arc<ListCache>,
+    /// Tool results by tool and parameters, shared like the list cache
+    results: Arc<ResultCache<CallToolResult>>,
+    /// Tunables read by the tools; bind_address stays process-wide
+    config: Arc<ServerConfig>,
+    /// Reflector stores nodes and pods are read from in watch mode
+    watch: Option<WatchStores>,
+    /// False when there is no client and startup found no Kubernetes configuration
+    cluster_configured: bool,
+    /// Basis and allocated CPU (cores) and memory (GB) of this session's previous get_cluster_capacity
+    /// call; every session (connection) builds its own ClusterInsights
+    last_capacity: Arc<Mutex<Option<(ResourceBasis, f64, f64)>>>,
+    /// Snapshots this session's get_cluster_capacity calls recorded for forecast_capacity, oldest first
+    trend: Arc<Mutex<VecDeque<CapacitySnapshot>>>,
+}
+
+// kube::Client is not Debug
+impl std::fmt::Debug for ClusterInsights {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClusterInsights")
+            .field("tool_router", &self.tool_router)
+            .field("client", &self.client.as_ref().map(|_| "Client"))
+            .field("cache", &self.cache)
+            .field("results", &self.results.len())
+            .field("config", &self.config)
+            .field("watch", &self.watch.is_some())
+            .field("cluster_configured", &self.cluster_configured)
+            .field("last_capacity", &self.last_capacity)
+            .field("trend", &self.trend.lock().map_or(0, |trend| trend.len()))
+            .finish()
+    }
+}
+
+/// Builds a ClusterInsights with an explicit client and configuration, for embedding the
+/// tools in another program without CONFIG_PATH, environment variables or a kubeconfig
+#[derive(Clone, Default)]
+pub struct ClusterInsightsBuilder {
+    client: Option<Client>,
+    config: Option<ServerConfig>,
+    watch: Option<WatchStores>,
+    /// List and result caches created by the first build and shared, like the reflector stores,
+    /// by every ClusterInsights built from this builder or its clones
+    caches: Arc<OnceCell<SharedCaches>>,
+}
+
+/// The list cache and result cache the sessions of one builder share
+#[derive(Debug, Clone)]
+struct SharedCaches {
+    lists: Arc<ListCache>,
+    results: Arc<ResultCache<CallToolResult>>,
+}
+
+impl SharedCaches {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            lists: Arc::new(ListCache::new(ttl)),
+            results: Arc::new(ResultCache::new(ttl, RESULT_CACHE_CAPACITY)),
+        }
+    }
+}
+
+impl std::fmt::Debug for ClusterInsightsBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClusterInsightsBuilder")
+            .field("client", &self.client.as_ref().map(|_| "Client"))
+            .field("config", &self.config)
+            .field("watch", &self.watch.is_some())
+            .field("caches", &self.caches.get().is_some())
+            .finish()
+    }
+}
+
+impl ClusterInsightsBuilder {
+    /// Use this client for all API calls instead of `Client::try_default()`
+    #[allow(dead_code)] // Only used when embedding
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self.caches = Arc::default();
+        self
+    }
+
+    /// Use this configuration instead of the one loaded from CONFIG_PATH and the environment
+    #[allow(dead_code)] // Only used when embedding
+    pub fn config(mut self, config: ServerConfig) -> Self {
+        self.config = Some(config);
+        self.caches = Arc::default();
+        self
+    }
+
+    /// Start Node and Pod reflectors on this builder's client (or the default kubeconfig) when its
+    /// configuration enables watch mode. Every ClusterInsights built afterwards reads from them.
+    pub async fn start_watch_mode(mut self) -> Result<Self, String> {
+        let enabled = self.config.as_ref().map_or_else(|| config::get().watch_mode, |config| config.watch_mode);
+        if !enabled {
+            return Ok(self);
+        }
+
+        let client = match &self.client {
+            Some(client) => client.clone(),
+            None => Client::try_default().await
+                .map_err(|e| format!("Failed to create Kubernetes client: {}", e))?,
+        };
+        self.watch = Some(watch::start_watch_mode(client).await?);
+        Ok(self)
+    }
+
+    /// Validate the configuration and build. The list and result caches (TTL from the configuration) are
+    /// shared with every other ClusterInsights of this builder, unless a client was injected: those caches
+    /// belong to the one instance.
+    pub fn build(self) -> Result<ClusterInsights, String> {
+        let config = match self.config {
+            Some(config) => config.validated()?,
+            None => config::get().clone(),
+        };
+        let ttl = Duration::from_secs(config.cache_ttl_seconds);
+        let SharedCaches { lists: cache, results } = match self.client {
+            Some(_) => SharedCaches::new(ttl),
+            None => self.caches.get_or_init(|| SharedCaches::new(ttl)).clone(),
+        };
+
+        let mut tool_router = ClusterInsights::tool_router();
+        for name in &config.disabled_tools {
+            if !tool_router.has_route(name) {
+                tracing::warn!("Ignoring unknown tool '{}' in DISABLED_TOOLS", name);
+            }
+            tool_router.remove_route(name);
+        }
+
+        Ok(ClusterInsights {
+            tool_router,
+            cluster_configured: self.client.is_some() || CLUSTER_CONFIGURED.get().copied().unwrap_or(true),
+            client: self.client,
+            cache,
+            results,
+            config: Arc::new(config),
+            watch: self.watch,
+            last_capacity: Arc::default(),
+            trend: Arc::default(),
+        })
+    }
+}
+
+impl ClusterInsights {
+    /// Builder for a ClusterInsights with a custom client or configuration
+    pub fn builder() -> ClusterInsightsBuilder {
+        ClusterInsightsBuilder::default()
+    }
+
+    /// Successful tool result in the envelope, truncated to this instance's MAX_RESPONSE_ITEMS / MAX_RESPONSE_BYTES
+    fn success_result<T: Serialize>(&self, data: &T) -> CallToolResult {
+        success_result_within(data, self.config.max_response_items, self.config.max_response_bytes)
+    }
+
+    /// Successful tool result as JSON Lines, truncated to this instance's MAX_RESPONSE_ITEMS / MAX_RESPONSE_BYTES
+    fn jsonl_result<T: Serialize>(&self, items: &[T]) -> CallToolResult {
+        jsonl_result_within(items, self.config.max_response_items, self.config.max_response_bytes)
+    }
+
+    /// Kubernetes client for the next API call
+    async fn client(&self) -> Result<Client, String> {
+        match &self.client {
+            Some(client) => Ok(client.clone()),
+            None if !self.cluster_configured => Err(NO_CLUSTER_MESSAGE.to_string()),
+            None => Client::try_default().await.map_err(|e| match e {
+                kube::Error::InferConfig(_) => NO_CLUSTER_MESSAGE.to_string(),
+                e => format!("Failed to create Kubernetes client: {}", e),
+            }),
+        }
+    }
+
+    /// Fetch the resource sets selected in `options` concurrently. Pods are paged and pod metrics retried
+    /// by the list helpers; a set not requested stays empty and costs no API call.
+    async fn fetch_cluster_state(&self, options: ClusterStateOptions<'_>) -> Result<ClusterState, String> {
+        let fresh = options.fresh;
+        let nodes = async {
+            if !options.nodes {
+                return Ok((Vec::new(), None));
+            }
+            match self.cached_with_age(&self.cache.nodes, fresh) {
+                Some((nodes, age)) => Ok((nodes, Some(age))),
+                None => self.list_nodes(fresh).await.map(|nodes| (nodes, None)),
+            }
+        };
+        let pods = async {
+            if !options.pods {
+                return Ok((Vec::new(), None));
+            }
+            match self.cached_with_age(&self.cache.pods, fresh) {
+                Some((pods, age)) => Ok((pods, Some(age))),
+                None => self.list_all_pods(fresh, options.progress).await.map(|pods| (pods, None)),
+            }
+        };
+        let namespaces = async {
+            if options.namespaces { self.list_namespace_names(fresh).await } else { Ok(Vec::new()) }
+        };
+        let pod_usage = async {
+            match options.pod_usage.then(|| self.list_pod_usage()) {
+                None => Ok((PodUsage::new(), None)),
+                Some(listing) => match listing.await {
+                    Ok(usage) => Ok((usage, None)),
+                    Err(e) if options.metrics_optional => Ok((PodUsage::new(), Some(e))),
+                    Err(e) => Err(e),
+                },
+            }
+        };
+
+        let ((nodes, nodes_age), (pods, pods_age), namespaces, (pod_usage, metrics_error)) =
+            futures::try_join!(nodes, pods, namespaces, pod_usage)?;
+        Ok(ClusterState { nodes, pods, namespaces, pod_usage, metrics_error, cache_age: nodes_age.max(pods_age) })
+    }
+
+    /// A list cache entry and its age, unless `fresh` is set or the always-current watch caches serve the list
+    fn cached_with_age<T: Clone>(&self, slot: &TtlSlot<T>, fresh: bool) -> Option<(Vec<T>, Duration)> {
+        if fresh || self.watch.is_some() {
+            return None;
+        }
+        slot.get_with_age()
+    }
+
+    /// List all nodes and pods in the cluster
+    async fn list_nodes_and_pods(&self, fresh: bool) -> Result<(Vec<Node>, Vec<Pod>), String> {
+        let state = self.fetch_cluster_state(ClusterStateOptions { nodes: true, pods: true, fresh, ..Default::default() }).await?;
+        
+        Ok((state.nodes, state.pods))
+    }
+
+    /// List all nodes, from the watch or list cache unless `fresh` is set
+    async fn list_nodes(&self, fresh: bool) -> Result<Vec<Node>, String> {
+        if !fresh {
+            if let Some(nodes) = self.watch.as_ref().map(WatchStores::nodes).or_else(|| self.cache.nodes.get()) {
+                return Ok(nodes);
+            }
+        }
+        
+        let nodes = Api::<Node>::all(self.client().await?).list(&Default::default()).await
+            .map_err(|e| format!("Failed to list nodes: {}", e))?;
+        self.cache.nodes.put(&nodes.items);
+        
+        Ok(nodes.items)
+    }
+
+    /// List the names of all namespaces, from the list cache unless `fresh` is set
+    async fn list_namespace_names(&self, fresh: bool) -> Result<Vec<String>, String> {
+        if !fresh {
+            if let Some(names) = self.cache.namespaces.get() {
+                return Ok(names);
+            }
+        }
+        
+        let namespaces = Api::<Namespace>::all(self.client().await?).list(&Default::default()).await
+            .map_err(|e| format!("Failed to list namespaces: {}", e))?;
+        let names: Vec<String> = namespaces.items.iter()
+            .map(|ns| ns.metadata.name.clone().unwrap_or_default())
+            .collect();
+        self.cache.namespaces.put(&names);
+        
+        Ok(names)
+    }
+
+    /// List pods, optionally in a single namespace, from the watch or list cache unless `fresh` is set
+    async fn list_pods(&self, namespace: Option<&str>, fresh: bool) -> Result<Vec<Pod>, String> {
+        let Some(namespace) = namespace else {
+            return self.list_all_pods(fresh, None).await;
+        };
+
+        if !fresh {
+            if let Some(pods) = self.watch.as_ref().map(WatchStores::pods).or_else(|| self.cache.pods.get()) {
+                return Ok(pods.into_iter()
+                    .filter(|pod| pod.metadata.namespace.as_deref() == Some(namespace))
+                    .collect());
+            }
+        }
+        
+        let pods = Api::<Pod>::namespaced(self.client().await?, namespace).list(&Default::default()).await
+            .map_err(|e| format!("Failed to list pods in namespace {}: {}", namespace, e))?;
+        
+        Ok(pods.items)
+    }
+
+    /// List the pods of all namespaces. Unless `fresh`, uses the watch cache or the list cache;
+    /// otherwise pages through the API server, reporting the pods listed so far after every page.
+    async fn list_all_pods(&self, fresh: bool, progress: Option<&Progress>) -> Result<Vec<Pod>, String> {
+        if !fresh {
+            if let Some(pods) = self.watch.as_ref().map(WatchStores::pods).or_else(|| self.cache.pods.get()) {
+                return Ok(pods);
+            }
+        }
+
+        let api = Api::<Pod>::all(self.client().await?);
+        let mut params = ListParams::default().limit(POD_PAGE_SIZE);
+        let mut pods = Vec::new();
+
+        loop {
+            let page = api.list(&params).await
+                .map_err(|e| format!("Failed to list pods: {}", e))?;
+            pods.extend(page.items);
+
+            if let Some(progress) = progress {
+                let remaining = page.metadata.remaining_item_count.and_then(|r| usize::try_from(r).ok());
+                progress.report(pods.len(), remaining.map(|r| pods.len() + r), format!("Listed {} pods", pods.len()));
+            }
+
+            match page.metadata.continue_ {
+                Some(token) if !token.is_empty() => params = params.continue_token(&token),
+                _ => break,
+            }
+        }
+
+        self.cache.pods.put(&pods);
+        Ok(pods)
+    }
+
+    /// Get cluster capacity
+    async fn get_cluster_capacity_internal(
+        &self,
+        basis: ResourceBasis,
+        exclude_namespaces: &[String],
+        lang: Lang,
+        fresh: bool,
+    ) -> Result<ClusterCapacityResponse, String> {
+        let mut state = self.fetch_cluster_state(Self::state_for_basis(basis, fresh)).await?;
+        let usage = (basis == ResourceBasis::Usage).then_some(&state.pod_usage);
+        let freed = exclude_namespace_pods(&mut state.pods, exclude_namespaces, basis, usage);
+        
+        let mut capacity = Self::compute_cluster_capacity(&state.nodes, &state.pods, basis, usage);
+        capacity.explanation = messages::cluster_capacity(lang, &capacity);
+        if !exclude_namespaces.is_empty() {
+            capacity.explanation.push_str(&format!(" {}", messages::excluded_namespaces(lang, exclude_namespaces, freed.cores_and_gb())));
+        }
+        if let Some(note) = messages::resource_basis(lang, basis) {
+            capacity.explanation.push_str(&format!(" {}", note));
+        }
+        capacity.from_cache = state.cache_age.is_some();
+        capacity.cache_age_seconds = state.cache_age.map(|age| age.as_secs());
+        self.record_capacity_trend(&capacity, exclude_namespaces);
+        
+        Ok(capacity)
+    }
+
+    /// Add a get_cluster_capacity result to this session's trend buffer. The forecast extrapolates
+    /// requests of the whole cluster, so only those snapshots join the trend.
+    fn record_capacity_trend(&self, capacity: &ClusterCapacityResponse, exclude_namespaces: &[String]) {
+        if capacity.basis == ResourceBasis::Requests && exclude_namespaces.is_empty() {
+            record_snapshot(&self.trend, capacity);
+        }
+    }
+
+    /// Replay what a get_cluster_capacity call records for the session when the result cache serves it:
+    /// the allocation still joins the trend and becomes the previous call the next delta compares with
+    fn observe_cached_capacity(&self, arguments: Option<&serde_json::Map<String, serde_json::Value>>, cached: &CallToolResult) {
+        let params = serde_json::from_value::<ClusterCapacityParams>(serde_json::Value::Object(arguments.cloned().unwrap_or_default()));
+        let capacity = cached.content.first()
+            .and_then(|content| content.as_text())
+            .and_then(|text| serde_json::from_str::<ToolEnvelope<serde_json::Value>>(&text.text).ok())
+            .and_then(|envelope| envelope.data)
+            .and_then(|data| serde_json::from_value::<ClusterCapacityResponse>(data).ok());
+        let (Ok(params), Some(mut capacity)) = (params, capacity) else { return };
+
+        let exclude_namespaces = params.exclude_namespaces.as_deref().unwrap_or_default();
+        self.record_capacity_trend(&capacity, exclude_namespaces);
+        if exclude_namespaces.is_empty() {
+            self.observe_capacity(&mut capacity, false, Lang::En);
+        }
+    }
+
+    /// Remember the allocation of this get_cluster_capacity call and, with `include_delta`, report
+    /// the change since the session's previous call. Allocations by different bases are not compared.
+    fn observe_capacity(&self, capacity: &mut ClusterCapacityResponse, include_delta: bool, lang: Lang) {
+        let current = (capacity.basis, capacity.allocated_cpu_cores, capacity.allocated_memory_gb);
+        let previous = self.last_capacity.lock().unwrap_or_else(|e| e.into_inner()).replace(current);
+        if !include_delta {
+            return;
+        }
+
+        let delta = previous
+            .filter(|(basis, _, _)| *basis == capacity.basis)
+            .map(|(_, cpu_cores, memory_gb)| (capacity.allocated_cpu_cores - cpu_cores, capacity.allocated_memory_gb - memory_gb));
+        capacity.delta_allocated_cpu_cores = delta.map(|(cpu_cores, _)| cpu_cores);
+        capacity.delta_allocated_memory_gb = delta.map(|(_, memory_gb)| memory_gb);
+        capacity.explanation.push_str(&format!(" {}", messages::capacity_delta(lang, delta)));
+    }
+
+    /// Nodes and pods, plus the pod metrics the Usage basis needs
+    fn state_for_basis(basis: ResourceBasis, fresh: bool) -> ClusterStateOptions<'static> {
+        ClusterStateOptions { nodes: true, pods: true, pod_usage: basis == ResourceBasis::Usage, fresh, ..Default::default() }
+    }
+
+    /// Compute cluster capacity from node and pod lists, counting what pods hold by `basis`
+    /// (`usage` feeds the Usage basis)
+    fn compute_cluster_capacity(nodes: &[Node], pods: &[Pod], basis: ResourceBasis, usage: Option<&PodUsage>) -> ClusterCapacityResponse {
+        let total: Resources = nodes.iter().map(node_capacity).sum();
+        
+        let mut allocated = Resources::default();
+        let mut pending = Resources::default();
+        let mut pending_pod_count = 0;
+        let mut gated_pod_count = 0;
+        
+        // Only running pods bound to a node hold its capacity; unbound pods are demand still waiting
+        // for a node, unless scheduling gates hold them back on purpose. Finished pods hold nothing.
+        for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+            let amounts = basis.pod_amounts(pod, usage);
+            if pod_node_name(pod).is_some() {
+                allocated += amounts;
+            } else if pod_is_gated(pod) {
+                gated_pod_count += 1;
+            } else {
+                pending += amounts;
+                pending_pod_count += 1;
+            }
+        }
+        
+        let available = total - allocated;
+        
+        let node_count = nodes.len();
+        
+        let mut capacity = ClusterCapacityResponse {
+            total_cpu_cores: total.cores(),
+            total_memory_gb: total.gb(),
+            allocated_cpu_cores: allocated.cores(),
+            allocated_memory_gb: allocated.gb(),
+            available_cpu_cores: available.cores(),
+            available_memory_gb: available.gb(),
+            node_count,
+            pending_cpu_cores: pending.cores(),
+            pending_memory_gb: pending.gb(),
+            pending_pod_count,
+            gated_pod_count,
+            basis,
+            explanation: String::new(),
+            from_cache: false,
+            cache_age_seconds: None,
+            delta_allocated_cpu_cores: None,
+            delta_allocated_memory_gb: None,
+        };
+        capacity.explanation = messages::cluster_capacity(Lang::En, &capacity);
+        
+        capacity
+    }
+    
+    /// Check if resources fit
+    async fn check_resource_fit_internal(
+        &self,
+        params: &CheckResourceFitParams,
+        lang: Lang,
+    ) -> Result<CheckResourceFitResponse, String> {
+        let CheckResourceFitParams { cpu_cores, memory_gb, node_cpu_cores, node_memory_gb, .. } = *params;
+        let node_groups = if params.consider_autoscaling { params.node_groups.as_deref() } else { None };
+
+        // Supplied totals replace the cluster entirely: no nodes or pods, so the scale-up
+        // math relies on node_cpu_cores/node_memory_gb
+        let mut preemption = None;
+        let exclude_namespaces = params.exclude_namespaces.as_deref().unwrap_or_default();
+        let mut freed = Resources::default();
+        let (nodes, pods, mut capacity) = match &params.cluster_override {
+            Some(totals) => (Vec::new(), Vec::new(), totals.to_capacity()),
+            None => {
+                let mut state = self.fetch_cluster_state(Self::state_for_basis(params.basis, params.cache.fresh.unwrap_or(false))).await?;
+                let usage = (params.basis == ResourceBasis::Usage).then_some(&state.pod_usage);
+                freed = exclude_namespace_pods(&mut state.pods, exclude_namespaces, params.basis, usage);
+                let capacity = Self::compute_cluster_capacity(&state.nodes, &state.pods, params.basis, usage);
+                if let Some(priority_class) = &params.priority_class {
+                    let class = self.get_priority_class(priority_class).await?;
+                    preemption = Some(preemptible_capacity(&class, &state.pods, params.basis, usage));
+                }
+                (state.nodes, state.pods, capacity)
+            }
+        };
+        let target_utilization = params.target_utilization_percent.filter(|target| *target < 100.0);
+        if let Some(target) = target_utilization {
+            capacity.cap_utilization(target);
+        }
+        if params.subtract_pending_demand {
+            capacity.subtract_pending_demand();
+        }
+        if let Some(preemption) = preemption.as_ref().filter(|preemption| preemption.preempts) {
+            capacity.reclaim_preemptible(preemption);
+        }
+        
+        let tolerance = self.config.fit_tolerance;
+        let mut response = Self::build_resource_fit_response(cpu_cores, memory_gb, &capacity, tolerance, lang);
+        if let Some(extra_resources) = &params.extra_resources {
+            Self::apply_extra_resources(&mut response, extra_resources, &nodes, &pods, tolerance, lang)?;
+        }
+        if params.cluster_override.is_some() {
+            response.explanation.push_str(&format!(" {}", messages::offline_estimate(lang)));
+        }
+        if let Some(target) = target_utilization {
+            response.explanation.push_str(&format!(" {}", messages::utilization_cap(lang, target, &capacity)));
+        }
+        if !exclude_namespaces.is_empty() {
+            response.explanation.push_str(&format!(" {}", messages::excluded_namespaces(lang, exclude_namespaces, freed.cores_and_gb())));
+        }
+        if let Some(note) = messages::resource_basis(lang, params.basis) {
+            response.explanation.push_str(&format!(" {}", note));
+        }
+        if params.subtract_pending_demand {
+            response.explanation.push_str(&format!(" {}", messages::pending_demand(lang, &capacity)));
+        }
+        if let Some(preemption) = preemption {
+            response.explanation.push_str(&format!(" {}", messages::preemption(lang, &preemption)));
+            response.preemption = Some(preemption);
+        }
+        
+        // Scale-up math covers CPU and memory only
+        if !response.cpu_fits || !response.memory_fits {
+            let scale_up = Self::recommend_scale_up(
+                cpu_cores - capacity.available_cpu_cores,
+                memory_gb - capacity.available_memory_gb,
+                node_cpu_cores,
+                node_memory_gb,
+                &nodes,
+                &pods,
+                lang,
+            );
+            response.explanation.push_str(&format!(" {}", scale_up.explanation));
+            if let Some(node_groups) = node_groups {
+                let nodes_needed = autoscale_nodes_needed(&scale_up, node_groups);
+                response.fits_after_scaleup = Some(nodes_needed.is_some());
+                response.autoscale_nodes_needed = nodes_needed;
+                response.explanation.push_str(&format!(" {}", messages::autoscale(lang, nodes_needed)));
+            }
+            response.scale_up = Some(scale_up);
+        }
+        
+        Ok(response)
+    }
+
+    /// Get a PriorityClass by name
+    async fn get_priority_class(&self, name: &str) -> Result<PriorityClass, String> {
+        Api::<PriorityClass>::all(self.client().await?)
+            .get_opt(name).await
+            .map_err(|e| format!("Failed to get priority class '{}': {}", name, e))?
+            .ok_or_else(|| format!("PriorityClass '{}' not found", name))
+    }
+
+    /// Build the resource fit response from the cluster capacity; a shortfall within `tolerance` still fits
+    fn build_resource_fit_response(
+        cpu_cores: f64,
+        memory_gb: f64,
+        capacity: &ClusterCapacityResponse,
+        tolerance: f64,
+        lang: Lang,
+    ) -> CheckResourceFitResponse {
+        let cpu_fits = fits_within(capacity.available_cpu_cores, cpu_cores, tolerance);
+        let memory_fits = fits_within(capacity.available_memory_gb, memory_gb, tolerance);
+        let fits = cpu_fits && memory_fits;
+        
+        let cpu_utilization_percent = if capacity.total_cpu_cores > 0.0 {
+            (capacity.allocated_cpu_cores + cpu_cores) / capacity.total_cpu_cores * 100.0
+        } else {
+            0.0
+        };
+        
+        let memory_utilization_percent = if capacity.total_memory_gb > 0.0 {
+            (capacity.allocated_memory_gb + memory_gb) / capacity.total_memory_gb * 100.0
+        } else {
+            0.0
+        };
+        
+        let explanation = messages::resource_fit(
+            lang,
+            cpu_cores,
+            memory_gb,
+            capacity,
+            cpu_utilization_percent,
+            memory_utilization_percent,
+        );
+        
+        CheckResourceFitResponse {
+            fits,
+            cpu_fits,
+            memory_fits,
+            available_cpu_cores: capacity.available_cpu_cores,
+            available_memory_gb: capacity.available_memory_gb,
+            cpu_utilization_percent,
+            memory_utilization_percent,
+            basis: capacity.basis,
+            explanation,
+            scale_up: None,
+            fits_after_scaleup: None,
+            autoscale_nodes_needed: None,
+            extra_resources: None,
+            preemption: None,
+        }
+    }
+
+    /// Check each extra resource against the summed node allocatable minus the effective requests of
+    /// the scheduled, non-terminal pods, and fold the result into `fits` and the explanation
+    fn apply_extra_resources(
+        response: &mut CheckResourceFitResponse,
+        extra_resources: &BTreeMap<String, String>,
+        nodes: &[Node],
+        pods: &[Pod],
+        tolerance: f64,
+        lang: Lang,
+    ) -> Result<(), String> {
+        let mut fits = Vec::new();
+        for (resource, quantity) in extra_resources {
+            let requested = parse_quantity_units(quantity)
+                .ok_or_else(|| format!("Invalid quantity '{}' for extra resource '{}'", quantity, resource))?;
+
+            let allocatable: i64 = nodes.iter()
+                .filter_map(|node| node.status.as_ref()?.allocatable.as_ref()?.get(resource))
+                .map(quantity_to_count)
+                .sum();
+            let allocated: i64 = pods.iter()
+                .filter(|pod| !pod_is_terminal(pod) && pod_node_name(pod).is_some())
+                .map(|pod| pod_effective_request_count(pod, resource))
+                .sum();
+
+            let (requested, available) = (requested as f64, (allocatable - allocated) as f64);
+            let fit = ExtraResourceFit { resource: resource.clone(), requested, available, fits: fits_within(available, requested, tolerance) };
+            response.fits &= fit.fits;
+            response.explanation.push_str(&format!(" {}", messages::extra_resource_fit(lang, &fit)));
+            fits.push(fit);
+        }
+
+        response.extra_resources = Some(fits);
+        Ok(())
+    }
+
+    /// Recommend how many nodes to add to cover a CPU/memory shortfall.
+    /// The node size defaults to the median allocatable of existing nodes, and each new node
+    /// is assumed to run one pod of every DaemonSet.
+    fn recommend_scale_up(
+        cpu_shortfall_cores: f64,
+        memory_shortfall_gb: f64,
+        node_cpu_cores: Option<f64>,
+        node_memory_gb: Option<f64>,
+        nodes: &[Node],
+        pods: &[Pod],
+        lang: Lang,
+    ) -> ScaleUpRecommendation {
+        let cpu_shortfall_cores = cpu_shortfall_cores.max(0.0);
+        let memory_shortfall_gb = memory_shortfall_gb.max(0.0);
+
+        let (node_size_source, node_cpu_cores, node_memory_gb) = match (node_cpu_cores, node_memory_gb) {
+            (Some(cpu), Some(memory)) => ("parameter".to_string(), cpu, memory),
+            (cpu, memory) => {
+                let allocatable: Vec<(f64, f64)> = nodes.iter().map(node_allocatable).collect();
+                (
+                    "median of existing nodes".to_string(),
+                    cpu.unwrap_or_else(|| median(allocatable.iter().map(|a| a.0).collect())),
+                    memory.unwrap_or_else(|| median(allocatable.iter().map(|a| a.1).collect())),
+                )
+            }
+        };
+
+        let (daemonset_cpu_overhead_cores, daemonset_memory_overhead_gb) = daemonset_overhead(pods);
+        let usable_cpu_cores = node_cpu_cores - daemonset_cpu_overhead_cores;
+        let usable_memory_gb = node_memory_gb - daemonset_memory_overhead_gb;
+
+        let nodes_needed = nodes_needed_for_shortfall(
+            cpu_shortfall_cores,
+            memory_shortfall_gb,
+            usable_cpu_cores,
+            usable_memory_gb,
+        );
+
+        let mut recommendation = ScaleUpRecommendation {
+            cpu_shortfall_cores,
+            memory_shortfall_gb,
+            node_cpu_cores,
+            node_memory_gb,
+            node_size_source,
+            daemonset_cpu_overhead_cores,
+            daemonset_memory_overhead_gb,
+            nodes_needed,
+            explanation: String::new(),
+        };
+        recommendation.explanation = messages::scale_up(lang, &recommendation);
+        
+        recommendation
+    }
+    
+    /// Get node breakdown
+    async fn get_node_breakdown_internal(
+        &self,
+        params: &NodeBreakdownParams,
+        lang: Lang,
+        fresh: bool,
+    ) -> Result<NodeBreakdownResponse, String> {
+        let state = self.fetch_cluster_state(Self::state_for_basis(params.basis, fresh)).await?;
+        let usage = (params.basis == ResourceBasis::Usage).then_some(&state.pod_usage);
+        
+        let mut node_infos = Self::build_node_infos(&state.nodes, &state.pods, params.basis, usage);
+        let orphaned_pod_count = orphaned_pods(&state.nodes, &state.pods).len();
+        let offset = match &params.cursor {
+            Some(cursor) => cursor_offset(&mut node_infos, params.sort_by, cursor)?,
+            None => params.offset.unwrap_or(0),
+        };
+        Ok(Self::build_node_breakdown_within(node_infos, &state.nodes, params, offset, orphaned_pod_count, &self.config, lang))
+    }
+
+    /// The node breakdown page for `params`, shrunk until it fits the configured MAX_RESPONSE_ITEMS /
+    /// MAX_RESPONSE_BYTES. Truncation never cuts the page, so next_offset and next_cursor resume right
+    /// after the last node returned.
+    fn build_node_breakdown_within(
+        node_infos: Vec<NodeInfo>,
+        nodes: &[Node],
+        params: &NodeBreakdownParams,
+        offset: usize,
+        orphaned_pod_count: usize,
+        config: &ServerConfig,
+        lang: Lang,
+    ) -> NodeBreakdownResponse {
+        let mut limit = match config.max_response_items {
+            0 => params.limit,
+            max_items => Some(params.limit.map_or(max_items, |limit| limit.min(max_items))),
+        };
+        loop {
+            let mut response = Self::build_node_breakdown(node_infos.clone(), params.sort_by, offset, limit, params.basis, orphaned_pod_count, lang);
+            let page_len = response.nodes.len();
+            if params.group_identical {
+                let groups = Self::build_node_groups(nodes, &response.nodes);
+                response.explanation.push_str(&format!(" {}", messages::node_groups(lang, groups.len(), response.total_nodes)));
+                response.nodes.clear();
+                response.node_groups = Some(groups);
+            }
+            if page_len <= 1 || fits_response_bytes(&response, config.max_response_bytes) {
+                return response;
+            }
+            limit = Some(page_len / 2);
+        }
+    }
+
+    /// Collapse node infos into groups of nodes with the same instance type and allocatable whose CPU and memory
+    /// utilization fall in the same NODE_GROUP_BAND_PERCENT band. Largest group first.
+    fn build_node_groups(nodes: &[Node], node_infos: &[NodeInfo]) -> Vec<NodeGroup> {
+        let instance_types: HashMap<&str, &str> = nodes.iter()
+            .filter_map(|node| {
+                let labels = node.metadata.labels.as_ref()?;
+                let instance_type = INSTANCE_TYPE_LABELS.iter().find_map(|label| labels.get(*label))?;
+                Some((node.metadata.name.as_deref()?, instance_type.as_str()))
+            })
+            .collect();
+        let band = |allocated: f64, allocatable: f64| {
+            if allocatable > 0.0 { (allocated / allocatable * 100.0 / NODE_GROUP_BAND_PERCENT).floor() as i64 } else { 0 }
+        };
+
+        // Allocatable is keyed in millicores and MB so identical nodes compare equal
+        let mut grouped: BTreeMap<(&str, i64, i64, i64, i64), Vec<&NodeInfo>> = BTreeMap::new();
+        for info in node_infos {
+            let key = (
+                instance_types.get(info.name.as_str()).copied().unwrap_or("unknown"),
+                (info.allocatable_cpu_cores * 1000.0).round() as i64,
+                (info.allocatable_memory_gb * 1024.0).round() as i64,
+                band(info.allocated_cpu_cores, info.allocatable_cpu_cores),
+                band(info.allocated_memory_gb, info.allocatable_memory_gb),
+            );
+            grouped.entry(key).or_default().push(info);
+        }
+
+        let range = |values: Vec<f64>| AvailabilityRange {
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            avg: values.iter().sum::<f64>() / values.len() as f64,
+        };
+        let mut groups: Vec<NodeGroup> = grouped.into_iter()
+            .map(|((instance_type, ..), infos)| NodeGroup {
+                instance_type: instance_type.to_string(),
+                node_count: infos.len(),
+                nodes: infos.iter().map(|info| info.name.clone()).collect(),
+                allocatable_cpu_cores: infos[0].allocatable_cpu_cores,
+                allocatable_memory_gb: infos[0].allocatable_memory_gb,
+                available_cpu_cores: range(infos.iter().map(|info| info.available_cpu_cores).collect()),
+                available_memory_gb: range(infos.iter().map(|info| info.available_memory_gb).collect()),
+                pod_count: infos.iter().map(|info| info.pod_count).sum(),
+            })
+            .collect();
+        groups.sort_by(|a, b| b.node_count.cmp(&a.node_count));
+
+        groups
+    }
+
+    /// Sort the node infos (aggregated by `basis`) and cut out the page starting at `offset`
+    /// with at most `limit` nodes. `orphaned_pod_count` pods bound to missing nodes are in no node info.
+    fn build_node_breakdown(
+        mut node_infos: Vec<NodeInfo>,
+        sort_by: NodeSortBy,
+        offset: usize,
+        limit: Option<usize>,
+        basis: ResourceBasis,
+        orphaned_pod_count: usize,
+        lang: Lang,
+    ) -> NodeBreakdownResponse {
+        sort_node_infos(&mut node_infos, sort_by);
+        
+        let total_nodes = node_infos.len();
+        let nodes: Vec<NodeInfo> = node_infos.into_iter()
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
+        let next_offset = Some(offset + nodes.len()).filter(|next| *next < total_nodes);
+        let next_cursor = next_offset.and_then(|_| nodes.last()).map(|last| NodeCursor::after(last, sort_by).encode());
+        
+        let mut explanation = messages::node_breakdown(lang, total_nodes, basis);
+        if nodes.len() < total_nodes {
+            explanation.push_str(&format!(" {}", messages::node_page(lang, offset, nodes.len(), total_nodes)));
+        }
+        if let Some(note) = messages::resource_basis(lang, basis) {
+            explanation.push_str(&format!(" {}", note));
+        }
+        if orphaned_pod_count > 0 {
+            explanation.push_str(&format!(" {}", messages::orphaned_pods(lang, orphaned_pod_count)));
+        }
+        
+        NodeBreakdownResponse {
+            nodes,
+            total_nodes,
+            offset,
+            next_offset,
+            next_cursor,
+            basis,
+            orphaned_pod_count,
+            node_groups: None,
+            explanation,
+        }
+    }
+
+    /// Build the per-node resource information from node and pod lists, counting what pods
+    /// hold by `basis` (`usage` feeds the Usage basis)
+    fn build_node_infos(nodes: &[Node], pods: &[Pod], basis: ResourceBasis, usage: Option<&PodUsage>) -> Vec<NodeInfo> {
+        let mut node_infos = Vec::new();
+        
+        for node in nodes {
+            let name = node.metadata.name.clone().unwrap_or_default();
+            
+            let total = node_capacity(node);
+            let allocatable = node_allocatable_resources(node);
+            
+            let mut allocated = Resources::default();
+            let mut pod_count = 0;
+            
+            for pod in pods.iter().filter(|pod| !pod_is_terminal(pod) && pod_node_name(pod) == Some(name.as_str())) {
+                pod_count += 1;
+                allocated += basis.pod_amounts(pod, usage);
+            }
+            
+            let available = total - allocated;
+            
+            node_infos.push(NodeInfo {
+                name,
+                total_cpu_cores: total.cores(),
+                total_memory_gb: total.gb(),
+                allocatable_cpu_cores: allocatable.cores(),
+                allocatable_memory_gb: allocatable.gb(),
+                allocated_cpu_cores: allocated.cores(),
+                allocated_memory_gb: allocated.gb(),
+                available_cpu_cores: available.cores(),
+                available_memory_gb: available.gb(),
+                pod_count,
+            });
+        }
+        
+        node_infos
+    }
+
+    /// Find pods whose effective requests exceed what any single node can allocate
+    async fn find_unschedulable_by_size_internal(&self, lang: Lang, fresh: bool) -> Result<UnschedulableBySizeResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Ok(Self::build_unschedulable_by_size(&nodes, &pods, lang))
+    }
+
+    /// Flag pods that fit on no node: no single node's allocatable covers both their CPU and memory
+    fn build_unschedulable_by_size(nodes: &[Node], pods: &[Pod], lang: Lang) -> UnschedulableBySizeResponse {
+        let allocatable: Vec<(f64, f64)> = nodes.iter().map(node_allocatable).collect();
+        let largest_node_cpu_cores = allocatable.iter().map(|(cpu, _)| *cpu).fold(0.0, f64::max);
+        let largest_node_memory_gb = allocatable.iter().map(|(_, mem)| *mem).fold(0.0, f64::max);
+
+        let mut total_pods_checked = 0;
+        let mut oversized_pods = Vec::new();
+
+        for pod in pods {
+            let phase = pod.status.as_ref().and_then(|s| s.phase.clone()).unwrap_or_default();
+            if phase == "Succeeded" || phase == "Failed" {
+                continue;
+            }
+            total_pods_checked += 1;
+
+            let (cpu_requests_cores, memory_requests_gb) = pod_effective_requests(pod);
+            let fits_some_node = allocatable.iter().any(|(cpu, mem)| {
+                *cpu >= cpu_requests_cores && *mem >= memory_requests_gb
+            });
+
+            if !fits_some_node {
+                oversized_pods.push(OversizedPod {
+                    name: pod.metadata.name.clone().unwrap_or_default(),
+                    namespace: pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                    node: pod_node(pod),
+                    phase,
+                    cpu_requests_cores,
+                    memory_requests_gb,
+                    exceeds_cpu: cpu_requests_cores > largest_node_cpu_cores,
+                    exceeds_memory: memory_requests_gb > largest_node_memory_gb,
+                });
+            }
+        }
+
+        oversized_pods.sort_by(|a, b| a.namespace.cmp(&b.namespace).then_with(|| a.name.cmp(&b.name)));
+
+        let mut response = UnschedulableBySizeResponse {
+            total_oversized: oversized_pods.len(),
+            pods: oversized_pods,
+            total_pods_checked,
+            largest_node_cpu_cores,
+            largest_node_memory_gb,
+            explanation: String::new(),
+        };
+        response.explanation = messages::unschedulable_by_size(lang, &response);
+        response
+    }
+    
+    /// Get the pods of a node
+    async fn get_pods_on_node_internal(&self, node_name: &str, check_reschedulable: bool, lang: Lang, fresh: bool) -> Result<PodsOnNodeResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Self::build_pods_on_node(node_name, &nodes, &pods, check_reschedulable, lang)
+    }
+
+    /// List the non-terminal pods of a node and optionally bin-pack the movable ones
+    /// (first-fit decreasing by CPU) onto the free allocatable of the other schedulable nodes
+    fn build_pods_on_node(
+        node_name: &str,
+        nodes: &[Node],
+        pods: &[Pod],
+        check_reschedulable: bool,
+        lang: Lang,
+    ) -> Result<PodsOnNodeResponse, String> {
+        let node = nodes.iter()
+            .find(|n| n.metadata.name.as_deref() == Some(node_name))
+            .ok_or_else(|| format!("Node '{}' not found", node_name))?;
+        let cordoned = node_is_cordoned(node);
+
+        let mut node_pods: Vec<NodePod> = pods.iter()
+            .filter(|pod| pod_node_name(pod) == Some(node_name))
+            .filter_map(|pod| {
+                let phase = pod.status.as_ref().and_then(|s| s.phase.clone()).unwrap_or_default();
+                if phase == "Succeeded" || phase == "Failed" {
+                    return None;
+                }
+                let (cpu_requests_cores, memory_requests_gb) = pod_effective_requests(pod);
+                Some(NodePod {
+                    name: pod.metadata.name.clone().unwrap_or_default(),
+                    namespace: pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                    phase,
+                    cpu_requests_cores,
+                    memory_requests_gb,
+                    daemonset: daemonset_owner(pod).is_some(),
+                    reschedulable: None,
+                    target_node: None,
+                })
+            })
+            .collect();
+        node_pods.sort_by(|a, b| a.namespace.cmp(&b.namespace).then_with(|| a.name.cmp(&b.name)));
+
+        let mut unreschedulable_pods = None;
+        if check_reschedulable {
+            let mut free: Vec<(String, f64, f64)> = Self::build_node_infos(nodes, pods, ResourceBasis::Requests, None).into_iter()
+                .filter(|info| info.name != node_name)
+                .filter(|info| nodes.iter().any(|n| n.metadata.name.as_deref() == Some(&info.name) && !node_is_cordoned(n)))
+                .map(|info| (
+                    info.name,
+                    info.allocatable_cpu_cores - info.allocated_cpu_cores,
+                    info.allocatable_memory_gb - info.allocated_memory_gb,
+                ))
+                .collect();
+
+            let mut order: Vec<usize> = (0..node_pods.len()).filter(|&i| !node_pods[i].daemonset).collect();
+            order.sort_by(|&a, &b| node_pods[b].cpu_requests_cores.total_cmp(&node_pods[a].cpu_requests_cores));
+
+            let mut failed = 0;
+            for i in order {
+                let pod = &mut node_pods[i];
+                let slot = free.iter_mut()
+                    .find(|(_, cpu, memory)| *cpu >= pod.cpu_requests_cores && *memory >= pod.memory_requests_gb);
+                match slot {
+                    Some((target, cpu, memory)) => {
+                        *cpu -= pod.cpu_requests_cores;
+                        *memory -= pod.memory_requests_gb;
+                        pod.reschedulable = Some(true);
+                        pod.target_node = Some(target.clone());
+                    }
+                    None => {
+                        pod.reschedulable = Some(false);
+                        failed += 1;
+                    }
+                }
+            }
+
+            unreschedulable_pods = Some(failed);
+        }
+
+        let mut response = PodsOnNodeResponse {
+            node: node_name.to_string(),
+            cordoned,
+            total_pods: node_pods.len(),
+            pods: node_pods,
+            unreschedulable_pods,
+            explanation: String::new(),
+        };
+        response.explanation = messages::pods_on_node(lang, &response);
+        Ok(response)
+    }
+
+    /// Get namespace usage
+    async fn get_namespace_usage_internal(
+        &self,
+        include_terminal: bool,
+        selected: Option<&[String]>,
+        lang: Lang,
+        fresh: bool,
+    ) -> Result<NamespaceUsageResponse, String> {
+        let state = self.fetch_cluster_state(ClusterStateOptions { pods: true, namespaces: true, fresh, ..Default::default() }).await?;
+        
+        Self::build_namespace_usage(&state.namespaces, &state.pods, selected, include_terminal, lang)
+    }
+
+    /// Usage of every namespace, or of just the selected ones (which must exist) plus their combined total
+    fn build_namespace_usage(
+        namespace_names: &[String],
+        pods: &[Pod],
+        selected: Option<&[String]>,
+        include_terminal: bool,
+        lang: Lang,
+    ) -> Result<NamespaceUsageResponse, String> {
+        let missing: Vec<&str> = selected.iter().copied().flatten()
+            .filter(|name| !namespace_names.contains(*name))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!("Namespaces not found: {}", missing.join(", ")));
+        }
+
+        let in_selection = |namespace: &str| selected.is_none_or(|names| names.iter().any(|name| name == namespace));
+        let pods: Vec<Pod> = pods.iter()
+            .filter(|pod| in_selection(pod.metadata.namespace.as_deref().unwrap_or("default")))
+            .cloned()
+            .collect();
+        let namespace_names: Vec<String> = namespace_names.iter().filter(|name| in_selection(name.as_str())).cloned().collect();
+        
+        let namespace_usages = Self::build_namespace_usages(&namespace_names, &pods);
+        
+        let total_namespaces = namespace_usages.len();
+        
+        let combined = selected.map(|names| {
+            let mut totals = PodTotals::default();
+            for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+                totals.add(pod);
+            }
+            NamespaceUsage {
+                namespace: names.join(", "),
+                cpu_requests_cores: totals.requests.cores(),
+                memory_requests_gb: totals.requests.gb(),
+                cpu_limits_cores: totals.limits.cores(),
+                memory_limits_gb: totals.limits.gb(),
+                pod_count: totals.pod_count,
+            }
+        });
+        
+        let mut explanation = messages::namespace_usage(lang, total_namespaces);
+        if let Some(combined) = &combined {
+            explanation.push_str(&format!(" {}", messages::combined_namespace_usage(lang, combined)));
+        }
+        
+        Ok(NamespaceUsageResponse {
+            total_namespaces,
+            namespaces: namespace_usages,
+            sort: SortSpec { key: "cpu_requests_cores".to_string(), order: SortOrder::Descending, basis: ResourceBasis::Requests },
+            combined,
+            terminal_pods: include_terminal.then(|| Self::build_terminal_pods(&pods)),
+            explanation,
+        })
+    }
+
+    /// Succeeded and Failed pods, sorted by namespace and name
+    fn build_terminal_pods(pods: &[Pod]) -> Vec<TerminalPod> {
+        let mut terminal_pods: Vec<TerminalPod> = pods.iter()
+            .filter(|pod| pod_is_terminal(pod))
+            .map(|pod| TerminalPod {
+                name: pod.metadata.name.clone().unwrap_or_default(),
+                namespace: pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                phase: pod.status.as_ref().and_then(|s| s.phase.clone()).unwrap_or_default(),
+            })
+            .collect();
+        terminal_pods.sort_by(|a, b| a.namespace.cmp(&b.namespace).then_with(|| a.name.cmp(&b.name)));
+
+        terminal_pods
+    }
+
+    /// Aggregate pod requests and limits per namespace, including the given namespaces even without pods.
+    /// Terminal pods are skipped. Sorted by CPU requests (descending).
+    fn build_namespace_usages(namespace_names: &[String], pods: &[Pod]) -> Vec<NamespaceUsage> {
+        let mut namespace_totals: HashMap<String, PodTotals> = HashMap::new();
+        
+        // Initialize namespace usage
+        for name in namespace_names {
+            namespace_totals.insert(name.clone(), PodTotals::default());
+        }
+        
+        // Aggregate pod resources by namespace
+        for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+            let ns_name = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+            namespace_totals.entry(ns_name).or_default().add(pod);
+        }
+        
+        let mut namespace_usages: Vec<NamespaceUsage> = namespace_totals.into_iter()
+            .map(|(namespace, totals)| NamespaceUsage {
+                namespace,
+                cpu_requests_cores: totals.requests.cores(),
+                memory_requests_gb: totals.requests.gb(),
+                cpu_limits_cores: totals.limits.cores(),
+                memory_limits_gb: totals.limits.gb(),
+                pod_count: totals.pod_count,
+            })
+            .collect();
+        namespace_usages.sort_by(|a, b| b.cpu_requests_cores.partial_cmp(&a.cpu_requests_cores).unwrap());
+        
+        namespace_usages
+    }
+    
+    /// Get namespaces (and pods) whose limits exceed their requests
+    async fn get_burst_risk_internal(&self, ratio_threshold: f64, include_pods: bool, lang: Lang, fresh: bool) -> Result<BurstRiskResponse, String> {
+        let pods = self.list_pods(None, fresh).await?;
+
+        Ok(Self::build_burst_risk(&pods, ratio_threshold, include_pods, lang))
+    }
+
+    /// Flag namespaces (and optionally pods) whose limit/request ratio exceeds the threshold
+    fn build_burst_risk(pods: &[Pod], ratio_threshold: f64, include_pods: bool, lang: Lang) -> BurstRiskResponse {
+        let namespace_usages = Self::build_namespace_usages(&[], pods);
+        let total_namespaces_checked = namespace_usages.len();
+
+        let by_ratio = |a: &BurstRisk, b: &BurstRisk| b.max_ratio().total_cmp(&a.max_ratio());
+
+        let mut namespaces: Vec<BurstRisk> = namespace_usages.into_iter()
+            .map(|u| BurstRisk::new(
+                u.namespace,
+                None,
+                (u.cpu_requests_cores, u.memory_requests_gb),
+                (u.cpu_limits_cores, u.memory_limits_gb),
+            ))
+            .filter(|r| r.max_ratio() > ratio_threshold)
+            .collect();
+        namespaces.sort_by(by_ratio);
+
+        let flagged_pods = include_pods.then(|| {
+            let mut flagged: Vec<BurstRisk> = pods.iter()
+                .map(|pod| BurstRisk::new(
+                    pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                    Some(pod.metadata.name.clone().unwrap_or_default()),
+                    pod_requests(pod),
+                    pod_limits(pod),
+                ))
+                .filter(|r| r.max_ratio() > ratio_threshold)
+                .collect();
+            flagged.sort_by(by_ratio);
+            flagged
+        });
+
+        let highest = namespaces.first().map(|r| (r.namespace.as_str(), r.max_ratio()));
+        let explanation = messages::burst_risk(lang, namespaces.len(), total_namespaces_checked, ratio_threshold, highest);
+
+        BurstRiskResponse {
+            ratio_threshold,
+            namespaces,
+            pods: flagged_pods,
+            total_namespaces_checked,
+            explanation,
+        }
+    }
+
+    /// Find namespaces where many pods have no memory limit
+    async fn find_namespaces_without_limits_internal(&self, min_unlimited_fraction: f64, lang: Lang, fresh: bool) -> Result<NamespacesWithoutLimitsResponse, String> {
+        let pods = self.list_pods(None, fresh).await?;
+
+        Ok(Self::build_namespaces_without_limits(&pods, min_unlimited_fraction, lang))
+    }
+
+    /// Flag namespaces where at least `min_unlimited_fraction` of the pods lack a memory limit
+    fn build_namespaces_without_limits(pods: &[Pod], min_unlimited_fraction: f64, lang: Lang) -> NamespacesWithoutLimitsResponse {
+        let namespace_usages = Self::build_namespace_usages(&[], pods);
+        let total_namespaces_checked = namespace_usages.len();
+
+        let mut unlimited: HashMap<&str, usize> = HashMap::new();
+        for pod in pods.iter().filter(|pod| !pod_has_memory_limit(pod)) {
+            *unlimited.entry(pod.metadata.namespace.as_deref().unwrap_or("default")).or_default() += 1;
+        }
+
+        let mut namespaces: Vec<NamespaceLimitGap> = namespace_usages.into_iter()
+            .filter(|u| u.pod_count > 0)
+            .map(|u| {
+                let pods_without_memory_limit = unlimited.get(u.namespace.as_str()).copied().unwrap_or(0);
+                NamespaceLimitGap {
+                    unlimited_fraction: pods_without_memory_limit as f64 / u.pod_count as f64,
+                    namespace: u.namespace,
+                    pod_count: u.pod_count,
+                    pods_without_memory_limit,
+                }
+            })
+            .filter(|gap| gap.unlimited_fraction >= min_unlimited_fraction)
+            .collect();
+        namespaces.sort_by(|a, b| b.unlimited_fraction.total_cmp(&a.unlimited_fraction)
+            .then_with(|| b.pods_without_memory_limit.cmp(&a.pods_without_memory_limit))
+            .then_with(|| a.namespace.cmp(&b.namespace)));
+
+        let explanation = messages::namespaces_without_limits(
+            lang, namespaces.len(), total_namespaces_checked, min_unlimited_fraction, namespaces.first(),
+        );
+
+        NamespacesWithoutLimitsResponse {
+            min_unlimited_fraction,
+            namespaces,
+            total_namespaces_checked,
+            explanation,
+        }
+    }
+
+    /// Check which candidate pod sizes still fit on each node
+    async fn node_acceptance_matrix_internal(&self, sizes: &[PodSize], lang: Lang, fresh: bool) -> Result<NodeAcceptanceMatrixResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Ok(Self::build_node_acceptance_matrix(&Self::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None), sizes, lang))
+    }
+
+    /// Build the node × size matrix from the available resources of each node
+    fn build_node_acceptance_matrix(node_infos: &[NodeInfo], sizes: &[PodSize], lang: Lang) -> NodeAcceptanceMatrixResponse {
+        let mut nodes: Vec<NodeAcceptance> = node_infos.iter()
+            .map(|info| NodeAcceptance {
+                node: info.name.clone(),
+                available_cpu_cores: info.available_cpu_cores,
+                available_memory_gb: info.available_memory_gb,
+                accepts: sizes.iter()
+                    .map(|size| size.cpu_cores <= info.available_cpu_cores && size.memory_gb <= info.available_memory_gb)
+                    .collect(),
+            })
+            .collect();
+        nodes.sort_by(|a, b| a.node.cmp(&b.node));
+
+        let accepting_nodes: Vec<usize> = (0..sizes.len())
+            .map(|i| nodes.iter().filter(|row| row.accepts[i]).count())
+            .collect();
+        let full_nodes: Vec<&str> = nodes.iter()
+            .filter(|row| !row.accepts.contains(&true))
+            .map(|row| row.node.as_str())
+            .collect();
+
+        let explanation = messages::node_acceptance_matrix(lang, nodes.len(), sizes, &accepting_nodes, &full_nodes);
+
+        NodeAcceptanceMatrixResponse {
+            sizes: sizes.iter().map(|size| size.name.clone()).collect(),
+            nodes,
+            accepting_nodes,
+            explanation,
+        }
+    }
+
+    /// Find the largest pod on every node
+    async fn get_largest_pod_per_node_internal(&self, lang: Lang, fresh: bool) -> Result<LargestPodPerNodeResponse, String> {
+        let pods = self.list_pods(None, fresh).await?;
+
+        Ok(Self::build_largest_pod_per_node(&pods, lang))
+    }
+
+    /// Group the bound, non-terminal pods by node and keep the largest CPU and memory request of each.
+    /// Ties go to the first pod by namespace and name.
+    fn build_largest_pod_per_node(pods: &[Pod], lang: Lang) -> LargestPodPerNodeResponse {
+        let mut by_node: BTreeMap<&str, Vec<LargestPod>> = BTreeMap::new();
+        for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+            let Some(node) = pod_node_name(pod) else { continue };
+            let (cpu_requests_cores, memory_requests_gb) = pod_requests(pod);
+            by_node.entry(node).or_default().push(LargestPod {
+                name: pod.metadata.name.clone().unwrap_or_default(),
+                namespace: pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                cpu_requests_cores,
+                memory_requests_gb,
+            });
+        }
+
+        let nodes: Vec<NodeLargestPods> = by_node.into_iter()
+            .map(|(node, mut node_pods)| {
+                node_pods.sort_by(|a, b| a.namespace.cmp(&b.namespace).then_with(|| a.name.cmp(&b.name)));
+                // max_by keeps the last maximum, so search the reversed list to keep the first
+                let largest_by = |key: fn(&LargestPod) -> f64| node_pods.iter().rev()
+                    .max_by(|a, b| key(a).total_cmp(&key(b)))
+                    .cloned()
+                    .unwrap();
+                NodeLargestPods {
+                    node: node.to_string(),
+                    pod_count: node_pods.len(),
+                    largest_by_cpu: largest_by(|p| p.cpu_requests_cores),
+                    largest_by_memory: largest_by(|p| p.memory_requests_gb),
+                }
+            })
+            .collect();
+
+        let top = nodes.iter().max_by(|a, b| a.largest_by_cpu.cpu_requests_cores.total_cmp(&b.largest_by_cpu.cpu_requests_cores));
+        let explanation = messages::largest_pod_per_node(lang, nodes.len(), top);
+
+        LargestPodPerNodeResponse { nodes, explanation }
+    }
+
+    /// Find capacity stranded by unbalanced free CPU and memory
+    async fn get_stranded_capacity_internal(&self, cpu_to_memory_ratio: Option<f64>, lang: Lang, fresh: bool) -> Result<StrandedCapacityResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Self::build_stranded_capacity(&Self::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None), &pods, cpu_to_memory_ratio, lang)
+    }
+
+    /// Average CPU cores per GB of memory requested by the running pods, falling back to the
+    /// allocatable ratio of the nodes when the pods request no CPU or memory
+    fn average_request_ratio(node_infos: &[NodeInfo], pods: &[Pod]) -> f64 {
+        let (cpu, memory) = pods.iter()
+            .filter(|pod| !pod_is_terminal(pod))
+            .map(pod_requests)
+            .fold((0.0, 0.0), |acc, r| (acc.0 + r.0, acc.1 + r.1));
+        if cpu > 0.0 && memory > 0.0 {
+            return cpu / memory;
+        }
+
+        let cpu: f64 = node_infos.iter().map(|info| info.allocatable_cpu_cores).sum();
+        let memory: f64 = node_infos.iter().map(|info| info.allocatable_memory_gb).sum();
+        cpu / memory
+    }
+
+    /// Split the available resources of every node into what balanced pods of the reference ratio
+    /// can use and what is stranded: free CPU without free memory to pair it with, or vice versa
+    fn build_stranded_capacity(node_infos: &[NodeInfo], pods: &[Pod], cpu_to_memory_ratio: Option<f64>, lang: Lang) -> Result<StrandedCapacityResponse, String> {
+        let cpu_to_memory_ratio = cpu_to_memory_ratio.unwrap_or_else(|| Self::average_request_ratio(node_infos, pods));
+        if !cpu_to_memory_ratio.is_finite() || cpu_to_memory_ratio <= 0.0 {
+            return Err("Cannot derive a reference ratio: neither the pods nor the nodes report CPU and memory".to_string());
+        }
+        let memory_gb_per_core = 1.0 / cpu_to_memory_ratio;
+
+        let mut nodes: Vec<NodeStrandedCapacity> = node_infos.iter()
+            .map(|info| {
+                let available_cpu_cores = info.available_cpu_cores.max(0.0);
+                let available_memory_gb = info.available_memory_gb.max(0.0);
+                let usable_cpu_cores = available_cpu_cores.min(available_memory_gb / memory_gb_per_core);
+                let usable_memory_gb = usable_cpu_cores * memory_gb_per_core;
+                let stranded_cpu_cores = available_cpu_cores - usable_cpu_cores;
+                let stranded_memory_gb = available_memory_gb - usable_memory_gb;
+                let share = |stranded: f64, allocatable: f64| if allocatable > 0.0 { stranded / allocatable * 100.0 } else { 0.0 };
+                NodeStrandedCapacity {
+                    node: info.name.clone(),
+                    available_cpu_cores,
+                    available_memory_gb,
+                    usable_cpu_cores,
+                    usable_memory_gb,
+                    stranded_cpu_cores,
+                    stranded_memory_gb,
+                    stranded_percent: share(stranded_cpu_cores, info.allocatable_cpu_cores)
+                        .max(share(stranded_memory_gb, info.allocatable_memory_gb)),
+                }
+            })
+            .collect();
+        nodes.sort_by(|a, b| b.stranded_percent.total_cmp(&a.stranded_percent).then_with(|| a.node.cmp(&b.node)));
+
+        let total_stranded_cpu_cores: f64 = nodes.iter().map(|n| n.stranded_cpu_cores).sum();
+        let total_stranded_memory_gb: f64 = nodes.iter().map(|n| n.stranded_memory_gb).sum();
+
+        let explanation = messages::stranded_capacity(
+            lang,
+            nodes.len(),
+            memory_gb_per_core,
+            (total_stranded_cpu_cores, total_stranded_memory_gb),
+            nodes.first().filter(|worst| worst.stranded_percent > 0.0),
+        );
+
+        Ok(StrandedCapacityResponse {
+            cpu_to_memory_ratio,
+            nodes,
+            total_stranded_cpu_cores,
+            total_stranded_memory_gb,
+            explanation,
+        })
+    }
+
+    /// Find nodes whose pod requests exceed their allocatable
+    async fn find_overpacked_nodes_internal(&self, lang: Lang, fresh: bool) -> Result<OverpackedNodesResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Ok(Self::build_overpacked_nodes(&nodes, &pods, lang))
+    }
+
+    /// Compare the summed effective requests of the running pods on each node with its allocatable.
+    /// The scheduler never does this on its own, so a hit points at manual binding or a misconfigured node.
+    fn build_overpacked_nodes(nodes: &[Node], pods: &[Pod], lang: Lang) -> OverpackedNodesResponse {
+        let mut requested: HashMap<&str, (f64, f64, usize)> = HashMap::new();
+        for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+            let Some(node) = pod_node_name(pod) else { continue };
+            let (cpu, memory) = pod_effective_requests(pod);
+            let entry = requested.entry(node).or_default();
+            entry.0 += cpu;
+            entry.1 += memory;
+            entry.2 += 1;
+        }
+
+        let overage_ratio = |n: &OverpackedNode| {
+            let ratio = |over: f64, allocatable: f64| if allocatable > 0.0 { over / allocatable } else { f64::INFINITY };
+            ratio(n.cpu_overage_cores, n.allocatable_cpu_cores).max(ratio(n.memory_overage_gb, n.allocatable_memory_gb))
+        };
+
+        let mut overpacked: Vec<OverpackedNode> = nodes.iter()
+            .filter_map(|node| {
+                let name = node.metadata.name.clone().unwrap_or_default();
+                let (allocatable_cpu_cores, allocatable_memory_gb) = node_allocatable(node);
+                let (requested_cpu_cores, requested_memory_gb, pod_count) = requested.get(name.as_str()).copied().unwrap_or_default();
+                let cpu_overage_cores = (requested_cpu_cores - allocatable_cpu_cores).max(0.0);
+                let memory_overage_gb = (requested_memory_gb - allocatable_memory_gb).max(0.0);
+                (cpu_overage_cores > 0.0 || memory_overage_gb > 0.0).then_some(OverpackedNode {
+                    node: name,
+                    pod_count,
+                    allocatable_cpu_cores,
+                    allocatable_memory_gb,
+                    requested_cpu_cores,
+                    requested_memory_gb,
+                    cpu_overage_cores,
+                    memory_overage_gb,
+                })
+            })
+            .collect();
+        overpacked.sort_by(|a, b| overage_ratio(b).total_cmp(&overage_ratio(a)).then_with(|| a.node.cmp(&b.node)));
+
+        let explanation = messages::overpacked_nodes(lang, overpacked.len(), nodes.len(), overpacked.first());
+
+        OverpackedNodesResponse {
+            nodes: overpacked,
+            total_nodes_checked: nodes.len(),
+            explanation,
+        }
+    }
+
+    /// Report how evenly requests are spread across nodes
+    async fn get_balance_report_internal(&self, lang: Lang, fresh: bool) -> Result<BalanceReportResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Ok(Self::build_balance_report(&Self::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None), lang))
+    }
+
+    /// Spread of the per-node request utilization (requests / allocatable). A node's utilization for
+    /// ranking is the higher of CPU and memory, as that is the one that blocks scheduling first.
+    fn build_balance_report(node_infos: &[NodeInfo], lang: Lang) -> BalanceReportResponse {
+        let percent = |allocated: f64, allocatable: f64| if allocatable > 0.0 { allocated / allocatable * 100.0 } else { 0.0 };
+        let mut nodes: Vec<NodeUtilization> = node_infos.iter()
+            .map(|info| NodeUtilization {
+                node: info.name.clone(),
+                pod_count: info.pod_count,
+                cpu_utilization_percent: percent(info.allocated_cpu_cores, info.allocatable_cpu_cores),
+                memory_utilization_percent: percent(info.allocated_memory_gb, info.allocatable_memory_gb),
+            })
+            .collect();
+        let peak = |n: &NodeUtilization| n.cpu_utilization_percent.max(n.memory_utilization_percent);
+        nodes.sort_by(|a, b| peak(b).total_cmp(&peak(a)).then_with(|| a.node.cmp(&b.node)));
+
+        let spread = |values: Vec<f64>| {
+            if values.is_empty() {
+                return UtilizationSpread { mean_percent: 0.0, std_dev_percent: 0.0, coefficient_of_variation: 0.0 };
+            }
+            let count = values.len() as f64;
+            let mean_percent = values.iter().sum::<f64>() / count;
+            let std_dev_percent = (values.iter().map(|v| (v - mean_percent).powi(2)).sum::<f64>() / count).sqrt();
+            let coefficient_of_variation = if mean_percent > 0.0 { std_dev_percent / mean_percent } else { 0.0 };
+            UtilizationSpread { mean_percent, std_dev_percent, coefficient_of_variation }
+        };
+        let cpu = spread(nodes.iter().map(|n| n.cpu_utilization_percent).collect());
+        let memory = spread(nodes.iter().map(|n| n.memory_utilization_percent).collect());
+        let imbalanced = cpu.coefficient_of_variation > IMBALANCE_CV_THRESHOLD
+            || memory.coefficient_of_variation > IMBALANCE_CV_THRESHOLD;
+        let most_utilized = nodes.first().cloned();
+        let least_utilized = nodes.last().cloned();
+
+        let mut response = BalanceReportResponse {
+            cpu,
+            memory,
+            imbalanced,
+            most_utilized,
+            least_utilized,
+            nodes,
+            explanation: String::new(),
+        };
+        response.explanation = messages::balance_report(lang, &response, IMBALANCE_CV_THRESHOLD);
+        response
+    }
+
+    /// Report pod churn over the last `window_minutes`
+    async fn get_pod_churn_internal(&self, window_minutes: u64, lang: Lang, fresh: bool) -> Result<PodChurnResponse, String> {
+        let pods = self.list_pods(None, fresh).await?;
+        let events = match self.list_pod_deletion_events().await {
+            Ok(events) => Some(events),
+            Err(e) => {
+                diagnostics::warn("events_unavailable", format!("Pod deletion events unavailable, counting pod creations only: {}", e));
+                None
+            }
+        };
+
+        Ok(Self::build_pod_churn(&pods, events.as_deref(), window_minutes, chrono::Utc::now(), lang))
+    }
+
+    /// Events of controllers (ReplicaSet, StatefulSet, Job, ...) deleting pods. Pods deleted
+    /// directly leave no such event.
+    async fn list_pod_deletion_events(&self) -> Result<Vec<Event>, String> {
+        let events = Api::<Event>::all(self.client().await?)
+            .list(&ListParams::default().fields("reason=SuccessfulDelete")).await
+            .map_err(|e| format!("Failed to list events: {}", e))?;
+
+        Ok(events.items)
+    }
+
+    /// Count pod creations (creationTimestamp) and controller deletions (SuccessfulDelete events)
+    /// per namespace within `window_minutes` before `now`. Without events, only creations count.
+    fn build_pod_churn(
+        pods: &[Pod],
+        events: Option<&[Event]>,
+        window_minutes: u64,
+        now: chrono::DateTime<chrono::Utc>,
+        lang: Lang,
+    ) -> PodChurnResponse {
+        let since = now - chrono::Duration::minutes(window_minutes as i64);
+        let mut churn: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+
+        for pod in pods {
+            if pod.metadata.creation_timestamp.as_ref().is_some_and(|t| t.0 >= since) {
+                let namespace = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+                churn.entry(namespace).or_default().0 += 1;
+            }
+        }
+
+        for event in events.unwrap_or_default() {
+            let deletes_pod = event.reason.as_deref() == Some("SuccessfulDelete")
+                && event.message.as_deref().is_some_and(|m| m.starts_with("Deleted pod"));
+            let time = event.last_timestamp.as_ref().map(|t| t.0)
+                .or_else(|| event.event_time.as_ref().map(|t| t.0))
+                .or_else(|| event.metadata.creation_timestamp.as_ref().map(|t| t.0));
+            if deletes_pod && time.is_some_and(|t| t >= since) {
+                let namespace = event.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+                churn.entry(namespace).or_default().1 += 1;
+            }
+        }
+
+        let per_hour = |count: usize| if window_minutes > 0 { count as f64 * 60.0 / window_minutes as f64 } else { 0.0 };
+        let mut namespaces: Vec<NamespaceChurn> = churn.into_iter()
+            .map(|(namespace, (created_pods, deleted_pods))| NamespaceChurn {
+                namespace,
+                created_pods,
+                deleted_pods,
+                churn_per_hour: per_hour(created_pods + deleted_pods),
+            })
+            .collect();
+        namespaces.sort_by(|a, b| b.churn_per_hour.total_cmp(&a.churn_per_hour).then_with(|| a.namespace.cmp(&b.namespace)));
+
+        let created_pods: usize = namespaces.iter().map(|n| n.created_pods).sum();
+        let deleted_pods: usize = namespaces.iter().map(|n| n.deleted_pods).sum();
+        let mut response = PodChurnResponse {
+            window_minutes,
+            created_pods,
+            deleted_pods,
+            namespaces,
+            events_available: events.is_some(),
+            explanation: String::new(),
+        };
+        response.explanation = messages::pod_churn(lang, &response, per_hour(created_pods + deleted_pods));
+        response
+    }
+
+    /// Report GPU capacity and requests
+    async fn get_gpu_report_internal(&self, include_pods: bool, lang: Lang, fresh: bool) -> Result<GpuReportResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Ok(Self::build_gpu_report(&nodes, &pods, include_pods, lang))
+    }
+
+    /// Total, requested and idle GPUs per node and requested GPUs per namespace. Only running
+    /// (bound, non-terminal) pods hold GPUs; unbound ones count as pending.
+    fn build_gpu_report(nodes: &[Node], pods: &[Pod], include_pods: bool, lang: Lang) -> GpuReportResponse {
+        let mut gpu_pods: Vec<PodGpus> = pods.iter()
+            .filter(|pod| !pod_is_terminal(pod))
+            .filter_map(|pod| {
+                let gpus = pod_gpus(pod);
+                (gpus > 0).then(|| PodGpus {
+                    name: pod.metadata.name.clone().unwrap_or_default(),
+                    namespace: pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                    node: pod_node(pod),
+                    gpus,
+                })
+            })
+            .collect();
+        gpu_pods.sort_by(|a, b| b.gpus.cmp(&a.gpus).then_with(|| a.namespace.cmp(&b.namespace)).then_with(|| a.name.cmp(&b.name)));
+        let (running, pending): (Vec<&PodGpus>, Vec<&PodGpus>) = gpu_pods.iter().partition(|pod| pod.node != UNSCHEDULED_NODE);
+
+        let mut requested_by_node: HashMap<&str, u64> = HashMap::new();
+        let mut by_namespace: BTreeMap<&str, (u64, usize)> = BTreeMap::new();
+        for pod in &running {
+            *requested_by_node.entry(pod.node.as_str()).or_default() += pod.gpus;
+            let entry = by_namespace.entry(pod.namespace.as_str()).or_default();
+            entry.0 += pod.gpus;
+            entry.1 += 1;
+        }
+
+        let mut gpu_nodes: Vec<NodeGpus> = nodes.iter()
+            .filter_map(|node| {
+                let name = node.metadata.name.clone().unwrap_or_default();
+                let total_gpus = node_gpus(node);
+                let requested_gpus = requested_by_node.get(name.as_str()).copied().unwrap_or(0);
+                (total_gpus > 0 || requested_gpus > 0).then_some(NodeGpus {
+                    node: name,
+                    total_gpus,
+                    requested_gpus,
+                    idle_gpus: total_gpus.saturating_sub(requested_gpus),
+                })
+            })
+            .collect();
+        gpu_nodes.sort_by(|a, b| b.idle_gpus.cmp(&a.idle_gpus).then_with(|| a.node.cmp(&b.node)));
+
+        let mut namespaces: Vec<NamespaceGpus> = by_namespace.into_iter()
+            .map(|(namespace, (requested_gpus, pod_count))| NamespaceGpus { namespace: namespace.to_string(), requested_gpus, pod_count })
+            .collect();
+        namespaces.sort_by(|a, b| b.requested_gpus.cmp(&a.requested_gpus).then_with(|| a.namespace.cmp(&b.namespace)));
+
+        let total_gpus: u64 = gpu_nodes.iter().map(|n| n.total_gpus).sum();
+        let requested_gpus: u64 = gpu_nodes.iter().map(|n| n.requested_gpus).sum();
+        let idle_gpus: u64 = gpu_nodes.iter().map(|n| n.idle_gpus).sum();
+        let pending_gpus: u64 = pending.iter().map(|pod| pod.gpus).sum();
+
+        let pending_pod_count = pending.len();
+        let mut response = GpuReportResponse {
+            total_gpus,
+            requested_gpus,
+            idle_gpus,
+            pending_gpus,
+            nodes: gpu_nodes,
+            namespaces,
+            pods: include_pods.then_some(gpu_pods),
+            explanation: String::new(),
+        };
+        response.explanation = messages::gpu_report(lang, &response, pending_pod_count);
+        response
+    }
+
+    /// Estimate how many pods of one size still fit
+    async fn max_pods_of_size_internal(&self, cpu_cores: f64, memory_gb: f64, lang: Lang, fresh: bool) -> Result<MaxPodsOfSizeResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Ok(Self::build_max_pods_of_size(&Self::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None), cpu_cores, memory_gb, lang))
+    }
+
+    /// Pack the size into the available resources of each node, and compare with dividing the cluster totals
+    fn build_max_pods_of_size(node_infos: &[NodeInfo], cpu_cores: f64, memory_gb: f64, lang: Lang) -> MaxPodsOfSizeResponse {
+        let mut cpu_bound: u64 = 0;
+        let mut memory_bound: u64 = 0;
+        let mut nodes: Vec<NodePodCount> = node_infos.iter()
+            .map(|info| {
+                let by_cpu = copies_that_fit(info.available_cpu_cores, cpu_cores).unwrap_or(u64::MAX);
+                let by_memory = copies_that_fit(info.available_memory_gb, memory_gb).unwrap_or(u64::MAX);
+                cpu_bound = cpu_bound.saturating_add(by_cpu);
+                memory_bound = memory_bound.saturating_add(by_memory);
+                NodePodCount { node: info.name.clone(), pods: by_cpu.min(by_memory) }
+            })
+            .collect();
+        nodes.sort_by(|a, b| b.pods.cmp(&a.pods).then_with(|| a.node.cmp(&b.node)));
+
+        let max_pods: u64 = nodes.iter().map(|n| n.pods).sum();
+        let limiting_resource = if cpu_bound <= memory_bound { "cpu" } else { "memory" };
+
+        let total_cpu: f64 = node_infos.iter().map(|info| info.available_cpu_cores.max(0.0)).sum();
+        let total_memory: f64 = node_infos.iter().map(|info| info.available_memory_gb.max(0.0)).sum();
+        let naive_max_pods = copies_that_fit(total_cpu, cpu_cores).unwrap_or(u64::MAX)
+            .min(copies_that_fit(total_memory, memory_gb).unwrap_or(u64::MAX));
+
+        let mut response = MaxPodsOfSizeResponse {
+            cpu_cores,
+            memory_gb,
+            max_pods,
+            naive_max_pods,
+            limiting_resource: limiting_resource.to_string(),
+            nodes,
+            explanation: String::new(),
+        };
+        response.explanation = messages::max_pods_of_size(lang, &response);
+        response
+    }
+
+    /// Get resource usage grouped by a pod label
+    async fn get_usage_by_label_internal(&self, label_key: &str, lang: Lang, fresh: bool) -> Result<UsageByLabelResponse, String> {
+        let pods = self.list_pods(None, fresh).await?;
+
+        Ok(Self::build_usage_by_label(label_key, &pods, lang))
+    }
+
+    /// Aggregate requests, limits and pod counts per value of `label_key`
+    fn build_usage_by_label(label_key: &str, pods: &[Pod], lang: Lang) -> UsageByLabelResponse {
+        let mut totals_map: HashMap<String, PodTotals> = HashMap::new();
+
+        for pod in pods {
+            let value = pod.metadata.labels.as_ref()
+                .and_then(|labels| labels.get(label_key).cloned())
+                .unwrap_or_else(|| "unlabeled".to_string());
+
+            totals_map.entry(value).or_default().add(pod);
+        }
+
+        let mut groups: Vec<LabelUsage> = totals_map.into_iter()
+            .map(|(value, totals)| LabelUsage {
+                value,
+                cpu_requests_cores: totals.requests.cores(),
+                memory_requests_gb: totals.requests.gb(),
+                cpu_limits_cores: totals.limits.cores(),
+                memory_limits_gb: totals.limits.gb(),
+                pod_count: totals.pod_count,
+            })
+            .collect();
+        groups.sort_by(|a, b| b.cpu_requests_cores.total_cmp(&a.cpu_requests_cores)
+            .then_with(|| a.value.cmp(&b.value)));
+
+        let total_groups = groups.len();
+
+        let explanation = messages::usage_by_label(lang, total_groups, label_key);
+
+        UsageByLabelResponse {
+            label_key: label_key.to_string(),
+            groups,
+            total_groups,
+            explanation,
+        }
+    }
+
+    /// Get resource usage of the pods matching a label selector in all namespaces. The selector is
+    /// evaluated by the API server, so this never reads the list caches.
+    async fn get_usage_for_selector_internal(&self, label_selector: &str, lang: Lang, fresh: bool) -> Result<UsageForSelectorResponse, String> {
+        let selector = parse_label_selector(label_selector)?;
+        let no_labels = BTreeMap::new();
+        let pods: Vec<Pod> = self.list_all_pods(fresh, None).await?
+            .into_iter()
+            .filter(|pod| label_selector_matches(&selector, pod.metadata.labels.as_ref().unwrap_or(&no_labels)))
+            .collect();
+
+        Ok(Self::build_usage_for_selector(label_selector, &pods, lang))
+    }
+
+    /// Sum requests, limits and pod counts of the selected pods, skipping terminal ones
+    fn build_usage_for_selector(label_selector: &str, pods: &[Pod], lang: Lang) -> UsageForSelectorResponse {
+        let mut totals = PodTotals::default();
+        let mut namespaces = BTreeSet::new();
+
+        for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+            totals.add(pod);
+            namespaces.insert(pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()));
+        }
+
+        let mut response = UsageForSelectorResponse {
+            label_selector: label_selector.to_string(),
+            cpu_requests_cores: totals.requests.cores(),
+            memory_requests_gb: totals.requests.gb(),
+            cpu_limits_cores: totals.limits.cores(),
+            memory_limits_gb: totals.limits.gb(),
+            pod_count: totals.pod_count,
+            namespaces: namespaces.into_iter().collect(),
+            explanation: String::new(),
+        };
+        response.explanation = messages::usage_for_selector(lang, &response);
+
+        response
+    }
+
+    /// Get resource usage grouped by the kind of each pod's top-level owner
+    async fn get_usage_by_workload_kind_internal(&self, lang: Lang, fresh: bool) -> Result<UsageByWorkloadKindResponse, String> {
+        let pods = self.list_pods(None, fresh).await?;
+        let owners = self.list_workload_owners().await?;
+
+        Ok(Self::build_usage_by_workload_kind(&pods, &owners, lang))
+    }
+
+    /// Owners of the intermediate controllers pods point at: ReplicaSets (owned by Deployments)
+    /// and Jobs (owned by CronJobs)
+    async fn list_workload_owners(&self) -> Result<OwnerIndex, String> {
+        let client = self.client().await?;
+
+        let replica_sets = Api::<ReplicaSet>::all(client.clone()).list(&Default::default()).await
+            .map_err(|e| format!("Failed to list replica sets: {}", e))?;
+        let jobs = Api::<Job>::all(client).list(&Default::default()).await
+            .map_err(|e| format!("Failed to list jobs: {}", e))?;
+
+        Ok(owner_index(
+            replica_sets.items.iter().map(|rs| ("ReplicaSet", &rs.metadata))
+                .chain(jobs.items.iter().map(|job| ("Job", &job.metadata)))
+        ))
+    }
+
+    /// Aggregate requests, limits and pod counts per top-level owner kind
+    fn build_usage_by_workload_kind(pods: &[Pod], owners: &OwnerIndex, lang: Lang) -> UsageByWorkloadKindResponse {
+        let mut totals_map: HashMap<String, PodTotals> = HashMap::new();
+
+        for pod in pods {
+            totals_map.entry(top_level_owner_kind(pod, owners)).or_default().add(pod);
+        }
+
+        let mut kinds: Vec<WorkloadKindUsage> = totals_map.into_iter()
+            .map(|(kind, totals)| WorkloadKindUsage {
+                kind,
+                cpu_requests_cores: totals.requests.cores(),
+                memory_requests_gb: totals.requests.gb(),
+                cpu_limits_cores: totals.limits.cores(),
+                memory_limits_gb: totals.limits.gb(),
+                pod_count: totals.pod_count,
+            })
+            .collect();
+        kinds.sort_by(|a, b| b.cpu_requests_cores.total_cmp(&a.cpu_requests_cores)
+            .then_with(|| a.kind.cmp(&b.kind)));
+
+        let explanation = messages::usage_by_workload_kind(lang, pods.len(), kinds.len());
+
+        UsageByWorkloadKindResponse {
+            kinds,
+            total_pods: pods.len(),
+            explanation,
+        }
+    }
+
+    /// Check whether the replicas of each workload share identical requests and limits
+    async fn get_replica_consistency_internal(&self, namespace: Option<&str>, lang: Lang, fresh: bool) -> Result<ReplicaConsistencyResponse, String> {
+        let pods = self.list_pods(namespace, fresh).await?;
+        let owners = self.list_workload_owners().await?;
+
+        Ok(Self::build_replica_consistency(namespace, &pods, &owners, lang))
+    }
+
+    /// Group running and pending pods by top-level owner and list the requests and limits that differ
+    /// between the replicas of a group. Bare pods and single-replica workloads have nothing to compare.
+    fn build_replica_consistency(namespace: Option<&str>, pods: &[Pod], owners: &OwnerIndex, lang: Lang) -> ReplicaConsistencyResponse {
+        let mut groups: BTreeMap<(String, String, String), Vec<&Pod>> = BTreeMap::new();
+
+        for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+            let Some((owner_kind, owner_name)) = pod_workload(pod, owners) else { continue };
+            let pod_namespace = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+            groups.entry((pod_namespace, owner_kind, owner_name)).or_default().push(pod);
+        }
+
+        let mut workloads: Vec<WorkloadConsistency> = groups.into_iter()
+            .filter(|(_, replicas)| replicas.len() > 1)
+            .map(|((namespace, owner_kind, owner_name), replicas)| {
+                let replica_values: Vec<(String, ContainerResourceValues)> = replicas.iter()
+                    .map(|pod| (pod.metadata.name.clone().unwrap_or_default(), container_resource_values(pod)))
+                    .collect();
+                let keys: BTreeSet<&(String, &'static str, String)> = replica_values.iter()
+                    .flat_map(|(_, values)| values.keys())
+                    .collect();
+
+                let differences: Vec<ReplicaResourceDifference> = keys.into_iter()
+                    .filter_map(|key| {
+                        // Normalized value → (value as written by the first replica using it, replicas)
+                        let mut by_value: BTreeMap<Option<&str>, (String, Vec<String>)> = BTreeMap::new();
+                        for (pod_name, values) in &replica_values {
+                            let value = values.get(key);
+                            let (_, pods) = by_value.entry(value.map(|(normalized, _)| normalized.as_str()))
+                                .or_insert_with(|| (value.map(|(_, raw)| raw.clone()).unwrap_or_else(|| "unset".to_string()), Vec::new()));
+                            pods.push(pod_name.clone());
+                        }
+                        if by_value.len() < 2 {
+                            return None;
+                        }
+
+                        let mut values: Vec<ReplicaResourceValue> = by_value.into_values()
+                            .map(|(value, mut pods)| {
+                                pods.sort();
+                                ReplicaResourceValue { value, pods }
+                            })
+                            .collect();
+                        values.sort_by(|a, b| b.pods.len().cmp(&a.pods.len()).then_with(|| a.value.cmp(&b.value)));
+
+                        let (container, kind, resource) = key;
+                        Some(ReplicaResourceDifference {
+                            container: container.clone(),
+                            kind: kind.to_string(),
+                            resource: resource.clone(),
+                            values,
+                        })
+                    })
+                    .collect();
+
+                WorkloadConsistency {
+                    namespace,
+                    owner_kind,
+                    owner_name,
+                    replica_count: replicas.len(),
+                    consistent: differences.is_empty(),
+                    differences,
+                }
+            })
+            .collect();
+
+        // Stable sort keeps the namespace/kind/name order of the groups within each side
+        workloads.sort_by_key(|w| w.consistent);
+
+        let workloads_checked = workloads.len();
+        let inconsistent: Vec<String> = workloads.iter()
+            .filter(|w| !w.consistent)
+            .map(|w| format!("{} {}/{}", w.owner_kind, w.namespace, w.owner_name))
+            .collect();
+
+        let explanation = messages::replica_consistency(lang, workloads_checked, &inconsistent);
+
+        ReplicaConsistencyResponse {
+            namespace: namespace.map(str::to_string),
+            workloads,
+            workloads_checked,
+            inconsistent_workloads: inconsistent.len(),
+            explanation,
+        }
+    }
+
+    /// Get the distinct container images in use
+    async fn get_image_distribution_internal(&self, namespace: Option<&str>, lang: Lang, fresh: bool) -> Result<ImageDistributionResponse, String> {
+        let pods = self.list_pods(namespace, fresh).await?;
+
+        Ok(Self::build_image_distribution(namespace, &pods, lang))
+    }
+
+    /// Count the pods using each distinct image of their containers and init containers
+    fn build_image_distribution(namespace: Option<&str>, pods: &[Pod], lang: Lang) -> ImageDistributionResponse {
+        let mut image_map: HashMap<String, (usize, BTreeSet<String>)> = HashMap::new();
+
+        for pod in pods {
+            let Some(spec) = &pod.spec else { continue };
+            let pod_images: BTreeSet<&str> = spec.containers.iter()
+                .chain(spec.init_containers.iter().flatten())
+                .filter_map(|c| c.image.as_deref())
+                .collect();
+
+            for image in pod_images {
+                let (pod_count, namespaces) = image_map.entry(image.to_string()).or_default();
+                *pod_count += 1;
+                namespaces.insert(pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()));
+            }
+        }
+
+        let mut images: Vec<ImageUsage> = image_map.into_iter()
+            .map(|(image, (pod_count, namespaces))| ImageUsage {
+                image,
+                pod_count,
+                namespaces: namespaces.into_iter().collect(),
+            })
+            .collect();
+        images.sort_by(|a, b| b.pod_count.cmp(&a.pod_count).then_with(|| a.image.cmp(&b.image)));
+
+        let total_images = images.len();
+        let shared_images = images.iter().filter(|i| i.pod_count > 1).count();
+
+        let explanation = messages::image_distribution(lang, pods.len(), total_images, shared_images);
+
+        ImageDistributionResponse {
+            namespace: namespace.map(str::to_string),
+            images,
+            total_images,
+            total_pods: pods.len(),
+            explanation,
+        }
+    }
+
+    /// Get the split between system overhead and application workloads
+    async fn get_overhead_breakdown_internal(&self, system_namespaces: &[String], lang: Lang, fresh: bool) -> Result<OverheadBreakdownResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Ok(Self::build_overhead_breakdown(&nodes, &pods, system_namespaces, lang))
+    }
+
+    /// Split allocated requests into system overhead (system namespaces and DaemonSets) and workloads.
+    /// Requests are counted like get_cluster_capacity, so both shares add up to its allocated totals.
+    fn build_overhead_breakdown(nodes: &[Node], pods: &[Pod], system_namespaces: &[String], lang: Lang) -> OverheadBreakdownResponse {
+        let capacity = Self::compute_cluster_capacity(nodes, pods, ResourceBasis::Requests, None);
+        let mut system = (Resources::default(), 0);
+        let mut workloads = (Resources::default(), 0);
+
+        // Split exactly what get_cluster_capacity counts as allocated: unfinished pods bound to a node
+        for pod in pods.iter().filter(|pod| pod_node_name(pod).is_some() && !pod_is_terminal(pod)) {
+            let namespace = pod.metadata.namespace.as_deref().unwrap_or("default");
+            let is_system = system_namespaces.iter().any(|ns| ns == namespace) || daemonset_owner(pod).is_some();
+            let (amounts, pod_count) = if is_system { &mut system } else { &mut workloads };
+            *amounts += ResourceBasis::Requests.pod_amounts(pod, None);
+            *pod_count += 1;
+        }
+
+        let share = |(amounts, pod_count): (Resources, usize)| ResourceShare {
+            cpu_cores: amounts.cores(),
+            memory_gb: amounts.gb(),
+            cpu_percent: if capacity.total_cpu_cores > 0.0 { amounts.cores() / capacity.total_cpu_cores * 100.0 } else { 0.0 },
+            memory_percent: if capacity.total_memory_gb > 0.0 { amounts.gb() / capacity.total_memory_gb * 100.0 } else { 0.0 },
+            pod_count,
+        };
+
+        let mut response = OverheadBreakdownResponse {
+            total_cpu_cores: capacity.total_cpu_cores,
+            total_memory_gb: capacity.total_memory_gb,
+            system: share(system),
+            workloads: share(workloads),
+            system_namespaces: system_namespaces.to_vec(),
+            explanation: String::new(),
+        };
+        response.explanation = messages::overhead_breakdown(lang, &response);
+        response
+    }
+
+    /// Check whether the cluster survives losing its largest nodes
+    async fn check_n_minus_one_internal(&self, nodes_lost: usize, lang: Lang, fresh: bool) -> Result<NMinusOneResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Self::build_n_minus_one(&nodes, &pods, nodes_lost, lang)
+    }
+
+    /// Remove the `nodes_lost` largest nodes (by allocatable CPU, then memory) and bin-pack the pods
+    /// they ran (first-fit decreasing by CPU) onto the free allocatable of the remaining schedulable nodes.
+    /// DaemonSet pods of the lost nodes disappear with them.
+    fn build_n_minus_one(nodes: &[Node], pods: &[Pod], nodes_lost: usize, lang: Lang) -> Result<NMinusOneResponse, String> {
+        if nodes_lost >= nodes.len() {
+            return Err(format!("Cannot lose {} of {} nodes: at least one node must remain", nodes_lost, nodes.len()));
+        }
+
+        let mut by_size: Vec<&Node> = nodes.iter().collect();
+        by_size.sort_by(|a, b| {
+            let (a_cpu, a_memory) = node_allocatable(a);
+            let (b_cpu, b_memory) = node_allocatable(b);
+            b_cpu.total_cmp(&a_cpu).then_with(|| b_memory.total_cmp(&a_memory))
+        });
+        let (lost, remaining) = by_size.split_at(nodes_lost);
+        let lost_nodes: Vec<String> = lost.iter().map(|n| n.metadata.name.clone().unwrap_or_default()).collect();
+
+        let remaining_total: Resources = remaining.iter().map(|n| node_allocatable_resources(n)).sum();
+
+        let mut free: Vec<(String, Resources)> = remaining.iter()
+            .filter(|n| !node_is_cordoned(n))
+            .map(|n| (n.metadata.name.clone().unwrap_or_default(), node_allocatable_resources(n)))
+            .collect();
+
+        let mut requested = Resources::default();
+        let mut displaced: Vec<(String, Resources)> = Vec::new();
+
+        for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+            let Some(node_name) = pod_node_name(pod) else { continue };
+            let on_lost_node = lost_nodes.iter().any(|n| n == node_name);
+            if on_lost_node && daemonset_owner(pod).is_some() {
+                continue;
+            }
+
+            let effective = pod_effective_request_resources(pod);
+            requested += effective;
+
+            if on_lost_node {
+                let name = format!(
+                    "{}/{}",
+                    pod.metadata.namespace.as_deref().unwrap_or("default"),
+                    pod.metadata.name.as_deref().unwrap_or_default()
+                );
+                displaced.push((name, effective));
+            } else if let Some((_, slot)) = free.iter_mut().find(|(name, _)| name == node_name) {
+                *slot -= effective;
+            }
+        }
+
+        displaced.sort_by(|a, b| b.1.millicores.cmp(&a.1.millicores).then_with(|| a.0.cmp(&b.0)));
+        let displaced_pods = displaced.len();
+        let mut unplaceable_pods = Vec::new();
+
+        for (name, effective) in displaced {
+            let slot = free.iter_mut()
+                .find(|(_, slot)| slot.millicores >= effective.millicores && slot.bytes >= effective.bytes);
+            match slot {
+                Some((_, slot)) => *slot -= effective,
+                None => unplaceable_pods.push(name),
+            }
+        }
+
+        let (remaining_cpu_cores, remaining_memory_gb) = remaining_total.cores_and_gb();
+        let (requested_cpu_cores, requested_memory_gb) = requested.cores_and_gb();
+        let percent = |requested: f64, total: f64| if total > 0.0 { requested / total * 100.0 } else { 0.0 };
+        let cpu_utilization_percent = percent(requested_cpu_cores, remaining_cpu_cores);
+        let memory_utilization_percent = percent(requested_memory_gb, remaining_memory_gb);
+        let survives = unplaceable_pods.is_empty();
+
+        let mut response = NMinusOneResponse {
+            nodes_lost,
+            lost_nodes,
+            survives,
+            remaining_nodes: remaining.len(),
+            remaining_cpu_cores,
+            remaining_memory_gb,
+            requested_cpu_cores,
+            requested_memory_gb,
+            cpu_utilization_percent,
+            memory_utilization_percent,
+            displaced_pods,
+            unplaceable_pods,
+            explanation: String::new(),
+        };
+        response.explanation = messages::n_minus_one(lang, &response);
+        Ok(response)
+    }
+
+    /// Find pods stuck terminating
+    async fn find_stuck_pods_internal(&self, grace_period_seconds: u64, lang: Lang, fresh: bool) -> Result<StuckPodsResponse, String> {
+        let pods = self.list_pods(None, fresh).await?;
+
+        Ok(Self::build_stuck_pods(&pods, grace_period_seconds, chrono::Utc::now(), lang))
+    }
+
+    /// List pods whose deletionTimestamp is more than `grace_period_seconds` before `now`
+    fn build_stuck_pods(pods: &[Pod], grace_period_seconds: u64, now: chrono::DateTime<chrono::Utc>, lang: Lang) -> StuckPodsResponse {
+        let mut stuck_pods: Vec<StuckPod> = pods.iter()
+            .filter_map(|pod| {
+                let deleted_at = pod.metadata.deletion_timestamp.as_ref()?.0;
+                let terminating_seconds = (now - deleted_at).num_seconds();
+                if terminating_seconds <= grace_period_seconds as i64 {
+                    return None;
+                }
+
+                let (cpu_requests_cores, memory_requests_gb) = pod_effective_requests(pod);
+                Some(StuckPod {
+                    name: pod.metadata.name.clone().unwrap_or_default(),
+                    namespace: pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                    node: pod_node(pod),
+                    phase: pod.status.as_ref().and_then(|s| s.phase.clone()).unwrap_or_default(),
+                    deletion_timestamp: deleted_at.to_rfc3339(),
+                    terminating_seconds,
+                    finalizers: pod.metadata.finalizers.clone().unwrap_or_default(),
+                    cpu_requests_cores,
+                    memory_requests_gb,
+                })
+            })
+            .collect();
+
+        stuck_pods.sort_by(|a, b| b.terminating_seconds.cmp(&a.terminating_seconds).then_with(|| a.name.cmp(&b.name)));
+
+        let held_cpu_cores: f64 = stuck_pods.iter().map(|p| p.cpu_requests_cores).sum();
+        let held_memory_gb: f64 = stuck_pods.iter().map(|p| p.memory_requests_gb).sum();
+
+        let mut response = StuckPodsResponse {
+            total_stuck: stuck_pods.len(),
+            pods: stuck_pods,
+            held_cpu_cores,
+            held_memory_gb,
+            grace_period_seconds,
+            explanation: String::new(),
+        };
+        response.explanation = messages::stuck_pods(lang, &response);
+        response
+    }
+
+    /// Get pod resource stats
+    async fn get_pod_resource_stats_internal(
+        &self,
+        params: &PodResourceStatsParams,
+        lang: Lang,
+        progress: Option<&Progress>,
+    ) -> Result<PodResourceStatsResponse, String> {
+        let PodResourceStatsParams { include_raw, include_terminal, sort_by, strict_quantities, .. } = *params;
+        let mut state = self.fetch_cluster_state(ClusterStateOptions {
+            pods: true,
+            pod_usage: sort_by.uses_metrics(),
+            metrics_optional: true,
+            fresh: params.cache.fresh.unwrap_or(false),
+            progress,
+            ..Default::default()
+        }).await?;
+        let pods = &state.pods;
+        if let Some(progress) = progress {
+            progress.report(pods.len(), Some(pods.len()), format!("Ranking {} pods", pods.len()));
+        }
+        let usage = sort_by.uses_metrics().then(|| state.take_pod_usage());
+        
+        let mut response = Self::build_pod_resource_stats(&state.pods, include_raw, include_terminal, sort_by, usage, lang);
+        if strict_quantities {
+            response.warnings = Some(memory_quantity_warnings(&state.pods));
+        }
+        
+        Ok(response)
+    }
+
+    /// List a kind of the metrics.k8s.io API (metrics-server), retried up to METRICS_ATTEMPTS times
+    async fn list_metrics(&self, kind: &str, plural: &str) -> Result<Vec<DynamicObject>, String> {
+        let gvk = GroupVersionKind::gvk("metrics.k8s.io", "v1beta1", kind);
+        let resource = ApiResource::from_gvk_with_plural(&gvk, plural);
+        let api: Api<DynamicObject> = Api::all_with(self.client().await?, &resource);
+        
+        let mut attempt = 1;
+        loop {
+            match api.list(&Default::default()).await {
+                Ok(metrics) => return Ok(metrics.items),
+                Err(e) if attempt < METRICS_ATTEMPTS => {
+                    tracing::debug!("{} attempt {} of {} failed: {}", kind, attempt, METRICS_ATTEMPTS, e);
+                    attempt += 1;
+                    tokio::time::sleep(METRICS_RETRY_DELAY).await;
+                }
+                Err(e) => return Err(format!("Failed to list {} metrics after {} attempts: {}", plural.trim_end_matches('s'), METRICS_ATTEMPTS, e)),
+            }
+        }
+    }
+
+    /// Live usage of every pod from the metrics.k8s.io API (metrics-server)
+    async fn list_pod_usage(&self) -> Result<PodUsage, String> {
+        let metrics = self.list_metrics("PodMetrics", "pods").await?;
+        
+        Ok(metrics.iter()
+            .map(|m| {
+                let key = (
+                    m.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                    m.metadata.name.clone().unwrap_or_default(),
+                );
+                (key, pod_metrics_usage(&m.data))
+            })
+            .collect())
+    }
+
+    /// Rank pods by requests or, when `usage` is available, by live usage. A failed usage
+    /// lookup falls back to CPU requests and is reported in `warning`.
+    /// Terminal pods are skipped unless `include_terminal` is set.
+    fn build_pod_resource_stats(
+        pods: &[Pod],
+        include_raw: bool,
+        include_terminal: bool,
+        sort_by: PodSortBy,
+        usage: Option<Result<PodUsage, String>>,
+        lang: Lang,
+    ) -> PodResourceStatsResponse {
+        let metrics_available = usage.as_ref().map(|usage| usage.is_ok());
+        let (sort_by, usage, warning) = match usage {
+            Some(Ok(usage)) => (sort_by, Some(usage), None),
+            Some(Err(e)) => {
+                diagnostics::warn("metrics_unavailable", format!("Pod metrics unavailable, sorting by CPU requests: {}", e));
+                let warning = format!("Pod metrics unavailable ({}); sorted by CPU requests instead", e);
+                (PodSortBy::CpuRequests, None, Some(warning))
+            }
+            None => (sort_by, None, None),
+        };
+        
+        let mut pod_infos: Vec<PodResourceInfo> = pods.iter()
+            .filter(|pod| include_terminal || !pod_is_terminal(pod))
+            .map(|pod| {
+                let mut info = Self::build_pod_resource_info(pod, include_raw);
+                if let Some(usage) = &usage {
+                    let (cpu_cores, memory_gb) = usage.get(&(info.namespace.clone(), info.name.clone()))
+                        .copied()
+                        .unwrap_or_default();
+                    info.cpu_usage_millicores = Some((cpu_cores * 1000.0) as i64);
+                    info.memory_usage_mb = Some((memory_gb * 1024.0) as i64);
+                }
+                info
+            })
+            .collect();
+        
+        // Sort descending by the chosen criterion
+        match sort_by {
+            PodSortBy::CpuRequests => pod_infos.sort_by(|a, b| b.cpu_requests_millicores.cmp(&a.cpu_requests_millicores)),
+            PodSortBy::CpuUsage => pod_infos.sort_by(|a, b| b.cpu_usage_millicores.cmp(&a.cpu_usage_millicores)),
+            PodSortBy::MemoryUsage => pod_infos.sort_by(|a, b| b.memory_usage_mb.cmp(&a.memory_usage_mb)),
+        }
+        
+        let total_pods = pod_infos.len();
+        
+        // Take top 20 pods
+        let top_pods: Vec<PodResourceInfo> = pod_infos.into_iter().take(20).collect();
+        
+        let explanation = messages::pod_resource_stats(lang, total_pods, sort_by);
+        
+        PodResourceStatsResponse {
+            top_pods,
+            total_pods,
+            sorted_by: format!("{} (descending)", messages::sort_label(Lang::En, sort_by)),
+            sort: sort_by.sort_spec(),
+            metrics_available,
+            warning,
+            warnings: None,
+            explanation,
+        }
+    }
+
+    /// Build the resource information of a single pod.
+    /// With `include_raw`, the original quantity strings of all containers are kept (joined by " + ").
+    fn build_pod_resource_info(pod: &Pod, include_raw: bool) -> PodResourceInfo {
+        let name = pod.metadata.name.clone().unwrap_or_default();
+        let namespace = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+        let node = pod_node(pod);
+        
+        let mut cpu_requests_millicores = 0i64;
+        let mut memory_requests_bytes = 0i64;
+        let mut cpu_limits_millicores = 0i64;
+        let mut memory_limits_bytes = 0i64;
+        
+        let mut raw_cpu_requests = Vec::new();
+        let mut raw_memory_requests = Vec::new();
+        let mut raw_cpu_limits = Vec::new();
+        let mut raw_memory_limits = Vec::new();
+        
+        if let Some(spec) = &pod.spec {
+            for container in &spec.containers {
+                if let Some(requests) = container_request_quantities(pod, container) {
+                    if let Some(cpu) = requests.get("cpu") {
+                        cpu_requests_millicores += quantity_to_millicores(cpu);
+                        raw_cpu_requests.push(cpu.0.clone());
+                    }
+                    if let Some(memory) = requests.get("memory") {
+                        memory_requests_bytes += quantity_to_bytes(memory);
+                        raw_memory_requests.push(memory.0.clone());
+                    }
+                }
+                if let Some(limits) = container_limit_quantities(pod, container) {
+                    if let Some(cpu) = limits.get("cpu") {
+                        cpu_limits_millicores += quantity_to_millicores(cpu);
+                        raw_cpu_limits.push(cpu.0.clone());
+                    }
+                    if let Some(memory) = limits.get("memory") {
+                        memory_limits_bytes += quantity_to_bytes(memory);
+                        raw_memory_limits.push(memory.0.clone());
+                    }
+                }
+            }
+        }
+        
+        let raw = |values: Vec<String>| if include_raw { Some(values.join(" + ")) } else { None };
+        
+        PodResourceInfo {
+            name,
+            namespace,
+            cpu_requests_millicores,
+            memory_requests_mb: memory_requests_bytes / BYTES_PER_MB,
+            cpu_limits_millicores,
+            memory_limits_mb: memory_limits_bytes / BYTES_PER_MB,
+            node,
+            phase: pod_is_terminal(pod).then(|| pod.status.as_ref().and_then(|s| s.phase.clone()).unwrap_or_default()),
+            raw_cpu_request: raw(raw_cpu_requests),
+            raw_memory_request: raw(raw_memory_requests),
+            raw_cpu_limit: raw(raw_cpu_limits),
+            raw_memory_limit: raw(raw_memory_limits),
+            cpu_usage_millicores: None,
+            memory_usage_mb: None,
+        }
+    }
+
+    /// Check replica capacity
+    async fn check_replica_capacity_internal(
+        &self,
+        params: &CheckReplicaCapacityParams,
+        lang: Lang,
+    ) -> Result<CheckReplicaCapacityResponse, String> {
+        let CheckReplicaCapacityParams { app_name, namespace, replica_count, node_cpu_cores, node_memory_gb, .. } = params;
+        let replica_count = *replica_count;
+        let node_groups = if params.consider_autoscaling { params.node_groups.as_deref() } else { None };
+        let fresh = params.cache.fresh.unwrap_or(false);
+        validate_replica_count(replica_count, self.config.max_replica_count)?;
+        
+        let state = self.fetch_cluster_state(Self::state_for_basis(params.basis, fresh)).await?;
+        
+        // Find pods matching the app name
+        let matching_pods: Vec<&Pod> = state.pods.iter()
+            .filter(|pod| pod.metadata.namespace.as_deref() == Some(namespace.as_str()))
+            .filter(|pod| pod_matches_app(pod, app_name, params.normalize_names))
+            .collect();
+        
+        if matching_pods.is_empty() {
+            return Err(format!(
+                "No pods found matching '{}' in namespace '{}'",
+                app_name, namespace
+            ));
+        }
+        
+        // Use the first matching pod as reference
+        let reference_pod = matching_pods[0];
+
+        // Get cluster capacity, existing pods counted by the requested basis
+        let usage = (params.basis == ResourceBasis::Usage).then_some(&state.pod_usage);
+        let (nodes, all_pods) = (&state.nodes, &state.pods);
+        let mut capacity = Self::compute_cluster_capacity(nodes, all_pods, params.basis, usage);
+        let target_utilization = params.target_utilization_percent.filter(|target| *target < 100.0);
+        if let Some(target) = target_utilization {
+            capacity.cap_utilization(target);
+        }
+        if params.subtract_pending_demand {
+            capacity.subtract_pending_demand();
+        }
+        let preemption = match &params.priority_class {
+            Some(priority_class) => {
+                let class = self.get_priority_class(priority_class).await?;
+                Some(preemptible_capacity(&class, all_pods, params.basis, usage))
+            }
+            None => None,
+        };
+        if let Some(preemption) = preemption.as_ref().filter(|preemption| preemption.preempts) {
+            capacity.reclaim_preemptible(preemption);
+        }
+
+        let mut response = Self::build_replica_capacity_response(
+            params,
+            reference_pod,
+            matching_pods.len(),
+            &capacity,
+            self.config.fit_tolerance,
+            lang,
+        );
+        if let Some(target) = target_utilization {
+            response.explanation.push_str(&format!(" {}", messages::utilization_cap(lang, target, &capacity)));
+        }
+        if let Some(note) = messages::resource_basis(lang, params.basis) {
+            response.explanation.push_str(&format!(" {}", note));
+        }
+        if params.subtract_pending_demand {
+            response.explanation.push_str(&format!(" {}", messages::pending_demand(lang, &capacity)));
+        }
+        if let Some(preemption) = preemption {
+            response.explanation.push_str(&format!(" {}", messages::preemption(lang, &preemption)));
+            response.preemption = Some(preemption);
+        }
+
+        if !response.fits {
+            let scale_up = Self::recommend_scale_up(
+                response.total_cpu_required_cores - capacity.available_cpu_cores,
+                response.total_memory_required_gb - capacity.available_memory_gb,
+                *node_cpu_cores,
+                *node_memory_gb,
+                nodes,
+                all_pods,
+                lang,
+            );
+            response.explanation.push_str(&format!("\n\n{}", scale_up.explanation));
+            if let Some(node_groups) = node_groups {
+                let nodes_needed = autoscale_nodes_needed(&scale_up, node_groups);
+                response.fits_after_scaleup = Some(nodes_needed.is_some());
+                response.autoscale_nodes_needed = nodes_needed;
+                response.explanation.push_str(&format!("\n{}", messages::autoscale(lang, nodes_needed)));
+            }
+            response.scale_up = Some(scale_up);
+        }
+
+        response.anti_affinity_limit = anti_affinity_limit(reference_pod, nodes, all_pods);
+        if let Some(limit) = response.anti_affinity_limit.filter(|limit| *limit < replica_count) {
+            response.fits = false;
+            response.explanation.push_str(&format!("\n\n{}", messages::anti_affinity_limit(lang, limit, replica_count)));
+        }
+
+        Ok(response)
+    }
+
+    /// Build the replica capacity response from a reference pod and the cluster capacity;
+    /// a shortfall within `tolerance` still fits
+    fn build_replica_capacity_response(
+        params: &CheckReplicaCapacityParams,
+        reference_pod: &Pod,
+        current_pod_count: usize,
+        capacity: &ClusterCapacityResponse,
+        tolerance: f64,
+        lang: Lang,
+    ) -> CheckReplicaCapacityResponse {
+        let CheckReplicaCapacityParams { app_name, namespace, replica_count, .. } = params;
+        let replica_count = *replica_count;
+        let reference_pod_name = reference_pod.metadata.name.clone().unwrap_or_default();
+        
+        // Each replica needs the reference pod's effective requests, as the capacity is counted
+        // (resized requests from status, init containers, sidecars and overhead included)
+        let per_replica = pod_effective_request_resources(reference_pod);
+        let (cpu_per_replica, memory_per_replica) = per_replica.cores_and_gb();
+        
+        // Calculate total resources needed
+        let replicas = i64::from(replica_count.max(0));
+        let total = Resources {
+            millicores: per_replica.millicores.saturating_mul(replicas),
+            bytes: per_replica.bytes.saturating_mul(replicas),
+        };
+        let (total_cpu_required, total_memory_required) = total.cores_and_gb();
+        
+        // Check if resources fit; a resource the reference pod does not request never blocks the replicas
+        let fits = (cpu_per_replica == 0.0 || fits_within(capacity.available_cpu_cores, total_cpu_required, tolerance))
+                   && (memory_per_replica == 0.0 || fits_within(capacity.available_memory_gb, total_memory_required, tolerance));
+
+        // Without any requests the scheduler admits every replica, so requests say nothing about capacity
+        let warning = (cpu_per_replica == 0.0 && memory_per_replica == 0.0)
+            .then(|| messages::best_effort_reference(lang, &reference_pod_name, app_name));
+        
+        // Calculate projected utilization
+        let projected_cpu_utilization = if capacity.total_cpu_cores > 0.0 {
+            (capacity.allocated_cpu_cores + total_cpu_required) / capacity.total_cpu_cores * 100.0
+        } else {
+            0.0
+        };
+        
+        let projected_memory_utilization = if capacity.total_memory_gb > 0.0 {
+            (capacity.allocated_memory_gb + total_memory_required) / capacity.total_memory_gb * 100.0
+        } else {
+            0.0
+        };
+        
+        let mut response = CheckReplicaCapacityResponse {
+            fits,
+            reference_pod: reference_pod_name,
+            cpu_per_replica_cores: cpu_per_replica,
+            memory_per_replica_gb: memory_per_replica,
+            total_cpu_required_cores: total_cpu_required,
+            total_memory_required_gb: total_memory_required,
+            available_cpu_cores: capacity.available_cpu_cores,
+            available_memory_gb: capacity.available_memory_gb,
+            current_pod_count,
+            projected_cpu_utilization_percent: projected_cpu_utilization,
+            projected_memory_utilization_percent: projected_memory_utilization,
+            basis: capacity.basis,
+            explanation: String::new(),
+            scale_up: None,
+            fits_after_scaleup: None,
+            autoscale_nodes_needed: None,
+            warning,
+            preemption: None,
+            anti_affinity_limit: None,
+        };
+        response.explanation = messages::replica_capacity(lang, &response, app_name, namespace, replica_count, capacity);
+        
+        response
+    }
+
+    /// Check whether a Deployment can scale to a total number of replicas
+    async fn check_deployment_scale_internal(
+        &self,
+        deployment_name: &str,
+        namespace: &str,
+        target_replicas: i32,
+        lang: Lang, fresh: bool,
+    ) -> Result<DeploymentScaleResponse, String> {
+        let deployment = Api::<Deployment>::namespaced(self.client().await?, namespace)
+            .get_opt(deployment_name).await
+            .map_err(|e| format!("Failed to get deployment '{}': {}", deployment_name, e))?
+            .ok_or_else(|| format!("Deployment '{}' not found in namespace '{}'", deployment_name, namespace))?;
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Self::build_deployment_scale(&deployment, target_replicas, &nodes, &pods, lang)
+    }
+
+    /// Place the replicas a Deployment still needs to reach `target_replicas`, one at a time, on the
+    /// eligible node with the most free CPU (then memory) where the template's effective requests fit.
+    /// Free resources are allocatable minus the effective requests of the pods bound to each node,
+    /// which already include the Deployment's current replicas.
+    fn build_deployment_scale(deployment: &Deployment, target_replicas: i32, nodes: &[Node], pods: &[Pod], lang: Lang) -> Result<DeploymentScaleResponse, String> {
+        let name = deployment.metadata.name.clone().unwrap_or_default();
+        let namespace = deployment.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+        let template = deployment.spec.as_ref().map(|spec| &spec.template)
+            .ok_or_else(|| format!("Deployment '{}' has no spec", name))?;
+        let template_spec = template.spec.clone().unwrap_or_default();
+
+        let current_replicas = deployment.status.as_ref().and_then(|s| s.replicas)
+            .or_else(|| deployment.spec.as_ref().and_then(|s| s.replicas))
+            .unwrap_or(0);
+        let additional_replicas = (target_replicas - current_replicas).max(0);
+
+        let mut metadata = template.metadata.clone().unwrap_or_default();
+        metadata.namespace = Some(namespace.clone());
+        let replica = Pod { metadata, spec: Some(template_spec), status: None };
+        let per_replica = pod_effective_request_resources(&replica);
+
+        let ReplicaPacking { placements, excluded_nodes, eligible_nodes, schedulable_replicas } =
+            pack_replicas(&replica, per_replica, additional_replicas, nodes, pods);
+        let anti_affinity_limit = anti_affinity_limit(&replica, nodes, pods);
+
+        let unschedulable_replicas = additional_replicas - schedulable_replicas;
+        let fits = unschedulable_replicas == 0;
+
+        let mut response = DeploymentScaleResponse {
+            deployment: name,
+            namespace,
+            current_replicas,
+            target_replicas,
+            additional_replicas,
+            cpu_per_replica_cores: per_replica.cores(),
+            memory_per_replica_gb: per_replica.gb(),
+            fits,
+            schedulable_replicas,
+            unschedulable_replicas,
+            placements,
+            excluded_nodes,
+            anti_affinity_limit,
+            explanation: String::new(),
+        };
+        response.explanation = messages::deployment_scale(lang, &response, eligible_nodes, nodes.len());
+        Ok(response)
+    }
+
+    /// Check whether a number of a CronJob's Jobs can run at the same time
+    async fn check_cronjob_capacity_internal(
+        &self,
+        cronjob_name: &str,
+        namespace: &str,
+        concurrency: i32,
+        lang: Lang, fresh: bool,
+    ) -> Result<CronJobCapacityResponse, String> {
+        let cronjob = Api::<CronJob>::namespaced(self.client().await?, namespace)
+            .get_opt(cronjob_name).await
+            .map_err(|e| format!("Failed to get cronjob '{}': {}", cronjob_name, e))?
+            .ok_or_else(|| format!("CronJob '{}' not found in namespace '{}'", cronjob_name, namespace))?;
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Self::build_cronjob_capacity(&cronjob, concurrency, self.config.max_replica_count, &nodes, &pods, lang)
+    }
+
+    /// Place the pods of `concurrency` Jobs of a CronJob (each running its job template's parallelism)
+    /// alongside the pods already bound, as check_deployment_scale places replicas. The pods needed are
+    /// bounded by `max_pods` (max_replica_count), like the replicas of the other placement tools.
+    fn build_cronjob_capacity(cronjob: &CronJob, concurrency: i32, max_pods: i32, nodes: &[Node], pods: &[Pod], lang: Lang) -> Result<CronJobCapacityResponse, String> {
+        let name = cronjob.metadata.name.clone().unwrap_or_default();
+        let namespace = cronjob.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+        let spec = cronjob.spec.as_ref().ok_or_else(|| format!("CronJob '{}' has no spec", name))?;
+        let job_spec = spec.job_template.spec.as_ref()
+            .ok_or_else(|| format!("CronJob '{}' has no job template spec", name))?;
+        let concurrency_policy = spec.concurrency_policy.clone().unwrap_or_else(|| "Allow".to_string());
+
+        let parallelism = job_spec.parallelism.unwrap_or(1);
+        let pods_per_job = job_spec.completions.map_or(parallelism, |completions| parallelism.min(completions)).max(0);
+        let pods_needed = concurrency.saturating_mul(pods_per_job);
+        if pods_needed > max_pods {
+            return Err(format!(
+                "{} concurrent Jobs of {} pods need {} pods, more than the maximum of {} (set MAX_REPLICA_COUNT to change it)",
+                concurrency, pods_per_job, pods_needed, max_pods
+            ));
+        }
+
+        let mut metadata = job_spec.template.metadata.clone().unwrap_or_default();
+        metadata.namespace = Some(namespace.clone());
+        let pod = Pod { metadata, spec: Some(job_spec.template.spec.clone().unwrap_or_default()), status: None };
+        let per_pod = pod_effective_request_resources(&pod);
+
+        let ReplicaPacking { placements, excluded_nodes, eligible_nodes, schedulable_replicas } =
+            pack_replicas(&pod, per_pod, pods_needed, nodes, pods);
+        let anti_affinity_limit = anti_affinity_limit(&pod, nodes, pods);
+        let unschedulable_pods = pods_needed - schedulable_replicas;
+        let fits = unschedulable_pods == 0;
+        let total_cpu_cores = per_pod.cores() * f64::from(pods_needed);
+        let total_memory_gb = per_pod.gb() * f64::from(pods_needed);
+
+        let mut response = CronJobCapacityResponse {
+            cronjob: name,
+            namespace,
+            schedule: spec.schedule.clone(),
+            concurrency_policy,
+            concurrency,
+            pods_per_job,
+            pods_needed,
+            cpu_per_pod_cores: per_pod.cores(),
+            memory_per_pod_gb: per_pod.gb(),
+            total_cpu_cores,
+            total_memory_gb,
+            fits,
+            schedulable_pods: schedulable_replicas,
+            unschedulable_pods,
+            placements,
+            excluded_nodes,
+            anti_affinity_limit,
+            explanation: String::new(),
+        };
+        response.explanation = messages::cronjob_capacity(lang, &response, eligible_nodes, nodes.len());
+        Ok(response)
+    }
+
+    /// Find nodes whose memory or ephemeral storage usage is close to allocatable
+    async fn get_eviction_risk_internal(&self, margin_percent: f64, lang: Lang, fresh: bool) -> Result<EvictionRiskResponse, String> {
+        let nodes = self.list_nodes(fresh).await?;
+        let memory_usage = self.list_node_memory_usage().await;
+        let storage_usage = self.list_node_storage_usage(&nodes).await;
+
+        Ok(Self::build_eviction_risk(&nodes, memory_usage, storage_usage, margin_percent, lang))
+    }
+
+    /// Memory working set of every node from the metrics.k8s.io API (metrics-server)
+    async fn list_node_memory_usage(&self) -> Result<NodeUsageBytes, String> {
+        let metrics = self.list_metrics("NodeMetrics", "nodes").await?;
+
+        Ok(metrics.iter()
+            .map(|m| (m.metadata.name.clone().unwrap_or_default(), node_metrics_memory_bytes(&m.data)))
+            .collect())
+    }
+
+    /// Node filesystem usage of every node from the kubelet stats summary (through the API server's node proxy),
+    /// since metrics-server does not report ephemeral storage, at most kubelet_stats_concurrency nodes at a time.
+    /// Nodes whose kubelet does not answer are left out; an error only when none does.
+    async fn list_node_storage_usage(&self, nodes: &[Node]) -> Result<NodeUsageBytes, String> {
+        let client = self.client().await?;
+
+        let names: Vec<String> = nodes.iter().map(|node| node.metadata.name.clone().unwrap_or_default()).collect();
+        let summaries = futures::stream::iter(names).map(|name| {
+            let client = client.clone();
+            async move {
+                let request = kube::core::Request::new("/api/v1/nodes")
+                    .get_subresource("proxy/stats/summary", &name)
+                    .map_err(|e| format!("{}: {}", name, e))?;
+                let summary: serde_json::Value = client.request(request).await
+                    .map_err(|e| format!("{}: {}", name, e))?;
+                let used = kubelet_fs_used_bytes(&summary)
+                    .ok_or_else(|| format!("{}: no node filesystem stats", name))?;
+                Ok::<_, String>((name, used))
+            }
+        });
+
+        let mut summaries = summaries.buffer_unordered(self.config.kubelet_stats_concurrency);
+        let mut usage = NodeUsageBytes::new();
+        let mut errors = Vec::new();
+        while let Some(summary) = summaries.next().await {
+            match summary {
+                Ok((name, used)) => {
+                    usage.insert(name, used);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
+        // Summaries arrive in completion order; report failures by node name
+        errors.sort();
+        if usage.is_empty() && !errors.is_empty() {
+            return Err(format!("Failed to read kubelet stats: {}", errors.join("; ")));
+        }
+        for e in errors {
+            diagnostics::report(diagnostics::DiagnosticLevel::Info, "kubelet_stats_unavailable", format!("Kubelet stats unavailable for {}", e));
+        }
+
+        Ok(usage)
+    }
+
+    /// Flag nodes whose memory or ephemeral storage usage is within `margin_percent` of allocatable,
+    /// or whose kubelet already reports MemoryPressure/DiskPressure. Missing usage only drops that check.
+    fn build_eviction_risk(
+        nodes: &[Node],
+        memory_usage: Result<NodeUsageBytes, String>,
+        storage_usage: Result<NodeUsageBytes, String>,
+        margin_percent: f64,
+        lang: Lang,
+    ) -> EvictionRiskResponse {
+        let mut warnings = Vec::new();
+        let memory_metrics_available = memory_usage.is_ok();
+        let storage_metrics_available = storage_usage.is_ok();
+        let memory_usage = memory_usage.unwrap_or_else(|e| {
+            let warning = format!("Node memory metrics unavailable ({}); memory is only flagged on MemoryPressure", e);
+            diagnostics::warn("metrics_unavailable", warning.clone());
+            warnings.push(warning);
+            NodeUsageBytes::new()
+        });
+        let storage_usage = storage_usage.unwrap_or_else(|e| {
+            let warning = format!("Ephemeral storage usage unavailable ({}); storage is only flagged on DiskPressure", e);
+            diagnostics::warn("kubelet_stats_unavailable", warning.clone());
+            warnings.push(warning);
+            NodeUsageBytes::new()
+        });
+
+        let threshold_percent = 100.0 - margin_percent;
+        let percent = |used: i64, allocatable: i64| (allocatable > 0).then(|| used as f64 / allocatable as f64 * 100.0);
+        let gb = |bytes: i64| bytes as f64 / BYTES_PER_GB;
+
+        let mut risks: Vec<NodeEvictionRisk> = nodes.iter()
+            .map(|node| {
+                let name = node.metadata.name.clone().unwrap_or_default();
+                let memory_allocatable = node_allocatable_resources(node).bytes;
+                let storage_allocatable = node.status.as_ref()
+                    .and_then(|status| status.allocatable.as_ref())
+                    .and_then(|allocatable| allocatable.get("ephemeral-storage"))
+                    .map(quantity_to_bytes)
+                    .unwrap_or(0);
+
+                let memory_used = memory_usage.get(&name).copied();
+                let storage_used = storage_usage.get(&name).copied();
+                let memory_usage_percent = memory_used.and_then(|used| percent(used, memory_allocatable));
+                let storage_usage_percent = storage_used.and_then(|used| percent(used, storage_allocatable));
+
+                let resources_at_risk: Vec<String> = [("memory", memory_usage_percent), ("ephemeral-storage", storage_usage_percent)]
+                    .into_iter()
+                    .filter(|(_, used_percent)| used_percent.is_some_and(|p| p >= threshold_percent))
+                    .map(|(resource, _)| resource.to_string())
+                    .collect();
+                let pressure_conditions = node_pressure_conditions(node);
+
+                NodeEvictionRisk {
+                    node: name,
+                    memory_usage_gb: memory_used.map(gb),
+                    memory_allocatable_gb: gb(memory_allocatable),
+                    memory_usage_percent,
+                    storage_usage_gb: storage_used.map(gb),
+                    storage_allocatable_gb: gb(storage_allocatable),
+                    storage_usage_percent,
+                    at_risk: !resources_at_risk.is_empty() || !pressure_conditions.is_empty(),
+                    resources_at_risk,
+                    pressure_conditions,
+                }
+            })
+            .collect();
+
+        let highest = |risk: &NodeEvictionRisk| risk.memory_usage_percent.unwrap_or(0.0).max(risk.storage_usage_percent.unwrap_or(0.0));
+        risks.sort_by(|a, b| b.at_risk.cmp(&a.at_risk)
+            .then_with(|| highest(b).total_cmp(&highest(a)))
+            .then_with(|| a.node.cmp(&b.node)));
+
+        let mut response = EvictionRiskResponse {
+            margin_percent,
+            at_risk_nodes: risks.iter().filter(|risk| risk.at_risk).count(),
+            nodes: risks,
+            memory_metrics_available,
+            storage_metrics_available,
+            warnings,
+            explanation: String::new(),
+        };
+        response.explanation = messages::eviction_risk(lang, &response);
+        response
+    }
+
+    /// Get each namespace's share of the cluster's requests
+    async fn get_namespace_share_internal(&self, lang: Lang, fresh: bool) -> Result<NamespaceShareResponse, String> {
+        let pods = self.list_pods(None, fresh).await?;
+
+        Ok(Self::build_namespace_share(&pods, lang))
+    }
+
+    /// Each namespace's percentage of the summed CPU and memory requests of all namespaces, so the
+    /// shares add up to 100%. Counted like get_namespace_usage: terminal pods are skipped.
+    fn build_namespace_share(pods: &[Pod], lang: Lang) -> NamespaceShareResponse {
+        let namespace_usages = Self::build_namespace_usages(&[], pods);
+        let total_cpu_requests_cores: f64 = namespace_usages.iter().map(|u| u.cpu_requests_cores).sum();
+        let total_memory_requests_gb: f64 = namespace_usages.iter().map(|u| u.memory_requests_gb).sum();
+        let percent_of = |part: f64, total: f64| if total > 0.0 { part / total * 100.0 } else { 0.0 };
+
+        let mut namespaces: Vec<NamespaceShare> = namespace_usages.into_iter()
+            .map(|u| {
+                let cpu_share_percent = percent_of(u.cpu_requests_cores, total_cpu_requests_cores);
+                let memory_share_percent = percent_of(u.memory_requests_gb, total_memory_requests_gb);
+                NamespaceShare {
+                    namespace: u.namespace,
+                    cpu_requests_cores: u.cpu_requests_cores,
+                    memory_requests_gb: u.memory_requests_gb,
+                    cpu_share_percent,
+                    memory_share_percent,
+                    resource_share_percent: (cpu_share_percent + memory_share_percent) / 2.0,
+                    pod_count: u.pod_count,
+                }
+            })
+            .collect();
+        namespaces.sort_by(|a, b| b.resource_share_percent.total_cmp(&a.resource_share_percent)
+            .then_with(|| a.namespace.cmp(&b.namespace)));
+
+        let mut response = NamespaceShareResponse {
+            namespaces,
+            total_cpu_requests_cores,
+            total_memory_requests_gb,
+            explanation: String::new(),
+        };
+        response.explanation = messages::namespace_share(lang, &response);
+        response
+    }
+
+    /// Find pods whose requests are implausibly large or set by an init container
+    async fn find_request_outliers_internal(&self, median_multiple: f64, lang: Lang, fresh: bool) -> Result<RequestOutliersResponse, String> {
+        let pods = self.list_pods(None, fresh).await?;
+
+        Ok(Self::build_request_outliers(&pods, median_multiple, lang))
+    }
+
+    /// Flag non-terminal pods whose effective CPU or memory request is at least `median_multiple` times
+    /// the median pod's, or whose effective request is set by an init container rather than the app containers
+    fn build_request_outliers(pods: &[Pod], median_multiple: f64, lang: Lang) -> RequestOutliersResponse {
+        let pods: Vec<&Pod> = pods.iter().filter(|pod| !pod_is_terminal(pod)).collect();
+        let effective: Vec<Resources> = pods.iter().map(|pod| pod_effective_request_resources(pod)).collect();
+        let median_cpu_cores = median(effective.iter().map(|r| r.cores()).collect());
+        let median_memory_gb = median(effective.iter().map(|r| r.gb()).collect());
+        let multiple_of = |value: f64, median: f64| if median > 0.0 { value / median } else { 0.0 };
+
+        let mut outliers: Vec<RequestOutlier> = pods.iter().zip(&effective)
+            .filter_map(|(pod, effective)| {
+                let cpu_median_multiple = multiple_of(effective.cores(), median_cpu_cores);
+                let memory_median_multiple = multiple_of(effective.gb(), median_memory_gb);
+
+                let mut reasons = Vec::new();
+                if cpu_median_multiple >= median_multiple {
+                    reasons.push(format!("cpu {:.1}x the median pod", cpu_median_multiple));
+                }
+                if memory_median_multiple >= median_multiple {
+                    reasons.push(format!("memory {:.1}x the median pod", memory_median_multiple));
+                }
+                for (container, resource) in dominating_init_containers(pod) {
+                    reasons.push(format!("init container '{}' sets the effective {} request", container, resource));
+                }
+                if reasons.is_empty() {
+                    return None;
+                }
+
+                let containers = pod_request_resources(pod);
+                Some(RequestOutlier {
+                    name: pod.metadata.name.clone().unwrap_or_default(),
+                    namespace: pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                    node: pod_node_name(pod).unwrap_or("unscheduled").to_string(),
+                    containers_cpu_cores: containers.cores(),
+                    containers_memory_gb: containers.gb(),
+                    effective_cpu_cores: effective.cores(),
+                    effective_memory_gb: effective.gb(),
+                    cpu_median_multiple,
+                    memory_median_multiple,
+                    reasons,
+                    containers: container_request_breakdown(pod),
+                })
+            })
+            .collect();
+        let largest = |o: &RequestOutlier| o.cpu_median_multiple.max(o.memory_median_multiple);
+        outliers.sort_by(|a, b| largest(b).total_cmp(&largest(a))
+            .then_with(|| a.namespace.cmp(&b.namespace))
+            .then_with(|| a.name.cmp(&b.name)));
+
+        let mut response = RequestOutliersResponse {
+            median_multiple,
+            median_cpu_cores,
+            median_memory_gb,
+            pods: outliers,
+            total_pods_checked: pods.len(),
+            explanation: String::new(),
+        };
+        response.explanation = messages::request_outliers(lang, &response);
+        response
+    }
+
+    /// Get cluster capacity by node state
+    async fn get_capacity_by_node_state_internal(&self, lang: Lang, fresh: bool) -> Result<CapacityByNodeStateResponse, String> {
+        let nodes = self.list_nodes(fresh).await?;
+
+        Ok(Self::build_capacity_by_node_state(&nodes, lang))
+    }
+
+    /// Bucket node capacity into Ready (schedulable and Ready), Cordoned and NotReady (schedulable but not
+    /// Ready). Only Ready nodes take new pods; the other two buckets are the capacity that is stuck.
+    fn build_capacity_by_node_state(nodes: &[Node], lang: Lang) -> CapacityByNodeStateResponse {
+        const STATES: [&str; 3] = ["Ready", "Cordoned", "NotReady"];
+        let state_of = |node: &Node| match (node_is_cordoned(node), node_is_ready(node)) {
+            (true, _) => "Cordoned",
+            (false, true) => "Ready",
+            (false, false) => "NotReady",
+        };
+
+        let total: Resources = nodes.iter().map(node_capacity).sum();
+        let percent_of = |part: f64, total: f64| if total > 0.0 { part / total * 100.0 } else { 0.0 };
+
+        let states: Vec<NodeStateCapacity> = STATES.iter()
+            .map(|state| {
+                let in_state: Vec<&Node> = nodes.iter().filter(|node| state_of(node) == *state).collect();
+                let capacity: Resources = in_state.iter().map(|node| node_capacity(node)).sum();
+                let allocatable: Resources = in_state.iter().map(|node| node_allocatable_resources(node)).sum();
+                let mut names: Vec<String> = in_state.iter().map(|node| node.metadata.name.clone().unwrap_or_default()).collect();
+                names.sort();
+                NodeStateCapacity {
+                    state: state.to_string(),
+                    node_count: names.len(),
+                    nodes: names,
+                    cpu_cores: capacity.cores(),
+                    memory_gb: capacity.gb(),
+                    allocatable_cpu_cores: allocatable.cores(),
+                    allocatable_memory_gb: allocatable.gb(),
+                    cpu_percent: percent_of(capacity.cores(), total.cores()),
+                    memory_percent: percent_of(capacity.gb(), total.gb()),
+                }
+            })
+            .collect();
+
+        let stuck_cpu_cores: f64 = states[1..].iter().map(|s| s.cpu_cores).sum();
+        let stuck_memory_gb: f64 = states[1..].iter().map(|s| s.memory_gb).sum();
+
+        let mut response = CapacityByNodeStateResponse {
+            states,
+            total_cpu_cores: total.cores(),
+            total_memory_gb: total.gb(),
+            stuck_cpu_cores,
+            stuck_memory_gb,
+            explanation: String::new(),
+        };
+        response.explanation = messages::capacity_by_node_state(lang, &response);
+        response
+    }
+
+    /// Get each namespace's headroom against its fair share of the cluster
+    async fn get_namespace_headroom_internal(
+        &self,
+        weights: &BTreeMap<String, f64>,
+        lang: Lang, fresh: bool,
+    ) -> Result<NamespaceHeadroomResponse, String> {
+        let options = ClusterStateOptions { nodes: true, pods: true, namespaces: !weights.is_empty(), fresh, ..Default::default() };
+        let state = self.fetch_cluster_state(options).await?;
+
+        Self::build_namespace_headroom(&state.nodes, &state.pods, &state.namespaces, weights, lang)
+    }
+
+    /// Split the cluster capacity into fair shares by weight (1 unless given) among the namespaces that hold
+    /// pods or are weighted, and report each namespace's share minus its requests. Weighted namespaces must
+    /// be in `namespace_names`; requests are counted like get_namespace_usage (terminal pods skipped).
+    fn build_namespace_headroom(
+        nodes: &[Node],
+        pods: &[Pod],
+        namespace_names: &[String],
+        weights: &BTreeMap<String, f64>,
+        lang: Lang,
+    ) -> Result<NamespaceHeadroomResponse, String> {
+        let missing: Vec<&str> = weights.keys()
+            .filter(|name| !namespace_names.contains(name))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!("Namespaces not found: {}", missing.join(", ")));
+        }
+
+        let weighted: Vec<String> = weights.keys().cloned().collect();
+        let usages = Self::build_namespace_usages(&weighted, pods);
+        let total: Resources = nodes.iter().map(node_capacity).sum();
+        let weight_of = |namespace: &str| weights.get(namespace).copied().unwrap_or(1.0);
+        let total_weight: f64 = usages.iter().map(|u| weight_of(&u.namespace)).sum();
+
+        let mut namespaces: Vec<NamespaceHeadroom> = usages.into_iter()
+            .map(|u| {
+                let weight = weight_of(&u.namespace);
+                let fair_share_cpu_cores = total.cores() * weight / total_weight;
+                let fair_share_memory_gb = total.gb() * weight / total_weight;
+                let cpu_headroom_cores = fair_share_cpu_cores - u.cpu_requests_cores;
+                let memory_headroom_gb = fair_share_memory_gb - u.memory_requests_gb;
+                NamespaceHeadroom {
+                    namespace: u.namespace,
+                    weight,
+                    fair_share_cpu_cores,
+                    fair_share_memory_gb,
+                    cpu_requests_cores: u.cpu_requests_cores,
+                    memory_requests_gb: u.memory_requests_gb,
+                    cpu_headroom_cores,
+                    memory_headroom_gb,
+                    over_fair_share: cpu_headroom_cores < 0.0 || memory_headroom_gb < 0.0,
+                }
+            })
+            .collect();
+        let fraction_left = |h: &NamespaceHeadroom| {
+            let fraction = |headroom: f64, share: f64| if share > 0.0 { headroom / share } else { 0.0 };
+            fraction(h.cpu_headroom_cores, h.fair_share_cpu_cores).min(fraction(h.memory_headroom_gb, h.fair_share_memory_gb))
+        };
+        namespaces.sort_by(|a, b| fraction_left(a).total_cmp(&fraction_left(b))
+            .then_with(|| a.namespace.cmp(&b.namespace)));
+
+        let mut response = NamespaceHeadroomResponse {
+            over_fair_share_count: namespaces.iter().filter(|h| h.over_fair_share).count(),
+            namespaces,
+            total_cpu_cores: total.cores(),
+            total_memory_gb: total.gb(),
+            total_weight,
+            explanation: String::new(),
+        };
+        response.explanation = messages::namespace_headroom(lang, &response);
+        Ok(response)
+    }
+
+    /// Recommend the smallest node that hosts a workload next to the cluster's DaemonSets
+    async fn recommend_node_size_internal(
+        &self,
+        cpu_cores: f64,
+        memory_gb: f64,
+        lang: Lang, fresh: bool,
+    ) -> Result<RecommendNodeSizeResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+        let reserved = (self.config.node_reserved_cpu_cores, self.config.node_reserved_memory_gb);
+
+        Ok(Self::build_node_size_recommendation((cpu_cores, memory_gb), &nodes, &pods, reserved, lang))
+    }
+
+    /// Minimum allocatable for a node hosting `workload` (cores, GB) plus one pod of every DaemonSet in `pods`,
+    /// and the capacity to provision once the per-node `reserved` (cores, GB) is added
+    fn build_node_size_recommendation(
+        workload: (f64, f64),
+        nodes: &[Node],
+        pods: &[Pod],
+        reserved: (f64, f64),
+        lang: Lang,
+    ) -> RecommendNodeSizeResponse {
+        let (workload_cpu_cores, workload_memory_gb) = workload;
+        let (daemonset_cpu_overhead_cores, daemonset_memory_overhead_gb) = daemonset_overhead(pods);
+        let min_allocatable_cpu_cores = workload_cpu_cores + daemonset_cpu_overhead_cores;
+        let min_allocatable_memory_gb = workload_memory_gb + daemonset_memory_overhead_gb;
+        let (reserved_cpu_cores, reserved_memory_gb) = reserved;
+        let min_capacity_cpu_cores = min_allocatable_cpu_cores + reserved_cpu_cores;
+        let min_capacity_memory_gb = min_allocatable_memory_gb + reserved_memory_gb;
+
+        let large_enough_nodes: Vec<String> = nodes.iter()
+            .filter(|node| {
+                let (cpu, memory) = node_allocatable(node);
+                cpu >= min_allocatable_cpu_cores && memory >= min_allocatable_memory_gb
+            })
+            .map(|node| node.metadata.name.clone().unwrap_or_default())
+            .collect();
+
+        let mut response = RecommendNodeSizeResponse {
+            workload_cpu_cores,
+            workload_memory_gb,
+            daemonset_cpu_overhead_cores,
+            daemonset_memory_overhead_gb,
+            min_allocatable_cpu_cores,
+            min_allocatable_memory_gb,
+            reserved_cpu_cores,
+            reserved_memory_gb,
+            min_capacity_cpu_cores,
+            min_capacity_memory_gb,
+            large_enough_nodes,
+            explanation: String::new(),
+        };
+        response.explanation = messages::node_size_recommendation(lang, &response, nodes.len());
+        response
+    }
+
+    /// Find pods bound to nodes that no longer exist
+    async fn find_orphaned_pods_internal(&self, lang: Lang, fresh: bool) -> Result<OrphanedPodsResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Ok(Self::build_orphaned_pods(&nodes, &pods, lang))
+    }
+
+    /// List the pods whose spec.nodeName names a node missing from `nodes`. They hold no real node's
+    /// capacity, so get_node_breakdown attributes them to no node.
+    fn build_orphaned_pods(nodes: &[Node], pods: &[Pod], lang: Lang) -> OrphanedPodsResponse {
+        let mut orphans: Vec<OrphanedPod> = orphaned_pods(nodes, pods).into_iter()
+            .map(|pod| {
+                let (cpu_requests_cores, memory_requests_gb) = pod_requests(pod);
+                OrphanedPod {
+                    name: pod.metadata.name.clone().unwrap_or_default(),
+                    namespace: pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                    node: pod_node(pod),
+                    phase: pod.status.as_ref().and_then(|s| s.phase.clone()).unwrap_or_else(|| "Unknown".to_string()),
+                    cpu_requests_cores,
+                    memory_requests_gb,
+                }
+            })
+            .collect();
+        orphans.sort_by(|a, b| (&a.node, &a.namespace, &a.name).cmp(&(&b.node, &b.namespace, &b.name)));
+
+        let missing_nodes: Vec<String> = orphans.iter()
+            .map(|pod| pod.node.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let total_cpu_requests_cores: f64 = orphans.iter().map(|pod| pod.cpu_requests_cores).sum();
+        let total_memory_requests_gb: f64 = orphans.iter().map(|pod| pod.memory_requests_gb).sum();
+
+        let mut response = OrphanedPodsResponse {
+            pods: orphans,
+            missing_nodes,
+            total_cpu_requests_cores,
+            total_memory_requests_gb,
+            explanation: String::new(),
+        };
+        response.explanation = messages::orphaned_pods_report(lang, &response);
+        response
+    }
+
+    /// Recommend how far namespaces' requests could shrink while keeping headroom over their usage
+    async fn recommend_namespace_downsize_internal(
+        &self,
+        namespace: Option<&str>,
+        headroom_percent: f64,
+        lang: Lang, fresh: bool,
+    ) -> Result<NamespaceDownsizeResponse, String> {
+        let mut state = self.fetch_cluster_state(ClusterStateOptions {
+            pods: true,
+            pod_usage: true,
+            metrics_optional: true,
+            fresh,
+            ..Default::default()
+        }).await?;
+        if let Some(namespace) = namespace {
+            state.pods.retain(|pod| pod.metadata.namespace.as_deref() == Some(namespace));
+        }
+        let usage = state.take_pod_usage();
+
+        Ok(Self::build_namespace_downsize(&state.pods, usage, headroom_percent, lang))
+    }
+
+    /// Per namespace, the requests each non-terminal pod needs to keep `headroom_percent` above its usage,
+    /// capped at its current requests, and what that frees. Without pod metrics a pod's usage is only bounded
+    /// by its limits (requests for unlimited resources); since limits never lie below requests, nothing shows
+    /// as reducible. Pods missing from the metrics keep their requests.
+    fn build_namespace_downsize(pods: &[Pod], usage: Result<PodUsage, String>, headroom_percent: f64, lang: Lang) -> NamespaceDownsizeResponse {
+        let (usage, usage_source) = match usage {
+            Ok(usage) => (Some(usage), UsageSource::Metrics),
+            Err(e) => {
+                diagnostics::warn(
+                    "metrics_unavailable",
+                    format!("Pod metrics unavailable, using limits as the usage ceiling: {}", e),
+                );
+                (None, UsageSource::Limits)
+            }
+        };
+        let factor = 1.0 + headroom_percent / 100.0;
+
+        // Pods, requests, usage and recommended requests per namespace, summed exactly
+        let mut by_namespace: BTreeMap<String, (usize, Resources, Resources, Resources)> = BTreeMap::new();
+        for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+            let namespace = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+            let name = pod.metadata.name.clone().unwrap_or_default();
+            let requests = pod_request_resources(pod);
+            let (pod_usage, recommended) = match &usage {
+                Some(usage) => {
+                    let pod_usage = usage.get(&(namespace.clone(), name)).copied()
+                        .map_or(requests, |(cpu, memory)| Resources::from_cores_and_gb(cpu, memory));
+                    let with_headroom = Resources {
+                        millicores: (pod_usage.millicores as f64 * factor).ceil() as i64,
+                        bytes: (pod_usage.bytes as f64 * factor).ceil() as i64,
+                    };
+                    (pod_usage, with_headroom.min(requests))
+                }
+                None => {
+                    let ceiling = pod_limits_or_requests(pod);
+                    (ceiling, ceiling.min(requests))
+                }
+            };
+
+            let entry = by_namespace.entry(namespace).or_default();
+            entry.0 += 1;
+            entry.1 += requests;
+            entry.2 += pod_usage;
+            entry.3 += recommended;
+        }
+
+        let total_reducible: Resources = by_namespace.values().map(|(_, requests, _, recommended)| *requests - *recommended).sum();
+        let mut namespaces: Vec<NamespaceDownsize> = by_namespace.into_iter()
+            .map(|(namespace, (pod_count, requests, usage, recommended))| {
+                let reducible = requests - recommended;
+                NamespaceDownsize {
+                    namespace,
+                    pod_count,
+                    cpu_requests_cores: requests.cores(),
+                    memory_requests_gb: requests.gb(),
+                    cpu_usage_cores: usage.cores(),
+                    memory_usage_gb: usage.gb(),
+                    recommended_cpu_requests_cores: recommended.cores(),
+                    recommended_memory_requests_gb: recommended.gb(),
+                    cpu_reducible_cores: reducible.cores(),
+                    memory_reducible_gb: reducible.gb(),
+                }
+            })
+            .collect();
+        namespaces.sort_by(|a, b| b.cpu_reducible_cores.total_cmp(&a.cpu_reducible_cores)
+            .then_with(|| b.memory_reducible_gb.total_cmp(&a.memory_reducible_gb))
+            .then_with(|| a.namespace.cmp(&b.namespace)));
+        let (total_cpu_reducible_cores, total_memory_reducible_gb) = total_reducible.cores_and_gb();
+
+        let mut response = NamespaceDownsizeResponse {
+            namespaces,
+            headroom_percent,
+            usage_source,
+            total_cpu_reducible_cores,
+            total_memory_reducible_gb,
+            explanation: String::new(),
+        };
+        response.explanation = messages::namespace_downsize(lang, &response);
+        response
+    }
+
+    /// Build the selected report sections from one fetch of the cluster
+    async fn get_full_report_internal(
+        &self,
+        sections: &BTreeSet<ReportSection>,
+        lang: Lang,
+        fresh: bool,
+    ) -> Result<FullReportResponse, String> {
+        let namespaces = sections.contains(&ReportSection::Namespaces);
+        let state = self.fetch_cluster_state(ClusterStateOptions { nodes: true, pods: true, namespaces, fresh, ..Default::default() }).await?;
+
+        Self::build_full_report(&state.nodes, &state.pods, &state.namespaces, sections, lang, chrono::Utc::now())
+    }
+
+    /// Compose the selected sections as the individual tools report them with their defaults (requests basis,
+    /// nodes by name, pods by CPU requests), and one brief line per section as the explanation
+    fn build_full_report(
+        nodes: &[Node],
+        pods: &[Pod],
+        namespace_names: &[String],
+        sections: &BTreeSet<ReportSection>,
+        lang: Lang,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<FullReportResponse, String> {
+        use messages::Explained;
+
+        let mut report = FullReportResponse {
+            sections: sections.iter().copied().collect(),
+            capacity: None,
+            nodes: None,
+            namespaces: None,
+            top_pods: None,
+            health: None,
+            explanation: String::new(),
+        };
+        let mut lines = Vec::new();
+        let orphaned_pod_count = orphaned_pods(nodes, pods).len();
+
+        for section in sections {
+            match section {
+                ReportSection::Capacity => {
+                    let mut capacity = Self::compute_cluster_capacity(nodes, pods, ResourceBasis::Requests, None);
+                    capacity.explanation = messages::cluster_capacity(lang, &capacity);
+                    lines.push(capacity.brief(lang));
+                    report.capacity = Some(capacity);
+                }
+                ReportSection::Nodes => {
+                    let node_infos = Self::build_node_infos(nodes, pods, ResourceBasis::Requests, None);
+                    let breakdown = Self::build_node_breakdown(
+                        node_infos, NodeSortBy::Name, 0, None, ResourceBasis::Requests, orphaned_pod_count, lang,
+                    );
+                    lines.push(breakdown.brief(lang));
+                    report.nodes = Some(breakdown);
+                }
+                ReportSection::Namespaces => {
+                    let usage = Self::build_namespace_usage(namespace_names, pods, None, false, lang)?;
+                    lines.push(usage.brief(lang));
+                    report.namespaces = Some(usage);
+                }
+                ReportSection::TopPods => {
+                    let stats = Self::build_pod_resource_stats(pods, false, false, PodSortBy::CpuRequests, None, lang);
+                    lines.push(stats.brief(lang));
+                    report.top_pods = Some(stats);
+                }
+                ReportSection::Health => {
+                    let mut health = HealthSignals {
+                        pending_pod_count: pods.iter().filter(|pod| pod_node_name(pod).is_none() && !pod_is_terminal(pod) && !pod_is_gated(pod)).count(),
+                        orphaned_pod_count,
+                        stuck_pod_count: Self::build_stuck_pods(pods, DEFAULT_STUCK_GRACE_PERIOD_SECONDS, now, lang).total_stuck,
+                        overpacked_node_count: Self::build_overpacked_nodes(nodes, pods, lang).nodes.len(),
+                        cordoned_node_count: nodes.iter().filter(|node| node_is_cordoned(node)).count(),
+                        not_ready_node_count: nodes.iter().filter(|node| !node_is_ready(node)).count(),
+                        explanation: String::new(),
+                    };
+                    health.explanation = messages::health_signals(lang, &health);
+                    lines.push(health.explanation.clone());
+                    report.health = Some(health);
+                }
+            }
+        }
+
+        report.explanation = lines.join("\n");
+        Ok(report)
+    }
+
+    /// Find the pods held back by scheduling gates
+    async fn find_gated_pods_internal(&self, lang: Lang, fresh: bool) -> Result<GatedPodsResponse, String> {
+        let pods = self.list_pods(None, fresh).await?;
+
+        Ok(Self::build_gated_pods(&pods, lang))
+    }
+
+    /// List the unbound, non-terminal pods whose spec.schedulingGates is not empty. The scheduler
+    /// ignores them until every gate is removed, so get_cluster_capacity counts them in
+    /// gated_pod_count instead of the pending demand.
+    fn build_gated_pods(pods: &[Pod], lang: Lang) -> GatedPodsResponse {
+        let mut gated: Vec<GatedPod> = pods.iter()
+            .filter(|pod| pod_node_name(pod).is_none() && !pod_is_terminal(pod) && pod_is_gated(pod))
+            .map(|pod| {
+                let (cpu_requests_cores, memory_requests_gb) = pod_requests(pod);
+                GatedPod {
+                    name: pod.metadata.name.clone().unwrap_or_default(),
+                    namespace: pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                    gates: pod.spec.as_ref()
+                        .and_then(|spec| spec.scheduling_gates.as_ref())
+                        .map(|gates| gates.iter().map(|gate| gate.name.clone()).collect())
+                        .unwrap_or_default(),
+                    cpu_requests_cores,
+                    memory_requests_gb,
+                }
+            })
+            .collect();
+        gated.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+
+        let mut gates: BTreeMap<String, usize> = BTreeMap::new();
+        for gate in gated.iter().flat_map(|pod| pod.gates.iter()) {
+            *gates.entry(gate.clone()).or_insert(0) += 1;
+        }
+        let total_cpu_requests_cores: f64 = gated.iter().map(|pod| pod.cpu_requests_cores).sum();
+        let total_memory_requests_gb: f64 = gated.iter().map(|pod| pod.memory_requests_gb).sum();
+
+        let mut response = GatedPodsResponse {
+            pods: gated,
+            gates,
+            total_cpu_requests_cores,
+            total_memory_requests_gb,
+            explanation: String::new(),
+        };
+        response.explanation = messages::gated_pods(lang, &response);
+        response
+    }
+
+    /// Project each node's load if every pod used its full limits
+    async fn get_burst_projection_internal(&self, lang: Lang, fresh: bool) -> Result<BurstProjectionResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Ok(Self::build_burst_projection(&nodes, &pods, lang))
+    }
+
+    /// Sum the limits (requests where unset) of the non-terminal pods bound to each node and compare
+    /// them to its allocatable. The projected use is capped at allocatable, since a node cannot hand
+    /// out more than it has; the rest is the excess that full burst would fight over.
+    fn build_burst_projection(nodes: &[Node], pods: &[Pod], lang: Lang) -> BurstProjectionResponse {
+        let mut by_node: HashMap<&str, (Resources, usize, usize)> = HashMap::new();
+        for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+            if let Some(node) = pod_node_name(pod) {
+                let entry = by_node.entry(node).or_default();
+                entry.0 += pod_limits_or_requests(pod);
+                entry.1 += 1;
+                if !pod_has_cpu_and_memory_limits(pod) {
+                    entry.2 += 1;
+                }
+            }
+        }
+
+        let percent = |limits: f64, allocatable: f64| if allocatable > 0.0 { limits / allocatable * 100.0 } else { 0.0 };
+        let mut projections: Vec<NodeBurstProjection> = nodes.iter()
+            .map(|node| {
+                let name = node.metadata.name.clone().unwrap_or_default();
+                let allocatable = node_allocatable_resources(node);
+                let (limits, pod_count, unlimited_pod_count) = by_node.get(name.as_str()).copied().unwrap_or_default();
+                let excess = Resources {
+                    millicores: (limits.millicores - allocatable.millicores).max(0),
+                    bytes: (limits.bytes - allocatable.bytes).max(0),
+                };
+                NodeBurstProjection {
+                    node: name,
+                    allocatable_cpu_cores: allocatable.cores(),
+                    allocatable_memory_gb: allocatable.gb(),
+                    cpu_limits_cores: limits.cores(),
+                    memory_limits_gb: limits.gb(),
+                    cpu_burst_percent: percent(limits.cores(), allocatable.cores()),
+                    memory_burst_percent: percent(limits.gb(), allocatable.gb()),
+                    projected_cpu_cores: (limits - excess).cores(),
+                    projected_memory_gb: (limits - excess).gb(),
+                    cpu_excess_cores: excess.cores(),
+                    memory_excess_gb: excess.gb(),
+                    oversubscribed: excess != Resources::default(),
+                    pod_count,
+                    unlimited_pod_count,
+                }
+            })
+            .collect();
+        let peak = |n: &NodeBurstProjection| n.cpu_burst_percent.max(n.memory_burst_percent);
+        projections.sort_by(|a, b| peak(b).total_cmp(&peak(a)).then_with(|| a.node.cmp(&b.node)));
+
+        let oversubscribed_node_count = projections.iter().filter(|n| n.oversubscribed).count();
+        let projected_cpu_cores: f64 = projections.iter().map(|n| n.projected_cpu_cores).sum();
+        let projected_memory_gb: f64 = projections.iter().map(|n| n.projected_memory_gb).sum();
+        let total_cpu_excess_cores: f64 = projections.iter().map(|n| n.cpu_excess_cores).sum();
+        let total_memory_excess_gb: f64 = projections.iter().map(|n| n.memory_excess_gb).sum();
+
+        let mut response = BurstProjectionResponse {
+            nodes: projections,
+            oversubscribed_node_count,
+            projected_cpu_cores,
+            projected_memory_gb,
+            total_cpu_excess_cores,
+            total_memory_excess_gb,
+            explanation: String::new(),
+        };
+        response.explanation = messages::burst_projection(lang, &response);
+        response
+    }
+
+    /// Report ResourceQuota usage, counting only the pods each quota's scopes select
+    async fn get_quota_usage_internal(&self, namespace: Option<&str>, lang: Lang, fresh: bool) -> Result<QuotaUsageResponse, String> {
+        let client = self.client().await?;
+        let api = match namespace {
+            Some(namespace) => Api::<ResourceQuota>::namespaced(client, namespace),
+            None => Api::<ResourceQuota>::all(client),
+        };
+        let quotas = api.list(&Default::default()).await
+            .map_err(|e| format!("Failed to list resource quotas: {}", e))?;
+        let pods = self.list_pods(namespace, fresh).await?;
+
+        Ok(Self::build_quota_usage(&quotas.items, &pods, lang))
+    }
+
+    /// Compare each quota's hard CPU, memory and pod limits with what the non-terminal pods of
+    /// its namespace that its scopes select use
+    fn build_quota_usage(quotas: &[ResourceQuota], pods: &[Pod], lang: Lang) -> QuotaUsageResponse {
+        let mut by_namespace: HashMap<&str, Vec<&Pod>> = HashMap::new();
+        for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+            by_namespace.entry(pod.metadata.namespace.as_deref().unwrap_or("default")).or_default().push(pod);
+        }
+
+        let mut reports: Vec<NamespaceQuota> = quotas.iter()
+            .map(|quota| {
+                let namespace = quota.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+                let namespace_pods = by_namespace.get(namespace.as_str()).map(Vec::as_slice).unwrap_or_default();
+                let matching: Vec<&Pod> = namespace_pods.iter().copied().filter(|pod| quota_selects_pod(quota, pod)).collect();
+                let reported = quota.status.as_ref().and_then(|status| status.used.as_ref());
+
+                let mut resources: Vec<QuotaResourceUsage> = quota.spec.as_ref()
+                    .and_then(|spec| spec.hard.as_ref())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(resource, quantity)| {
+                        let hard = quota_amount(resource, quantity)?;
+                        let used: f64 = matching.iter().map(|pod| pod_quota_usage(resource, pod)).sum();
+                        Some(QuotaResourceUsage {
+                            resource: resource.clone(),
+                            hard,
+                            used,
+                            remaining: (hard - used).max(0.0),
+                            utilization_percent: if hard > 0.0 { used / hard * 100.0 } else { 0.0 },
+                            reported_used: reported.and_then(|used| used.get(resource)).and_then(|q| quota_amount(resource, q)),
+                        })
+                    })
+                    .collect();
+                resources.sort_by(|a, b| b.utilization_percent.total_cmp(&a.utilization_percent).then_with(|| a.resource.cmp(&b.resource)));
+
+                NamespaceQuota {
+                    namespace,
+                    name: quota.metadata.name.clone().unwrap_or_default(),
+                    scopes: quota_scopes(quota),
+                    matching_pod_count: matching.len(),
+                    namespace_pod_count: namespace_pods.len(),
+                    resources,
+                }
+            })
+            .collect();
+        let peak = |q: &NamespaceQuota| q.resources.first().map_or(0.0, |r| r.utilization_percent);
+        reports.sort_by(|a, b| peak(b).total_cmp(&peak(a))
+            .then_with(|| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name))));
+
+        let mut response = QuotaUsageResponse {
+            quotas: reports,
+            explanation: String::new(),
+        };
+        response.explanation = messages::quota_usage(lang, &response);
+        response
+    }
+
+    /// Report how densely and evenly each node is packed
+    async fn get_node_packing_internal(&self, over_packed_percent: f64, under_packed_percent: f64, lang: Lang, fresh: bool) -> Result<NodePackingResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Ok(Self::build_node_packing(&nodes, &pods, over_packed_percent, under_packed_percent, lang))
+    }
+
+    /// Pods per node, average pod size and the share of allocatable the running pods' effective
+    /// requests take. A node is over-packed when either resource reaches `over_packed_percent`, and
+    /// under-packed when both stay below `under_packed_percent`.
+    fn build_node_packing(nodes: &[Node], pods: &[Pod], over_packed_percent: f64, under_packed_percent: f64, lang: Lang) -> NodePackingResponse {
+        let mut requested: HashMap<&str, (Resources, usize)> = HashMap::new();
+        for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+            let Some(node) = pod_node_name(pod) else { continue };
+            let entry = requested.entry(node).or_default();
+            entry.0 += pod_effective_request_resources(pod);
+            entry.1 += 1;
+        }
+
+        let percent = |part: f64, total: f64| if total > 0.0 { part / total * 100.0 } else { 0.0 };
+        let mut total_requested = Resources::default();
+        let mut total_allocatable = Resources::default();
+        let mut packings: Vec<NodePacking> = nodes.iter()
+            .map(|node| {
+                let name = node.metadata.name.clone().unwrap_or_default();
+                let allocatable = node_allocatable_resources(node);
+                let (used, pod_count) = requested.get(name.as_str()).copied().unwrap_or_default();
+                total_requested += used;
+                total_allocatable += allocatable;
+
+                let max_pods = node.status.as_ref()
+                    .and_then(|status| status.allocatable.as_ref())
+                    .and_then(|allocatable| allocatable.get("pods"))
+                    .map(parse_device_count);
+                let per_pod = |amount: f64| if pod_count > 0 { amount / pod_count as f64 } else { 0.0 };
+                let cpu_utilization_percent = percent(used.cores(), allocatable.cores());
+                let memory_utilization_percent = percent(used.gb(), allocatable.gb());
+                let packing = if cpu_utilization_percent.max(memory_utilization_percent) >= over_packed_percent {
+                    PackingState::OverPacked
+                } else if cpu_utilization_percent.max(memory_utilization_percent) < under_packed_percent {
+                    PackingState::UnderPacked
+                } else {
+                    PackingState::Normal
+                };
+
+                NodePacking {
+                    node: name,
+                    pod_count,
+                    max_pods,
+                    pod_density_percent: max_pods.map(|max| percent(pod_count as f64, max as f64)),
+                    avg_pod_cpu_cores: per_pod(used.cores()),
+                    avg_pod_memory_gb: per_pod(used.gb()),
+                    cpu_utilization_percent,
+                    memory_utilization_percent,
+                    packing_efficiency_percent: (cpu_utilization_percent + memory_utilization_percent) / 2.0,
+                    imbalance_percent: (cpu_utilization_percent - memory_utilization_percent).abs(),
+                    packing,
+                }
+            })
+            .collect();
+        packings.sort_by(|a, b| b.packing_efficiency_percent.total_cmp(&a.packing_efficiency_percent).then_with(|| a.node.cmp(&b.node)));
+
+        let nodes_in = |state: PackingState| -> Vec<String> {
+            packings.iter().filter(|n| n.packing == state).map(|n| n.node.clone()).collect()
+        };
+        let over_packed_nodes = nodes_in(PackingState::OverPacked);
+        let under_packed_nodes = nodes_in(PackingState::UnderPacked);
+        let cluster_packing_efficiency_percent = (percent(total_requested.cores(), total_allocatable.cores())
+            + percent(total_requested.gb(), total_allocatable.gb())) / 2.0;
+
+        let mut response = NodePackingResponse {
+            nodes: packings,
+            over_packed_percent,
+            under_packed_percent,
+            over_packed_nodes,
+            under_packed_nodes,
+            cluster_packing_efficiency_percent,
+            explanation: String::new(),
+        };
+        response.explanation = messages::node_packing(lang, &response);
+        response
+    }
+
+    /// Find GPU nodes whose idle GPUs cannot be used for lack of CPU or memory
+    async fn find_stranded_gpus_internal(&self, cpu_cores_per_gpu: Option<f64>, memory_gb_per_gpu: Option<f64>, lang: Lang, fresh: bool) -> Result<StrandedGpusResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Ok(Self::build_stranded_gpus(&nodes, &pods, cpu_cores_per_gpu, memory_gb_per_gpu, lang))
+    }
+
+    /// Per GPU node, how many idle GPUs the free CPU and memory can still feed. A GPU pod is assumed
+    /// to request `cpu_cores_per_gpu` and `memory_gb_per_gpu` per GPU; unset, the median per-GPU
+    /// requests of the running GPU pods are used, and without any GPU pods each node's allocatable
+    /// divided by its GPUs. Idle GPUs beyond what fits are stranded.
+    fn build_stranded_gpus(nodes: &[Node], pods: &[Pod], cpu_cores_per_gpu: Option<f64>, memory_gb_per_gpu: Option<f64>, lang: Lang) -> StrandedGpusResponse {
+        let mut requested: HashMap<&str, (Resources, u64)> = HashMap::new();
+        let mut cpu_per_gpu_samples = Vec::new();
+        let mut memory_per_gpu_samples = Vec::new();
+        for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+            let Some(node) = pod_node_name(pod) else { continue };
+            let resources = pod_effective_request_resources(pod);
+            let gpus = pod_gpus(pod);
+            let entry = requested.entry(node).or_default();
+            entry.0 += resources;
+            entry.1 += gpus;
+            if gpus > 0 {
+                cpu_per_gpu_samples.push(resources.cores() / gpus as f64);
+                memory_per_gpu_samples.push(resources.gb() / gpus as f64);
+            }
+        }
+        let observed = !cpu_per_gpu_samples.is_empty();
+        let cpu_cores_per_gpu = cpu_cores_per_gpu.or_else(|| observed.then(|| median(cpu_per_gpu_samples)));
+        let memory_gb_per_gpu = memory_gb_per_gpu.or_else(|| observed.then(|| median(memory_per_gpu_samples)));
+
+        let percent = |part: f64, total: f64| if total > 0.0 { part / total * 100.0 } else { 0.0 };
+        let mut gpu_nodes: Vec<StrandedGpuNode> = nodes.iter()
+            .filter_map(|node| {
+                let total_gpus = node_gpus(node);
+                if total_gpus == 0 {
+                    return None;
+                }
+                let name = node.metadata.name.clone().unwrap_or_default();
+                let allocatable = node_allocatable_resources(node);
+                let (used, requested_gpus) = requested.get(name.as_str()).copied().unwrap_or_default();
+                let idle_gpus = total_gpus.saturating_sub(requested_gpus);
+                let available_cpu_cores = allocatable.cores() - used.cores();
+                let available_memory_gb = allocatable.gb() - used.gb();
+                let cpu_per_gpu = cpu_cores_per_gpu.unwrap_or(allocatable.cores() / total_gpus as f64);
+                let memory_per_gpu = memory_gb_per_gpu.unwrap_or(allocatable.gb() / total_gpus as f64);
+
+                let cpu_fit = copies_that_fit(available_cpu_cores, cpu_per_gpu).unwrap_or(u64::MAX);
+                let memory_fit = copies_that_fit(available_memory_gb, memory_per_gpu).unwrap_or(u64::MAX);
+                let schedulable_idle_gpus = idle_gpus.min(cpu_fit).min(memory_fit);
+                let stranded_gpus = idle_gpus - schedulable_idle_gpus;
+                let limiting_resource = (stranded_gpus > 0).then(|| if cpu_fit <= memory_fit { "cpu" } else { "memory" }.to_string());
+
+                Some(StrandedGpuNode {
+                    node: name,
+                    total_gpus,
+                    requested_gpus,
+                    idle_gpus,
+                    cpu_utilization_percent: percent(used.cores(), allocatable.cores()),
+                    memory_utilization_percent: percent(used.gb(), allocatable.gb()),
+                    available_cpu_cores,
+                    available_memory_gb,
+                    cpu_cores_per_gpu: cpu_per_gpu,
+                    memory_gb_per_gpu: memory_per_gpu,
+                    schedulable_idle_gpus,
+                    stranded_gpus,
+                    limiting_resource,
+                })
+            })
+            .collect();
+        gpu_nodes.sort_by(|a, b| b.stranded_gpus.cmp(&a.stranded_gpus)
+            .then_with(|| b.idle_gpus.cmp(&a.idle_gpus))
+            .then_with(|| a.node.cmp(&b.node)));
+
+        let stranded: Vec<&StrandedGpuNode> = gpu_nodes.iter().filter(|n| n.stranded_gpus > 0).collect();
+        let stranded_nodes: Vec<String> = stranded.iter().map(|n| n.node.clone()).collect();
+        let idle_gpus: u64 = gpu_nodes.iter().map(|n| n.idle_gpus).sum();
+        let stranded_gpus: u64 = stranded.iter().map(|n| n.stranded_gpus).sum();
+
+        let mut response = StrandedGpusResponse {
+            nodes: gpu_nodes,
+            stranded_nodes,
+            idle_gpus,
+            stranded_gpus,
+            cpu_cores_per_gpu,
+            memory_gb_per_gpu,
+            explanation: String::new(),
+        };
+        response.explanation = messages::stranded_gpus(lang, &response);
+        response
+    }
+
+    /// Report which share of pods declares CPU and memory requests
+    async fn get_request_coverage_internal(&self, lang: Lang, fresh: bool) -> Result<RequestCoverageResponse, String> {
+        let pods = self.list_pods(None, fresh).await?;
+
+        Ok(Self::build_request_coverage(&pods, lang))
+    }
+
+    /// Per namespace and cluster-wide, the share of non-terminal pods whose containers all request
+    /// CPU and memory. Pods without requests are invisible to the scheduler's bin packing, so a low
+    /// coverage means the capacity figures undercount what is really used.
+    fn build_request_coverage(pods: &[Pod], lang: Lang) -> RequestCoverageResponse {
+        #[derive(Default)]
+        struct Counts {
+            pods: usize,
+            cpu: usize,
+            memory: usize,
+            non_compliant: Vec<String>,
+        }
+
+        let mut by_namespace: BTreeMap<String, Counts> = BTreeMap::new();
+        for pod in pods.iter().filter(|pod| !pod_is_terminal(pod)) {
+            let namespace = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+            let counts = by_namespace.entry(namespace).or_default();
+            let requests_cpu = pod_requests_resource(pod, "cpu");
+            let requests_memory = pod_requests_resource(pod, "memory");
+            counts.pods += 1;
+            counts.cpu += usize::from(requests_cpu);
+            counts.memory += usize::from(requests_memory);
+            if !(requests_cpu && requests_memory) {
+                counts.non_compliant.push(pod.metadata.name.clone().unwrap_or_default());
+            }
+        }
+
+        let percent = |part: usize, total: usize| if total > 0 { part as f64 / total as f64 * 100.0 } else { 100.0 };
+        let (mut pod_count, mut cpu, mut memory, mut non_compliant_pod_count) = (0, 0, 0, 0);
+        let mut namespaces: Vec<NamespaceRequestCoverage> = by_namespace.into_iter()
+            .map(|(namespace, mut counts)| {
+                pod_count += counts.pods;
+                cpu += counts.cpu;
+                memory += counts.memory;
+                non_compliant_pod_count += counts.non_compliant.len();
+                counts.non_compliant.sort();
+
+                let compliant_pod_count = counts.pods - counts.non_compliant.len();
+                NamespaceRequestCoverage {
+                    namespace,
+                    pod_count: counts.pods,
+                    compliant_pod_count,
+                    non_compliant_pod_count: counts.non_compliant.len(),
+                    coverage_percent: percent(compliant_pod_count, counts.pods),
+                    cpu_coverage_percent: percent(counts.cpu, counts.pods),
+                    memory_coverage_percent: percent(counts.memory, counts.pods),
+                    non_compliant_pods: counts.non_compliant,
+                }
+            })
+            .collect();
+        namespaces.sort_by(|a, b| a.coverage_percent.total_cmp(&b.coverage_percent)
+            .then_with(|| b.non_compliant_pod_count.cmp(&a.non_compliant_pod_count))
+            .then_with(|| a.namespace.cmp(&b.namespace)));
+
+        let compliant_pod_count = pod_count - non_compliant_pod_count;
+        let coverage_percent = percent(compliant_pod_count, pod_count);
+        let mut response = RequestCoverageResponse {
+            pod_count,
+            compliant_pod_count,
+            non_compliant_pod_count,
+            coverage_percent,
+            cpu_coverage_percent: percent(cpu, pod_count),
+            memory_coverage_percent: percent(memory, pod_count),
+            namespaces,
+            explanation: String::new(),
+        };
+        response.explanation = messages::request_coverage(lang, &response);
+        response
+    }
+
+    /// Compare the memory-per-core ratio of the pod requests with that of the nodes
+    async fn get_request_ratio_mismatch_internal(&self, tolerance_percent: f64, lang: Lang, fresh: bool) -> Result<RequestRatioMismatchResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Self::build_request_ratio_mismatch(&nodes, &pods, tolerance_percent, lang)
+    }
+
+    /// Summed requests of the running pods against summed allocatable, as GB of memory per core.
+    /// If pods keep their aggregate shape and fill the cluster, the resource they are heavy on runs
+    /// out first and part of the other is stranded: 1 - 1/factor of the CPU for memory-heavy pods,
+    /// 1 - factor of the memory for CPU-heavy ones.
+    fn build_request_ratio_mismatch(nodes: &[Node], pods: &[Pod], tolerance_percent: f64, lang: Lang) -> Result<RequestRatioMismatchResponse, String> {
+        let requested: Resources = pods.iter()
+            .filter(|pod| !pod_is_terminal(pod))
+            .map(pod_effective_request_resources)
+            .sum();
+        let allocatable: Resources = nodes.iter().map(node_allocatable_resources).sum();
+        if requested.millicores <= 0 || requested.bytes <= 0 {
+            return Err("The running pods request no CPU or no memory; there is no request ratio to compare".to_string());
+        }
+        if allocatable.millicores <= 0 || allocatable.bytes <= 0 {
+            return Err("The nodes report no allocatable CPU or memory".to_string());
+        }
+
+        let pod_memory_gb_per_core = requested.gb() / requested.cores();
+        let node_memory_gb_per_core = allocatable.gb() / allocatable.cores();
+        let mismatch_factor = pod_memory_gb_per_core / node_memory_gb_per_core;
+        let tolerance = 1.0 + tolerance_percent / 100.0;
+        let mismatched = mismatch_factor > tolerance || mismatch_factor < 1.0 / tolerance;
+        let (stranded_resource, stranded_percent) = match (mismatched, mismatch_factor > 1.0) {
+            (false, _) => (None, 0.0),
+            (true, true) => (Some("cpu"), (1.0 - 1.0 / mismatch_factor) * 100.0),
+            (true, false) => (Some("memory"), (1.0 - mismatch_factor) * 100.0),
+        };
+        let closest_family = |gb_per_core: f64| NODE_FAMILIES.iter()
+            .min_by(|a, b| (a.0 / gb_per_core).ln().abs().total_cmp(&(b.0 / gb_per_core).ln().abs()))
+            .copied()
+            .unwrap_or(NODE_FAMILIES[1]);
+        let (family_ratio, family) = closest_family(pod_memory_gb_per_core);
+        let same_family = closest_family(node_memory_gb_per_core).1 == family;
+
+        let mut response = RequestRatioMismatchResponse {
+            requested_cpu_cores: requested.cores(),
+            requested_memory_gb: requested.gb(),
+            allocatable_cpu_cores: allocatable.cores(),
+            allocatable_memory_gb: allocatable.gb(),
+            pod_memory_gb_per_core,
+            node_memory_gb_per_core,
+            mismatch_factor,
+            mismatched,
+            stranded_resource: stranded_resource.map(str::to_string),
+            stranded_percent,
+            recommended_node_family: family.to_string(),
+            recommendation: String::new(),
+            explanation: String::new(),
+        };
+        response.recommendation = messages::ratio_recommendation(lang, &response, family_ratio, same_family);
+        response.explanation = messages::request_ratio_mismatch(lang, &response, tolerance_percent);
+        Ok(response)
+    }
+
+    /// Express the cluster capacity in reference pods
+    async fn get_capacity_in_pod_units_internal(&self, cpu_cores: f64, memory_gb: f64, lang: Lang, fresh: bool) -> Result<CapacityInPodUnitsResponse, String> {
+        let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+
+        Ok(Self::build_capacity_in_pod_units(&Self::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None), cpu_cores, memory_gb, lang))
+    }
+
+    /// Per node, allocatable, allocated and available CPU and memory as counts of a reference pod.
+    /// Total and available units follow the binding resource (the one yielding fewer pods);
+    /// allocated units follow the resource the placed pods use most of. A zero dimension of the
+    /// reference pod never binds.
+    fn build_capacity_in_pod_units(node_infos: &[NodeInfo], cpu_cores: f64, memory_gb: f64, lang: Lang) -> CapacityInPodUnitsResponse {
+        let units = |amount: f64, per_unit: f64| if per_unit > 0.0 { amount.max(0.0) / per_unit } else { f64::INFINITY };
+        let used = |amount: f64, per_unit: f64| if per_unit > 0.0 { amount / per_unit } else { 0.0 };
+
+        let mut nodes: Vec<NodePodUnits> = node_infos.iter()
+            .map(|info| {
+                let cpu_fit = copies_that_fit(info.available_cpu_cores, cpu_cores).unwrap_or(u64::MAX);
+                let memory_fit = copies_that_fit(info.available_memory_gb, memory_gb).unwrap_or(u64::MAX);
+                NodePodUnits {
+                    node: info.name.clone(),
+                    total_units: units(info.allocatable_cpu_cores, cpu_cores).min(units(info.allocatable_memory_gb, memory_gb)),
+                    allocated_units: used(info.allocated_cpu_cores, cpu_cores).max(used(info.allocated_memory_gb, memory_gb)),
+                    available_units: cpu_fit.min(memory_fit),
+                    binding_resource: if cpu_fit <= memory_fit { "cpu" } else { "memory" }.to_string(),
+                }
+            })
+            .collect();
+        nodes.sort_by(|a, b| b.available_units.cmp(&a.available_units).then_with(|| a.node.cmp(&b.node)));
+
+        let total_units: f64 = nodes.iter().map(|n| n.total_units).sum();
+        let allocated_units: f64 = nodes.iter().map(|n| n.allocated_units).sum();
+        let available_units: u64 = nodes.iter().map(|n| n.available_units).sum();
+        let cpu_bound_nodes = nodes.iter().filter(|n| n.binding_resource == "cpu").count();
+        let memory_bound_nodes = nodes.len() - cpu_bound_nodes;
+
+        let mut response = CapacityInPodUnitsResponse {
+            cpu_cores,
+            memory_gb,
+            total_units,
+            allocated_units,
+            available_units,
+            cpu_bound_nodes,
+            memory_bound_nodes,
+            nodes,
+            explanation: String::new(),
+        };
+        response.explanation = messages::capacity_in_pod_units(lang, &response);
+        response
+    }
+
+    /// Find every pod whose requests reach a threshold
+    async fn find_large_pods_internal(
+        &self,
+        min_cpu_cores: Option<f64>,
+        min_memory_gb: Option<f64>,
+        namespace: Option<&str>,
+        lang: Lang, fresh: bool,
+    ) -> Result<FindLargePodsResponse, String> {
+        let pods = self.list_pods(namespace, fresh).await?;
+
+        Ok(Self::build_large_pods(&pods, min_cpu_cores, min_memory_gb, lang))
+    }
+
+    /// Every non-terminal pod whose effective CPU requests reach `min_cpu_cores` or whose effective
+    /// memory requests reach `min_memory_gb`; complete, unlike the top-N rankings
+    fn build_large_pods(pods: &[Pod], min_cpu_cores: Option<f64>, min_memory_gb: Option<f64>, lang: Lang) -> FindLargePodsResponse {
+        let running: Vec<&Pod> = pods.iter().filter(|pod| !pod_is_terminal(pod)).collect();
+        let mut large: Vec<LargePod> = running.iter()
+            .filter_map(|pod| {
+                let (cpu_requests_cores, memory_requests_gb) = pod_effective_requests(pod);
+                let mut exceeds = Vec::new();
+                if min_cpu_cores.is_some_and(|min| cpu_requests_cores >= min) {
+                    exceeds.push("cpu".to_string());
+                }
+                if min_memory_gb.is_some_and(|min| memory_requests_gb >= min) {
+                    exceeds.push("memory".to_string());
+                }
+                (!exceeds.is_empty()).then(|| LargePod {
+                    name: pod.metadata.name.clone().unwrap_or_default(),
+                    namespace: pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string()),
+                    node: pod_node(pod),
+                    cpu_requests_cores,
+                    memory_requests_gb,
+                    exceeds,
+                })
+            })
+            .collect();
+        large.sort_by(|a, b| b.cpu_requests_cores.total_cmp(&a.cpu_requests_cores)
+            .then_with(|| b.memory_requests_gb.total_cmp(&a.memory_requests_gb))
+            .then_with(|| a.namespace.cmp(&b.namespace))
+            .then_with(|| a.name.cmp(&b.name)));
+
+        let mut response = FindLargePodsResponse {
+            pods: large,
+            min_cpu_cores,
+            min_memory_gb,
+            searched_pod_count: running.len(),
+            explanation: String::new(),
+        };
+        response.explanation = messages::large_pods(lang, &response);
+        response
+    }
+
+    /// Explain how one pod's effective request is computed
+    async fn explain_pod_accounting_internal(&self, name: &str, namespace: &str, lang: Lang, fresh: bool) -> Result<PodAccountingResponse, String> {
+        let pods = self.list_pods(Some(namespace), fresh).await?;
+        let pod = pods.iter()
+            .find(|pod| pod.metadata.name.as_deref() == Some(name))
+            .ok_or_else(|| format!("Pod '{}' not found in namespace '{}'", name, namespace))?;
+
+        Ok(Self::build_pod_accounting(pod, lang))
+    }
+
+    /// Step-by-step version of pod_effective_request_resources: the parsed resources of every
+    /// container, the app and sidecar sums, each init phase, the overhead and the effective request
+    fn build_pod_accounting(pod: &Pod, lang: Lang) -> PodAccountingResponse {
+        let raw = |list: Option<&BTreeMap<String, Quantity>>| -> BTreeMap<String, String> {
+            list.iter().flat_map(|list| list.iter()).map(|(resource, quantity)| (resource.clone(), quantity.0.clone())).collect()
+        };
+        let account = |container: &Container, role: ContainerRole| {
+            let (cpu_requests_cores, memory_requests_gb) = container_requests(pod, container).cores_and_gb();
+            let (cpu_limits_cores, memory_limits_gb) = container_limits(pod, container).cores_and_gb();
+            ContainerAccounting {
+                name: container.name.clone(),
+                role,
+                raw_requests: raw(container_request_quantities(pod, container)),
+                raw_limits: raw(container_limit_quantities(pod, container)),
+                from_resize_status: container_status_resources(pod, container).is_some(),
+                cpu_requests_cores,
+                memory_requests_gb,
+                cpu_limits_cores,
+                memory_limits_gb,
+                init_phase_cpu_cores: None,
+                init_phase_memory_gb: None,
+            }
+        };
+
+        let mut containers = Vec::new();
+        let mut app = Resources::default();
+        let mut sidecars = Resources::default();
+        let mut init = Resources::default();
+        let mut overhead = Resources::default();
+        if let Some(spec) = &pod.spec {
+            for container in &spec.containers {
+                app += container_requests(pod, container);
+                containers.push(account(container, ContainerRole::App));
+            }
+
+            for init_container in spec.init_containers.iter().flatten() {
+                let requests = container_requests(pod, init_container);
+                if init_container.restart_policy.as_deref() == Some("Always") {
+                    sidecars += requests;
+                    containers.push(account(init_container, ContainerRole::Sidecar));
+                } else {
+                    let phase = sidecars + requests;
+                    init = init.max(phase);
+                    let mut accounting = account(init_container, ContainerRole::Init);
+                    accounting.init_phase_cpu_cores = Some(phase.cores());
+                    accounting.init_phase_memory_gb = Some(phase.gb());
+                    containers.push(accounting);
+                }
+            }
+            overhead = Resources::of(spec.overhead.as_ref());
+        }
+
+        let running = app + sidecars;
+        let effective = running.max(init) + overhead;
+
+        let counted_as = if pod_is_terminal(pod) {
+            "Ignored"
+        } else if pod_node_name(pod).is_some() {
+            "Allocated"
+        } else if pod_is_gated(pod) {
+            "Gated"
+        } else {
+            "Pending"
+        };
+        let name = pod.metadata.name.clone().unwrap_or_default();
+        let namespace = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+
+        let mut response = PodAccountingResponse {
+            name,
+            namespace,
+            node: pod_node(pod),
+            counted_as: counted_as.to_string(),
+            containers,
+            app_cpu_cores: app.cores(),
+            app_memory_gb: app.gb(),
+            sidecar_cpu_cores: sidecars.cores(),
+            sidecar_memory_gb: sidecars.gb(),
+            init_max_cpu_cores: init.cores(),
+            init_max_memory_gb: init.gb(),
+            overhead_cpu_cores: overhead.cores(),
+            overhead_memory_gb: overhead.gb(),
+            effective_cpu_cores: effective.cores(),
+            effective_memory_gb: effective.gb(),
+            steps: Vec::new(),
+            explanation: String::new(),
+        };
+        response.steps = messages::pod_accounting_steps(lang, &response);
+        response.explanation = messages::pod_accounting(lang, &response);
+        response
+    }
+
+    /// Get namespace to node distribution
+    async fn get_namespace_node_distribution_internal(&self, lang: Lang, fresh: bool) -> Result<NamespaceNodeDistributionResponse, String> {
+        let pods = self.list_pods(None, fresh).await?;
+
+        Ok(Self::build_namespace_node_distribution(&pods, lang))
+    }
+
+    /// Group pods by namespace and node from a single pod list
+    fn build_namespace_node_distribution(pods: &[Pod], lang: Lang) -> NamespaceNodeDistributionResponse {
+        // Requests add up exactly per namespace and node, and become cores and GB only for the response
+        let mut placements: HashMap<String, BTreeMap<String, (usize, Resources)>> = HashMap::new();
+
+        for pod in pods {
+            let ns_name = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+            let placement = placements.entry(ns_name).or_default().entry(pod_node(pod)).or_default();
+            placement.0 += 1;
+            placement.1 += pod_request_resources(pod);
+        }
+
+        let mut namespaces: Vec<NamespaceNodeDistribution> = placements.into_iter()
+            .map(|(namespace, nodes)| {
+                let pod_count = nodes.values().map(|(pod_count, _)| pod_count).sum();
+                let busiest = nodes.values().map(|(pod_count, _)| *pod_count).max().unwrap_or(0);
+                let nodes = nodes.into_iter()
+                    .map(|(node, (pod_count, requests))| {
+                        let (cpu_requests_cores, memory_requests_gb) = requests.cores_and_gb();
+                        (node, NodePlacement { pod_count, cpu_requests_cores, memory_requests_gb })
+                    })
+                    .collect();
+                NamespaceNodeDistribution {
+                    namespace,
+                    nodes,
+                    pod_count,
+                    max_node_share_percent: if pod_count > 0 { busiest as f64 / pod_count as f64 * 100.0 } else { 0.0 },
+                }
+            })
+            .collect();
+        namespaces.sort_by(|a, b| b.pod_count.cmp(&a.pod_count).then_with(|| a.namespace.cmp(&b.namespace)));
+
+        let total_namespaces = namespaces.len();
+
+        let mut response = NamespaceNodeDistributionResponse {
+            namespaces,
+            total_namespaces,
+            explanation: String::new(),
+        };
+        response.explanation = messages::namespace_node_distribution(lang, total_namespaces);
+        response
+    }
+
+    /// Forecast capacity from the given snapshots, or from the trend buffer plus a live snapshot
+    async fn forecast_capacity_internal(
+        &self,
+        snapshots: Option<Vec<CapacitySnapshot>>,
+        threshold_percent: f64,
+        lang: Lang, fresh: bool,
+    ) -> Result<ForecastCapacityResponse, String> {
+        let snapshots = match snapshots {
+            Some(snapshots) => snapshots,
+            None => {
+                let (nodes, pods) = self.list_nodes_and_pods(fresh).await?;
+                record_snapshot(&self.trend, &Self::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None));
+                self.trend.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect()
+            }
+        };
+
+        Self::build_capacity_forecast(&snapshots, threshold_percent, lang)
+    }
+
+    /// Linearly extrapolate allocation growth to the utilization threshold
+    fn build_capacity_forecast(snapshots: &[CapacitySnapshot], threshold_percent: f64, lang: Lang) -> Result<ForecastCapacityResponse, String> {
+        let mut points = Vec::with_capacity(snapshots.len());
+        for snapshot in snapshots {
+            let time = chrono::DateTime::parse_from_rfc3339(&snapshot.timestamp)
+                .map_err(|e| format!("Invalid snapshot timestamp '{}': {}", snapshot.timestamp, e))?;
+            points.push((time.timestamp() as f64, snapshot));
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let (first, last) = match (points.first(), points.last()) {
+            (Some(first), Some(last)) if points.len() >= 2 && last.0 > first.0 => (first.0, last.1),
+            _ => return Err(
+                "At least two snapshots taken at different times are required; \
+                 query the cluster capacity again later or pass stored snapshots".to_string()
+            ),
+        };
+
+        let days: Vec<f64> = points.iter().map(|(time, _)| (time - first) / 86400.0).collect();
+        let cpu: Vec<f64> = points.iter().map(|(_, s)| s.allocated_cpu_cores).collect();
+        let memory: Vec<f64> = points.iter().map(|(_, s)| s.allocated_memory_gb).collect();
+
+        let cpu_growth_cores_per_day = linear_slope(&days, &cpu);
+        let memory_growth_gb_per_day = linear_slope(&days, &memory);
+
+        let days_until_cpu_threshold = days_until_threshold(
+            last.allocated_cpu_cores, last.total_cpu_cores, cpu_growth_cores_per_day, threshold_percent,
+        );
+        let days_until_memory_threshold = days_until_threshold(
+            last.allocated_memory_gb, last.total_memory_gb, memory_growth_gb_per_day, threshold_percent,
+        );
+
+        let (days_until_full, limiting_resource) = match (days_until_cpu_threshold, days_until_memory_threshold) {
+            (Some(cpu_days), Some(memory_days)) if memory_days < cpu_days => (Some(memory_days), Some("memory")),
+            (Some(cpu_days), _) => (Some(cpu_days), Some("cpu")),
+            (None, Some(memory_days)) => (Some(memory_days), Some("memory")),
+            (None, None) => (None, None),
+        };
+
+        let mut response = ForecastCapacityResponse {
+            threshold_percent,
+            snapshots_used: points.len(),
+            cpu_growth_cores_per_day,
+            memory_growth_gb_per_day,
+            days_until_cpu_threshold,
+            days_until_memory_threshold,
+            days_until_full,
+            limiting_resource: limiting_resource.map(str::to_string),
+            explanation: String::new(),
+        };
+        response.explanation = messages::capacity_forecast(lang, &response);
+        Ok(response)
+    }
+
+    /// List the tools registered in the tool router with their input schemas
+    fn list_capabilities_internal(&self, lang: Lang) -> ListCapabilitiesResponse {
+        let mut tools: Vec<ToolCapability> = self.tool_router.list_all()
+            .into_iter()
+            .map(|tool| ToolCapability {
+                name: tool.name.to_string(),
+                description: tool.description.map(|d| d.to_string()).unwrap_or_default(),
+                input_schema: serde_json::Value::Object(tool.input_schema.as_ref().clone()),
+            })
+            .collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let total_tools = tools.len();
+        let explanation = messages::capabilities(lang, total_tools);
+
+        ListCapabilitiesResponse {
+            tools,
+            total_tools,
+            explanation,
+        }
+    }
+}
+
+#[tool_router]
+impl ClusterInsights {
+    /// Default client and the process-wide configuration, with caches of its own and without watch mode
+    #[allow(dead_code)] // Only used when embedding
+    pub fn new() -> Self {
+        Self::builder().build().expect("the process-wide configuration is already validated")
+    }
+
+    /// Use the given client for all API calls instead of the default kubeconfig, with a private list cache
+    #[cfg(test)]
+    pub fn with_client(client: Client, cache_ttl: Duration) -> Self {
+        Self {
+            tool_router: Self::tool_router(),
+            client: Some(client),
+            cache: Arc::new(ListCache::new(cache_ttl)),
+            results: Arc::new(ResultCache::new(cache_ttl, RESULT_CACHE_CAPACITY)),
+            config: Arc::new(config::get().clone()),
+            watch: None,
+            cluster_configured: true,
+            last_capacity: Arc::default(),
+            trend: Arc::default(),
+        }
+    }
+
+    /// Get cluster capacity
+    #[tool(description = "Get total cluster capacity, allocated resources (requests by default), and available resources. \
+                          Returns detailed information about CPU cores and memory in GB across all nodes. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es', \
+                          basis (optional string) - count pods by 'Requests' (default), 'Limits' or 'Usage' (live usage, needs metrics-server), \
+                          include_delta (optional bool) - also report delta_allocated_cpu_cores/delta_allocated_memory_gb, the allocation change since the previous call of this session, \
+                          exclude_namespaces (optional array of strings) - compute capacity as if these namespaces' pods did not exist, e.g. 'how much frees up if I evacuate batch?'. \
+                          Example: Returns total 24 CPU cores, 96 GB memory, with 12 cores and 48 GB allocated.")]
+    pub async fn get_cluster_capacity(
+        &self,
+        params: Parameters<ClusterCapacityParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let exclude_namespaces = params.0.exclude_namespaces.as_deref().unwrap_or_default();
+        if params.0.include_delta && !exclude_namespaces.is_empty() {
+            return Ok(error_result("include_delta compares whole-cluster allocations and cannot be combined with exclude_namespaces".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_cluster_capacity_internal(params.0.basis, exclude_namespaces, lang, fresh).await {
+            Ok(mut result) => {
+                // A what-if without some namespaces is not an allocation the next delta should compare against
+                if exclude_namespaces.is_empty() {
+                    self.observe_capacity(&mut result, params.0.include_delta, lang);
+                }
+                Ok(self.success_result(&messages::with_detail(result, detail, lang)))
+            }
+            Err(e) => Ok(error_result(format!("Failed to get cluster capacity: {}", e))),
+        }
+    }
+
+    /// Check if resources fit in cluster
+    #[tool(description = "Check if specified CPU and memory resources can fit in the cluster. \
+                          Parameters: cpu_cores (float), memory_gb (float), \
+                          node_cpu_cores/node_memory_gb (optional float) - representative node size for scale-up math, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es', \
+                          consider_autoscaling (optional bool) with node_groups (array of {name, current_nodes, max_nodes}) - \
+                          also report fits_after_scaleup and the nodes the autoscaler must add, \
+                          cluster_override (optional {total_cpu_cores, total_memory_gb, allocated_cpu_cores, allocated_memory_gb}) - \
+                          check against these totals without contacting any cluster (pass node_cpu_cores/node_memory_gb for scale-up math), \
+                          extra_resources (optional map of resource name to quantity, e.g. {\"ephemeral-storage\": \"20Gi\"}) - \
+                          also check resources like ephemeral storage or device plugin resources against node allocatable minus pod requests, \
+                          basis (optional string) - 'Requests' (default), 'Limits' to count existing pods by their limits for a conservative, no-overcommit answer, \
+                          or 'Usage' to count them by live usage (needs metrics-server), \
+                          subtract_pending_demand (optional bool) - count the requests of Pending pods as already taken, for a pessimistic answer, \
+                          priority_class (optional string) - PriorityClass the request runs at; capacity held by lower-priority pods counts as available, since the scheduler can preempt them, \
+                          exclude_namespaces (optional array of strings) - check as if these namespaces' pods did not exist, e.g. whether the request fits once they are evacuated, \
+                          target_utilization_percent (optional float) - allocation policy cap, e.g. 80 to never exceed 80% allocation: available becomes total * 80% - allocated (default: 100). \
+                          Returns whether resources fit, available resources, and utilization percentages; \
+                          when they do not fit, recommends how many nodes to add (after DaemonSet overhead). \
+                          Example: cpu_cores=4, memory_gb=16 → checks if 4 cores and 16GB available.")]
+    pub async fn check_resource_fit(
+        &self,
+        params: Parameters<CheckResourceFitParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        if params.0.cpu_cores < 0.0 {
+            return Ok(error_result("CPU cores must be non-negative".to_string()));
+        }
+
+        if params.0.memory_gb < 0.0 {
+            return Ok(error_result("Memory GB must be non-negative".to_string()));
+        }
+
+        if params.0.node_cpu_cores.is_some_and(|v| v <= 0.0) || params.0.node_memory_gb.is_some_and(|v| v <= 0.0) {
+            return Ok(error_result("Node size must be positive".to_string()));
+        }
+
+        if params.0.target_utilization_percent.is_some_and(|v| !v.is_finite() || v <= 0.0 || v > 100.0) {
+            return Ok(error_result("Target utilization percent must be greater than 0 and at most 100".to_string()));
+        }
+
+        if let Some(totals) = &params.0.cluster_override {
+            let values = [totals.total_cpu_cores, totals.total_memory_gb, totals.allocated_cpu_cores, totals.allocated_memory_gb];
+            if values.iter().any(|v| !v.is_finite() || *v < 0.0) {
+                return Ok(error_result("Cluster override totals must be non-negative".to_string()));
+            }
+            if params.0.extra_resources.as_ref().is_some_and(|extra| !extra.is_empty()) {
+                return Ok(error_result("extra_resources cannot be checked against cluster_override".to_string()));
+            }
+            if params.0.basis != ResourceBasis::Requests {
+                return Ok(error_result(format!("basis {:?} cannot be applied to cluster_override totals", params.0.basis)));
+            }
+            if params.0.subtract_pending_demand {
+                return Ok(error_result("subtract_pending_demand needs the live cluster and cannot be applied to cluster_override totals".to_string()));
+            }
+            if params.0.priority_class.is_some() {
+                return Ok(error_result("priority_class needs the live cluster and cannot be applied to cluster_override totals".to_string()));
+            }
+            if params.0.exclude_namespaces.as_ref().is_some_and(|namespaces| !namespaces.is_empty()) {
+                return Ok(error_result("exclude_namespaces needs the live cluster and cannot be applied to cluster_override totals".to_string()));
+            }
+        }
+
+        if let Err(e) = validate_node_groups(params.0.consider_autoscaling, params.0.node_groups.as_deref()) {
+            return Ok(error_result(e));
+        }
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let detail = params.0.explanation_detail;
+        match self.check_resource_fit_internal(&params.0, lang).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to check resource fit: {}", e))),
+        }
+    }
+
+    /// Get node breakdown
+    #[tool(description = "Get detailed breakdown of each node in the cluster. \
+                          Lists each node with its total capacity, allocated resources (requests by default), \
+                          available resources, and pod count. \
+                          Parameters: sort_by (optional string) - Name (default), LeastAvailableCpu, LeastAvailableMemory or MostPods, \
+                          limit (optional int) and offset (optional int, default 0) - return one page of the sorted nodes \
+                          (use next_offset and total_nodes to page on large clusters), \
+                          cursor (optional string) - the next_cursor of the previous page, to continue after its last node; \
+                          unlike offset it neither repeats nor skips nodes when nodes are added or removed between calls, \
+                          basis (optional string) - count pods by 'Requests' (default), 'Limits' or 'Usage' (live usage, needs metrics-server), \
+                          group_identical (optional bool) - collapse nodes with the same instance type, allocatable and utilization band \
+                          into groups with a count and min/max/avg availability (shrinks the output on homogeneous clusters; not with limit/offset), \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: sort_by=LeastAvailableCpu, limit=10 → the 10 nodes with the least free CPU.")]
+    pub async fn get_node_breakdown(
+        &self,
+        params: Parameters<NodeBreakdownParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        if params.0.limit == Some(0) {
+            return Ok(error_result("limit must be at least 1".to_string()));
+        }
+        if params.0.group_identical && (params.0.limit.is_some() || params.0.offset.is_some() || params.0.cursor.is_some()) {
+            return Ok(error_result("group_identical returns every group; limit, offset and cursor only page individual nodes".to_string()));
+        }
+        if params.0.cursor.is_some() && params.0.offset.is_some() {
+            return Ok(error_result("Pass either offset or cursor, not both".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_node_breakdown_internal(&params.0, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get node breakdown: {}", e))),
+        }
+    }
+
+    /// Get namespace resource usage
+    #[tool(description = "Get resource usage per namespace. \
+                          Returns CPU/memory requests and limits for each namespace, along with pod count. \
+                          Results are sorted by CPU requests (descending). Succeeded and Failed pods never count towards usage. \
+                          Parameters: include_terminal (optional bool) - also list the Succeeded and Failed pods with their phase (e.g. for a Job audit), \
+                          namespaces (optional array of strings) - only report these namespaces (each must exist) and add their combined total, \
+                          e.g. all namespaces owned by one team, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: Returns namespaces with their total CPU/memory consumption; namespaces=['shop','shop-jobs'] also returns their sum.")]
+    pub async fn get_namespace_usage(
+        &self,
+        params: Parameters<NamespaceUsageParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        // Blank entries mean the server's default namespace; duplicates are counted once
+        let namespaces = match &params.0.namespaces {
+            Some(names) if names.is_empty() => return Ok(error_result("Namespaces cannot be empty".to_string())),
+            Some(names) => {
+                let mut resolved: Vec<String> = Vec::new();
+                for name in names {
+                    match resolve_namespace(name, self.config.default_namespace.clone()) {
+                        Ok(name) if !resolved.contains(&name) => resolved.push(name),
+                        Ok(_) => {}
+                        Err(e) => return Ok(error_result(e)),
+                    }
+                }
+                Some(resolved)
+            }
+            None => None,
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_namespace_usage_internal(params.0.include_terminal, namespaces.as_deref(), lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get namespace usage: {}", e))),
+        }
+    }
+
+    /// Get burst risk
+    #[tool(description = "Find namespaces (and optionally pods) whose limits vastly exceed their requests, a burst risk for nodes. \
+                          Parameters: ratio_threshold (optional float, default 2.0) - flag limit/request ratios above it, \
+                          include_pods (optional bool) - also list flagged pods, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns CPU and memory limit/request ratios, sorted by the highest ratio (descending). \
+                          Example: ratio_threshold=3 → namespace 'batch' flagged with CPU limits at 4.0x requests.")]
+    pub async fn get_burst_risk(
+        &self,
+        params: Parameters<BurstRiskParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let ratio_threshold = params.0.ratio_threshold.unwrap_or(2.0);
+        if ratio_threshold <= 0.0 {
+            return Ok(error_result("Ratio threshold must be positive".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_burst_risk_internal(ratio_threshold, params.0.include_pods, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get burst risk: {}", e))),
+        }
+    }
+
+    /// Find namespaces without memory limits
+    #[tool(description = "Find namespaces where many pods have no memory limit, a sign of missing LimitRange governance \
+                          and an eviction hazard for their nodes. A pod counts as unlimited when any container lacks a memory limit. \
+                          Parameters: min_unlimited_fraction (optional float between 0 and 1, default 0.5) - flag namespaces \
+                          where at least this fraction of pods is unlimited, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns per flagged namespace the pod count and the number of pods without a memory limit. \
+                          Example: min_unlimited_fraction=0.8 → namespace 'legacy' flagged with 12 of 12 pods unlimited.")]
+    pub async fn find_namespaces_without_limits(
+        &self,
+        params: Parameters<NamespacesWithoutLimitsParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let min_unlimited_fraction = params.0.min_unlimited_fraction.unwrap_or(0.5);
+        if min_unlimited_fraction <= 0.0 || min_unlimited_fraction > 1.0 {
+            return Ok(error_result("Unlimited fraction must be greater than 0 and at most 1".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.find_namespaces_without_limits_internal(min_unlimited_fraction, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to find namespaces without limits: {}", e))),
+        }
+    }
+
+    /// Check which pod sizes each node accepts
+    #[tool(description = "Check which of several standard pod sizes each node can still accept, for mixed-size workload placement. \
+                          A size fits a node when both its CPU and memory are within the node's available resources. \
+                          Parameters: sizes (array of {name, cpu_cores, memory_gb}) - candidate pod sizes, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns a node × size boolean matrix and the number of nodes accepting each size. \
+                          Example: sizes=[{small, 0.5, 1}, {large, 4, 16}] → 'small' fits on 5 nodes, 'large' fits on 1.")]
+    pub async fn node_acceptance_matrix(
+        &self,
+        params: Parameters<NodeAcceptanceMatrixParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let sizes = &params.0.sizes;
+        if sizes.is_empty() {
+            return Ok(error_result("At least one pod size is required".to_string()));
+        }
+        for size in sizes {
+            if size.name.trim().is_empty() {
+                return Ok(error_result("Pod size name must not be empty".to_string()));
+            }
+            if !size.cpu_cores.is_finite() || size.cpu_cores < 0.0 {
+                return Ok(error_result(format!("CPU cores of size '{}' must be non-negative", size.name)));
+            }
+            if !size.memory_gb.is_finite() || size.memory_gb < 0.0 {
+                return Ok(error_result(format!("Memory GB of size '{}' must be non-negative", size.name)));
+            }
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.node_acceptance_matrix_internal(sizes, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to build node acceptance matrix: {}", e))),
+        }
+    }
+
+    /// Find the largest pod per node
+    #[tool(description = "Find the single biggest resource consumer on each node, for manual rebalancing and draining. \
+                          Returns per node the pod with the highest CPU request and the pod with the highest memory request \
+                          (they may be the same pod), with their requests; Succeeded, Failed and unscheduled pods are skipped. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: worker-1 → largest by CPU: 'ml/trainer-0' (4 cores, 16 GB), largest by memory: 'db/postgres-0' (1 core, 24 GB).")]
+    pub async fn get_largest_pod_per_node(
+        &self,
+        params: Parameters<LargestPodPerNodeParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_largest_pod_per_node_internal(lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to find largest pods: {}", e))),
+        }
+    }
+
+    /// Find stranded capacity per node
+    #[tool(description = "Find capacity stranded by fragmentation on each node: free CPU paired with no free memory \
+                          (or vice versa), which balanced pods cannot use. \
+                          Per node, the usable capacity is the free CPU and memory that pods of the reference ratio can fill together; the rest is stranded. \
+                          Parameters: cpu_to_memory_ratio (optional float) - CPU cores per GB of memory of a typical pod \
+                          (default: the cluster's average request ratio), \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns usable and stranded CPU/memory per node, sorted by the stranded share, and cluster totals. \
+                          Example: cpu_to_memory_ratio=0.25 → 'worker-2' has 14.75 cores stranded (free CPU but only 1 GB free memory).")]
+    pub async fn get_stranded_capacity(
+        &self,
+        params: Parameters<StrandedCapacityParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        if params.0.cpu_to_memory_ratio.is_some_and(|ratio| !ratio.is_finite() || ratio <= 0.0) {
+            return Ok(error_result("cpu_to_memory_ratio must be positive".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_stranded_capacity_internal(params.0.cpu_to_memory_ratio, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to compute stranded capacity: {}", e))),
+        }
+    }
+
+    /// Find overpacked nodes
+    #[tool(description = "Find nodes where the summed requests of the running pods exceed the node's allocatable CPU or memory, \
+                          a sign of manual binding, a scheduler bug or a node whose allocatable shrank (config or admission problem). \
+                          Uses effective requests (init containers, sidecars and pod overhead included); Succeeded and Failed pods are skipped. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns the offending nodes with their allocatable, requested and overage CPU/memory. \
+                          Example: 'worker-3' requests 9.5 cores on 8 allocatable → 1.5 cores over.")]
+    pub async fn find_overpacked_nodes(
+        &self,
+        params: Parameters<OverpackedNodesParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.find_overpacked_nodes_internal(lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to find overpacked nodes: {}", e))),
+        }
+    }
+
+    /// Report node utilization balance
+    #[tool(description = "Detect a lopsided cluster where some nodes are nearly full while others sit idle. \
+                          Computes each node's requests as a percentage of its allocatable CPU and memory, then the mean, \
+                          standard deviation and coefficient of variation across nodes; a coefficient above 0.3 flags the cluster as imbalanced. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns the spread per resource, the most and least utilized nodes and a rebalancing suggestion. \
+                          Example: 'worker-1' at 90% CPU and 'worker-2' at 10% → CV 0.8, imbalanced.")]
+    pub async fn get_balance_report(
+        &self,
+        params: Parameters<BalanceReportParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_balance_report_internal(lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to compute the balance report: {}", e))),
+        }
+    }
+
+    /// Report pod churn
+    #[tool(description = "Report pod churn: pods created (from creationTimestamp) and deleted by controllers (from SuccessfulDelete events) \
+                          within a recent window, with a churn rate per namespace. High churn signals CrashLoopBackOff or scaling storms \
+                          and helps correlate capacity spikes with workload instability. \
+                          Parameters: window_minutes (optional int) - look-back window in minutes (default: 60), \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns created and deleted counts overall and per namespace, highest churn first. \
+                          Example: window_minutes=30 → namespace 'batch' created 40 and deleted 38 pods (156 per hour).")]
+    pub async fn get_pod_churn(
+        &self,
+        params: Parameters<PodChurnParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let window_minutes = params.0.window_minutes.unwrap_or(DEFAULT_CHURN_WINDOW_MINUTES);
+        if window_minutes == 0 {
+            return Ok(error_result("window_minutes must be positive".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_pod_churn_internal(window_minutes, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to compute pod churn: {}", e))),
+        }
+    }
+
+    /// Report GPU usage
+    #[tool(description = "Report GPU capacity and usage: per node the allocatable, requested and idle GPUs (total minus requested), \
+                          and per namespace the requested GPUs. Counts nvidia.com/gpu and amd.com/gpu; Succeeded and Failed pods are skipped, \
+                          and GPUs of pods still waiting for a node are reported as pending. \
+                          Parameters: include_pods (optional bool) - also list every pod requesting GPUs, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: 'gpu-1' has 8 GPUs with pods requesting 5 → 3 idle.")]
+    pub async fn get_gpu_report(
+        &self,
+        params: Parameters<GpuReportParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_gpu_report_internal(params.0.include_pods, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to compute the GPU report: {}", e))),
+        }
+    }
+
+    /// Estimate how many pods of one size fit
+    #[tool(description = "Estimate how many more pods of one size the cluster can run, e.g. 'how many 2-core/4GB pods fit?'. \
+                          Packs the size into the available resources of every node (the realistic answer) and also divides the \
+                          cluster-wide available resources (the naive answer, which ignores fragmentation). \
+                          Parameters: cpu_cores (float) - CPU request of one pod, memory_gb (float) - memory request of one pod, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns both counts, the per-node counts and the limiting resource. \
+                          Example: cpu_cores=2, memory_gb=4 → 7 pods fit, limited by cpu (naive division: 9).")]
+    pub async fn max_pods_of_size(
+        &self,
+        params: Parameters<MaxPodsOfSizeParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let MaxPodsOfSizeParams { cpu_cores, memory_gb, .. } = params.0;
+        if !cpu_cores.is_finite() || cpu_cores < 0.0 {
+            return Ok(error_result("CPU cores must be non-negative".to_string()));
+        }
+        if !memory_gb.is_finite() || memory_gb < 0.0 {
+            return Ok(error_result("Memory GB must be non-negative".to_string()));
+        }
+        if cpu_cores == 0.0 && memory_gb == 0.0 {
+            return Ok(error_result("CPU cores or memory GB must be positive".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.max_pods_of_size_internal(cpu_cores, memory_gb, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to estimate pods of size: {}", e))),
+        }
+    }
+
+    /// Get resource usage by pod label
+    #[tool(description = "Get resource usage grouped by the value of a pod label, for chargeback by team or cost center. \
+                          Parameters: label_key (string) - label to group by, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns CPU/memory requests and limits and pod count per label value, sorted by CPU requests, \
+                          with pods lacking the label in an 'unlabeled' bucket. \
+                          Example: label_key='team' → usage of team=payments, team=search and unlabeled pods.")]
+    pub async fn get_usage_by_label(
+        &self,
+        params: Parameters<UsageByLabelParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        if params.0.label_key.is_empty() {
+            return Ok(error_result("Label key cannot be empty".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_usage_by_label_internal(&params.0.label_key, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get usage by label: {}", e))),
+        }
+    }
+
+    /// Get resource usage for a label selector
+    #[tool(description = "Get total resource usage of the pods matching a label selector across all namespaces, \
+                          e.g. a platform addon whose pods share a label but live in several namespaces. \
+                          Parameters: label_selector (string) - Kubernetes label selector such as 'app.kubernetes.io/part-of=monitoring' \
+                          or 'tier in (cache,queue)', \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns summed CPU/memory requests and limits, the pod count and the namespaces involved; \
+                          Succeeded and Failed pods are skipped. \
+                          Example: label_selector='addon=logging' → 12 pods in logging and kube-system requesting 3 CPU and 6 GB.")]
+    pub async fn get_usage_for_selector(
+        &self,
+        params: Parameters<UsageForSelectorParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        if params.0.label_selector.trim().is_empty() {
+            return Ok(error_result("Label selector cannot be empty".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_usage_for_selector_internal(params.0.label_selector.trim(), lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get usage for selector: {}", e))),
+        }
+    }
+
+    /// Get resource usage by workload kind
+    #[tool(description = "Get resource usage grouped by the kind of each pod's top-level owner \
+                          (Deployment, StatefulSet, DaemonSet, CronJob, Job, or Pod for bare pods), \
+                          to see which controller type consumes the cluster. Owner references are followed \
+                          through ReplicaSets to their Deployment and through Jobs to their CronJob. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns CPU/memory requests and limits and pod count per kind, sorted by CPU requests. \
+                          Example: Deployment 14.5 cores across 60 pods, DaemonSet 2.4 cores across 12 pods.")]
+    pub async fn get_usage_by_workload_kind(
+        &self,
+        params: Parameters<UsageByWorkloadKindParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_usage_by_workload_kind_internal(lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get usage by workload kind: {}", e))),
+        }
+    }
+
+    /// Check replica consistency
+    #[tool(description = "Check whether the replicas of each workload share identical CPU/memory requests and limits, \
+                          grouping pods by their top-level owner (ReplicaSet pods by Deployment, Job pods by CronJob). \
+                          Differing values catch partial or stuck rollouts and pods edited by hand. \
+                          Parameters: namespace (optional string) - only check workloads in this namespace \
+                          (blank means the server's DEFAULT_NAMESPACE), \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns every workload with two or more replicas, inconsistent ones first, with the differing values \
+                          and the replicas using each. \
+                          Example: Deployment shop/web → 3 replicas with memory limit 1Gi, 1 replica with 2Gi.")]
+    pub async fn get_replica_consistency(
+        &self,
+        params: Parameters<ReplicaConsistencyParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let namespace = match params.0.namespace.as_deref() {
+            Some(namespace) => match resolve_namespace(namespace, self.config.default_namespace.clone()) {
+                Ok(namespace) => Some(namespace),
+                Err(e) => return Ok(error_result(e)),
+            },
+            None => None,
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_replica_consistency_internal(namespace.as_deref(), lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to check replica consistency: {}", e))),
+        }
+    }
+
+    /// Get distinct container images
+    #[tool(description = "List the distinct container images in the cluster with the number of pods using each, \
+                          to spot duplication and plan registry bandwidth and node disk for image pulls. \
+                          Parameters: namespace (optional string) - only count pods in this namespace \
+                          (blank means the server's DEFAULT_NAMESPACE), \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns images sorted by pod count with the namespaces using them. \
+                          Example: namespace='web' → nginx:1.27 used by 6 pods, redis:7 used by 2 pods.")]
+    pub async fn get_image_distribution(
+        &self,
+        params: Parameters<ImageDistributionParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let namespace = match params.0.namespace.as_deref() {
+            Some(namespace) => match resolve_namespace(namespace, self.config.default_namespace.clone()) {
+                Ok(namespace) => Some(namespace),
+                Err(e) => return Ok(error_result(e)),
+            },
+            None => None,
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_image_distribution_internal(namespace.as_deref(), lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get image distribution: {}", e))),
+        }
+    }
+
+    /// Get system overhead vs workload breakdown
+    #[tool(description = "Split allocated resources (requests) into system overhead and application workloads, \
+                          to show how much capacity is truly left for teams. \
+                          Overhead is every pod in a system namespace plus DaemonSet pods in any namespace. \
+                          Parameters: system_namespaces (optional array of strings) - defaults to kube-system, kube-public, \
+                          kube-node-lease and monitoring, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns cores/GB and percent of total cluster capacity for each side. \
+                          Example: system overhead 3.20 cores (13.3%), workloads 12.50 cores (52.1%) of 24 cores.")]
+    pub async fn get_overhead_breakdown(
+        &self,
+        params: Parameters<OverheadBreakdownParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let system_namespaces: Vec<String> = match params.0.system_namespaces {
+            Some(namespaces) => namespaces,
+            None => DEFAULT_SYSTEM_NAMESPACES.iter().map(|ns| ns.to_string()).collect(),
+        };
+        if system_namespaces.iter().any(String::is_empty) {
+            return Ok(error_result("System namespace names cannot be empty".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_overhead_breakdown_internal(&system_namespaces, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get overhead breakdown: {}", e))),
+        }
+    }
+
+    /// Find pods stuck terminating
+    #[tool(description = "Find pods stuck in Terminating: their deletionTimestamp is older than a grace period, \
+                          yet they still count against their node's capacity. \
+                          Parameters: grace_period_seconds (optional int, default 300), \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns each pod's node, finalizers, time spent terminating and effective CPU/memory requests, \
+                          plus the total capacity they hold. \
+                          Example: grace_period_seconds=600 → 2 pods on worker-3 terminating for 3 hours, holding 4 cores.")]
+    pub async fn find_stuck_pods(
+        &self,
+        params: Parameters<StuckPodsParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let grace_period_seconds = params.0.grace_period_seconds.unwrap_or(DEFAULT_STUCK_GRACE_PERIOD_SECONDS);
+        if grace_period_seconds > i64::MAX as u64 {
+            return Ok(error_result("Grace period is too large".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.find_stuck_pods_internal(grace_period_seconds, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to find stuck pods: {}", e))),
+        }
+    }
+
+    /// Check N-1 (or N-k) resilience
+    #[tool(description = "Check whether the cluster survives losing its largest node(s), for SLA planning. \
+                          Removes the largest nodes by allocatable CPU (then memory) and bin-packs the pods they ran \
+                          onto the free allocatable of the remaining schedulable nodes; DaemonSet pods are dropped with their node. \
+                          Parameters: nodes_lost (optional int, default 1) - lose the k largest nodes (N-k), \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns whether all pods still fit, the pods that would not, and the resulting CPU/memory utilization. \
+                          Example: nodes_lost=1 → losing worker-1 leaves 3 of 12 displaced pods unplaceable, CPU at 104.2%.")]
+    pub async fn check_n_minus_one(
+        &self,
+        params: Parameters<NMinusOneParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let nodes_lost = params.0.nodes_lost.unwrap_or(1);
+        if nodes_lost == 0 {
+            return Ok(error_result("nodes_lost must be at least 1".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.check_n_minus_one_internal(nodes_lost as usize, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to check node loss: {}", e))),
+        }
+    }
+
+    /// Get pod resource statistics
+    #[tool(description = "Get top pods by resource consumption. \
+                          Returns the top 20 pods sorted by CPU requests, showing CPU/memory requests and limits. \
+                          Includes namespace, node assignment, and resource metrics in millicores and MB. \
+                          Parameters: include_raw (optional bool) - also return the original quantity strings \
+                          (raw_cpu_request, raw_memory_request, raw_cpu_limit, raw_memory_limit) for auditing unit conversion, \
+                          include_terminal (optional bool) - also list Succeeded and Failed pods, marked with their phase, \
+                          sort_by (optional string) - CpuRequests (default), CpuUsage or MemoryUsage to rank by live usage \
+                          from metrics.k8s.io (adds cpu_usage_millicores/memory_usage_mb; falls back to requests with a warning \
+                          when metrics-server is unavailable), \
+                          format (optional string) - Json (default) or JsonLines to return one JSON object per pod per line \
+                          (no envelope, for jq and streaming consumers; only the top_pods entries, without totals, warnings or explanation), \
+                          strict_quantities (optional bool) - add warnings for memory quantities without a unit that are implausibly small \
+                          (e.g. '512', which is 512 bytes, where '512Mi' was meant), \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: Returns top resource-consuming pods across the cluster.")]
+    pub async fn get_pod_resource_stats(
+        &self,
+        params: Parameters<PodResourceStatsParams>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let detail = params.0.explanation_detail;
+        // Clients that pass a progress token get notified while the pods are paged in
+        let progress = Progress::for_request(&context.meta, context.peer.clone());
+        match self.get_pod_resource_stats_internal(&params.0, lang, progress.as_ref()).await {
+            Ok(result) if params.0.format == PodOutputFormat::JsonLines => Ok(self.jsonl_result(&result.top_pods)),
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get pod resource stats: {}", e))),
+        }
+    }
+
+    /// Check replica capacity
+    #[tool(description = "Check if cluster has capacity to add more replicas of an application. \
+                          Finds an existing pod matching the app name in the specified namespace, \
+                          calculates its resource requirements, and checks if the cluster can accommodate \
+                          the requested number of additional replicas. \
+                          Parameters: app_name (string) - name or pattern to match pods, \
+                          namespace (string) - Kubernetes namespace, defaults to the server's DEFAULT_NAMESPACE when blank, \
+                          replica_count (int) - number of additional replicas needed, \
+                          node_cpu_cores/node_memory_gb (optional float) - representative node size for scale-up math, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es', \
+                          consider_autoscaling (optional bool) with node_groups (array of {name, current_nodes, max_nodes}) - \
+                          also report fits_after_scaleup and the nodes the autoscaler must add, \
+                          subtract_pending_demand (optional bool) - count the requests of Pending pods as already taken, for a pessimistic answer, \
+                          priority_class (optional string) - PriorityClass the request runs at; capacity held by lower-priority pods counts as available, since the scheduler can preempt them, \
+                          normalize_names (optional bool) - strip generated hash suffixes from pod names and match app_name exactly (payments-7d9f8b-abcde → payments), so 'payments' no longer matches 'payments-worker' pods, \
+                          target_utilization_percent (optional float) - allocation policy cap, e.g. 80 to never exceed 80% allocation: available becomes total * 80% - allocated (default: 100), \
+                          basis (optional string) - 'Requests' (default), 'Limits' to count existing pods by their limits for a conservative, no-overcommit answer, \
+                          or 'Usage' to count them by live usage (needs metrics-server). \
+                          Returns detailed capacity analysis including per-replica requirements, total needs, \
+                          cluster availability, and projected utilization. \
+                          Example: app_name='my-application', namespace='default', replica_count=10")]
+    pub async fn check_replica_capacity(
+        &self,
+        params: Parameters<CheckReplicaCapacityParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        if let Err(e) = validate_replica_count(params.0.replica_count, self.config.max_replica_count) {
+            return Ok(error_result(e));
+        }
+
+        if params.0.app_name.is_empty() {
+            return Ok(error_result("Application name cannot be empty".to_string()));
+        }
+
+        let mut params = params;
+        params.0.namespace = match resolve_namespace(&params.0.namespace, self.config.default_namespace.clone()) {
+            Ok(namespace) => namespace,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        if params.0.node_cpu_cores.is_some_and(|v| v <= 0.0) || params.0.node_memory_gb.is_some_and(|v| v <= 0.0) {
+            return Ok(error_result("Node size must be positive".to_string()));
+        }
+
+        if params.0.target_utilization_percent.is_some_and(|v| !v.is_finite() || v <= 0.0 || v > 100.0) {
+            return Ok(error_result("Target utilization percent must be greater than 0 and at most 100".to_string()));
+        }
+
+        if let Err(e) = validate_node_groups(params.0.consider_autoscaling, params.0.node_groups.as_deref()) {
+            return Ok(error_result(e));
+        }
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let detail = params.0.explanation_detail;
+        match self.check_replica_capacity_internal(&params.0, lang).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to check replica capacity: {}", e))),
+        }
+    }
+
+    /// Check whether a Deployment can scale to a replica count
+    #[tool(description = "Check whether a Deployment can scale to a total number of replicas, e.g. 'can deployment web scale to 10?'. \
+                          Reads the pod template's requests, subtracts the replicas that already exist and bin-packs the additional \
+                          ones onto the free allocatable of the nodes the template can run on, honoring its nodeSelector, \
+                          required node affinity and tolerations, and skipping cordoned nodes. \
+                          Parameters: deployment (string) - Deployment name, namespace (string) - its namespace \
+                          (blank means the server's DEFAULT_NAMESPACE), target_replicas (integer) - total replicas wanted, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns how many additional replicas are schedulable, where they would go and which nodes are excluded and why. \
+                          Example: deployment='web', namespace='shop', target_replicas=10 → 7 more needed, 5 fit (3 on node-b, 2 on node-a), 2 stay Pending.")]
+    pub async fn check_deployment_scale(
+        &self,
+        params: Parameters<DeploymentScaleParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        if let Err(e) = validate_replica_count(params.0.target_replicas, self.config.max_replica_count) {
+            return Ok(error_result(e));
+        }
+
+        if params.0.deployment.trim().is_empty() {
+            return Ok(error_result("Deployment name cannot be empty".to_string()));
+        }
+
+        let namespace = match resolve_namespace(&params.0.namespace, self.config.default_namespace.clone()) {
+            Ok(namespace) => namespace,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.check_deployment_scale_internal(params.0.deployment.trim(), &namespace, params.0.target_replicas, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to check deployment scale: {}", e))),
+        }
+    }
+
+    /// Check whether a CronJob's peak concurrency fits
+    #[tool(description = "Check whether a number of a CronJob's Jobs can run at the same time, e.g. when overlapping runs spike capacity. \
+                          Reads the job template's requests and parallelism, and bin-packs concurrency × pods-per-Job pods onto the free \
+                          allocatable of the nodes the template can run on, alongside the current allocation, honoring its nodeSelector, \
+                          required node affinity and tolerations, and skipping cordoned nodes. \
+                          Parameters: cronjob (string) - CronJob name, namespace (string) - its namespace \
+                          (blank means the server's DEFAULT_NAMESPACE), concurrency (integer) - Jobs assumed to run at once, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns the pods needed, their total requests, how many are schedulable, where they would go and which nodes are excluded. \
+                          Example: cronjob='report', namespace='batch', concurrency=3 → 6 pods of 1 core each, 5 fit, 1 stays Pending.")]
+    pub async fn check_cronjob_capacity(
+        &self,
+        params: Parameters<CronJobCapacityParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        if params.0.concurrency <= 0 {
+            return Ok(error_result("concurrency must be positive".to_string()));
+        }
+        if params.0.concurrency > self.config.max_replica_count {
+            return Ok(error_result(format!(
+                "concurrency {} exceeds the maximum of {} (set MAX_REPLICA_COUNT to change it)",
+                params.0.concurrency, self.config.max_replica_count
+            )));
+        }
+
+        if params.0.cronjob.trim().is_empty() {
+            return Ok(error_result("CronJob name cannot be empty".to_string()));
+        }
+
+        let namespace = match resolve_namespace(&params.0.namespace, self.config.default_namespace.clone()) {
+            Ok(namespace) => namespace,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.check_cronjob_capacity_internal(params.0.cronjob.trim(), &namespace, params.0.concurrency, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to check cronjob capacity: {}", e))),
+        }
+    }
+
+    /// Recommend how far each namespace's requests could shrink
+    #[tool(description = "Compute how much each namespace's requests could be reduced safely, the inverse of a scale-up check. \
+                          Each pod's recommended requests keep a headroom above its live usage from metrics-server, never above its \
+                          current requests; the difference is the capacity that would free up. Without metrics, usage is only bounded by \
+                          the pods' limits (requests where no limit is set), which never lie below requests, so nothing is reported as reducible. \
+                          Succeeded and Failed pods never count; pods missing from the metrics keep their requests. \
+                          Parameters: namespace (optional string) - only this namespace (default: all; blank means the server's DEFAULT_NAMESPACE), \
+                          headroom_percent (optional float, default 20) - headroom kept above usage, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns every namespace with its requests, usage, recommended requests and reducible CPU and memory, most reducible first. \
+                          Example: 'batch' requesting 10 cores and using 3 → recommended 3.6 cores with 20% headroom, 6.4 cores freed.")]
+    pub async fn recommend_namespace_downsize(
+        &self,
+        params: Parameters<NamespaceDownsizeParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let headroom_percent = params.0.headroom_percent.unwrap_or(DEFAULT_DOWNSIZE_HEADROOM_PERCENT);
+        if !headroom_percent.is_finite() || headroom_percent < 0.0 {
+            return Ok(error_result("Headroom percent must be non-negative".to_string()));
+        }
+
+        let namespace = match params.0.namespace.as_deref() {
+            Some(namespace) => match resolve_namespace(namespace, self.config.default_namespace.clone()) {
+                Ok(namespace) => Some(namespace),
+                Err(e) => return Ok(error_result(e)),
+            },
+            None => None,
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.recommend_namespace_downsize_internal(namespace.as_deref(), headroom_percent, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to recommend namespace downsize: {}", e))),
+        }
+    }
+
+    /// Get a combined report of the selected insights
+    #[tool(description = "Get a one-shot cluster review combining several insights from a single fetch of nodes, pods and namespaces, \
+                          instead of calling each tool. Sections: Capacity (as get_cluster_capacity), Nodes (as get_node_breakdown, by name), \
+                          Namespaces (as get_namespace_usage), TopPods (as get_pod_resource_stats, by CPU requests) and Health \
+                          (pending, orphaned and stuck pods; overpacked, cordoned and NotReady nodes). All use the requests basis. \
+                          Parameters: sections (optional list, default all) - the sections to include, \
+                          lang (optional string) - 'en' or 'es'. \
+                          Returns the included sections, each shaped like its tool's response, and one summary line per section. \
+                          Example: sections=['Capacity', 'Health'] → capacity and health only.")]
+    pub async fn get_full_report(
+        &self,
+        params: Parameters<FullReportParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let sections: BTreeSet<ReportSection> = match &params.0.sections {
+            Some(sections) if sections.is_empty() => return Ok(error_result("Sections cannot be empty".to_string())),
+            Some(sections) => sections.iter().copied().collect(),
+            None => ReportSection::ALL.into_iter().collect(),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_full_report_internal(&sections, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to build the full report: {}", e))),
+        }
+    }
+
+    /// Find pods held back by scheduling gates
+    #[tool(description = "Find pending pods held back by spec.schedulingGates. The scheduler ignores such pods until every gate is removed, \
+                          so get_cluster_capacity counts them in gated_pod_count instead of the pending demand. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns the gated pods with their gates and requests, the pod count per gate and the summed requests. \
+                          Example: 'batch/train-0' gated by 'example.com/quota' with 4 CPU requested.")]
+    pub async fn find_gated_pods(
+        &self,
+        params: Parameters<GatedPodsParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.find_gated_pods_internal(lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to find gated pods: {}", e))),
+        }
+    }
+
+    /// Project node load at full burst
+    #[tool(description = "Project each node's load if every pod used its full limits: sums the limits of the pods bound to each node \
+                          (the request where a container sets no limit) and compares them to allocatable, the overcommit risk per node. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns per node the summed limits, their percentage of allocatable, the projected use capped at allocatable \
+                          and the excess beyond it, sorted by the highest percentage, plus cluster totals. \
+                          Example: node 'worker-2' flagged with memory limits at 140% of allocatable, 9.6 GB over.")]
+    pub async fn get_burst_projection(
+        &self,
+        params: Parameters<BurstProjectionParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_burst_projection_internal(lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to project burst: {}", e))),
+        }
+    }
+
+    /// Get ResourceQuota usage
+    #[tool(description = "Report how much of each ResourceQuota's CPU, memory and pod limits is used. Scoped quotas \
+                          (spec.scopes or scopeSelector: Terminating, NotTerminating, BestEffort, NotBestEffort, PriorityClass, \
+                          CrossNamespacePodAffinity) only count the pods their scopes select, so the headroom is computed against the right pods. \
+                          Parameters: namespace (optional string) - only report the quotas of this namespace (blank means the server's DEFAULT_NAMESPACE), \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns per quota the scopes, the pods they select and hard, used and remaining amounts per resource, by highest utilization. \
+                          Example: quota 'batch/best-effort' scoped to BestEffort counts 1 of 3 pods, at 50% of pods.")]
+    pub async fn get_quota_usage(
+        &self,
+        params: Parameters<QuotaUsageParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let namespace = match params.0.namespace.as_deref() {
+            Some(namespace) => match resolve_namespace(namespace, self.config.default_namespace.clone()) {
+                Ok(namespace) => Some(namespace),
+                Err(e) => return Ok(error_result(e)),
+            },
+            None => None,
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_quota_usage_internal(namespace.as_deref(), lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get quota usage: {}", e))),
+        }
+    }
+
+    /// Get per-node pod density and packing efficiency
+    #[tool(description = "Report how well each node is packed: pods per node (and against its max pods), average pod size, \
+                          CPU and memory utilization from effective requests, and a packing efficiency averaging the two, highlighting \
+                          over- and under-packed nodes for rebalancing and node-size decisions. \
+                          Parameters: over_packed_percent (optional float, default 85) - flag nodes with CPU or memory utilization at or above it, \
+                          under_packed_percent (optional float, default 30) - flag nodes with CPU and memory utilization both below it, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns the nodes by packing efficiency, the flagged nodes and the cluster's packing efficiency. \
+                          Example: node 'worker-1' with 40 of 110 pods, 0.25 cores and 0.5 GB per pod, 91% CPU → over-packed.")]
+    pub async fn get_node_packing(
+        &self,
+        params: Parameters<NodePackingParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let over_packed_percent = params.0.over_packed_percent.unwrap_or(DEFAULT_OVER_PACKED_PERCENT);
+        let under_packed_percent = params.0.under_packed_percent.unwrap_or(DEFAULT_UNDER_PACKED_PERCENT);
+        if !(0.0..=100.0).contains(&over_packed_percent) || !(0.0..=100.0).contains(&under_packed_percent) {
+            return Ok(error_result("over_packed_percent and under_packed_percent must be between 0 and 100".to_string()));
+        }
+        if under_packed_percent >= over_packed_percent {
+            return Ok(error_result("under_packed_percent must be below over_packed_percent".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_node_packing_internal(over_packed_percent, under_packed_percent, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get node packing: {}", e))),
+        }
+    }
+
+    /// Find GPU nodes with idle GPUs but no CPU or memory to use them
+    #[tool(description = "Find stranded GPUs: idle GPUs on nodes whose CPU or memory is too exhausted to run another GPU pod, \
+                          the waste caused by CPU-only workloads crowding expensive GPU nodes. Per GPU node reports the idle GPUs, \
+                          CPU and memory utilization from effective requests, how many idle GPUs still fit and how many are stranded. \
+                          Parameters: cpu_cores_per_gpu (optional float) and memory_gb_per_gpu (optional float) - requests of a GPU pod per GPU \
+                          (default: the median of the running GPU pods, else each node's allocatable divided by its GPUs), \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: 'gpu-1' with 3 idle GPUs at 100% CPU → 3 GPUs stranded, short of cpu.")]
+    pub async fn find_stranded_gpus(
+        &self,
+        params: Parameters<StrandedGpusParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let cpu_cores_per_gpu = params.0.cpu_cores_per_gpu;
+        let memory_gb_per_gpu = params.0.memory_gb_per_gpu;
+        if [cpu_cores_per_gpu, memory_gb_per_gpu].into_iter().flatten().any(|v| !v.is_finite() || v < 0.0) {
+            return Ok(error_result("cpu_cores_per_gpu and memory_gb_per_gpu must not be negative".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.find_stranded_gpus_internal(cpu_cores_per_gpu, memory_gb_per_gpu, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to find stranded GPUs: {}", e))),
+        }
+    }
+
+    /// Get the share of pods declaring CPU and memory requests
+    #[tool(description = "Report request coverage as a governance metric: the percentage of pods, cluster-wide and per namespace, \
+                          whose containers all declare CPU and memory requests, plus the non-compliant pods per namespace. \
+                          Pods without requests are placed as if they needed nothing, so low coverage makes capacity figures unreliable. \
+                          Succeeded and Failed pods are skipped. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns the overall, CPU and memory coverage and the namespaces, least covered first. \
+                          Example: 45 of 50 pods request CPU and memory → 90% coverage, 'batch' has 5 non-compliant pods.")]
+    pub async fn get_request_coverage(
+        &self,
+        params: Parameters<RequestCoverageParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_request_coverage_internal(lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get request coverage: {}", e))),
+        }
+    }
+
+    /// Compare the pod request ratio with the node shape
+    #[tool(description = "Detect a systemic CPU-to-memory mismatch between workloads and nodes: compares the GB of memory per core \
+                          requested by all running pods with the GB per core the nodes offer, reports which resource ends up stranded and how much \
+                          once pods of that shape fill the cluster, and recommends a node family or a review of the pod requests. \
+                          Parameters: tolerance_percent (optional float, default 25) - deviation between the ratios tolerated before flagging, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: pods at 1 GB/core on 4 GB/core nodes → CPU runs out first, 75% of the memory stranded; add compute-optimized nodes.")]
+    pub async fn get_request_ratio_mismatch(
+        &self,
+        params: Parameters<RequestRatioMismatchParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let tolerance_percent = params.0.tolerance_percent.unwrap_or(DEFAULT_RATIO_TOLERANCE_PERCENT);
+        if !tolerance_percent.is_finite() || tolerance_percent < 0.0 {
+            return Ok(error_result("tolerance_percent must not be negative".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_request_ratio_mismatch_internal(tolerance_percent, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to compare request ratios: {}", e))),
+        }
+    }
+
+    /// Express capacity in reference pods
+    #[tool(description = "Express cluster capacity in 'standard pods' instead of cores and GB: given a reference pod size, \
+                          reports how many such pods the cluster and each node hold in total, how many pods' worth of requests are placed, \
+                          and how many more fit, counting by the binding resource (whichever of CPU and memory runs out first). \
+                          Parameters: cpu_cores (float) and memory_gb (float) - requests of the reference pod (one may be 0 to ignore that resource), \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: cpu_cores=1, memory_gb=2 → the cluster holds 48 pods, 30 placed, 18 more fit (CPU binds on 3 nodes).")]
+    pub async fn get_capacity_in_pod_units(
+        &self,
+        params: Parameters<CapacityInPodUnitsParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let CapacityInPodUnitsParams { cpu_cores, memory_gb, .. } = params.0;
+        if !cpu_cores.is_finite() || cpu_cores < 0.0 {
+            return Ok(error_result("CPU cores must be non-negative".to_string()));
+        }
+        if !memory_gb.is_finite() || memory_gb < 0.0 {
+            return Ok(error_result("Memory GB must be non-negative".to_string()));
+        }
+        if cpu_cores == 0.0 && memory_gb == 0.0 {
+            return Ok(error_result("CPU cores or memory GB must be positive".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_capacity_in_pod_units_internal(cpu_cores, memory_gb, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to express capacity in pod units: {}", e))),
+        }
+    }
+
+    /// Find pods whose requests reach a threshold
+    #[tool(description = "List every pod whose CPU or memory requests reach a threshold, e.g. for alerting on oversized pods. \
+                          Unlike the top-N rankings the result is complete: all matching pods, however many. \
+                          Effective requests are used (init containers, sidecars and pod overhead included); Succeeded and Failed pods are skipped. \
+                          Parameters: min_cpu_cores (optional float) and/or min_memory_gb (optional float) - at least one is required; \
+                          a pod matching either is listed, \
+                          namespace (optional string) - only search this namespace, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: min_cpu_cores=4 → every pod requesting 4 cores or more, with the thresholds it reaches.")]
+    pub async fn find_large_pods(
+        &self,
+        params: Parameters<FindLargePodsParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let FindLargePodsParams { min_cpu_cores, min_memory_gb, .. } = params.0;
+        if min_cpu_cores.is_none() && min_memory_gb.is_none() {
+            return Ok(error_result("Pass min_cpu_cores, min_memory_gb or both".to_string()));
+        }
+        if [min_cpu_cores, min_memory_gb].into_iter().flatten().any(|v| !v.is_finite() || v < 0.0) {
+            return Ok(error_result("min_cpu_cores and min_memory_gb must not be negative".to_string()));
+        }
+        let namespace = match params.0.namespace.as_deref() {
+            Some(namespace) => match resolve_namespace(namespace, self.config.default_namespace.clone()) {
+                Ok(namespace) => Some(namespace),
+                Err(e) => return Ok(error_result(e)),
+            },
+            None => None,
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.find_large_pods_internal(min_cpu_cores, min_memory_gb, namespace.as_deref(), lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to find large pods: {}", e))),
+        }
+    }
+
+    /// Explain how a pod's requests are accounted
+    #[tool(description = "Debug capacity numbers by showing, step by step, how one pod's effective request is computed: \
+                          each container's requests and limits as written and as parsed (after any in-place resize), \
+                          the app container sum, native sidecar additions, every init container phase and the largest one, \
+                          the pod overhead and the final effective request aggregated by the capacity tools. \
+                          Use it when the numbers disagree with kubectl describe node. \
+                          Parameters: name (string) - pod name, namespace (string) - defaults to the server's DEFAULT_NAMESPACE when blank, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: name='web-1', namespace='shop' → app 600m + sidecar 100m = 700m vs init 1 core → effective 1 core.")]
+    pub async fn explain_pod_accounting(
+        &self,
+        params: Parameters<PodAccountingParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        if params.0.name.trim().is_empty() {
+            return Ok(error_result("Pod name cannot be empty".to_string()));
+        }
+        let namespace = match resolve_namespace(&params.0.namespace, self.config.default_namespace.clone()) {
+            Ok(namespace) => namespace,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.explain_pod_accounting_internal(&params.0.name, &namespace, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to explain pod accounting: {}", e))),
+        }
+    }
+
+    /// Get nodes close to eviction thresholds
+    #[tool(description = "Find nodes whose actual memory or ephemeral storage usage is within a margin of allocatable, \
+                          the early warning before the kubelet starts evicting pods (e.g. OOM-driven evictions). \
+                          Memory comes from metrics-server (metrics.k8s.io) and ephemeral storage from the kubelet stats summary; \
+                          without them the tool still flags nodes reporting MemoryPressure or DiskPressure and explains what is missing. \
+                          Parameters: margin_percent (optional float, default 10) - flag usage at or above (100 - margin)% of allocatable, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns every node with its usage percentages, at-risk nodes first and then by the highest percentage. \
+                          Example: margin_percent=10 → node-a flagged with memory at 94.1% of allocatable.")]
+    pub async fn get_eviction_risk(
+        &self,
+        params: Parameters<EvictionRiskParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let margin_percent = params.0.margin_percent.unwrap_or(DEFAULT_EVICTION_MARGIN_PERCENT);
+        if !(0.0..100.0).contains(&margin_percent) {
+            return Ok(error_result("Margin percent must be at least 0 and below 100".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_eviction_risk_internal(margin_percent, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to compute eviction risk: {}", e))),
+        }
+    }
+
+    /// Get each namespace's share of the cluster's requests
+    #[tool(description = "Get each namespace's share of the cluster: its percentage of all CPU requests and of all memory requests, \
+                          plus a blended resource share (the average of the two), sorted by blended share (descending). \
+                          A no-config chargeback proxy for comparing teams without dollar pricing; the shares add up to 100%. \
+                          Succeeded and Failed pods never count. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: Returns namespace 'shop' with 62.5% of CPU, 37.5% of memory and a 50% blended share.")]
+    pub async fn get_namespace_share(
+        &self,
+        params: Parameters<NamespaceShareParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_namespace_share_internal(lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get namespace share: {}", e))),
+        }
+    }
+
+    /// Find pods with implausibly large or surprising effective requests
+    #[tool(description = "Find pods whose summed container requests are implausibly large, e.g. every container requesting a whole node's worth, \
+                          so the pod will never schedule: flags pods whose effective CPU or memory request is at least median_multiple times \
+                          the median pod's, and pods whose effective request is set by an init container rather than the app containers. \
+                          Effective requests include init containers, native sidecars and pod overhead; Succeeded and Failed pods are skipped. \
+                          Parameters: median_multiple (optional float, default 10) - multiple of the median pod that counts as an outlier, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns each flagged pod with its reasons, the per-container requests and the computed effective request. \
+                          Example: median_multiple=10 → pod 'batch/etl' requesting 24 cores, 48.0x the median pod.")]
+    pub async fn find_request_outliers(
+        &self,
+        params: Parameters<RequestOutliersParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let median_multiple = params.0.median_multiple.unwrap_or(DEFAULT_OUTLIER_MEDIAN_MULTIPLE);
+        if !median_multiple.is_finite() || median_multiple < 1.0 {
+            return Ok(error_result("Median multiple must be at least 1".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.find_request_outliers_internal(median_multiple, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to find request outliers: {}", e))),
+        }
+    }
+
+    /// Get cluster capacity by node state
+    #[tool(description = "Get the cluster capacity bucketed by node state: Ready (schedulable and Ready), Cordoned (unschedulable) \
+                          and NotReady (schedulable but not Ready), with CPU/memory capacity, allocatable, share of the cluster and node count per bucket. \
+                          Makes the capacity stuck on cordoned or broken nodes visible, the capacity other tools exclude from placement. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: Returns 10 Ready nodes with 40 cores, 1 Cordoned and 1 NotReady node with 8 cores stuck.")]
+    pub async fn get_capacity_by_node_state(
+        &self,
+        params: Parameters<CapacityByNodeStateParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_capacity_by_node_state_internal(lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get capacity by node state: {}", e))),
+        }
+    }
+
+    /// Get each namespace's headroom against its fair share of the cluster
+    #[tool(description = "Estimate how much more each namespace can grow before exceeding its fair share of the cluster. \
+                          The total CPU and memory of all nodes is split among the namespaces holding pods (plus any weighted ones) \
+                          in proportion to their weights; headroom is the fair share minus current requests, negative when over. \
+                          Succeeded and Failed pods never count. \
+                          Parameters: weights (optional map of namespace to positive number, default 1 each) - e.g. {\"prod\": 3} \
+                          gives prod three times the share of every other namespace (each must exist), \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns every namespace with its fair share, requests and headroom, most constrained first. \
+                          Example: two namespaces on 8 cores → 4 cores each; 'web' requesting 1 core has 3 cores of headroom.")]
+    pub async fn get_namespace_headroom(
+        &self,
+        params: Parameters<NamespaceHeadroomParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let weights = params.0.weights.clone().unwrap_or_default();
+        if weights.values().any(|w| !w.is_finite() || *w <= 0.0) {
+            return Ok(error_result("Namespace weights must be positive".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_namespace_headroom_internal(&weights, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get namespace headroom: {}", e))),
+        }
+    }
+
+    /// Recommend the smallest node that hosts a workload next to the cluster's DaemonSets
+    #[tool(description = "Compute the minimum node size needed to host a workload, e.g. before adding a node group. \
+                          The minimum allocatable is the workload plus one pod of every DaemonSet in the cluster; the minimum capacity \
+                          adds the per-node reserve for the kubelet and OS (node_reserved_cpu_cores / node_reserved_memory_gb settings). \
+                          Parameters: pod_spec (optional object) - a Pod spec whose effective requests (init containers, sidecars and overhead included) \
+                          size the workload, or cpu_cores and memory_gb (optional floats) instead, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns the workload, DaemonSet overhead, minimum allocatable, reserve, minimum capacity and the existing nodes already large enough. \
+                          Example: cpu_cores=2, memory_gb=4 with 0.5 cores / 1 GB of DaemonSets → at least 2.50 cores and 5.00 GB allocatable.")]
+    pub async fn recommend_node_size(
+        &self,
+        params: Parameters<RecommendNodeSizeParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let workload = match (&params.0.pod_spec, params.0.cpu_cores, params.0.memory_gb) {
+            (Some(spec), None, None) => match serde_json::from_value::<PodSpec>(spec.clone()) {
+                Ok(spec) => pod_effective_requests(&Pod { spec: Some(spec), ..Default::default() }),
+                Err(e) => return Ok(error_result(format!("Invalid pod_spec: {}", e))),
+            },
+            (None, Some(cpu_cores), Some(memory_gb)) => (cpu_cores, memory_gb),
+            _ => return Ok(error_result("Provide either pod_spec or both cpu_cores and memory_gb".to_string())),
+        };
+        if !workload.0.is_finite() || !workload.1.is_finite() || workload.0 < 0.0 || workload.1 < 0.0 {
+            return Ok(error_result("CPU and memory must be non-negative".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.recommend_node_size_internal(workload.0, workload.1, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to recommend node size: {}", e))),
+        }
+    }
+
+    /// Find pods bound to nodes that no longer exist
+    #[tool(description = "Find pods whose spec.nodeName points at a node that no longer exists, e.g. after abrupt node loss. \
+                          Such ghost pods still look scheduled but hold no real node's capacity; get_node_breakdown leaves them out \
+                          and reports how many there are. Succeeded and Failed pods are skipped. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns the orphaned pods with their missing node, phase and requests, the missing nodes and the summed requests. \
+                          Example: node 'worker-3' was deleted → 'shop/web-7' still bound to worker-3 with 500m CPU requested.")]
+    pub async fn find_orphaned_pods(
+        &self,
+        params: Parameters<OrphanedPodsParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.find_orphaned_pods_internal(lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to find orphaned pods: {}", e))),
+        }
+    }
+
+    /// Get namespace to node distribution
+    #[tool(description = "Get where each namespace's pods run. \
+                          Returns, per namespace, a map of node to pod count and aggregated CPU/memory requests, \
+                          plus the share of pods on the busiest node to spot namespaces concentrated on one node. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: Returns namespace 'web' with 3 pods on node-a and 1 pod on node-b.")]
+    pub async fn get_namespace_node_distribution(
+        &self,
+        params: Parameters<NamespaceNodeDistributionParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_namespace_node_distribution_internal(lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get namespace node distribution: {}", e))),
+        }
+    }
+
+    /// Find pods too large for any node
+    #[tool(description = "Find pods whose effective requests exceed what any single node can allocate. \
+                          Such pods will never schedule until their requests shrink or a larger node is added. \
+                          Effective requests include init containers, native sidecars and pod overhead. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: Returns a pod requesting 512 GB of memory when the largest node has 256 GB.")]
+    pub async fn find_unschedulable_by_size(
+        &self,
+        params: Parameters<UnschedulableBySizeParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.find_unschedulable_by_size_internal(lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to find unschedulable pods: {}", e))),
+        }
+    }
+
+    /// Get the pods on a node
+    #[tool(description = "List the pods running on a node, e.g. before cordoning or draining it. \
+                          Parameters: node_name (string), check_reschedulable (optional bool) - also bin-pack \
+                          the non-DaemonSet pods onto the free allocatable of the other schedulable nodes, \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Returns each pod's effective CPU/memory requests and, with the check, whether it fits elsewhere. \
+                          Example: node_name='worker-3', check_reschedulable=true")]
+    pub async fn get_pods_on_node(
+        &self,
+        params: Parameters<PodsOnNodeParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        if params.0.node_name.is_empty() {
+            return Ok(error_result("Node name cannot be empty".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.get_pods_on_node_internal(&params.0.node_name, params.0.check_reschedulable, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to get pods on node: {}", e))),
+        }
+    }
+
+    /// Forecast days until the cluster is full
+    #[tool(description = "Forecast how many days remain until the cluster reaches a utilization threshold. \
+                          Linearly extrapolates allocated CPU/memory (requests) growth from capacity snapshots \
+                          and reports the limiting resource. \
+                          Parameters: snapshots (optional array of {timestamp, total_cpu_cores, total_memory_gb, \
+                          allocated_cpu_cores, allocated_memory_gb}) - stored snapshots, defaults to the \
+                          trend buffer filled by this session's get_cluster_capacity calls; threshold_percent (optional float, default 90); \
+                          lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: threshold_percent=80 → 'cluster reaches 80% memory utilization in about 42.0 days'.")]
+    pub async fn forecast_capacity(
+        &self,
+        params: Parameters<ForecastCapacityParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        let threshold_percent = params.0.threshold_percent.unwrap_or(90.0);
+        if threshold_percent <= 0.0 || threshold_percent > 100.0 {
+            return Ok(error_result("Threshold percent must be between 0 and 100".to_string()));
+        }
+
+        let fresh = params.0.cache.fresh.unwrap_or(false);
+        let detail = params.0.explanation_detail;
+        match self.forecast_capacity_internal(params.0.snapshots, threshold_percent, lang, fresh).await {
+            Ok(result) => Ok(self.success_result(&messages::with_detail(result, detail, lang))),
+            Err(e) => Ok(error_result(format!("Failed to forecast capacity: {}", e))),
+        }
+    }
+
+    /// List server capabilities
+    #[tool(description = "List all tools exposed by this server with their descriptions and input JSON schemas. \
+                          Useful to introspect available functions from within a conversation. \
+                          Parameters: lang (optional string) - explanation language, 'en' (default) or 'es'. \
+                          Example: Returns get_cluster_capacity, check_resource_fit, ... with their parameters.")]
+    pub async fn list_capabilities(
+        &self,
+        params: Parameters<ListCapabilitiesParams>
+    ) -> Result<CallToolResult, McpError> {
+        let _timer = RequestTimer::new();
+        increment_requests();
+
+        let lang = match Lang::parse(params.0.lang.as_deref()) {
+            Ok(lang) => lang,
+            Err(e) => return Ok(error_result(e)),
+        };
+
+        Ok(self.success_result(&messages::with_detail(
+            self.list_capabilities_internal(lang),
+            params.0.explanation_detail,
+            lang,
+        )))
+    }
+}
+
+impl ServerHandler for ClusterInsights {
+    /// Route a tool call, collecting the diagnostics it reports into its result envelope. Successful
+    /// results are cached by tool and parameters for CACHE_TTL_SECONDS; `fresh` skips the lookup.
+    /// A hit is marked as served from the cache.
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        if self.config.disabled_tools.iter().any(|name| *name == request.name) {
+            return Err(McpError::invalid_params(
+                format!("tool '{}' is disabled on this server (DISABLED_TOOLS)", request.name),
+                None,
+            ));
+        }
+
+        let key = result_cache_key(&request.name, request.arguments.as_ref());
+        let fresh = request.arguments.as_ref()
+            .and_then(|arguments| arguments.get("fresh"))
+            .and_then(|fresh| fresh.as_bool())
+            .unwrap_or(false);
+        if let Some((cached, age)) = key.as_deref().filter(|_| !fresh).and_then(|key| self.results.get_with_age(key)) {
+            increment_requests();
+            if request.name == "get_cluster_capacity" {
+                self.observe_cached_capacity(request.arguments.as_ref(), &cached);
+            }
+            return Ok(cached_result(cached, age));
+        }
+
+        let call = ToolCallContext::new(self, request, context);
+        let result = diagnostics::scope(self.tool_router.call(call)).await?;
+        if let Some(key) = key.filter(|_| result.is_error != Some(true)) {
+            self.results.put(key, &result);
+        }
+        Ok(result)
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(self.tool_router.list_all()))
+    }
+
+    fn get_info(&self) -> ServerInfo {
+        // Read basic information from .env file (replaced by sync script during release)
+        let name = "cluster-insights-mcp-rs".to_string();
+        let version = "1.3.2".to_string();
+        let title = "Cluster Insights Engine MCP Server".to_string();
+        let website_url = "https://github.com/alpha-hack-program/cluster-insights-mcp-rs.git".to_string();
+
+        let mut info = ServerInfo {
+            instructions: Some(
+                "Kubernetes Cluster Insights providing resource analysis functions:\
+                 \n\n1. get_cluster_capacity - Get total cluster capacity, allocated resources, and availability\
+                 \n2. check_resource_fit - Check if specified resources can fit in the cluster\
+                 \n3. get_node_breakdown - Get detailed breakdown of each node's resources\
+                 \n4. get_namespace_usage - Get resource usage per namespace\
+                 \n5. get_pod_resource_stats - Get top pods by resource consumption\
+                 \n6. check_replica_capacity - Check if cluster can accommodate additional application replicas\
+                 \n7. list_capabilities - List all tools with their descriptions and input schemas\
+                 \n8. get_namespace_node_distribution - Get per-namespace pod distribution across nodes\
+                 \n9. find_unschedulable_by_size - Find pods too large for any single node\
+                 \n10. forecast_capacity - Estimate days until the cluster reaches a utilization threshold\
+                 \n11. get_pods_on_node - List a node's pods and whether they can be rescheduled elsewhere\
+                 \n12. get_usage_by_label - Get resource usage grouped by a pod label value\
+                 \n13. get_burst_risk - Find namespaces and pods whose limits far exceed their requests\
+                 \n14. get_image_distribution - List distinct container images and how many pods use each\
+                 \n15. get_overhead_breakdown - Split allocated resources into system overhead and application workloads\
+                 \n16. find_stuck_pods - Find pods stuck terminating that still hold node capacity\
+                 \n17. get_usage_by_workload_kind - Get resource usage grouped by top-level owner kind (Deployment, DaemonSet, ...)\
+                 \n18. check_n_minus_one - Check whether the cluster survives losing its largest node(s)\
+                 \n19. find_namespaces_without_limits - Find namespaces where many pods have no memory limit\
+                 \n20. node_acceptance_matrix - Check which candidate pod sizes each node can still accept\
+                 \n21. max_pods_of_size - Estimate how many more pods of one size fit, packed node by node\
+                 \n22. get_largest_pod_per_node - Find the pods with the highest CPU and memory requests on each node\
+                 \n23. get_stranded_capacity - Find free CPU or memory per node that balanced pods cannot use\
+                 \n24. find_overpacked_nodes - Find nodes whose pod requests exceed their allocatable\
+                 \n25. get_balance_report - Detect imbalanced node utilization and suggest rebalancing\
+                 \n26. get_pod_churn - Count recently created and deleted pods per namespace\
+                 \n27. get_gpu_report - Report total, requested and idle GPUs per node and namespace\
+                 \n28. get_usage_for_selector - Total requests, limits and pods matching a label selector across namespaces\
+                 \n29. get_replica_consistency - Find workloads whose replicas have differing requests or limits\
+                 \n30. check_deployment_scale - Check whether a Deployment can scale to N replicas and where they would go\
+                 \n31. get_eviction_risk - Flag nodes whose memory or ephemeral storage usage is close to allocatable\
+                 \n32. get_namespace_share - Each namespace's percentage of the cluster's CPU and memory requests\
+                 \n33. find_request_outliers - Flag pods with implausibly large effective requests or init containers setting them\
+                 \n34. get_capacity_by_node_state - Capacity of Ready, cordoned and not-ready nodes\
+                 \n35. get_namespace_headroom - How much each namespace can grow before exceeding its (weighted) fair share\
+                 \n36. recommend_node_size - Smallest node that hosts a pod spec next to the DaemonSets\
+                 \n37. find_orphaned_pods - Pods bound to nodes that no longer exist\
+                 \n38. check_cronjob_capacity - Check whether N concurrent Jobs of a CronJob fit\
+                 \n39. recommend_namespace_downsize - Compute how much each namespace's requests could shrink\
+                 \n40. get_full_report - Combine capacity, nodes, namespaces, top pods and health in one report\
+                 \n41. find_gated_pods - Pending pods held back by scheduling gates\
+                 \n42. get_burst_projection - Per node, whether full use of the pods' limits would exceed allocatable\
+                 \n43. get_quota_usage - ResourceQuota usage, counting only the pods scoped quotas select\
+                 \n44. get_node_packing - Pods per node, average pod size and packing efficiency, flagging over- and under-packed nodes\
+                 \n45. find_stranded_gpus - Idle GPUs on nodes whose CPU or memory is too exhausted to run another GPU pod\
+                 \n46. get_request_coverage - Percentage of pods declaring CPU and memory requests, cluster-wide and per namespace\
+                 \n47. get_request_ratio_mismatch - Compare the pods' memory-per-core request ratio with the nodes' and recommend a node shape\
+                 \n48. get_capacity_in_pod_units - Total, allocated and available capacity as counts of a reference pod, per node and cluster-wide\
+                 \n49. find_large_pods - Every pod whose CPU or memory requests reach a threshold, optionally in one namespace\
+                 \n50. explain_pod_accounting - Step-by-step breakdown of how one pod's effective request is computed\
+                 \n\nEvery function accepts an optional lang parameter ('en' or 'es') for the explanation text.\
+                 \nEvery function accepts an optional explanation_detail parameter: None, Brief (one-line summary) or Full (default).\
+                 \nEvery function that reads the cluster accepts an optional fresh parameter: true bypasses the cache and re-queries the cluster, \
+                 slower but guaranteed current (e.g. right after creating pods).\
+                 \n\nAll functions query live Kubernetes cluster data via kubeconfig.".into()
+            ),
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            server_info: rmcp::model::Implementation {
+                name: name,
+                version: version, 
+                title: Some(title), 
+                icons: None, 
+                website_url: Some(website_url) 
+            },
+            ..Default::default()
+        };
+        // The list above is static; name the tools this deployment removed from the router
+        if !self.config.disabled_tools.is_empty() {
+            if let Some(instructions) = info.instructions.as_mut() {
+                instructions.push_str(&format!("\n\nDisabled on this server: {}.", self.config.disabled_tools.join(", ")));
+            }
+        }
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+    use rmcp::model::{NumberOrString, ProgressToken};
+
+    fn test_pod(name: &str, namespace: &str, node: Option<&str>, cpu: &str, memory: &str) -> Pod {
+        serde_json::from_value(serde_json::json!({
+            "metadata": { "name": name, "namespace": namespace },
+            "spec": {
+                "nodeName": node,
+                "containers": [{
+                    "name": "main",
+                    "resources": { "requests": { "cpu": cpu, "memory": memory } }
+                }]
+            }
+        })).unwrap()
+    }
+
+    fn test_node(name: &str, cpu: &str, memory: &str) -> Node {
+        serde_json::from_value(serde_json::json!({
+            "metadata": { "name": name },
+            "status": {
+                "capacity": { "cpu": cpu, "memory": memory },
+                "allocatable": { "cpu": cpu, "memory": memory }
+            }
+        })).unwrap()
+    }
+
+    fn test_capacity(total_cpu: f64, total_memory: f64, allocated_cpu: f64, allocated_memory: f64) -> ClusterCapacityResponse {
+        ClusterCapacityResponse {
+            total_cpu_cores: total_cpu,
+            total_memory_gb: total_memory,
+            allocated_cpu_cores: allocated_cpu,
+            allocated_memory_gb: allocated_memory,
+            available_cpu_cores: total_cpu - allocated_cpu,
+            available_memory_gb: total_memory - allocated_memory,
+            node_count: 1,
+            pending_cpu_cores: 0.0,
+            pending_memory_gb: 0.0,
+            pending_pod_count: 0,
+            gated_pod_count: 0,
+            basis: ResourceBasis::Requests,
+            explanation: String::new(),
+            from_cache: false,
+            cache_age_seconds: None,
+            delta_allocated_cpu_cores: None,
+            delta_allocated_memory_gb: None,
+        }
+    }
+
+    fn replica_params(app_name: &str, replica_count: i32) -> CheckReplicaCapacityParams {
+        CheckReplicaCapacityParams {
+            app_name: app_name.to_string(),
+            namespace: "default".to_string(),
+            replica_count,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fit_tolerance_at_boundary() {
+        // 4.0 - 3.7 rounds to 0.2999999999999998 cores available
+        let capacity = test_capacity(4.0, 16.0, 3.7, 8.0);
+        assert!(capacity.available_cpu_cores < 0.3);
+
+        let fit = ClusterInsights::build_resource_fit_response(0.3, 8.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        assert!(fit.fits && fit.cpu_fits && fit.memory_fits);
+        assert!(!ClusterInsights::build_resource_fit_response(0.3, 8.0, &capacity, 0.0, Lang::En).fits);
+        assert!(!ClusterInsights::build_resource_fit_response(0.301, 8.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En).fits);
+
+        // Three replicas of 100m sum to 0.30000000000000004 cores
+        let pod = test_pod("web-1", "default", Some("node-1"), "100m", "1Gi");
+        let params = replica_params("web", 3);
+        let replicas = ClusterInsights::build_replica_capacity_response(&params, &pod, 1, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        assert!(replicas.fits);
+        assert!(!ClusterInsights::build_replica_capacity_response(&params, &pod, 1, &capacity, 0.0, Lang::En).fits);
+    }
+
+    #[test]
+    fn test_replica_capacity_uses_effective_requests() {
+        // Resized up to 1 core in status, with a 2 GiB init container and 128 MiB of overhead
+        let pod: Pod = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "web-1", "namespace": "shop" },
+            "spec": {
+                "nodeName": "node-a",
+                "overhead": { "memory": "128Mi" },
+                "initContainers": [{ "name": "warm-cache", "resources": { "requests": { "cpu": "100m", "memory": "2Gi" } } }],
+                "containers": [{ "name": "app", "resources": { "requests": { "cpu": "500m", "memory": "1Gi" } } }]
+            },
+            "status": {
+                "containerStatuses": [{
+                    "name": "app", "image": "web", "imageID": "", "ready": true, "restartCount": 0,
+                    "resources": { "requests": { "cpu": "1", "memory": "1Gi" } }
+                }]
+            }
+        })).unwrap();
+        let capacity = test_capacity(8.0, 32.0, 0.0, 0.0);
+
+        let response = ClusterInsights::build_replica_capacity_response(&replica_params("web", 3), &pod, 1, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        assert_eq!((response.cpu_per_replica_cores, response.memory_per_replica_gb), pod_effective_requests(&pod));
+        assert_eq!((response.cpu_per_replica_cores, response.memory_per_replica_gb), (1.0, 2.125));
+        assert_eq!((response.total_cpu_required_cores, response.total_memory_required_gb), (3.0, 6.375));
+        assert_eq!(response.basis, ResourceBasis::Requests);
+
+        // The response is labeled with the basis the capacity counted existing pods by
+        let by_limits = ClusterCapacityResponse { basis: ResourceBasis::Limits, ..capacity };
+        let response = ClusterInsights::build_replica_capacity_response(&replica_params("web", 3), &pod, 1, &by_limits, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        assert_eq!(response.basis, ResourceBasis::Limits);
+    }
+
+    #[test]
+    fn test_format_cpu_and_memory() {
+        assert_eq!(format_cpu(0.05), "50m");
+        assert_eq!(format_cpu(2.5), "2.50 cores");
+        assert_eq!(format_memory_gb(0.0625), "64 MiB");
+        assert_eq!(format_memory_gb(16.0), "16.00 GB");
+    }
+
+    #[test]
+    fn test_replica_capacity_explanation_small_pod() {
+        let pod = test_pod("tiny-1", "default", Some("node-1"), "50m", "64Mi");
+        let capacity = test_capacity(8.0, 32.0, 2.0, 8.0);
+        let params = replica_params("tiny", 3);
+        let response = ClusterInsights::build_replica_capacity_response(&params, &pod, 1, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+
+        assert!(response.fits);
+        assert!(response.explanation.contains("CPU per replica: 50m"));
+        assert!(response.explanation.contains("Memory per replica: 64 MiB"));
+        assert!(response.explanation.contains("CPU: 150m"));
+        assert!(!response.explanation.contains("0.000"));
+    }
+
+    #[test]
+    fn test_explanation_language() {
+        assert_eq!(Lang::parse(None), Ok(Lang::En));
+        assert_eq!(Lang::parse(Some("ES")), Ok(Lang::Es));
+        assert!(Lang::parse(Some("fr")).is_err());
+
+        let capacity = test_capacity(8.0, 32.0, 2.0, 8.0);
+        let english = messages::cluster_capacity(Lang::En, &capacity);
+        let spanish = messages::cluster_capacity(Lang::Es, &capacity);
+        assert!(english.starts_with("Cluster has 1 nodes."));
+        assert!(spanish.starts_with("El clúster tiene 1 nodos."));
+
+        let fit = ClusterInsights::build_resource_fit_response(1.0, 1.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::Es);
+        assert!(fit.explanation.starts_with("Los recursos CABEN"));
+
+        let pods = vec![test_pod("web-1", "default", Some("node-a"), "1", "1Gi")];
+        let quota = ClusterInsights::build_quota_usage(&[], &pods, Lang::Es);
+        assert_eq!(quota.explanation, "No se encontró ninguna ResourceQuota.");
+        let coverage = ClusterInsights::build_request_coverage(&pods, Lang::Es);
+        let brief = messages::with_detail(coverage, ExplanationDetail::Brief, Lang::Es);
+        assert!(brief.explanation.starts_with("El 100.0% de 1 pods declara"));
+    }
+
+    #[test]
+    fn test_forecast_capacity_two_snapshots() {
+        let snapshot = |timestamp: &str, cpu: f64, memory: f64| CapacitySnapshot {
+            timestamp: timestamp.to_string(),
+            total_cpu_cores: 100.0,
+            total_memory_gb: 400.0,
+            allocated_cpu_cores: cpu,
+            allocated_memory_gb: memory,
+        };
+        let snapshots = vec![
+            snapshot("2025-01-11T00:00:00Z", 60.0, 200.0),
+            snapshot("2025-01-01T00:00:00Z", 50.0, 180.0),
+        ];
+
+        let forecast = ClusterInsights::build_capacity_forecast(&snapshots, 90.0, Lang::En).unwrap();
+        assert_eq!(forecast.snapshots_used, 2);
+        assert!((forecast.cpu_growth_cores_per_day - 1.0).abs() < 1e-9);
+        assert!((forecast.memory_growth_gb_per_day - 2.0).abs() < 1e-9);
+        // CPU: (90 - 60) / 1 = 30 days; memory: (360 - 200) / 2 = 80 days
+        assert_eq!(forecast.days_until_full, Some(30.0));
+        assert_eq!(forecast.limiting_resource.as_deref(), Some("cpu"));
+
+        assert!(ClusterInsights::build_capacity_forecast(&snapshots[..1], 90.0, Lang::En).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_watch_mode_reflects_updates() {
+        use kube::runtime::{reflector, watcher};
+
+        let (nodes, mut node_writer) = reflector::store::<Node>();
+        let (pods, mut pod_writer) = reflector::store::<Pod>();
+        node_writer.apply_watcher_event(&watcher::Event::Apply(test_node("node-a", "4", "16Gi")));
+        pod_writer.apply_watcher_event(&watcher::Event::Apply(test_pod("web-1", "default", Some("node-a"), "1", "2Gi")));
+        let mut cluster_insights = ClusterInsights::new();
+        cluster_insights.watch = Some(WatchStores::new(nodes, pods));
+
+        let before = cluster_insights.get_cluster_capacity_internal(ResourceBasis::Requests, &[], Lang::En, false).await.unwrap();
+        assert_eq!(before.allocated_cpu_cores, 1.0);
+
+        pod_writer.apply_watcher_event(&watcher::Event::Apply(test_pod("web-1", "default", Some("node-a"), "2", "2Gi")));
+
+        let after = cluster_insights.get_cluster_capacity_internal(ResourceBasis::Requests, &[], Lang::En, false).await.unwrap();
+        assert_eq!(after.allocated_cpu_cores, 2.0);
+    }
+
+    /// Client backed by a fake API server with one node, one namespace and one pod, recording requested paths
+    fn mock_client() -> (Client, Arc<Mutex<Vec<String>>>) {
+        let requested_paths = Arc::new(Mutex::new(Vec::<String>::new()));
+        let recorder = requested_paths.clone();
+        let service = tower::service_fn(move |request: http::Request<kube::client::Body>| {
+            let recorder = recorder.clone();
+            async move {
+                let path = request.uri().path().to_string();
+                recorder.lock().unwrap().push(path.clone());
+                let body = match path.as_str() {
+                    "/api/v1/nodes" => serde_json::json!({
+                        "apiVersion": "v1", "kind": "NodeList", "metadata": {},
+                        "items": [test_node("node-a", "4", "16Gi")],
+                    }),
+                    "/api/v1/namespaces" => serde_json::json!({
+                        "apiVersion": "v1", "kind": "NamespaceList", "metadata": {},
+                        "items": [{ "metadata": { "name": "default" } }],
+                    }),
+                    _ => serde_json::json!({
+                        "apiVersion": "v1", "kind": "PodList", "metadata": {},
+                        "items": [test_pod("web-1", "default", Some("node-a"), "1", "2Gi")],
+                    }),
+                };
+                let body = kube::client::Body::from(serde_json::to_vec(&body).unwrap());
+                Ok::<_, std::convert::Infallible>(http::Response::new(body))
+            }
+        });
+
+        (Client::new(service, "default"), requested_paths)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_cluster_state_skips_unrequested_sets() {
+        let (client, requested_paths) = mock_client();
+        let cluster_insights = ClusterInsights::with_client(client, Duration::ZERO);
+
+        let options = ClusterStateOptions { nodes: true, namespaces: true, fresh: true, ..Default::default() };
+        let state = cluster_insights.fetch_cluster_state(options).await.unwrap();
+        assert_eq!(state.nodes.len(), 1);
+        assert_eq!(state.namespaces, ["default"]);
+        assert!(state.pods.is_empty());
+        assert!(state.pod_usage.is_empty());
+        let mut paths = requested_paths.lock().unwrap().clone();
+        paths.sort();
+        assert_eq!(paths, ["/api/v1/namespaces", "/api/v1/nodes"]);
+
+        requested_paths.lock().unwrap().clear();
+        let options = ClusterStateOptions { pods: true, fresh: true, ..Default::default() };
+        let state = cluster_insights.fetch_cluster_state(options).await.unwrap();
+        assert_eq!(state.pods.len(), 1);
+        assert!(state.nodes.is_empty());
+        assert_eq!(*requested_paths.lock().unwrap(), ["/api/v1/pods"]);
+    }
+
+    #[tokio::test]
+    async fn test_namespace_usage_does_not_list_nodes() {
+        let (client, requested_paths) = mock_client();
+        let cluster_insights = ClusterInsights::with_client(client, Duration::ZERO);
+        let result = cluster_insights.get_namespace_usage(Parameters(NamespaceUsageParams::default())).await.unwrap();
+        assert_eq!(envelope_of(&result).status, "ok");
+
+        let paths = requested_paths.lock().unwrap();
+        assert!(paths.iter().any(|p| p == "/api/v1/namespaces"));
+        assert!(paths.iter().any(|p| p.ends_with("/pods")));
+        assert!(!paths.iter().any(|p| p.contains("/nodes")), "unexpected node request in {:?}", paths);
+    }
+
+    #[tokio::test]
+    async fn test_pod_pagination_reports_progress() {
+        let service = tower::service_fn(|request: http::Request<kube::client::Body>| async move {
+            let query = request.uri().query().unwrap_or_default().to_string();
+            assert!(query.contains("limit=500"), "unpaginated pod list: {}", query);
+            let (name, metadata) = if query.contains("continue=page-2") {
+                ("web-2", serde_json::json!({}))
+            } else {
+                ("web-1", serde_json::json!({ "continue": "page-2", "remainingItemCount": 1 }))
+            };
+            let body = serde_json::json!({
+                "apiVersion": "v1", "kind": "PodList", "metadata": metadata,
+                "items": [test_pod(name, "default", Some("node-a"), "1", "2Gi")],
+            });
+            let body = kube::client::Body::from(serde_json::to_vec(&body).unwrap());
+            Ok::<_, std::convert::Infallible>(http::Response::new(body))
+        });
+        let cluster_insights = ClusterInsights::with_client(Client::new(service, "default"), Duration::ZERO);
+        let (progress, mut notifications) = Progress::channel(ProgressToken(NumberOrString::Number(7)));
+
+        let pods = cluster_insights.list_all_pods(true, Some(&progress)).await.unwrap();
+        assert_eq!(pods.len(), 2);
+
+        drop(progress);
+        let mut reported = Vec::new();
+        while let Some(notification) = notifications.recv().await {
+            assert_eq!(notification.progress_token, ProgressToken(NumberOrString::Number(7)));
+            reported.push((notification.progress, notification.total));
+        }
+        assert_eq!(reported, vec![(1.0, Some(2.0)), (2.0, None)]);
+    }
+
+    #[tokio::test]
+    async fn test_pod_stats_fall_back_when_metrics_keep_failing() {
+        let metrics_calls = Arc::new(Mutex::new(0));
+        let counter = metrics_calls.clone();
+        let service = tower::service_fn(move |request: http::Request<kube::client::Body>| {
+            let counter = counter.clone();
+            async move {
+                let response = if request.uri().path().starts_with("/apis/metrics.k8s.io") {
+                    *counter.lock().unwrap() += 1;
+                    let status = serde_json::json!({
+                        "apiVersion": "v1", "kind": "Status", "status": "Failure",
+                        "message": "the server is currently unable to handle the request",
+                        "reason": "ServiceUnavailable", "code": 503,
+                    });
+                    http::Response::builder()
+                        .status(503)
+                        .body(kube::client::Body::from(serde_json::to_vec(&status).unwrap()))
+                        .unwrap()
+                } else {
+                    let pods = serde_json::json!({
+                        "apiVersion": "v1", "kind": "PodList", "metadata": {},
+                        "items": [test_pod("web-1", "default", Some("node-a"), "1", "2Gi")],
+                    });
+                    http::Response::new(kube::client::Body::from(serde_json::to_vec(&pods).unwrap()))
+                };
+                Ok::<_, std::convert::Infallible>(response)
+            }
+        });
+        let cluster_insights = ClusterInsights::with_client(Client::new(service, "default"), Duration::ZERO);
+
+        let response = cluster_insights
+            .get_pod_resource_stats_internal(&PodResourceStatsParams {
+                sort_by: PodSortBy::CpuUsage,
+                cache: CacheParams { fresh: Some(true) },
+                ..Default::default()
+            }, Lang::En, None)
+            .await
+            .unwrap();
+        assert_eq!(*metrics_calls.lock().unwrap(), METRICS_ATTEMPTS);
+        assert_eq!(response.metrics_available, Some(false));
+        assert!(response.warning.unwrap().contains("sorted by CPU requests"));
+        assert_eq!(response.top_pods[0].name, "web-1");
+        assert_eq!(response.top_pods[0].cpu_requests_millicores, 1000);
+        assert_eq!(response.top_pods[0].cpu_usage_millicores, None);
+    }
+
+    #[tokio::test]
+    async fn test_cluster_capacity_reports_cache_age() {
+        let (client, _) = mock_client();
+        let cluster_insights = ClusterInsights::with_client(client, Duration::from_secs(3600));
+        let fetched_at = Instant::now() - Duration::from_secs(5);
+        *cluster_insights.cache.nodes.entry.lock().unwrap() = Some((fetched_at, vec![test_node("node-a", "4", "16Gi")]));
+        *cluster_insights.cache.pods.entry.lock().unwrap() = Some((fetched_at, vec![]));
+
+        let cached = cluster_insights.get_cluster_capacity_internal(ResourceBasis::Requests, &[], Lang::En, false).await.unwrap();
+        let live = cluster_insights.get_cluster_capacity_internal(ResourceBasis::Requests, &[], Lang::En, true).await.unwrap();
+        assert!(!live.from_cache);
+        assert_eq!(live.cache_age_seconds, None);
+
+        assert!(cached.from_cache);
+        assert!(cached.cache_age_seconds.unwrap() >= 5);
+        assert_eq!(cached.allocated_cpu_cores, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_capacity_delta_since_previous_call() {
+        let (client, _) = mock_client();
+        let cluster_insights = ClusterInsights::with_client(client, Duration::ZERO);
+        let nodes = vec![test_node("node-a", "4", "16Gi")];
+        let mut pods = vec![test_pod("web-1", "shop", Some("node-a"), "1", "2Gi")];
+
+        // The first call of a session has nothing to compare with
+        let mut first = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        cluster_insights.observe_capacity(&mut first, true, Lang::En);
+        assert_eq!((first.delta_allocated_cpu_cores, first.delta_allocated_memory_gb), (None, None));
+        assert!(first.explanation.ends_with("No previous call in this session to compare with."));
+
+        pods.push(test_pod("web-2", "shop", Some("node-a"), "500m", "1Gi"));
+        let mut second = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        cluster_insights.observe_capacity(&mut second, true, Lang::En);
+        assert_eq!(second.delta_allocated_cpu_cores, Some(0.5));
+        assert_eq!(second.delta_allocated_memory_gb, Some(1.0));
+        assert!(second.explanation.ends_with("Allocation changed by +0.50 CPU cores and +1.00 GB memory since the previous call."));
+
+        // Without include_delta the call is still observed, just not reported
+        pods.pop();
+        let mut third = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        cluster_insights.observe_capacity(&mut third, false, Lang::En);
+        assert_eq!(third.delta_allocated_cpu_cores, None);
+        let mut fourth = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        cluster_insights.observe_capacity(&mut fourth, true, Lang::En);
+        assert_eq!(fourth.delta_allocated_cpu_cores, Some(0.0));
+
+        // Another session starts from scratch
+        let (client, _) = mock_client();
+        let other_session = ClusterInsights::with_client(client, Duration::ZERO);
+        let mut other = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        other_session.observe_capacity(&mut other, true, Lang::En);
+        assert_eq!(other.delta_allocated_cpu_cores, None);
+
+        // Trend snapshots stay with the session that recorded them
+        record_snapshot(&cluster_insights.trend, &fourth);
+        assert_eq!(cluster_insights.trend.lock().unwrap().len(), 1);
+        assert!(other_session.trend.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cached_capacity_is_still_observed() {
+        let (client, _) = mock_client();
+        let cluster_insights = ClusterInsights::with_client(client, Duration::ZERO);
+        let cached = cluster_insights.get_cluster_capacity(Parameters(ClusterCapacityParams::default())).await.unwrap();
+        assert_eq!(cluster_insights.trend.lock().unwrap().len(), 1);
+
+        // A hit served from the result cache joins the trend and becomes the previous call too
+        cluster_insights.last_capacity.lock().unwrap().take();
+        cluster_insights.observe_cached_capacity(None, &cached);
+        assert_eq!(cluster_insights.trend.lock().unwrap().len(), 2);
+        assert!(cluster_insights.last_capacity.lock().unwrap().is_some());
+
+        // A what-if without some namespaces is neither
+        let what_if = serde_json::json!({ "exclude_namespaces": ["default"] });
+        cluster_insights.last_capacity.lock().unwrap().take();
+        cluster_insights.observe_cached_capacity(what_if.as_object(), &cached);
+        assert_eq!(cluster_insights.trend.lock().unwrap().len(), 2);
+        assert!(cluster_insights.last_capacity.lock().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fresh_bypasses_cache() {
+        let (client, requested_paths) = mock_client();
+        let cluster_insights = ClusterInsights::with_client(client, Duration::from_secs(3600));
+        let node_requests = || requested_paths.lock().unwrap().iter().filter(|p| *p == "/api/v1/nodes").count();
+
+        // Nodes come from the list cache within the TTL
+        cluster_insights.get_cluster_capacity(Parameters(ClusterCapacityParams::default())).await.unwrap();
+        cluster_insights.get_cluster_capacity(Parameters(ClusterCapacityParams::default())).await.unwrap();
+        assert_eq!(node_requests(), 1);
+
+        // fresh is a top-level parameter, flattened in from CacheParams
+        let fresh: ClusterCapacityParams = serde_json::from_value(serde_json::json!({ "fresh": true })).unwrap();
+        assert_eq!(fresh.cache.fresh, Some(true));
+        let result = cluster_insights.get_cluster_capacity(Parameters(fresh)).await.unwrap();
+        assert_eq!(envelope_of(&result).data.unwrap()["total_cpu_cores"], 4.0);
+        assert_eq!(node_requests(), 2);
+    }
+
+    #[test]
+    fn test_result_cache_keys_by_tool_and_params() {
+        let args = |value: serde_json::Value| value.as_object().cloned();
+        let key_a = result_cache_key("get_namespace_usage", args(serde_json::json!({ "namespaces": ["a"] })).as_ref()).unwrap();
+        let key_b = result_cache_key("get_namespace_usage", args(serde_json::json!({ "namespaces": ["b"] })).as_ref()).unwrap();
+        assert_ne!(key_a, key_b);
+
+        // Key order, null members and fresh do not change the key; the tool name does
+        let reordered = args(serde_json::json!({ "lang": "en", "namespaces": ["a"], "basis": null }));
+        let with_lang = result_cache_key("get_namespace_usage", args(serde_json::json!({ "namespaces": ["a"], "lang": "en", "fresh": true })).as_ref());
+        assert_eq!(result_cache_key("get_namespace_usage", reordered.as_ref()), with_lang);
+        assert_ne!(result_cache_key("get_burst_risk", args(serde_json::json!({ "namespaces": ["a"] })).as_ref()).unwrap(), key_a);
+        assert_eq!(result_cache_key("get_cluster_capacity", None), result_cache_key("get_cluster_capacity", args(serde_json::json!({})).as_ref()));
+        assert_eq!(result_cache_key("forecast_capacity", None), None);
+        assert_eq!(result_cache_key("get_cluster_capacity", args(serde_json::json!({ "include_delta": true })).as_ref()), None);
+
+        let cache = ResultCache::new(Duration::from_secs(3600), 2);
+        cache.put(key_a.clone(), &"usage of a".to_string());
+        assert_eq!(cache.get(&key_a).as_deref(), Some("usage of a"));
+        assert_eq!(cache.get(&key_b), None);
+        std::thread::sleep(Duration::from_millis(1));
+        cache.put(key_b.clone(), &"usage of b".to_string());
+        assert_eq!(cache.get(&key_a).as_deref(), Some("usage of a"));
+        assert_eq!(cache.get(&key_b).as_deref(), Some("usage of b"));
+
+        // A full cache evicts the entry closest to expiry
+        cache.put("third".to_string(), &"third".to_string());
+        assert_eq!(cache.get(&key_a), None);
+        assert_eq!(cache.get(&key_b).as_deref(), Some("usage of b"));
+
+        let disabled = ResultCache::new(Duration::ZERO, 2);
+        disabled.put(key_a.clone(), &"usage of a".to_string());
+        assert_eq!(disabled.get(&key_a), None);
+    }
+
+    #[test]
+    fn test_result_cache_concurrent_access() {
+        let cache = Arc::new(ResultCache::new(Duration::from_secs(3600), RESULT_CACHE_CAPACITY));
+        let threads: Vec<_> = (0..8)
+            .map(|thread| {
+                let cache = cache.clone();
+                std::thread::spawn(move || {
+                    for i in 0..20 {
+                        let key = format!("tool:{}:{}", thread, i);
+                        cache.put(key.clone(), &i);
+                        assert_eq!(cache.get(&key), Some(i));
                     }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(cache.len(), 160);
+    }
+
+    #[tokio::test]
+    async fn test_builder_shares_caches_across_sessions() {
+        let builder = ClusterInsights::builder().config(ServerConfig { cache_ttl_seconds: 60, ..Default::default() });
+        let first = builder.clone().build().unwrap();
+        let second = builder.clone().build().unwrap();
+        assert!(Arc::ptr_eq(&first.cache, &second.cache));
+        assert!(Arc::ptr_eq(&first.results, &second.results));
+
+        // A new configuration starts new caches, and an injected client keeps them per instance
+        let reconfigured = builder.clone().config(ServerConfig::default()).build().unwrap();
+        assert!(!Arc::ptr_eq(&first.cache, &reconfigured.cache));
+        let with_client = builder.client(mock_client().0);
+        let (first, second) = (with_client.clone().build().unwrap(), with_client.build().unwrap());
+        assert!(!Arc::ptr_eq(&first.cache, &second.cache));
+        assert!(!Arc::ptr_eq(&first.results, &second.results));
+    }
+
+    #[test]
+    fn test_cached_result_reports_its_age() {
+        let capacity = test_capacity(8.0, 32.0, 2.0, 8.0);
+        let envelope = envelope_of(&cached_result(success_result_within(&capacity, 0, 0), Duration::from_secs(7)));
+        let data = envelope.data.unwrap();
+        assert_eq!(data["from_cache"], true);
+        assert_eq!(data["cache_age_seconds"], 7);
+
+        // Lists already 5 s old when the result was cached are 12 s old on the hit
+        let listed = ClusterCapacityResponse { from_cache: true, cache_age_seconds: Some(5), ..capacity };
+        let data = envelope_of(&cached_result(success_result_within(&listed, 0, 0), Duration::from_secs(7))).data.unwrap();
+        assert_eq!(data["cache_age_seconds"], 12);
+
+        // Results without freshness fields come back unchanged
+        let usage = success_result_within(&serde_json::json!({ "namespaces": [] }), 0, 0);
+        let text = usage.content[0].as_text().unwrap().text.clone();
+        assert_eq!(cached_result(usage, Duration::from_secs(7)).content[0].as_text().unwrap().text, text);
+    }
+
+    fn envelope_of(result: &CallToolResult) -> ToolEnvelope<serde_json::Value> {
+        let text = &result.content[0].as_text().expect("text content").text;
+        serde_json::from_str(text).expect("tool output is an envelope")
+    }
+
+    #[test]
+    fn test_oversized_node_breakdown_is_truncated() {
+        let nodes: Vec<Node> = (0..40).map(|i| test_node(&format!("node-{:02}", i), "4", "16Gi")).collect();
+        let node_infos = ClusterInsights::build_node_infos(&nodes, &[], ResourceBasis::Requests, None);
+        let breakdown = ClusterInsights::build_node_breakdown(node_infos, NodeSortBy::Name, 0, None, ResourceBasis::Requests, 0, Lang::En);
+
+        let envelope = envelope_of(&success_result_within(&breakdown, 0, 0));
+        assert!(!envelope.truncated);
+        assert_eq!(envelope.truncation_note, None);
+        assert_eq!(envelope.data.unwrap()["nodes"].as_array().unwrap().len(), 40);
+
+        let envelope = envelope_of(&success_result_within(&breakdown, 10, 0));
+        assert!(envelope.truncated);
+        assert!(envelope.truncation_note.unwrap().contains("nodes (40 → 10). Narrow the query"));
+        let data = envelope.data.unwrap();
+        assert_eq!(data["nodes"].as_array().unwrap().len(), 10);
+        assert_eq!(data["nodes"][0]["name"], "node-00");
+        assert_eq!(data["total_nodes"], 40);
+
+        // The byte limit halves the list until the response fits
+        let result = success_result_within(&breakdown, 0, 4000);
+        assert!(result.content[0].as_text().unwrap().text.len() <= 4000);
+        let envelope = envelope_of(&result);
+        assert!(envelope.truncated);
+        let kept = envelope.data.unwrap()["nodes"].as_array().unwrap().len();
+        assert!(kept > 0 && kept < 40);
+        assert!(envelope.truncation_note.unwrap().contains(&format!("nodes (40 → {})", kept)));
+    }
+
+    #[test]
+    fn test_node_breakdown_pages_within_response_limits() {
+        let nodes: Vec<Node> = (0..25).map(|i| test_node(&format!("node-{:02}", i), "4", "16Gi")).collect();
+        let node_infos = ClusterInsights::build_node_infos(&nodes, &[], ResourceBasis::Requests, None);
+        let params = NodeBreakdownParams { limit: Some(10), ..Default::default() };
+
+        // The item cap shortens each page instead of truncating it, so offsets skip nothing
+        let config = ServerConfig { max_response_items: 4, ..Default::default() };
+        let mut seen = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = ClusterInsights::build_node_breakdown_within(node_infos.clone(), &nodes, &params, offset, 0, &config, Lang::En);
+            let envelope = envelope_of(&success_result_within(&page, config.max_response_items, config.max_response_bytes));
+            assert!(!envelope.truncated);
+            seen.extend(page.nodes.iter().map(|n| n.name.clone()));
+            match page.next_offset {
+                Some(next) => offset = next,
+                None => break,
+            }
+        }
+        assert_eq!(seen, node_infos.iter().map(|n| n.name.clone()).collect::<Vec<_>>());
+
+        // So does the byte limit, for cursors too
+        let config = ServerConfig { max_response_bytes: 3000, ..Default::default() };
+        let mut node_infos = node_infos;
+        let mut seen = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let offset = cursor.as_deref().map_or(0, |cursor| cursor_offset(&mut node_infos, NodeSortBy::Name, cursor).unwrap());
+            let page = ClusterInsights::build_node_breakdown_within(node_infos.clone(), &nodes, &params, offset, 0, &config, Lang::En);
+            let result = success_result_within(&page, config.max_response_items, config.max_response_bytes);
+            assert!(!envelope_of(&result).truncated);
+            assert!(page.nodes.len() < 10);
+            seen.extend(page.nodes.iter().map(|n| n.name.clone()));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(seen, node_infos.iter().map(|n| n.name.clone()).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_unparseable_node_capacity_produces_diagnostic() {
+        let nodes = vec![test_node("node-a", "lots", "16Gi"), test_node("node-b", "4", "16Gi")];
+
+        let result = diagnostics::scope(async {
+            let capacity = ClusterInsights::compute_cluster_capacity(&nodes, &[], ResourceBasis::Requests, None);
+            assert_eq!(capacity.total_cpu_cores, 4.0);
+            success_result_within(&capacity, 0, 0)
+        }).await;
+        let envelope = envelope_of(&result);
+        assert_eq!(envelope.diagnostics.len(), 1);
+        assert_eq!(envelope.diagnostics[0].level, diagnostics::DiagnosticLevel::Warning);
+        assert_eq!(envelope.diagnostics[0].code, "invalid_quantity");
+        assert!(envelope.diagnostics[0].message.contains("'lots'"));
+
+        // Nothing is carried over to the next call, and a clean result has no diagnostics field
+        let clean = diagnostics::scope(async { success_result_within(&1, 0, 0) }).await;
+        let text = &clean.content[0].as_text().unwrap().text;
+        assert!(!text.contains("diagnostics"));
+    }
+
+    #[test]
+    fn test_pod_stats_json_lines() {
+        let pods = vec![
+            test_pod("web-1", "shop", Some("node-a"), "1", "2Gi"),
+            test_pod("web-2", "shop", Some("node-a"), "500m", "1Gi"),
+            test_pod("db-0", "data", Some("node-b"), "2", "8Gi"),
+        ];
+        let response = ClusterInsights::build_pod_resource_stats(&pods, false, false, PodSortBy::CpuRequests, None, Lang::En);
+
+        let result = jsonl_result_within(&response.top_pods, 0, 0);
+        let text = &result.content[0].as_text().unwrap().text;
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), pods.len());
+        for line in &lines {
+            let pod: PodResourceInfo = serde_json::from_str(line).unwrap();
+            assert!(!pod.name.is_empty());
+        }
+        assert!(lines[0].contains("\"db-0\""));
+        assert_eq!(to_jsonl::<PodResourceInfo>(&[]).unwrap(), "");
+
+        // The item limit applies to the lines too, with the marker on a last line of its own
+        let result = jsonl_result_within(&response.top_pods, 2, 0);
+        let text = &result.content[0].as_text().unwrap().text;
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].contains("\"web-1\""));
+        let marker: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!(marker["truncated"], true);
+        assert!(marker["truncation_note"].as_str().unwrap().contains("data (3 → 2)"));
+    }
+
+    #[tokio::test]
+    async fn test_tool_result_envelope() {
+        let ok = success_result_within(&test_capacity(8.0, 32.0, 2.0, 8.0), 0, 0);
+        let envelope = envelope_of(&ok);
+        assert_eq!(ok.is_error, Some(false));
+        assert_eq!(envelope.status, "ok");
+        assert_eq!(envelope.data.unwrap()["total_cpu_cores"], 8.0);
+        assert!(envelope.error.is_none());
+
+        let cluster_insights = ClusterInsights::new();
+        let error = cluster_insights.check_resource_fit(Parameters(CheckResourceFitParams {
+            cpu_cores: -1.0,
+            memory_gb: 1.0,
+            ..Default::default()
+        })).await.unwrap();
+        let envelope = envelope_of(&error);
+        assert_eq!(error.is_error, Some(true));
+        assert_eq!(envelope.status, "error");
+        assert_eq!(envelope.error.as_deref(), Some("CPU cores must be non-negative"));
+        assert!(envelope.data.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_builder_with_custom_config() {
+        let (client, requested_paths) = mock_client();
+        let cluster_insights = ClusterInsights::builder()
+            .client(client)
+            .config(ServerConfig {
+                max_replica_count: 5,
+                default_namespace: Some(" team-a ".to_string()),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+
+        let error = cluster_insights.check_replica_capacity(Parameters(CheckReplicaCapacityParams {
+            app_name: "web".to_string(),
+            replica_count: 10,
+            ..Default::default()
+        })).await.unwrap();
+        assert_eq!(error.is_error, Some(true));
+        assert!(envelope_of(&error).error.unwrap().contains("exceeds the maximum of 5"));
+
+        // A blank namespace falls back to the configured default, trimmed by validation
+        cluster_insights.get_image_distribution(Parameters(ImageDistributionParams {
+            namespace: Some(String::new()),
+            cache: CacheParams { fresh: Some(true) },
+            ..Default::default()
+        })).await.unwrap();
+        assert!(requested_paths.lock().unwrap().iter().any(|p| p == "/api/v1/namespaces/team-a/pods"));
+
+        // The response limits are the instance's too
+        let limited = ClusterInsights::builder()
+            .config(ServerConfig { max_response_items: 1, ..Default::default() })
+            .build()
+            .unwrap();
+        let capabilities = limited.list_capabilities(Parameters(ListCapabilitiesParams::default())).await.unwrap();
+        let envelope = envelope_of(&capabilities);
+        assert!(envelope.truncated);
+        assert_eq!(envelope.data.unwrap()["tools"].as_array().unwrap().len(), 1);
+
+        let invalid = ClusterInsights::builder()
+            .config(ServerConfig { max_replica_count: 0, ..Default::default() })
+            .build();
+        assert!(invalid.unwrap_err().contains("max_replica_count must be positive"));
+    }
+
+    #[tokio::test]
+    async fn test_tools_without_cluster_config() {
+        let cluster_insights = ClusterInsights { cluster_configured: false, ..ClusterInsights::new() };
+
+        let result = cluster_insights.get_cluster_capacity(Parameters(ClusterCapacityParams {
+            cache: CacheParams { fresh: Some(true) },
+            ..Default::default()
+        })).await.unwrap();
+        assert_eq!(result.is_error, Some(true));
+        let error = envelope_of(&result).error.unwrap();
+        assert!(error.contains("No Kubernetes configuration found: set KUBECONFIG"));
+
+        // The offline estimator still answers
+        let result = cluster_insights.check_resource_fit(Parameters(CheckResourceFitParams {
+            cpu_cores: 1.0,
+            memory_gb: 1.0,
+            cluster_override: Some(ClusterTotals {
+                total_cpu_cores: 4.0,
+                total_memory_gb: 16.0,
+                allocated_cpu_cores: 0.0,
+                allocated_memory_gb: 0.0,
+                node_count: 1,
+            }),
+            ..Default::default()
+        })).await.unwrap();
+        assert_eq!(result.is_error, Some(false));
+    }
+
+    #[tokio::test]
+    async fn test_resource_fit_cluster_override_without_client() {
+        // No client and no kubeconfig needed: the override skips every API call
+        let cluster_insights = ClusterInsights::new();
+        let params = CheckResourceFitParams {
+            cpu_cores: 6.0,
+            memory_gb: 8.0,
+            node_cpu_cores: Some(8.0),
+            node_memory_gb: Some(32.0),
+            cluster_override: Some(ClusterTotals {
+                total_cpu_cores: 16.0,
+                total_memory_gb: 64.0,
+                allocated_cpu_cores: 12.0,
+                allocated_memory_gb: 32.0,
+                node_count: 2,
+            }),
+            ..Default::default()
+        };
+
+        let result = cluster_insights.check_resource_fit(Parameters(params)).await.unwrap();
+        let data = envelope_of(&result).data.unwrap();
+        assert_eq!(data["fits"], false);
+        assert_eq!(data["cpu_fits"], false);
+        assert_eq!(data["memory_fits"], true);
+        assert_eq!(data["available_cpu_cores"], 4.0);
+        assert_eq!(data["scale_up"]["nodes_needed"], 1);
+        assert!(data["explanation"].as_str().unwrap().contains("supplied cluster totals"));
+    }
+
+    #[test]
+    fn test_resource_fit_extra_resources() {
+        let node: Node = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "node-a" },
+            "status": {
+                "capacity": { "cpu": "8", "memory": "32Gi", "ephemeral-storage": "100Gi" },
+                "allocatable": { "cpu": "8", "memory": "32Gi", "ephemeral-storage": "50Gi" }
+            }
+        })).unwrap();
+        let mut pod = test_pod("builder", "ci", Some("node-a"), "2", "4Gi");
+        let requests = pod.spec.as_mut().unwrap().containers[0].resources.as_mut().unwrap().requests.as_mut().unwrap();
+        requests.insert("ephemeral-storage".to_string(), Quantity("40Gi".to_string()));
+        let (nodes, pods) = (vec![node], vec![pod]);
+
+        let capacity = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        let mut response = ClusterInsights::build_resource_fit_response(4.0, 8.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        let extra = BTreeMap::from([("ephemeral-storage".to_string(), "20Gi".to_string())]);
+        ClusterInsights::apply_extra_resources(&mut response, &extra, &nodes, &pods, config::DEFAULT_FIT_TOLERANCE, Lang::En).unwrap();
+
+        // CPU and memory fit, but only 10Gi of the 50Gi allocatable storage is left
+        assert!(response.cpu_fits);
+        assert!(response.memory_fits);
+        assert!(!response.fits);
+        let storage = &response.extra_resources.as_ref().unwrap()[0];
+        assert_eq!(storage.resource, "ephemeral-storage");
+        assert_eq!(storage.requested, 20.0 * BYTES_PER_GB);
+        assert_eq!(storage.available, 10.0 * BYTES_PER_GB);
+        assert!(!storage.fits);
+        assert!(response.explanation.contains("ephemeral-storage does NOT fit"));
+
+        let missing = BTreeMap::from([("nvidia.com/gpu".to_string(), "two".to_string())]);
+        let error = ClusterInsights::apply_extra_resources(&mut response, &missing, &nodes, &pods, config::DEFAULT_FIT_TOLERANCE, Lang::En).unwrap_err();
+        assert!(error.contains("nvidia.com/gpu"));
+        // Fractions of a unit round up, like Kubernetes does
+        assert_eq!(parse_quantity_units("500m"), Some(1));
+        assert_eq!(quantity_to_count(&Quantity("1500m".to_string())), 2);
+    }
+
+    #[test]
+    fn test_extra_resources_count_effective_requests_of_running_pods() {
+        let node: Node = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "node-a" },
+            "status": { "allocatable": { "cpu": "8", "memory": "32Gi", "ephemeral-storage": "100Gi" } }
+        })).unwrap();
+        let storage_pod = |name: &str, node: Option<&str>, init: &str, sidecar: &str, app: &str| -> Pod {
+            let container = |name: &str, storage: &str| serde_json::json!({
+                "name": name,
+                "resources": { "requests": { "cpu": "100m", "memory": "1Gi", "ephemeral-storage": storage } }
+            });
+            let mut sidecar = container("proxy", sidecar);
+            sidecar["restartPolicy"] = "Always".into();
+            serde_json::from_value(serde_json::json!({
+                "metadata": { "name": name, "namespace": "ci" },
+                "spec": {
+                    "nodeName": node,
+                    "initContainers": [sidecar, container("unpack", init)],
+                    "containers": [container("main", app)],
+                    "overhead": { "ephemeral-storage": "1Gi" }
                 }
+            })).unwrap()
+        };
+        // The init container (30Gi) plus the sidecar started before it outweighs the app container
+        let running = storage_pod("builder", Some("node-a"), "30Gi", "5Gi", "10Gi");
+        let mut finished = storage_pod("finished", Some("node-a"), "1Gi", "1Gi", "40Gi");
+        finished.status = Some(serde_json::from_value(serde_json::json!({ "phase": "Succeeded" })).unwrap());
+        let pending = storage_pod("pending", None, "1Gi", "1Gi", "40Gi");
+        assert_eq!(pod_effective_request_count(&running, "ephemeral-storage"), 36 << 30);
+        let (nodes, pods) = (vec![node], vec![running, finished, pending]);
+
+        let capacity = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        let mut response = ClusterInsights::build_resource_fit_response(1.0, 1.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        let extra = BTreeMap::from([("ephemeral-storage".to_string(), "60Gi".to_string())]);
+        ClusterInsights::apply_extra_resources(&mut response, &extra, &nodes, &pods, config::DEFAULT_FIT_TOLERANCE, Lang::En).unwrap();
+
+        // Only the running pod holds storage: the Completed and the unscheduled pod count for nothing
+        let storage = &response.extra_resources.as_ref().unwrap()[0];
+        assert_eq!(storage.available, 64.0 * BYTES_PER_GB);
+        assert!(storage.fits);
+    }
+
+    #[test]
+    fn test_resource_fit_limits_basis() {
+        let nodes = vec![test_node("node-a", "4", "16Gi")];
+        let mut bursty = test_pod("bursty", "batch", Some("node-a"), "1", "2Gi");
+        let resources = bursty.spec.as_mut().unwrap().containers[0].resources.as_mut().unwrap();
+        resources.limits = Some(BTreeMap::from([("cpu".to_string(), Quantity("3".to_string()))]));
+        let pods = vec![bursty];
+
+        let by_requests = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        assert_eq!(by_requests.allocated_cpu_cores, 1.0);
+        assert!(ClusterInsights::build_resource_fit_response(2.0, 4.0, &by_requests, config::DEFAULT_FIT_TOLERANCE, Lang::En).fits);
+
+        // Memory has no limit, so the request counts
+        let by_limits = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Limits, None);
+        assert_eq!(by_limits.allocated_cpu_cores, 3.0);
+        assert_eq!(by_limits.allocated_memory_gb, by_requests.allocated_memory_gb);
+        let response = ClusterInsights::build_resource_fit_response(2.0, 4.0, &by_limits, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        assert!(!response.fits);
+        assert!(!response.cpu_fits);
+        assert!(response.memory_fits);
+    }
+
+    #[test]
+    fn test_resource_basis_aggregation() {
+        let nodes = vec![test_node("node-a", "4", "16Gi")];
+        let mut web = test_pod("web", "shop", Some("node-a"), "1", "2Gi");
+        let resources = web.spec.as_mut().unwrap().containers[0].resources.as_mut().unwrap();
+        resources.limits = Some(BTreeMap::from([("cpu".to_string(), Quantity("3".to_string()))]));
+        let pods = vec![web, test_pod("queued", "shop", None, "1", "1Gi")];
+        let usage: PodUsage = HashMap::from([(("shop".to_string(), "web".to_string()), (0.25, 0.5))]);
+
+        let totals = |basis: ResourceBasis| {
+            let capacity = ClusterInsights::compute_cluster_capacity(&nodes, &pods, basis, Some(&usage));
+            let node_infos = ClusterInsights::build_node_infos(&nodes, &pods, basis, Some(&usage));
+            assert_eq!(capacity.basis, basis);
+            assert_eq!(node_infos[0].allocated_cpu_cores, capacity.allocated_cpu_cores);
+            assert_eq!(node_infos[0].allocated_memory_gb, capacity.allocated_memory_gb);
+            capacity
+        };
+
+        let requests = totals(ResourceBasis::Requests);
+        assert_eq!((requests.allocated_cpu_cores, requests.allocated_memory_gb), (1.0, 2.0));
+        assert_eq!(requests.pending_cpu_cores, 1.0);
+        assert!(requests.explanation.contains("Allocated (requests)"));
+
+        let limits = totals(ResourceBasis::Limits);
+        assert_eq!((limits.allocated_cpu_cores, limits.allocated_memory_gb), (3.0, 2.0));
+
+        // The pending pod has no metrics, so it adds no demand under Usage
+        let used = totals(ResourceBasis::Usage);
+        assert_eq!((used.allocated_cpu_cores, used.allocated_memory_gb), (0.25, 0.5));
+        assert_eq!(used.pending_cpu_cores, 0.0);
+        assert_eq!(used.available_cpu_cores, 3.75);
+        assert!(used.explanation.contains("Allocated (usage)"));
+
+        let fit = ClusterInsights::build_resource_fit_response(3.5, 4.0, &used, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        assert_eq!(fit.basis, ResourceBasis::Usage);
+        assert!(fit.fits);
+    }
+
+    #[test]
+    fn test_resource_fit_subtract_pending_demand() {
+        let nodes = vec![test_node("node-a", "4", "16Gi")];
+        let pods = vec![
+            test_pod("running", "web", Some("node-a"), "1", "2Gi"),
+            test_pod("waiting", "batch", None, "2", "4Gi"),
+        ];
+
+        let mut capacity = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        assert_eq!(capacity.available_cpu_cores, 3.0);
+        assert!(ClusterInsights::build_resource_fit_response(2.0, 4.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En).fits);
+
+        capacity.subtract_pending_demand();
+        assert_eq!(capacity.available_cpu_cores, 1.0);
+        assert_eq!(capacity.available_memory_gb, 10.0);
+        let response = ClusterInsights::build_resource_fit_response(2.0, 4.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        assert!(!response.fits);
+        assert!(!response.cpu_fits);
+        assert!(response.memory_fits);
+        assert_eq!(
+            messages::pending_demand(Lang::En, &capacity),
+            "Available capacity is net of the 2.00 cores CPU and 4.00 GB memory requested by 1 Pending pods."
+        );
+    }
+
+    #[test]
+    fn test_resource_fit_target_utilization() {
+        let nodes = vec![test_node("node-a", "4", "16Gi")];
+        let pods = vec![test_pod("running", "web", Some("node-a"), "1", "2Gi")];
+
+        let mut capacity = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        assert!(ClusterInsights::build_resource_fit_response(2.5, 4.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En).fits);
+
+        // 70% of 4 cores is 2.8, of which 1 is allocated
+        capacity.cap_utilization(70.0);
+        assert!((capacity.available_cpu_cores - 1.8).abs() < 1e-9);
+        assert!((capacity.available_memory_gb - 9.2).abs() < 1e-9);
+        let response = ClusterInsights::build_resource_fit_response(2.5, 4.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        assert!(!response.fits);
+        assert!(!response.cpu_fits);
+        assert!(response.memory_fits);
+        assert_eq!(
+            messages::utilization_cap(Lang::En, 70.0, &capacity),
+            "Available capacity is capped at 70% allocation, keeping 1.20 cores CPU and 4.80 GB memory in reserve."
+        );
+    }
+
+    #[test]
+    fn test_resource_fit_binding_constraint() {
+        let capacity = test_capacity(8.0, 32.0, 4.0, 30.0);
+
+        let fit = ClusterInsights::build_resource_fit_response(2.0, 4.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        assert!(!fit.fits);
+        assert!(fit.cpu_fits);
+        assert!(!fit.memory_fits);
+    }
+
+    #[test]
+    fn test_resource_fit_explanation_detail_levels() {
+        let capacity = test_capacity(8.0, 32.0, 4.0, 16.0);
+
+        let full = ClusterInsights::build_resource_fit_response(1.0, 1.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        let full_text = full.explanation.clone();
+        let full = messages::with_detail(full, ExplanationDetail::Full, Lang::En);
+        assert_eq!(full.explanation, full_text);
+        assert!(full.explanation.starts_with("Resources FIT in cluster."));
+
+        let brief = ClusterInsights::build_resource_fit_response(1.0, 1.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        let brief = messages::with_detail(brief, ExplanationDetail::Brief, Lang::En);
+        assert_eq!(brief.explanation, "Fits: 4.00 cores, 16.00 GB available.");
+        assert!(!brief.explanation.contains('\n'));
+
+        let none = ClusterInsights::build_resource_fit_response(1.0, 1.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        let none = messages::with_detail(none, ExplanationDetail::None, Lang::En);
+        assert!(none.explanation.is_empty());
+        assert!(none.fits);
+
+        let params: CheckResourceFitParams = serde_json::from_value(serde_json::json!({
+            "cpu_cores": 1.0, "memory_gb": 1.0, "explanation_detail": "Brief"
+        })).unwrap();
+        assert_eq!(params.explanation_detail, ExplanationDetail::Brief);
+        assert_eq!(CheckResourceFitParams::default().explanation_detail, ExplanationDetail::Full);
+    }
+
+    #[test]
+    fn test_resolve_namespace_uses_default_when_blank() {
+        assert_eq!(resolve_namespace("", Some("team-a".to_string())), Ok("team-a".to_string()));
+        assert_eq!(resolve_namespace("  ", Some("team-a".to_string())), Ok("team-a".to_string()));
+        assert_eq!(resolve_namespace("web", Some("team-a".to_string())), Ok("web".to_string()));
+        assert!(resolve_namespace("", None).unwrap_err().starts_with("Namespace cannot be empty"));
+    }
+
+    #[test]
+    fn test_replica_count_bounds() {
+        let max = config::DEFAULT_MAX_REPLICA_COUNT;
+        assert!(validate_replica_count(0, max).is_err());
+        let error = validate_replica_count(2_000_000_000, max).unwrap_err();
+        assert!(error.contains("exceeds the maximum of 100000"));
+        assert!(validate_replica_count(max, max).is_ok());
+
+        let pod = test_pod("tiny-1", "default", Some("node-1"), "50m", "64Mi");
+        let capacity = test_capacity(8.0, 32.0, 2.0, 8.0);
+        let params = replica_params("tiny", config::DEFAULT_MAX_REPLICA_COUNT);
+        let response = ClusterInsights::build_replica_capacity_response(&params, &pod, 1, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+
+        assert!(!response.fits);
+        assert!((response.total_cpu_required_cores - 5000.0).abs() < 1e-6);
+        assert!(response.projected_cpu_utilization_percent.is_finite());
+        assert!(response.explanation.contains("Maximum possible replicas based on CPU: 120"));
+    }
+
+    #[test]
+    fn test_replica_capacity_best_effort_reference_pod() {
+        let mut pod = test_pod("batch-1", "default", Some("node-1"), "0", "0");
+        pod.spec.as_mut().unwrap().containers[0].resources = None;
+        // Overcommitted cluster: negative availability must not turn into Inf/NaN replica counts
+        let capacity = test_capacity(8.0, 32.0, 9.0, 40.0);
+        let params = replica_params("batch", 5);
+        let response = ClusterInsights::build_replica_capacity_response(&params, &pod, 1, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+
+        assert!(response.fits);
+        assert_eq!(response.cpu_per_replica_cores, 0.0);
+        assert_eq!(response.memory_per_replica_gb, 0.0);
+        assert!(response.warning.as_deref().unwrap().contains("BestEffort"));
+        assert!(response.explanation.contains("cannot be estimated"));
+        assert!(!response.explanation.contains("inf"));
+        assert!(!response.explanation.contains("NaN"));
+    }
+
+    #[test]
+    fn test_quantity_to_cores() {
+        assert_eq!(quantity_to_cores(&Quantity("2".to_string())), 2.0);
+        assert_eq!(quantity_to_cores(&Quantity("500m".to_string())), 0.5);
+        assert_eq!(quantity_to_cores(&Quantity("100m".to_string())), 0.1);
+    }
+
+    #[test]
+    fn test_quantity_to_gb() {
+        assert_eq!(quantity_to_gb(&Quantity("1Gi".to_string())), 1.0);
+        assert_eq!(quantity_to_gb(&Quantity("512Mi".to_string())), 0.5);
+    }
+
+    #[test]
+    fn test_memory_quantity_binary_and_decimal_units() {
+        // Decimal and binary suffixes pinned to their byte values
+        assert_eq!(parse_memory_bytes("1G"), Ok(1_000_000_000));
+        assert_eq!(parse_memory_bytes("1Gi"), Ok(1 << 30));
+        assert_eq!(parse_memory_bytes("1M"), Ok(1_000_000));
+        assert_eq!(parse_memory_bytes("1Mi"), Ok(1 << 20));
+        assert_eq!(parse_memory_bytes("1k"), Ok(1_000));
+        assert_eq!(parse_memory_bytes("1Ki"), Ok(1 << 10));
+        assert_eq!(parse_memory_bytes("0.5"), Ok(1));
+        // Kubernetes spells the decimal kilo suffix lowercase only, and milli rounds up to a whole byte
+        assert!(parse_memory_bytes("1K").is_err());
+        assert_eq!(parse_memory_bytes("1500m"), Ok(2));
+
+        // GB in responses is GiB: "1Gi" is exactly 1, "1G" is 10^9 / 2^30
+        assert_eq!(quantity_to_gb(&Quantity("1Gi".to_string())), 1.0);
+        assert_eq!(quantity_to_gb(&Quantity("1G".to_string())), 1e9 / BYTES_PER_GB);
+        assert!((quantity_to_gb(&Quantity("1G".to_string())) - 0.931).abs() < 1e-3);
+
+        // Every conversion agrees on the byte count
+        for s in ["1G", "1Gi", "1M", "1Mi", "1.5Gi", "250M"] {
+            let bytes = parse_memory_bytes(s).unwrap();
+            assert_eq!(quantity_to_bytes(&Quantity(s.to_string())), bytes);
+            assert_eq!(quantity_to_gb(&Quantity(s.to_string())), bytes as f64 / BYTES_PER_GB);
+            assert_eq!(parse_quantity_units(s), Some(bytes));
+        }
+    }
+
+    #[test]
+    fn test_parse_memory_bytes_fractions_and_bytes() {
+        assert_eq!(parse_memory_bytes("1.5Gi"), Ok(3 << 29));
+        assert_eq!(parse_memory_bytes("0.5Ti"), Ok(512 << 30));
+        assert_eq!(parse_memory_bytes("1073741824"), Ok(1 << 30));
+        assert_eq!(parse_memory_bytes("1500000000"), Ok(1_500_000_000));
+    }
+
+    #[test]
+    fn test_parse_memory_bytes_rejects_negative_and_malformed() {
+        assert_eq!(parse_memory_bytes("-1Gi"), Err("Negative memory quantity '-1Gi'".to_string()));
+        assert!(parse_memory_bytes("-500").is_err());
+        assert!(parse_memory_bytes("Gi").is_err());
+        assert!(parse_memory_bytes("1.5Xi").is_err());
+        assert!(parse_memory_bytes("NaN").is_err());
+        assert_eq!(quantity_to_gb(&Quantity("-1Gi".to_string())), 0.0);
+    }
+
+    #[test]
+    fn test_strict_memory_quantity_warnings() {
+        assert!(is_implausible_bare_memory("512"));
+        assert!(!is_implausible_bare_memory("512Mi"));
+        assert!(!is_implausible_bare_memory("0"));
+        assert!(!is_implausible_bare_memory("2147483648"));
+        // Parsed like every other memory quantity: exponents count, millibytes and malformed values do not
+        assert!(is_implausible_bare_memory("5e2"));
+        assert!(!is_implausible_bare_memory("5e6"));
+        assert!(!is_implausible_bare_memory("512m"));
+        assert!(!is_implausible_bare_memory("-512"));
+
+        let pods = vec![
+            test_pod("typo", "web", Some("node-a"), "100m", "512"),
+            test_pod("fine", "web", Some("node-a"), "100m", "512Mi"),
+        ];
+        let warnings = memory_quantity_warnings(&pods);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("'web/typo' container 'main' has memory request '512'"));
+        assert!(warnings[0].ends_with("did you mean '512Mi'?"));
+
+        // Lenient by default: the bare number still parses as bytes
+        let stats = ClusterInsights::build_pod_resource_stats(&pods, false, false, PodSortBy::CpuRequests, None, Lang::En);
+        assert_eq!(stats.warnings, None);
+    }
+
+    #[test]
+    fn test_quantity_to_gb_peta_exa() {
+        assert_eq!(quantity_to_gb(&Quantity("1Pi".to_string())), 1024.0 * 1024.0);
+        assert_eq!(quantity_to_gb(&Quantity("1Ei".to_string())), 1024.0 * 1024.0 * 1024.0);
+        let one_p = quantity_to_gb(&Quantity("1P".to_string()));
+        assert!((one_p - 1e15 / (1024.0 * 1024.0 * 1024.0)).abs() < 1e-6);
+        let one_e = quantity_to_gb(&Quantity("1E".to_string()));
+        assert!((one_e - 1e18 / (1024.0 * 1024.0 * 1024.0)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_exact_quantity_accounting() {
+        assert_eq!(quantity_to_millicores(&Quantity("0.57".to_string())), 570);
+        assert_eq!(quantity_to_millicores(&Quantity("1500u".to_string())), 2);
+        assert_eq!(quantity_to_millicores(&Quantity("1e-3".to_string())), 1);
+        assert_eq!(quantity_to_millicores(&Quantity("-1".to_string())), 0);
+        assert_eq!(quantity_to_bytes(&Quantity("1.5Gi".to_string())), 3 << 29);
+        assert_eq!(quantity_to_bytes(&Quantity("1e3".to_string())), 1000);
+
+        // 3000 pods of 1m and 1Ki sum to exactly 3 cores and 3000Ki
+        let pods: Vec<Pod> = (0..3000)
+            .map(|i| test_pod(&format!("tiny-{}", i), "default", Some("node-a"), "1m", "1Ki"))
+            .collect();
+        let nodes = vec![test_node("node-a", "4", "8Gi")];
+
+        let capacity = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        assert_eq!(capacity.allocated_cpu_cores, 3.0);
+        assert_eq!(capacity.available_cpu_cores, 1.0);
+        assert_eq!(capacity.allocated_memory_gb, 3000.0 / (1024.0 * 1024.0));
+
+        let usages = ClusterInsights::build_namespace_usages(&[], &pods);
+        assert_eq!(usages[0].cpu_requests_cores, 3.0);
+    }
+
+    #[test]
+    fn test_namespace_node_distribution() {
+        let pods = vec![
+            test_pod("web-1", "web", Some("node-a"), "500m", "1Gi"),
+            test_pod("web-2", "web", Some("node-a"), "500m", "1Gi"),
+            test_pod("web-3", "web", Some("node-b"), "250m", "512Mi"),
+        ];
+        let response = ClusterInsights::build_namespace_node_distribution(&pods, Lang::En);
+
+        assert_eq!(response.total_namespaces, 1);
+        let web = &response.namespaces[0];
+        assert_eq!(web.pod_count, 3);
+        assert_eq!(web.nodes.len(), 2);
+        assert_eq!(web.nodes["node-a"].pod_count, 2);
+        assert_eq!(web.nodes["node-a"].cpu_requests_cores, 1.0);
+        assert_eq!(web.nodes["node-a"].memory_requests_gb, 2.0);
+        assert_eq!(web.nodes["node-b"].pod_count, 1);
+        assert_eq!(web.nodes["node-b"].memory_requests_gb, 0.5);
+    }
+
+    #[test]
+    fn test_nodes_needed_for_shortfall_rounds_up() {
+        // 10 cores short, 3.5 usable cores per node -> 2.86 -> 3 nodes
+        assert_eq!(nodes_needed_for_shortfall(10.0, 0.0, 3.5, 14.0), Some(3));
+        // Memory is the binding resource: 20 GB short, 6 GB usable -> 4 nodes
+        assert_eq!(nodes_needed_for_shortfall(1.0, 20.0, 3.5, 6.0), Some(4));
+        // Exact multiples do not round up
+        assert_eq!(nodes_needed_for_shortfall(8.0, 0.0, 4.0, 16.0), Some(2));
+        // A node fully consumed by overhead cannot help
+        assert_eq!(nodes_needed_for_shortfall(1.0, 0.0, 0.0, 16.0), None);
+    }
+
+    #[test]
+    fn test_recommend_scale_up_with_daemonset_overhead() {
+        let mut agent: Pod = test_pod("agent-abc", "kube-system", Some("node-1"), "500m", "1Gi");
+        agent.metadata.owner_references = Some(vec![serde_json::from_value(serde_json::json!({
+            "apiVersion": "apps/v1",
+            "kind": "DaemonSet",
+            "name": "agent",
+            "uid": "1"
+        })).unwrap()]);
+        let pods = vec![agent, test_pod("app-1", "default", Some("node-1"), "2", "4Gi")];
+
+        let recommendation = ClusterInsights::recommend_scale_up(10.0, 2.0, Some(4.0), Some(16.0), &[], &pods, Lang::En);
+
+        assert_eq!(recommendation.node_size_source, "parameter");
+        assert_eq!(recommendation.daemonset_cpu_overhead_cores, 0.5);
+        assert_eq!(recommendation.daemonset_memory_overhead_gb, 1.0);
+        // 10 cores / (4 - 0.5) usable cores per node = 2.86 -> 3 nodes
+        assert_eq!(recommendation.nodes_needed, Some(3));
+    }
+
+    #[test]
+    fn test_autoscale_fits_after_scaleup() {
+        // 10 cores short on 4-core nodes without DaemonSets: 3 nodes needed
+        let scale_up = ClusterInsights::recommend_scale_up(10.0, 2.0, Some(4.0), Some(16.0), &[], &[], Lang::En);
+        assert_eq!(scale_up.nodes_needed, Some(3));
+
+        let group = |name: &str, current_nodes: u32, max_nodes: u32| NodeGroupLimit {
+            name: name.to_string(),
+            current_nodes,
+            max_nodes,
+            node_cpu_cores: None,
+            node_memory_gb: None,
+        };
+
+        assert_eq!(autoscale_nodes_needed(&scale_up, &[group("workers", 3, 10)]), Some(3));
+        assert_eq!(autoscale_nodes_needed(&scale_up, &[group("workers", 3, 4), group("spot", 0, 2)]), Some(3));
+        assert_eq!(autoscale_nodes_needed(&scale_up, &[group("workers", 3, 5)]), None);
+
+        assert!(validate_node_groups(true, Some(&[group("workers", 3, 10)])).is_ok());
+        assert!(validate_node_groups(false, None).is_ok());
+        assert!(validate_node_groups(true, None).unwrap_err().starts_with("node_groups is required"));
+        assert!(validate_node_groups(true, Some(&[group("workers", 3, 2)])).unwrap_err().contains("at least current_nodes"));
+        let zero_sized = NodeGroupLimit { node_cpu_cores: Some(0.0), ..group("workers", 3, 10) };
+        assert_eq!(validate_node_groups(true, Some(&[zero_sized])).unwrap_err(), "Node size must be positive");
+    }
+
+    #[test]
+    fn test_get_pods_on_node() {
+        let nodes = vec![
+            test_node("node-a", "4", "16Gi"),
+            test_node("node-b", "2", "8Gi"),
+        ];
+        let pods = vec![
+            test_pod("api-1", "web", Some("node-a"), "1500m", "2Gi"),
+            test_pod("api-2", "web", Some("node-a"), "1", "1Gi"),
+            test_pod("db-0", "data", Some("node-b"), "500m", "1Gi"),
+        ];
+
+        let listing = ClusterInsights::build_pods_on_node("node-a", &nodes, &pods, false, Lang::En).unwrap();
+        assert_eq!(listing.total_pods, 2);
+        assert_eq!(listing.pods.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(), vec!["api-1", "api-2"]);
+        assert!(listing.pods.iter().all(|p| p.reschedulable.is_none()));
+
+        // node-b has 1.5 cores free: api-1 fits, api-2 no longer does
+        let check = ClusterInsights::build_pods_on_node("node-a", &nodes, &pods, true, Lang::En).unwrap();
+        assert_eq!(check.unreschedulable_pods, Some(1));
+        assert_eq!(check.pods[0].target_node.as_deref(), Some("node-b"));
+        assert_eq!(check.pods[1].reschedulable, Some(false));
+
+        assert!(ClusterInsights::build_pods_on_node("missing", &nodes, &pods, false, Lang::En).is_err());
+    }
+
+    #[test]
+    fn test_burst_risk() {
+        let with_limits = |mut pod: Pod, cpu: &str, memory: &str| {
+            let resources = pod.spec.as_mut().unwrap().containers[0].resources.as_mut().unwrap();
+            resources.limits = Some(BTreeMap::from([
+                ("cpu".to_string(), Quantity(cpu.to_string())),
+                ("memory".to_string(), Quantity(memory.to_string())),
+            ]));
+            pod
+        };
+        let pods = vec![
+            with_limits(test_pod("job-1", "batch", Some("node-a"), "500m", "1Gi"), "2", "2Gi"),
+            with_limits(test_pod("job-2", "batch", Some("node-a"), "500m", "1Gi"), "2", "2Gi"),
+            with_limits(test_pod("web-1", "web", Some("node-a"), "1", "1Gi"), "1500m", "1Gi"),
+        ];
+
+        let response = ClusterInsights::build_burst_risk(&pods, 2.0, true, Lang::En);
+        assert_eq!(response.total_namespaces_checked, 2);
+        assert_eq!(response.namespaces.len(), 1);
+        let batch = &response.namespaces[0];
+        assert_eq!(batch.namespace, "batch");
+        assert_eq!(batch.cpu_limit_ratio, Some(4.0));
+        assert_eq!(batch.memory_limit_ratio, Some(2.0));
+        assert_eq!(response.pods.as_ref().map(|p| p.len()), Some(2));
+
+        let without_pods = ClusterInsights::build_burst_risk(&pods, 2.0, false, Lang::En);
+        assert!(without_pods.pods.is_none());
+    }
+
+    #[test]
+    fn test_namespaces_without_limits() {
+        let limited = |name: &str, namespace: &str| {
+            let mut pod = test_pod(name, namespace, Some("node-a"), "100m", "128Mi");
+            let resources = pod.spec.as_mut().unwrap().containers[0].resources.as_mut().unwrap();
+            resources.limits = Some(BTreeMap::from([("memory".to_string(), Quantity("256Mi".to_string()))]));
+            pod
+        };
+        let pods = vec![
+            test_pod("legacy-1", "legacy", Some("node-a"), "100m", "128Mi"),
+            test_pod("legacy-2", "legacy", Some("node-a"), "100m", "128Mi"),
+            limited("shop-1", "shop"),
+            limited("shop-2", "shop"),
+            test_pod("shop-3", "shop", Some("node-a"), "100m", "128Mi"),
+        ];
+
+        let response = ClusterInsights::build_namespaces_without_limits(&pods, 0.5, Lang::En);
+        assert_eq!(response.total_namespaces_checked, 2);
+        assert_eq!(response.namespaces.len(), 1);
+        let legacy = &response.namespaces[0];
+        assert_eq!(legacy.namespace, "legacy");
+        assert_eq!(legacy.pods_without_memory_limit, 2);
+        assert_eq!(legacy.unlimited_fraction, 1.0);
+
+        let strict = ClusterInsights::build_namespaces_without_limits(&pods, 0.3, Lang::En);
+        assert_eq!(strict.namespaces.len(), 2);
+        assert_eq!(strict.namespaces[1].pods_without_memory_limit, 1);
+    }
+
+    #[test]
+    fn test_node_breakdown_paging() {
+        let nodes: Vec<Node> = (0..25).map(|i| test_node(&format!("node-{:02}", i), "8", "32Gi")).collect();
+        // node-07 is the fullest on CPU
+        let pods = vec![
+            test_pod("big", "shop", Some("node-07"), "6", "4Gi"),
+            test_pod("small", "shop", Some("node-13"), "2", "4Gi"),
+        ];
+        let node_infos = ClusterInsights::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None);
+
+        let mut offset = 0;
+        let mut pages = Vec::new();
+        loop {
+            let page = ClusterInsights::build_node_breakdown(node_infos.clone(), NodeSortBy::Name, offset, Some(10), ResourceBasis::Requests, 0, Lang::En);
+            assert_eq!(page.total_nodes, 25);
+            assert_eq!(page.offset, offset);
+            pages.push(page.nodes.iter().map(|n| n.name.clone()).collect::<Vec<_>>());
+            match page.next_offset {
+                Some(next) => offset = next,
+                None => break,
             }
         }
-        
-        let mut allocated_cpu_cores = 0.0;
-        let mut allocated_memory_gb = 0.0;
-        
-        for pod in &pods.items {
-            if let Some(spec) = &pod.spec {
-                for container in &spec.containers {
-                    if let Some(resources) = &container.resources {
-                        if let Some(requests) = &resources.requests {
-                            if let Some(cpu) = requests.get("cpu") {
-                                allocated_cpu_cores += quantity_to_cores(cpu);
-                            }
-                            if let Some(memory) = requests.get("memory") {
-                                allocated_memory_gb += quantity_to_gb(memory);
+        assert_eq!(pages.iter().map(Vec::len).collect::<Vec<_>>(), vec![10, 10, 5]);
+        assert_eq!(pages[0][0], "node-00");
+        assert_eq!(pages[2][4], "node-24");
+
+        let fullest = ClusterInsights::build_node_breakdown(node_infos.clone(), NodeSortBy::LeastAvailableCpu, 0, Some(10), ResourceBasis::Requests, 0, Lang::En);
+        assert_eq!(fullest.nodes[0].name, "node-07");
+        assert_eq!(fullest.nodes[1].name, "node-13");
+        assert!(fullest.explanation.contains("Showing nodes 1-10 of 25"));
+
+        let everything = ClusterInsights::build_node_breakdown(node_infos, NodeSortBy::Name, 0, None, ResourceBasis::Requests, 0, Lang::En);
+        assert_eq!(everything.nodes.len(), 25);
+        assert_eq!(everything.next_offset, None);
+    }
+
+    #[test]
+    fn test_node_breakdown_cursor_paging() {
+        let nodes: Vec<Node> = (0..25).map(|i| test_node(&format!("node-{:02}", i), "8", "32Gi")).collect();
+        let pods: Vec<Pod> = (0..25).map(|i| test_pod(&format!("pod-{}", i), "shop", Some(format!("node-{:02}", i).as_str()), &format!("{}m", i % 4 * 100), "1Gi")).collect();
+        let mut node_infos = ClusterInsights::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None);
+
+        let mut seen = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let offset = match &cursor {
+                Some(cursor) => cursor_offset(&mut node_infos, NodeSortBy::LeastAvailableCpu, cursor).unwrap(),
+                None => 0,
+            };
+            let page = ClusterInsights::build_node_breakdown(node_infos.clone(), NodeSortBy::LeastAvailableCpu, offset, Some(7), ResourceBasis::Requests, 0, Lang::En);
+            seen.extend(page.nodes.iter().map(|n| n.name.clone()));
+            assert_eq!(page.next_cursor.is_some(), page.next_offset.is_some());
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        let mut unique = seen.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!((seen.len(), unique.len()), (25, 25));
+
+        // A node removed before the cursor shifts offsets but not the cursor's position
+        let first = ClusterInsights::build_node_breakdown(node_infos.clone(), NodeSortBy::Name, 0, Some(10), ResourceBasis::Requests, 0, Lang::En);
+        node_infos.retain(|info| info.name != "node-03");
+        let offset = cursor_offset(&mut node_infos, NodeSortBy::Name, first.next_cursor.as_deref().unwrap()).unwrap();
+        let second = ClusterInsights::build_node_breakdown(node_infos.clone(), NodeSortBy::Name, offset, Some(10), ResourceBasis::Requests, 0, Lang::En);
+        assert_eq!(second.nodes[0].name, "node-10");
+
+        assert!(cursor_offset(&mut node_infos, NodeSortBy::MostPods, first.next_cursor.as_deref().unwrap()).is_err());
+        assert_eq!(cursor_offset(&mut node_infos, NodeSortBy::Name, "zz"), Err("Invalid cursor".to_string()));
+
+        // Keys travel as their bit pattern, so even values JSON cannot hold come back exactly
+        for key in [f64::NAN, -0.0, 0.1 + 0.2] {
+            node_infos[0].available_cpu_cores = key;
+            let cursor = NodeCursor::after(&node_infos[0], NodeSortBy::LeastAvailableCpu);
+            assert_eq!(NodeCursor::decode(&cursor.encode()), Ok(cursor));
+        }
+    }
+
+    #[test]
+    fn test_largest_pod_per_node() {
+        let mut finished = test_pod("report", "jobs", Some("node-a"), "8", "32Gi");
+        finished.status = Some(serde_json::from_value(serde_json::json!({ "phase": "Succeeded" })).unwrap());
+        let pods = vec![
+            test_pod("api", "shop", Some("node-a"), "2", "1Gi"),
+            test_pod("cache", "shop", Some("node-a"), "500m", "8Gi"),
+            test_pod("web", "shop", Some("node-b"), "1", "2Gi"),
+            test_pod("pending", "shop", None, "16", "64Gi"),
+            finished,
+        ];
+
+        let response = ClusterInsights::build_largest_pod_per_node(&pods, Lang::En);
+        assert_eq!(response.nodes.len(), 2);
+        let node_a = &response.nodes[0];
+        assert_eq!(node_a.node, "node-a");
+        assert_eq!(node_a.pod_count, 2);
+        assert_eq!(node_a.largest_by_cpu.name, "api");
+        assert_eq!(node_a.largest_by_cpu.cpu_requests_cores, 2.0);
+        assert_eq!(node_a.largest_by_memory.name, "cache");
+        assert_eq!(node_a.largest_by_memory.memory_requests_gb, 8.0);
+        assert_eq!(response.nodes[1].largest_by_cpu.name, "web");
+        assert!(response.explanation.contains("shop/api on node-a"));
+    }
+
+    #[test]
+    fn test_overpacked_nodes() {
+        let nodes = vec![test_node("node-a", "4", "16Gi"), test_node("node-b", "4", "16Gi")];
+        let pods = vec![
+            test_pod("bound-1", "shop", Some("node-a"), "3", "8Gi"),
+            test_pod("bound-2", "shop", Some("node-a"), "2", "4Gi"),
+            test_pod("web", "shop", Some("node-b"), "2", "4Gi"),
+        ];
+
+        let response = ClusterInsights::build_overpacked_nodes(&nodes, &pods, Lang::En);
+        assert_eq!(response.total_nodes_checked, 2);
+        assert_eq!(response.nodes.len(), 1);
+        let node_a = &response.nodes[0];
+        assert_eq!(node_a.node, "node-a");
+        assert_eq!(node_a.pod_count, 2);
+        assert_eq!(node_a.requested_cpu_cores, 5.0);
+        assert_eq!(node_a.cpu_overage_cores, 1.0);
+        assert_eq!(node_a.memory_overage_gb, 0.0);
+        assert!(response.explanation.contains("Worst: 'node-a'"));
+    }
+
+    #[test]
+    fn test_balance_report() {
+        let nodes = vec![
+            test_node("busy", "10", "10Gi"),
+            test_node("idle-a", "10", "10Gi"),
+            test_node("idle-b", "10", "10Gi"),
+        ];
+        let pods = vec![
+            test_pod("web", "shop", Some("busy"), "9", "8Gi"),
+            test_pod("cron", "shop", Some("idle-a"), "1", "1Gi"),
+            test_pod("agent", "shop", Some("idle-b"), "1", "1Gi"),
+        ];
+
+        let response = ClusterInsights::build_balance_report(&ClusterInsights::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None), Lang::En);
+        assert!(response.imbalanced);
+        assert!((response.cpu.mean_percent - 110.0 / 3.0).abs() < 1e-9);
+        assert!(response.cpu.coefficient_of_variation > 0.9);
+        assert_eq!(response.most_utilized.unwrap().node, "busy");
+        assert_eq!(response.least_utilized.unwrap().node, "idle-b");
+        assert!(response.explanation.contains("Consider rebalancing"));
+
+        let even = vec![test_pod("a", "shop", Some("busy"), "5", "5Gi"), test_pod("b", "shop", Some("idle-a"), "5", "5Gi")];
+        let response = ClusterInsights::build_balance_report(&ClusterInsights::build_node_infos(&nodes[..2], &even, ResourceBasis::Requests, None), Lang::En);
+        assert!(!response.imbalanced);
+        assert_eq!(response.cpu.std_dev_percent, 0.0);
+    }
+
+    #[test]
+    fn test_pod_churn() {
+        let now = chrono::Utc::now();
+        let created = |name: &str, namespace: &str, minutes_ago: i64| {
+            let mut pod = test_pod(name, namespace, Some("node-a"), "100m", "128Mi");
+            pod.metadata.creation_timestamp = Some(Time(now - chrono::Duration::minutes(minutes_ago)));
+            pod
+        };
+        let pods = vec![
+            created("job-1", "batch", 5),
+            created("job-2", "batch", 30),
+            created("web-1", "web", 20),
+            created("old", "web", 120),
+        ];
+        let deletion = |namespace: &str, minutes_ago: i64| -> Event {
+            serde_json::from_value(serde_json::json!({
+                "metadata": { "name": "rs.1", "namespace": namespace },
+                "involvedObject": { "kind": "ReplicaSet" },
+                "reason": "SuccessfulDelete",
+                "message": "Deleted pod: job-0",
+                "lastTimestamp": (now - chrono::Duration::minutes(minutes_ago)).to_rfc3339(),
+            })).unwrap()
+        };
+        let events = vec![deletion("batch", 10), deletion("batch", 90)];
+
+        let response = ClusterInsights::build_pod_churn(&pods, Some(&events), 60, now, Lang::En);
+        assert_eq!(response.created_pods, 3);
+        assert_eq!(response.deleted_pods, 1);
+        assert!(response.events_available);
+        assert_eq!(response.namespaces[0].namespace, "batch");
+        assert_eq!(response.namespaces[0].created_pods, 2);
+        assert_eq!(response.namespaces[0].churn_per_hour, 3.0);
+        assert_eq!(response.namespaces[1].created_pods, 1);
+
+        let response = ClusterInsights::build_pod_churn(&pods, None, 180, now, Lang::En);
+        assert_eq!(response.created_pods, 4);
+        assert!(!response.events_available);
+        assert!(response.explanation.contains("deletions are not counted"));
+    }
+
+    #[test]
+    fn test_gpu_report() {
+        let with_gpus = |mut node: Node, count: &str| {
+            let allocatable = node.status.as_mut().unwrap().allocatable.as_mut().unwrap();
+            allocatable.insert("nvidia.com/gpu".to_string(), Quantity(count.to_string()));
+            node
+        };
+        let requesting = |mut pod: Pod, resource: &str, count: &str| {
+            let resources = pod.spec.as_mut().unwrap().containers[0].resources.as_mut().unwrap();
+            resources.limits = Some(BTreeMap::from([(resource.to_string(), Quantity(count.to_string()))]));
+            pod
+        };
+        let nodes = vec![with_gpus(test_node("gpu-1", "32", "128Gi"), "8"), test_node("cpu-1", "8", "32Gi")];
+        let pods = vec![
+            requesting(test_pod("train", "ml", Some("gpu-1"), "4", "16Gi"), "nvidia.com/gpu", "3"),
+            requesting(test_pod("serve", "inference", Some("gpu-1"), "2", "8Gi"), "nvidia.com/gpu", "2"),
+            requesting(test_pod("queued", "ml", None, "4", "16Gi"), "amd.com/gpu", "1"),
+            test_pod("web", "shop", Some("cpu-1"), "1", "2Gi"),
+        ];
+
+        let response = ClusterInsights::build_gpu_report(&nodes, &pods, true, Lang::En);
+        assert_eq!(response.total_gpus, 8);
+        assert_eq!(response.requested_gpus, 5);
+        assert_eq!(response.idle_gpus, 3);
+        assert_eq!(response.pending_gpus, 1);
+        assert_eq!(response.nodes.len(), 1);
+        assert_eq!(response.nodes[0].idle_gpus, 3);
+        assert_eq!(response.namespaces[0].namespace, "ml");
+        assert_eq!(response.namespaces[0].requested_gpus, 3);
+        assert_eq!(response.pods.unwrap().len(), 3);
+        assert!(response.explanation.starts_with("5 of 8 GPUs on 1 nodes are requested and 3 are idle."));
+
+        assert_eq!(ClusterInsights::build_gpu_report(&nodes, &pods, false, Lang::En).pods, None);
+    }
+
+    #[test]
+    fn test_namespace_usage_combined() {
+        let namespaces: Vec<String> = ["shop", "shop-jobs", "shop-cache", "web"].iter().map(|n| n.to_string()).collect();
+        let pods = vec![
+            test_pod("api-1", "shop", Some("node-a"), "1", "2Gi"),
+            test_pod("api-2", "shop", Some("node-a"), "500m", "1Gi"),
+            test_pod("report-1", "shop-jobs", Some("node-b"), "250m", "512Mi"),
+            test_pod("redis-0", "shop-cache", Some("node-b"), "250m", "512Mi"),
+            test_pod("nginx-1", "web", Some("node-a"), "2", "4Gi"),
+        ];
+        let selected: Vec<String> = vec!["shop".to_string(), "shop-jobs".to_string(), "shop-cache".to_string()];
+
+        let response = ClusterInsights::build_namespace_usage(&namespaces, &pods, Some(&selected), false, Lang::En).unwrap();
+        assert_eq!(response.total_namespaces, 3);
+        assert!(response.namespaces.iter().all(|usage| usage.namespace != "web"));
+        let combined = response.combined.unwrap();
+        assert_eq!(combined.namespace, "shop, shop-jobs, shop-cache");
+        assert_eq!(combined.cpu_requests_cores, 2.0);
+        assert_eq!(combined.memory_requests_gb, 4.0);
+        assert_eq!(combined.pod_count, 4);
+        assert!(response.explanation.contains("Together, shop, shop-jobs, shop-cache have 4 pods requesting 2.00 cores CPU and 4.00 GB memory."));
+
+        let all = ClusterInsights::build_namespace_usage(&namespaces, &pods, None, false, Lang::En).unwrap();
+        assert_eq!(all.total_namespaces, 4);
+        assert!(all.combined.is_none());
+
+        let unknown = vec!["shop".to_string(), "nope".to_string()];
+        let error = ClusterInsights::build_namespace_usage(&namespaces, &pods, Some(&unknown), false, Lang::En).unwrap_err();
+        assert_eq!(error, "Namespaces not found: nope");
+    }
+
+    #[test]
+    fn test_deployment_scale() {
+        let deployment: Deployment = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "web", "namespace": "shop" },
+            "spec": {
+                "replicas": 3,
+                "selector": { "matchLabels": { "app": "web" } },
+                "template": {
+                    "metadata": { "labels": { "app": "web" } },
+                    "spec": {
+                        "nodeSelector": { "pool": "web" },
+                        "tolerations": [{ "key": "dedicated", "operator": "Equal", "value": "web", "effect": "NoSchedule" }],
+                        "containers": [{ "name": "main", "resources": { "requests": { "cpu": "1", "memory": "1Gi" } } }]
+                    }
+                }
+            },
+            "status": { "replicas": 3 }
+        })).unwrap();
+
+        let pool_node = |name: &str, pool: &str, taint: Option<(&str, &str)>| {
+            let mut node = test_node(name, "4", "8Gi");
+            node.metadata.labels = Some(BTreeMap::from([("pool".to_string(), pool.to_string())]));
+            node.spec = Some(serde_json::from_value(serde_json::json!({
+                "taints": taint.map(|(key, value)| vec![serde_json::json!({ "key": key, "value": value, "effect": "NoSchedule" })])
+            })).unwrap());
+            node
+        };
+        let nodes = vec![
+            pool_node("node-a", "web", Some(("dedicated", "web"))),
+            pool_node("node-b", "web", None),
+            pool_node("node-c", "batch", None),
+            pool_node("node-d", "web", Some(("gpu", "true"))),
+        ];
+        let pods = vec![
+            test_pod("web-1", "shop", Some("node-a"), "1", "1Gi"),
+            test_pod("web-2", "shop", Some("node-a"), "1", "1Gi"),
+            test_pod("web-3", "shop", Some("node-b"), "1", "1Gi"),
+        ];
+
+        let response = ClusterInsights::build_deployment_scale(&deployment, 10, &nodes, &pods, Lang::En).unwrap();
+        assert_eq!(response.current_replicas, 3);
+        assert_eq!(response.additional_replicas, 7);
+        assert_eq!(response.cpu_per_replica_cores, 1.0);
+        // node-a has 2 cores free and node-b 3; node-c and node-d are off limits
+        assert!(!response.fits);
+        assert_eq!(response.schedulable_replicas, 5);
+        assert_eq!(response.unschedulable_replicas, 2);
+        let placements: Vec<(&str, i32)> = response.placements.iter().map(|p| (p.node.as_str(), p.replicas)).collect();
+        assert_eq!(placements, vec![("node-b", 3), ("node-a", 2)]);
+        let excluded: Vec<(&str, &str)> = response.excluded_nodes.iter().map(|n| (n.node.as_str(), n.reason.as_str())).collect();
+        assert_eq!(excluded, vec![
+            ("node-c", "nodeSelector pool=web does not match"),
+            ("node-d", "untolerated taint gpu=true:NoSchedule"),
+        ]);
+
+        let within_reach = ClusterInsights::build_deployment_scale(&deployment, 8, &nodes, &pods, Lang::En).unwrap();
+        assert!(within_reach.fits);
+        assert_eq!(within_reach.schedulable_replicas, 5);
+
+        let already = ClusterInsights::build_deployment_scale(&deployment, 2, &nodes, &pods, Lang::En).unwrap();
+        assert_eq!(already.additional_replicas, 0);
+        assert!(already.fits && already.placements.is_empty());
+    }
+
+    #[test]
+    fn test_cronjob_capacity() {
+        let cronjob: CronJob = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "report", "namespace": "batch" },
+            "spec": {
+                "schedule": "*/5 * * * *",
+                "jobTemplate": {
+                    "spec": {
+                        "parallelism": 2,
+                        "template": {
+                            "spec": {
+                                "restartPolicy": "Never",
+                                "containers": [{ "name": "main", "resources": { "requests": { "cpu": "1", "memory": "2Gi" } } }]
                             }
                         }
                     }
                 }
             }
+        })).unwrap();
+        let nodes = vec![test_node("node-a", "4", "16Gi"), test_node("node-b", "4", "16Gi")];
+        let pods = vec![test_pod("web-1", "shop", Some("node-a"), "1", "2Gi")];
+
+        // 3 Jobs of 2 pods: 6 cores and 12 GB against 7 free cores
+        let response = ClusterInsights::build_cronjob_capacity(&cronjob, 3, 1000, &nodes, &pods, Lang::En).unwrap();
+        assert_eq!(response.concurrency_policy, "Allow");
+        assert_eq!((response.pods_per_job, response.pods_needed), (2, 6));
+        assert_eq!((response.cpu_per_pod_cores, response.memory_per_pod_gb), (1.0, 2.0));
+        assert_eq!((response.total_cpu_cores, response.total_memory_gb), (6.0, 12.0));
+        assert!(response.fits);
+        let placements: Vec<(&str, i32)> = response.placements.iter().map(|p| (p.node.as_str(), p.replicas)).collect();
+        assert_eq!(placements, vec![("node-a", 3), ("node-b", 3)]);
+
+        let peak = ClusterInsights::build_cronjob_capacity(&cronjob, 4, 1000, &nodes, &pods, Lang::En).unwrap();
+        assert!(!peak.fits);
+        assert_eq!((peak.schedulable_pods, peak.unschedulable_pods), (7, 1));
+
+        // 4 Jobs of 2 pods exceed a maximum of 6 pods even though 4 is within it
+        let too_many = ClusterInsights::build_cronjob_capacity(&cronjob, 4, 6, &nodes, &pods, Lang::En);
+        assert!(too_many.unwrap_err().contains("need 8 pods, more than the maximum of 6"));
+    }
+
+    #[test]
+    fn test_required_anti_affinity_limits_replicas() {
+        let anti_affinity = serde_json::json!({
+            "podAntiAffinity": {
+                "requiredDuringSchedulingIgnoredDuringExecution": [{
+                    "labelSelector": { "matchLabels": { "app": "web" } },
+                    "topologyKey": "kubernetes.io/hostname"
+                }]
+            }
+        });
+        let container = serde_json::json!({ "name": "main", "resources": { "requests": { "cpu": "1", "memory": "1Gi" } } });
+        let deployment: Deployment = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "web", "namespace": "shop" },
+            "spec": {
+                "replicas": 1,
+                "selector": { "matchLabels": { "app": "web" } },
+                "template": {
+                    "metadata": { "labels": { "app": "web" } },
+                    "spec": { "affinity": anti_affinity, "containers": [container] }
+                }
+            }
+        })).unwrap();
+        let nodes: Vec<Node> = ["node-a", "node-b", "node-c", "node-d"].iter()
+            .map(|name| {
+                let mut node = test_node(name, "16", "64Gi");
+                node.metadata.labels = Some(BTreeMap::from([("kubernetes.io/hostname".to_string(), name.to_string())]));
+                node
+            })
+            .collect();
+        let web: Pod = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "web-1", "namespace": "shop", "labels": { "app": "web" } },
+            "spec": { "nodeName": "node-a", "affinity": anti_affinity, "containers": [container] }
+        })).unwrap();
+        // Same labels in another namespace: outside the term's scope
+        let mut other = test_pod("web-x", "other", Some("node-b"), "1", "1Gi");
+        other.metadata.labels = Some(BTreeMap::from([("app".to_string(), "web".to_string())]));
+        let pods = vec![web.clone(), other];
+
+        // Resources fit all 9 additional replicas, but node-a already holds one and each node takes one
+        let response = ClusterInsights::build_deployment_scale(&deployment, 10, &nodes, &pods, Lang::En).unwrap();
+        assert!(!response.fits);
+        assert_eq!((response.schedulable_replicas, response.unschedulable_replicas), (3, 6));
+        assert_eq!(response.anti_affinity_limit, Some(3));
+        let placements: Vec<(&str, i32)> = response.placements.iter().map(|p| (p.node.as_str(), p.replicas)).collect();
+        assert_eq!(placements, vec![("node-b", 1), ("node-c", 1), ("node-d", 1)]);
+        assert!(response.explanation.contains("anti-affinity allows at most 3 more replicas"));
+
+        // The replica capacity check caps the reference pod the same way
+        assert_eq!(anti_affinity_limit(&web, &nodes, &pods), Some(3));
+        // Nodes without the topology label leave the term unbounded
+        assert_eq!(anti_affinity_limit(&web, &[test_node("node-e", "16", "64Gi")], &pods), None);
+    }
+
+    #[test]
+    fn test_full_report_sections() {
+        let nodes = vec![test_node("node-a", "4", "16Gi")];
+        let pods = vec![
+            test_pod("web-1", "shop", Some("node-a"), "1", "2Gi"),
+            test_pod("web-2", "shop", None, "1", "2Gi"),
+            test_pod("ghost", "shop", Some("node-gone"), "1", "2Gi"),
+        ];
+        let namespaces = vec!["shop".to_string()];
+        let selected = BTreeSet::from([ReportSection::Health, ReportSection::Capacity]);
+
+        let report = ClusterInsights::build_full_report(&nodes, &pods, &namespaces, &selected, Lang::En, chrono::Utc::now()).unwrap();
+        assert_eq!(report.sections, vec![ReportSection::Capacity, ReportSection::Health]);
+        assert_eq!(report.capacity.as_ref().unwrap().node_count, 1);
+        let health = report.health.as_ref().unwrap();
+        assert_eq!((health.pending_pod_count, health.orphaned_pod_count, health.stuck_pod_count), (1, 1, 0));
+        assert!(report.nodes.is_none() && report.namespaces.is_none() && report.top_pods.is_none());
+        assert_eq!(report.explanation.lines().count(), 2);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(json.get("capacity").is_some() && json.get("health").is_some());
+        for omitted in ["nodes", "namespaces", "top_pods"] {
+            assert!(json.get(omitted).is_none(), "{} should be omitted", omitted);
+        }
+
+        let all = BTreeSet::from(ReportSection::ALL);
+        let full = ClusterInsights::build_full_report(&nodes, &pods, &namespaces, &all, Lang::En, chrono::Utc::now()).unwrap();
+        assert!(full.nodes.is_some() && full.namespaces.is_some() && full.top_pods.is_some());
+        assert_eq!(full.nodes.unwrap().orphaned_pod_count, 1);
+    }
+
+    #[test]
+    fn test_priority_class_reclaims_lower_priority_pods() {
+        let with_priority = |mut pod: Pod, priority: i32| {
+            pod.spec.as_mut().unwrap().priority = Some(priority);
+            pod
+        };
+        let nodes = vec![test_node("node-a", "4", "16Gi")];
+        let pods = vec![
+            test_pod("batch-1", "batch", Some("node-a"), "3", "8Gi"),
+            with_priority(test_pod("api-1", "web", Some("node-a"), "1", "4Gi"), 1000),
+            with_priority(test_pod("pending-1", "batch", None, "1", "1Gi"), 0),
+        ];
+        let class = |preemption_policy: Option<&str>| -> PriorityClass {
+            serde_json::from_value(serde_json::json!({
+                "metadata": { "name": "high" },
+                "value": 1000,
+                "preemptionPolicy": preemption_policy
+            })).unwrap()
+        };
+
+        // The node is full, so 2 cores only fit by preempting batch-1 (priority 0); api-1 has the same priority
+        let mut capacity = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        assert!(!ClusterInsights::build_resource_fit_response(2.0, 4.0, &capacity, 0.0, Lang::En).fits);
+        let preemption = preemptible_capacity(&class(None), &pods, ResourceBasis::Requests, None);
+        assert!(preemption.preempts);
+        assert_eq!(preemption.preemptible_pod_count, 1);
+        assert_eq!((preemption.reclaimable_cpu_cores, preemption.reclaimable_memory_gb), (3.0, 8.0));
+        capacity.reclaim_preemptible(&preemption);
+        assert!(ClusterInsights::build_resource_fit_response(2.0, 4.0, &capacity, 0.0, Lang::En).fits);
+        assert!(messages::preemption(Lang::En, &preemption).contains("held by 1 lower-priority pods count as available"));
+
+        // preemptionPolicy Never keeps lower-priority pods in place
+        assert!(!preemptible_capacity(&class(Some("Never")), &pods, ResourceBasis::Requests, None).preempts);
+    }
+
+    #[test]
+    fn test_namespace_downsize() {
+        let mut capped = test_pod("api-1", "web", Some("node-a"), "1", "1Gi");
+        capped.spec.as_mut().unwrap().containers[0].resources.as_mut().unwrap().limits =
+            Some(BTreeMap::from([("cpu".to_string(), Quantity("2".to_string()))]));
+        let pods = vec![
+            test_pod("job-1", "batch", Some("node-a"), "6", "8Gi"),
+            test_pod("job-2", "batch", Some("node-b"), "4", "8Gi"),
+            capped,
+        ];
+        let usage: PodUsage = HashMap::from([
+            (("batch".to_string(), "job-1".to_string()), (2.0, 2.0)),
+            (("batch".to_string(), "job-2".to_string()), (1.0, 2.0)),
+            (("web".to_string(), "api-1".to_string()), (1.5, 0.5)),
+        ]);
+
+        // batch requests 10 cores and uses 3: 3.6 cores keep 20% headroom
+        let response = ClusterInsights::build_namespace_downsize(&pods, Ok(usage), 20.0, Lang::En);
+        assert_eq!(response.usage_source, UsageSource::Metrics);
+        let batch = &response.namespaces[0];
+        assert_eq!((batch.namespace.as_str(), batch.pod_count), ("batch", 2));
+        assert_eq!((batch.cpu_requests_cores, batch.cpu_usage_cores), (10.0, 3.0));
+        assert!((batch.recommended_cpu_requests_cores - 3.6).abs() < 1e-9);
+        assert!((batch.cpu_reducible_cores - 6.4).abs() < 1e-9);
+        assert!((batch.memory_reducible_gb - 11.2).abs() < 1e-9);
+        // web uses more CPU than it requests: its CPU stays, only memory shrinks
+        let web = &response.namespaces[1];
+        assert_eq!((web.recommended_cpu_requests_cores, web.cpu_reducible_cores), (1.0, 0.0));
+        assert!((web.recommended_memory_requests_gb - 0.6).abs() < 1e-9);
+        assert!((response.total_cpu_reducible_cores - 6.4).abs() < 1e-9);
+        assert!(response.explanation.contains("batch frees the most"));
+
+        // Without metrics the limits (or requests) are the usage ceiling and nothing is reducible
+        let degraded = ClusterInsights::build_namespace_downsize(&pods, Err("metrics-server not installed".to_string()), 20.0, Lang::En);
+        assert_eq!(degraded.usage_source, UsageSource::Limits);
+        let web = degraded.namespaces.iter().find(|n| n.namespace == "web").unwrap();
+        assert_eq!((web.cpu_usage_cores, web.cpu_reducible_cores), (2.0, 0.0));
+        assert_eq!((degraded.total_cpu_reducible_cores, degraded.total_memory_reducible_gb), (0.0, 0.0));
+        assert!(degraded.explanation.starts_with("Pod metrics are unavailable, so nothing can be reported as reducible"));
+    }
+
+    #[test]
+    fn test_namespace_share() {
+        let pods = vec![
+            test_pod("web", "shop", Some("node-a"), "3", "4Gi"),
+            test_pod("worker", "batch", Some("node-a"), "1", "6Gi"),
+            test_pod("db", "batch", Some("node-b"), "2", "2Gi"),
+        ];
+
+        let response = ClusterInsights::build_namespace_share(&pods, Lang::En);
+        assert_eq!(response.total_cpu_requests_cores, 6.0);
+        assert_eq!(response.total_memory_requests_gb, 12.0);
+        assert_eq!(response.namespaces.len(), 2);
+        let batch = &response.namespaces[0];
+        assert_eq!(batch.namespace, "batch");
+        assert_eq!(batch.pod_count, 2);
+        assert_eq!(batch.cpu_share_percent, 50.0);
+        assert!((batch.memory_share_percent - 200.0 / 3.0).abs() < 1e-9);
+        assert!((batch.resource_share_percent - 175.0 / 3.0).abs() < 1e-9);
+        let shop = &response.namespaces[1];
+        assert!((shop.resource_share_percent - 125.0 / 3.0).abs() < 1e-9);
+        assert!((batch.resource_share_percent + shop.resource_share_percent - 100.0).abs() < 1e-9);
+        assert!(response.explanation.contains("batch holds the largest share"));
+
+        // Two namespaces splitting the cluster evenly hold half each
+        let even = vec![
+            test_pod("a", "team-a", Some("node-a"), "2", "4Gi"),
+            test_pod("b", "team-b", Some("node-b"), "2", "4Gi"),
+        ];
+        let response = ClusterInsights::build_namespace_share(&even, Lang::En);
+        for share in &response.namespaces {
+            assert!((share.cpu_share_percent - 50.0).abs() < 1e-9);
+            assert!((share.memory_share_percent - 50.0).abs() < 1e-9);
+            assert!((share.resource_share_percent - 50.0).abs() < 1e-9);
+        }
+        assert_eq!(response.namespaces[0].namespace, "team-a");
+
+        assert!(ClusterInsights::build_namespace_share(&[], Lang::En).namespaces.is_empty());
+    }
+
+    #[test]
+    fn test_request_outliers() {
+        let container = |name: &str, cpu: &str, memory: &str| serde_json::json!({
+            "name": name, "resources": { "requests": { "cpu": cpu, "memory": memory } },
+        });
+        // Three containers each asking for a whole node
+        let etl: Pod = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "etl", "namespace": "batch" },
+            "spec": { "containers": [container("extract", "8", "32Gi"), container("transform", "8", "32Gi"), container("load", "8", "32Gi")] },
+        })).unwrap();
+        // A migration init container four times the app, below the median multiple
+        let migrate: Pod = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "api", "namespace": "shop" },
+            "spec": {
+                "nodeName": "node-a",
+                "initContainers": [container("migrate", "2", "1Gi")],
+                "containers": [container("api", "500m", "1Gi")],
+            },
+        })).unwrap();
+        let pods = vec![
+            test_pod("web-1", "shop", Some("node-a"), "500m", "1Gi"),
+            test_pod("web-2", "shop", Some("node-a"), "500m", "1Gi"),
+            test_pod("web-3", "shop", Some("node-b"), "500m", "1Gi"),
+            migrate,
+            etl,
+        ];
+
+        let response = ClusterInsights::build_request_outliers(&pods, 10.0, Lang::En);
+        assert_eq!(response.total_pods_checked, 5);
+        assert_eq!((response.median_cpu_cores, response.median_memory_gb), (0.5, 1.0));
+        assert_eq!(response.pods.len(), 2);
+
+        let etl = &response.pods[0];
+        assert_eq!((etl.name.as_str(), etl.node.as_str()), ("etl", "unscheduled"));
+        assert_eq!((etl.effective_cpu_cores, etl.effective_memory_gb), (24.0, 96.0));
+        assert_eq!((etl.cpu_median_multiple, etl.memory_median_multiple), (48.0, 96.0));
+        assert_eq!(etl.reasons, ["cpu 48.0x the median pod", "memory 96.0x the median pod"]);
+        assert_eq!(etl.containers.len(), 3);
+        assert!(etl.containers.iter().all(|c| c.kind == "container" && c.cpu_requests_cores == 8.0));
+
+        let api = &response.pods[1];
+        assert_eq!(api.name, "api");
+        assert_eq!((api.containers_cpu_cores, api.effective_cpu_cores), (0.5, 2.0));
+        assert_eq!(api.reasons, ["init container 'migrate' sets the effective cpu request"]);
+        assert_eq!(api.containers[0].kind, "init");
+        assert!(response.explanation.starts_with("2 of 5 pods have surprising effective requests: batch/etl"));
+
+        // A looser multiple still flags the init container, never the ordinary pods
+        let response = ClusterInsights::build_request_outliers(&pods, 100.0, Lang::En);
+        assert_eq!(response.pods.len(), 1);
+        assert_eq!(response.pods[0].name, "api");
+    }
+
+    #[test]
+    fn test_capacity_by_node_state() {
+        let with_ready = |mut node: Node, ready: &str| {
+            node.status.as_mut().unwrap().conditions = Some(vec![serde_json::from_value(serde_json::json!({
+                "type": "Ready", "status": ready,
+            })).unwrap()]);
+            node
+        };
+        let mut cordoned = with_ready(test_node("node-b", "8", "32Gi"), "True");
+        cordoned.spec.get_or_insert_with(Default::default).unschedulable = Some(true);
+        let nodes = vec![
+            with_ready(test_node("node-a", "4", "16Gi"), "True"),
+            cordoned,
+            with_ready(test_node("node-c", "4", "16Gi"), "Unknown"),
+        ];
+
+        let response = ClusterInsights::build_capacity_by_node_state(&nodes, Lang::En);
+        let states: Vec<(&str, usize, f64)> = response.states.iter().map(|s| (s.state.as_str(), s.node_count, s.cpu_cores)).collect();
+        assert_eq!(states, [("Ready", 1, 4.0), ("Cordoned", 1, 8.0), ("NotReady", 1, 4.0)]);
+        assert_eq!(response.states[1].nodes, ["node-b"]);
+        assert_eq!(response.states[2].nodes, ["node-c"]);
+        assert_eq!(response.states[0].cpu_percent, 25.0);
+        assert_eq!((response.total_cpu_cores, response.total_memory_gb), (16.0, 64.0));
+        assert_eq!((response.stuck_cpu_cores, response.stuck_memory_gb), (12.0, 48.0));
+        assert!(response.explanation.starts_with("3 nodes: 1 Ready (4.00 cores CPU, 16.00 GB memory)"));
+
+        // Without a Ready condition a node counts as not ready; an empty cluster still lists every state
+        let unknown = ClusterInsights::build_capacity_by_node_state(&[test_node("node-d", "2", "8Gi")], Lang::En);
+        assert_eq!(unknown.states[2].node_count, 1);
+        let empty = ClusterInsights::build_capacity_by_node_state(&[], Lang::En);
+        assert_eq!(empty.states.len(), 3);
+        assert!(empty.states.iter().all(|s| s.node_count == 0 && s.cpu_percent == 0.0));
+    }
+
+    #[test]
+    fn test_namespace_headroom() {
+        let nodes = vec![test_node("node-a", "8", "32Gi")];
+        let pods = vec![
+            test_pod("web", "web", Some("node-a"), "1", "4Gi"),
+            test_pod("etl-1", "batch", Some("node-a"), "2", "10Gi"),
+            test_pod("etl-2", "batch", Some("node-a"), "1", "10Gi"),
+        ];
+        let names = vec!["batch".to_string(), "web".to_string(), "idle".to_string()];
+
+        // Equal weights: 4 cores and 16 GB each
+        let response = ClusterInsights::build_namespace_headroom(&nodes, &pods, &names, &BTreeMap::new(), Lang::En).unwrap();
+        assert_eq!(response.total_weight, 2.0);
+        assert_eq!(response.over_fair_share_count, 1);
+        let batch = &response.namespaces[0];
+        assert_eq!(batch.namespace, "batch");
+        assert_eq!((batch.fair_share_cpu_cores, batch.fair_share_memory_gb), (4.0, 16.0));
+        assert_eq!((batch.cpu_headroom_cores, batch.memory_headroom_gb), (1.0, -4.0));
+        assert!(batch.over_fair_share);
+        let web = &response.namespaces[1];
+        assert_eq!((web.cpu_headroom_cores, web.memory_headroom_gb), (3.0, 12.0));
+        assert!(!web.over_fair_share);
+        assert!(response.explanation.contains("1 namespace(s) request more than their fair share: batch"));
+
+        // A weighted namespace without pods still takes its share
+        let weights = BTreeMap::from([("batch".to_string(), 2.0), ("idle".to_string(), 1.0)]);
+        let response = ClusterInsights::build_namespace_headroom(&nodes, &pods, &names, &weights, Lang::En).unwrap();
+        assert_eq!(response.total_weight, 4.0);
+        let share = |namespace: &str| response.namespaces.iter().find(|h| h.namespace == namespace).unwrap().fair_share_cpu_cores;
+        assert_eq!((share("batch"), share("web"), share("idle")), (4.0, 2.0, 2.0));
+
+        let missing = BTreeMap::from([("ghost".to_string(), 1.0)]);
+        let error = ClusterInsights::build_namespace_headroom(&nodes, &pods, &names, &missing, Lang::En).unwrap_err();
+        assert_eq!(error, "Namespaces not found: ghost");
+    }
+
+    #[test]
+    fn test_recommend_node_size() {
+        let nodes = vec![test_node("small", "2", "8Gi"), test_node("large", "4", "16Gi")];
+        let mut agent = test_pod("log-agent-x", "logging", Some("small"), "500m", "1Gi");
+        agent.metadata.owner_references = Some(vec![serde_json::from_value(serde_json::json!({
+            "apiVersion": "apps/v1",
+            "kind": "DaemonSet",
+            "name": "log-agent",
+            "uid": "1"
+        })).unwrap()]);
+        let pods = vec![agent, test_pod("web-1", "shop", Some("large"), "1", "2Gi")];
+        let spec: PodSpec = serde_json::from_value(serde_json::json!({
+            "containers": [{ "name": "app", "resources": { "requests": { "cpu": "2", "memory": "4Gi" } } }]
+        })).unwrap();
+        let workload = pod_effective_requests(&Pod { spec: Some(spec), ..Default::default() });
+        assert_eq!(workload, (2.0, 4.0));
+
+        // Without DaemonSets the workload alone fits the small node
+        let response = ClusterInsights::build_node_size_recommendation(workload, &nodes, &pods[1..], (0.0, 0.0), Lang::En);
+        assert_eq!((response.min_allocatable_cpu_cores, response.min_allocatable_memory_gb), (2.0, 4.0));
+        assert_eq!(response.large_enough_nodes, ["small", "large"]);
+
+        // The log agent on every node bumps the size past the small node
+        let response = ClusterInsights::build_node_size_recommendation(workload, &nodes, &pods, (0.1, 1.0), Lang::En);
+        assert_eq!((response.daemonset_cpu_overhead_cores, response.daemonset_memory_overhead_gb), (0.5, 1.0));
+        assert_eq!((response.min_allocatable_cpu_cores, response.min_allocatable_memory_gb), (2.5, 5.0));
+        assert_eq!((response.min_capacity_cpu_cores, response.min_capacity_memory_gb), (2.6, 6.0));
+        assert_eq!(response.large_enough_nodes, ["large"]);
+        assert!(response.explanation.starts_with("A node needs at least 2.50 cores CPU and 5.00 GB memory allocatable"));
+    }
+
+    #[test]
+    fn test_orphaned_pods() {
+        let nodes = vec![test_node("node-a", "4", "16Gi")];
+        let mut finished = test_pod("job-1", "batch", Some("node-gone"), "1", "1Gi");
+        finished.status = Some(serde_json::from_value(serde_json::json!({ "phase": "Succeeded" })).unwrap());
+        let pods = vec![
+            test_pod("web-1", "shop", Some("node-a"), "1", "2Gi"),
+            test_pod("web-2", "shop", Some("node-gone"), "500m", "1Gi"),
+            test_pod("pending", "shop", None, "1", "1Gi"),
+            finished,
+        ];
+
+        let response = ClusterInsights::build_orphaned_pods(&nodes, &pods, Lang::En);
+        assert_eq!(response.pods.len(), 1);
+        assert_eq!(response.pods[0].name, "web-2");
+        assert_eq!(response.pods[0].node, "node-gone");
+        assert_eq!(response.missing_nodes, ["node-gone"]);
+        assert_eq!(response.total_cpu_requests_cores, 0.5);
+        assert!(response.explanation.starts_with("1 pods are bound to 1 node(s) that no longer exist: node-gone."));
+
+        // The ghost allocation lands on no node of the breakdown
+        let node_infos = ClusterInsights::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None);
+        let breakdown = ClusterInsights::build_node_breakdown(node_infos, NodeSortBy::Name, 0, None, ResourceBasis::Requests, 1, Lang::En);
+        assert_eq!(breakdown.nodes[0].allocated_cpu_cores, 1.0);
+        assert_eq!(breakdown.nodes[0].pod_count, 1);
+        assert_eq!(breakdown.orphaned_pod_count, 1);
+        assert!(breakdown.explanation.ends_with("1 pod(s) bound to nodes that no longer exist are left out; see find_orphaned_pods."));
+
+        assert!(ClusterInsights::build_orphaned_pods(&nodes, &pods[..1], Lang::En).pods.is_empty());
+    }
+
+    #[test]
+    fn test_node_groups() {
+        let typed = |name: &str, instance_type: &str, cpu: &str, memory: &str| {
+            let mut node = test_node(name, cpu, memory);
+            node.metadata.labels = Some(BTreeMap::from([("node.kubernetes.io/instance-type".to_string(), instance_type.to_string())]));
+            node
+        };
+        let nodes = vec![
+            typed("worker-1", "m5.xlarge", "4", "16Gi"),
+            typed("worker-2", "m5.xlarge", "4", "16Gi"),
+            typed("worker-3", "m5.xlarge", "4", "16Gi"),
+            typed("big-1", "m5.2xlarge", "8", "32Gi"),
+        ];
+        let pods = vec![
+            test_pod("web-1", "shop", Some("worker-1"), "1", "2Gi"),
+            test_pod("web-2", "shop", Some("worker-2"), "1", "2Gi"),
+            test_pod("web-3", "shop", Some("worker-3"), "1100m", "2Gi"),
+        ];
+        let node_infos = ClusterInsights::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None);
+
+        let groups = ClusterInsights::build_node_groups(&nodes, &node_infos);
+        assert_eq!(groups.len(), 2);
+        let workers = &groups[0];
+        assert_eq!(workers.instance_type, "m5.xlarge");
+        assert_eq!(workers.nodes, ["worker-1", "worker-2", "worker-3"]);
+        assert_eq!(workers.pod_count, 3);
+        assert_eq!((workers.available_cpu_cores.min, workers.available_cpu_cores.max), (2.9, 3.0));
+        assert!((workers.available_cpu_cores.avg - 8.9 / 3.0).abs() < 1e-9);
+        assert_eq!(workers.available_memory_gb.avg, 14.0);
+        assert_eq!((groups[1].instance_type.as_str(), groups[1].node_count), ("m5.2xlarge", 1));
+
+        // A worker in another utilization band splits off
+        let busy = vec![test_pod("web-1", "shop", Some("worker-1"), "3", "2Gi")];
+        let node_infos = ClusterInsights::build_node_infos(&nodes, &busy, ResourceBasis::Requests, None);
+        assert_eq!(ClusterInsights::build_node_groups(&nodes, &node_infos).len(), 3);
+    }
+
+    #[test]
+    fn test_eviction_risk() {
+        let with_storage = |mut node: Node| {
+            let status = node.status.as_mut().unwrap();
+            status.allocatable.as_mut().unwrap().insert("ephemeral-storage".to_string(), Quantity("100Gi".to_string()));
+            node
+        };
+        let mut pressured = with_storage(test_node("node-c", "4", "8Gi"));
+        pressured.status.as_mut().unwrap().conditions = Some(vec![serde_json::from_value(serde_json::json!({
+            "type": "DiskPressure", "status": "True"
+        })).unwrap()]);
+        let nodes = vec![
+            with_storage(test_node("node-a", "4", "8Gi")),
+            with_storage(test_node("node-b", "4", "8Gi")),
+            pressured,
+        ];
+        let gib = 1024 * 1024 * 1024;
+        let memory = NodeUsageBytes::from([
+            ("node-a".to_string(), 7 * gib + gib / 2),
+            ("node-b".to_string(), 2 * gib),
+            ("node-c".to_string(), gib),
+        ]);
+
+        let response = ClusterInsights::build_eviction_risk(
+            &nodes, Ok(memory.clone()), Err("kubelet stats unavailable".to_string()), 10.0, Lang::En,
+        );
+        assert!(response.memory_metrics_available);
+        assert!(!response.storage_metrics_available);
+        assert_eq!(response.warnings.len(), 1);
+        assert_eq!(response.at_risk_nodes, 2);
+
+        let node_a = &response.nodes[0];
+        assert_eq!(node_a.node, "node-a");
+        assert!(node_a.at_risk);
+        assert_eq!(node_a.memory_usage_percent, Some(93.75));
+        assert_eq!(node_a.resources_at_risk, vec!["memory"]);
+        assert_eq!(node_a.storage_usage_percent, None);
+        assert_eq!(response.nodes[1].node, "node-c");
+        assert_eq!(response.nodes[1].pressure_conditions, vec!["DiskPressure"]);
+        assert!(!response.nodes[2].at_risk);
+        assert!(response.explanation.starts_with("2 of 3 nodes are within 10% of allocatable memory or ephemeral storage or report pressure: \
+                                                  node-a (memory 93.8%), node-c (DiskPressure)."));
+
+        // With a 5% margin 93.75% is no longer flagged, while node-c still reports DiskPressure
+        let narrow = ClusterInsights::build_eviction_risk(&nodes, Ok(memory), Ok(NodeUsageBytes::new()), 5.0, Lang::En);
+        assert!(narrow.nodes.iter().all(|n| n.resources_at_risk.is_empty()));
+        assert_eq!(narrow.at_risk_nodes, 1);
+    }
+
+    #[test]
+    fn test_stranded_capacity() {
+        let nodes = vec![test_node("cpu-heavy", "16", "16Gi"), test_node("balanced", "4", "16Gi")];
+        let pods = vec![test_pod("cache", "shop", Some("cpu-heavy"), "1", "15Gi")];
+        let node_infos = ClusterInsights::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None);
+
+        // 15 free cores but only 1 GB free memory: at 4 GB per core, a quarter core is usable
+        let response = ClusterInsights::build_stranded_capacity(&node_infos, &pods, Some(0.25), Lang::En).unwrap();
+        let worst = &response.nodes[0];
+        assert_eq!(worst.node, "cpu-heavy");
+        assert_eq!(worst.usable_cpu_cores, 0.25);
+        assert_eq!(worst.usable_memory_gb, 1.0);
+        assert_eq!(worst.stranded_cpu_cores, 14.75);
+        assert_eq!(worst.stranded_memory_gb, 0.0);
+        assert!((worst.stranded_percent - 14.75 / 16.0 * 100.0).abs() < 1e-9);
+        assert_eq!(response.nodes[1].stranded_percent, 0.0);
+        assert_eq!(response.total_stranded_cpu_cores, 14.75);
+
+        // CPU-heavy pods (1 core per GB) can use the whole free GB with one core
+        let cpu_heavy = ClusterInsights::build_stranded_capacity(&node_infos, &pods, Some(1.0), Lang::En).unwrap();
+        assert_eq!(cpu_heavy.nodes[0].stranded_cpu_cores, 14.0);
+        // ... but strand memory on the balanced node: 4 free cores cover only 4 of its 16 GB
+        let balanced = cpu_heavy.nodes.iter().find(|n| n.node == "balanced").unwrap();
+        assert_eq!(balanced.stranded_memory_gb, 12.0);
+        assert!(cpu_heavy.total_stranded_cpu_cores < response.total_stranded_cpu_cores);
+
+        // The default ratio is the average request ratio of the pods: 1 core / 15 GB
+        let default_ratio = ClusterInsights::build_stranded_capacity(&node_infos, &pods, None, Lang::En).unwrap();
+        assert!((default_ratio.cpu_to_memory_ratio - 1.0 / 15.0).abs() < 1e-9);
+        assert!(ClusterInsights::build_stranded_capacity(&[], &[], None, Lang::En).is_err());
+    }
+
+    #[test]
+    fn test_max_pods_of_size_fragmentation() {
+        let nodes = vec![test_node("node-a", "4", "16Gi"), test_node("node-b", "4", "16Gi")];
+        let pods = vec![
+            test_pod("web-a", "shop", Some("node-a"), "1", "2Gi"),
+            test_pod("web-b", "shop", Some("node-b"), "1", "2Gi"),
+        ];
+
+        // 6 cores are free in total, but only 3 per node: one 2-core pod per node
+        let node_infos = ClusterInsights::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None);
+        let response = ClusterInsights::build_max_pods_of_size(&node_infos, 2.0, 4.0, Lang::En);
+        assert_eq!(response.naive_max_pods, 3);
+        assert_eq!(response.max_pods, 2);
+        assert_eq!(response.limiting_resource, "cpu");
+        assert_eq!(response.nodes.iter().map(|n| n.pods).collect::<Vec<_>>(), vec![1, 1]);
+        assert!(response.explanation.contains("would suggest 3"));
+
+        let memory_bound = ClusterInsights::build_max_pods_of_size(&node_infos, 0.0, 8.0, Lang::En);
+        assert_eq!(memory_bound.max_pods, 2);
+        assert_eq!(memory_bound.limiting_resource, "memory");
+    }
+
+    #[test]
+    fn test_node_acceptance_matrix() {
+        let nodes = vec![test_node("node-b", "4", "16Gi"), test_node("node-a", "8", "32Gi")];
+        let pods = vec![
+            test_pod("busy", "shop", Some("node-b"), "3", "8Gi"),
+            test_pod("light", "shop", Some("node-a"), "1", "4Gi"),
+        ];
+        let sizes = vec![
+            PodSize { name: "small".to_string(), cpu_cores: 0.5, memory_gb: 1.0 },
+            PodSize { name: "large".to_string(), cpu_cores: 4.0, memory_gb: 16.0 },
+        ];
+
+        let node_infos = ClusterInsights::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None);
+        let response = ClusterInsights::build_node_acceptance_matrix(&node_infos, &sizes, Lang::En);
+        assert_eq!(response.sizes, vec!["small", "large"]);
+        assert_eq!(response.nodes[0].node, "node-a");
+        assert_eq!(response.nodes[0].accepts, vec![true, true]);
+        // node-b has 1 core and 8 GB left: only the small size fits
+        assert_eq!(response.nodes[1].node, "node-b");
+        assert_eq!(response.nodes[1].accepts, vec![true, false]);
+        assert_eq!(response.accepting_nodes, vec![2, 1]);
+        assert_eq!(response.explanation, "Of 2 nodes, 'small' fits on 2, 'large' fits on 1.");
+    }
+
+    #[test]
+    fn test_usage_by_label() {
+        let labeled = |name: &str, team: Option<&str>, cpu: &str, memory: &str| {
+            let mut pod = test_pod(name, "default", Some("node-a"), cpu, memory);
+            pod.metadata.labels = team.map(|t| BTreeMap::from([("team".to_string(), t.to_string())]));
+            pod
+        };
+        let pods = vec![
+            labeled("pay-1", Some("payments"), "1", "2Gi"),
+            labeled("pay-2", Some("payments"), "500m", "1Gi"),
+            labeled("search-1", Some("search"), "2", "4Gi"),
+            labeled("batch-1", None, "250m", "512Mi"),
+        ];
+
+        let response = ClusterInsights::build_usage_by_label("team", &pods, Lang::En);
+        assert_eq!(response.total_groups, 3);
+        let values: Vec<&str> = response.groups.iter().map(|g| g.value.as_str()).collect();
+        assert_eq!(values, vec!["search", "payments", "unlabeled"]);
+
+        let payments = &response.groups[1];
+        assert_eq!(payments.pod_count, 2);
+        assert!((payments.cpu_requests_cores - 1.5).abs() < 1e-9);
+        assert!((payments.memory_requests_gb - 3.0).abs() < 1e-9);
+        assert_eq!(response.groups[2].pod_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_usage_for_selector() {
+        let service = tower::service_fn(move |_request: http::Request<kube::client::Body>| async move {
+            let labeled = |mut pod: Pod, labels: serde_json::Value| {
+                pod.metadata.labels = Some(serde_json::from_value(labels).unwrap());
+                pod
+            };
+            let mut finished = labeled(test_pod("migrate", "logging", Some("node-a"), "1", "1Gi"), serde_json::json!({"addon": "logging"}));
+            finished.status = Some(serde_json::from_value(serde_json::json!({"phase": "Succeeded"})).unwrap());
+            let pods = serde_json::json!({
+                "apiVersion": "v1", "kind": "PodList", "metadata": {},
+                "items": [
+                    labeled(test_pod("collector", "logging", Some("node-a"), "500m", "1Gi"), serde_json::json!({"addon": "logging"})),
+                    labeled(test_pod("forwarder", "kube-system", Some("node-b"), "250m", "512Mi"), serde_json::json!({"addon": "logging", "tier": "edge"})),
+                    labeled(test_pod("web", "shop", Some("node-b"), "2", "4Gi"), serde_json::json!({"addon": "none"})),
+                    test_pod("bare", "shop", Some("node-b"), "1", "1Gi"),
+                    finished,
+                ],
+            });
+            Ok::<_, std::convert::Infallible>(http::Response::new(kube::client::Body::from(serde_json::to_vec(&pods).unwrap())))
+        });
+        let cluster_insights = ClusterInsights::with_client(Client::new(service, "default"), Duration::ZERO);
+
+        let response = cluster_insights.get_usage_for_selector_internal("addon=logging", Lang::En, false).await.unwrap();
+        assert_eq!(response.pod_count, 2);
+        assert_eq!(response.namespaces, vec!["kube-system", "logging"]);
+        assert!((response.cpu_requests_cores - 0.75).abs() < 1e-9);
+        assert!((response.memory_requests_gb - 1.5).abs() < 1e-9);
+        assert!(response.explanation.starts_with("2 pods match 'addon=logging' across 2 namespaces"));
+
+        // Set-based and negated requirements are matched like the API server does
+        for (selector, pod_count) in [("addon in (logging, none)", 3), ("addon,tier notin (edge)", 2), ("addon!=logging", 2), ("!addon", 1), ("tier==edge", 1)] {
+            let response = cluster_insights.get_usage_for_selector_internal(selector, Lang::En, false).await.unwrap();
+            assert_eq!(response.pod_count, pod_count, "{}", selector);
         }
-        
-        let available_cpu_cores = total_cpu_cores - allocated_cpu_cores;
-        let available_memory_gb = total_memory_gb - allocated_memory_gb;
-        
-        let node_count = nodes.items.len();
-        
-        let explanation = format!(
-            "Cluster has {} nodes. Total capacity: {:.2} CPU cores, {:.2} GB memory. \
-             Allocated (requests): {:.2} CPU cores ({:.1}%), {:.2} GB memory ({:.1}%). \
-             Available: {:.2} CPU cores, {:.2} GB memory.",
-            node_count,
-            total_cpu_cores, total_memory_gb,
-            allocated_cpu_cores, (allocated_cpu_cores / total_cpu_cores * 100.0),
-            allocated_memory_gb, (allocated_memory_gb / total_memory_gb * 100.0),
-            available_cpu_cores, available_memory_gb
-        );
-        
-        Ok(ClusterCapacityResponse {
-            total_cpu_cores,
-            total_memory_gb,
-            allocated_cpu_cores,
-            allocated_memory_gb,
-            available_cpu_cores,
-            available_memory_gb,
-            node_count,
-            explanation,
-        })
+        assert_eq!(parse_label_selector("tier in cache"), Err("Invalid label selector 'tier in cache'".to_string()));
+        assert!(parse_label_selector("addon=logging,").is_err());
     }
-    
-    /// Check if resources fit
-    async fn check_resource_fit_internal(cpu_cores: f64, memory_gb: f64) -> Result<CheckResourceFitResponse, String> {
-        let capacity = Self::get_cluster_capacity_internal().await?;
-        
-        let fits = capacity.available_cpu_cores >= cpu_cores && capacity.available_memory_gb >= memory_gb;
-        
-        let cpu_utilization_percent = if capacity.total_cpu_cores > 0.0 {
-            (capacity.allocated_cpu_cores + cpu_cores) / capacity.total_cpu_cores * 100.0
-        } else {
-            0.0
+
+    #[test]
+    fn test_usage_by_workload_kind() {
+        let owned_by = |pod: &mut Pod, kind: &str, name: &str| {
+            pod.metadata.owner_references = Some(vec![serde_json::from_value(serde_json::json!({
+                "apiVersion": "apps/v1", "kind": kind, "name": name, "uid": name, "controller": true
+            })).unwrap()]);
         };
-        
-        let memory_utilization_percent = if capacity.total_memory_gb > 0.0 {
-            (capacity.allocated_memory_gb + memory_gb) / capacity.total_memory_gb * 100.0
-        } else {
-            0.0
+        let mut web_1 = test_pod("web-abc-1", "shop", Some("node-a"), "1", "2Gi");
+        let mut web_2 = test_pod("web-abc-2", "shop", Some("node-a"), "1", "2Gi");
+        let mut agent = test_pod("agent-x", "logging", Some("node-a"), "250m", "512Mi");
+        owned_by(&mut web_1, "ReplicaSet", "web-abc");
+        owned_by(&mut web_2, "ReplicaSet", "web-abc");
+        owned_by(&mut agent, "DaemonSet", "agent");
+
+        let mut replica_set = ObjectMeta {
+            name: Some("web-abc".to_string()),
+            namespace: Some("shop".to_string()),
+            ..Default::default()
         };
-        
-        let explanation = if fits {
-            format!(
-                "Resources FIT in cluster. Requested: {:.2} CPU cores, {:.2} GB memory. \
-                 Available: {:.2} CPU cores, {:.2} GB memory. \
-                 After allocation, cluster would be at {:.1}% CPU and {:.1}% memory utilization.",
-                cpu_cores, memory_gb,
-                capacity.available_cpu_cores, capacity.available_memory_gb,
-                cpu_utilization_percent, memory_utilization_percent
-            )
-        } else {
-            let cpu_shortage = if capacity.available_cpu_cores < cpu_cores {
-                format!("CPU shortage: {:.2} cores needed but only {:.2} available. ", 
-                    cpu_cores - capacity.available_cpu_cores, capacity.available_cpu_cores)
-            } else {
-                String::new()
-            };
-            let memory_shortage = if capacity.available_memory_gb < memory_gb {
-                format!("Memory shortage: {:.2} GB needed but only {:.2} GB available.",
-                    memory_gb - capacity.available_memory_gb, capacity.available_memory_gb)
-            } else {
-                String::new()
+        replica_set.owner_references = Some(vec![serde_json::from_value(serde_json::json!({
+            "apiVersion": "apps/v1", "kind": "Deployment", "name": "web", "uid": "web", "controller": true
+        })).unwrap()]);
+        let owners = owner_index([("ReplicaSet", &replica_set)]);
+
+        let response = ClusterInsights::build_usage_by_workload_kind(&[web_1, web_2, agent], &owners, Lang::En);
+        assert_eq!(response.total_pods, 3);
+        let kinds: Vec<&str> = response.kinds.iter().map(|k| k.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["Deployment", "DaemonSet"]);
+
+        let deployment = &response.kinds[0];
+        assert_eq!(deployment.pod_count, 2);
+        assert!((deployment.cpu_requests_cores - 2.0).abs() < 1e-9);
+        assert!((deployment.memory_requests_gb - 4.0).abs() < 1e-9);
+        assert_eq!(response.kinds[1].pod_count, 1);
+    }
+
+    #[test]
+    fn test_replica_consistency() {
+        let owned_by = |pod: &mut Pod, kind: &str, name: &str| {
+            pod.metadata.owner_references = Some(vec![serde_json::from_value(serde_json::json!({
+                "apiVersion": "apps/v1", "kind": kind, "name": name, "uid": name, "controller": true
+            })).unwrap()]);
+        };
+        let with_memory_limit = |mut pod: Pod, limit: &str| {
+            let resources = pod.spec.as_mut().unwrap().containers[0].resources.as_mut().unwrap();
+            resources.limits = Some(BTreeMap::from([("memory".to_string(), Quantity(limit.to_string()))]));
+            pod
+        };
+        let mut web_1 = with_memory_limit(test_pod("web-1", "shop", Some("node-a"), "500m", "1Gi"), "1Gi");
+        let mut web_2 = with_memory_limit(test_pod("web-2", "shop", Some("node-a"), "0.5", "1024Mi"), "1Gi");
+        let mut web_3 = with_memory_limit(test_pod("web-3", "shop", Some("node-b"), "500m", "1Gi"), "2Gi");
+        let mut db_1 = test_pod("db-0", "shop", Some("node-a"), "1", "4Gi");
+        let mut db_2 = test_pod("db-1", "shop", Some("node-b"), "1", "4Gi");
+        let mut single = test_pod("batch-1", "shop", Some("node-b"), "1", "1Gi");
+        owned_by(&mut web_1, "ReplicaSet", "web-abc");
+        owned_by(&mut web_2, "ReplicaSet", "web-abc");
+        owned_by(&mut web_3, "ReplicaSet", "web-def");
+        owned_by(&mut db_1, "StatefulSet", "db");
+        owned_by(&mut db_2, "StatefulSet", "db");
+        owned_by(&mut single, "Job", "batch");
+
+        // Both ReplicaSets belong to the web Deployment, so a partial rollout shows up
+        let replica_set = |name: &str| {
+            let mut meta = ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some("shop".to_string()),
+                ..Default::default()
             };
-            
-            format!(
-                "Resources DO NOT FIT in cluster. Requested: {:.2} CPU cores, {:.2} GB memory. \
-                 Available: {:.2} CPU cores, {:.2} GB memory. {}{}",
-                cpu_cores, memory_gb,
-                capacity.available_cpu_cores, capacity.available_memory_gb,
-                cpu_shortage, memory_shortage
-            )
+            meta.owner_references = Some(vec![serde_json::from_value(serde_json::json!({
+                "apiVersion": "apps/v1", "kind": "Deployment", "name": "web", "uid": "web", "controller": true
+            })).unwrap()]);
+            meta
         };
-        
-        Ok(CheckResourceFitResponse {
-            fits,
-            available_cpu_cores: capacity.available_cpu_cores,
-            available_memory_gb: capacity.available_memory_gb,
-            cpu_utilization_percent,
-            memory_utilization_percent,
-            explanation,
-        })
+        let (web_abc, web_def) = (replica_set("web-abc"), replica_set("web-def"));
+        let owners = owner_index([("ReplicaSet", &web_abc), ("ReplicaSet", &web_def)]);
+
+        let pods = vec![db_1, db_2, web_1, web_2, web_3, single];
+        let response = ClusterInsights::build_replica_consistency(None, &pods, &owners, Lang::En);
+        assert_eq!(response.workloads_checked, 2);
+        assert_eq!(response.inconsistent_workloads, 1);
+
+        let web = &response.workloads[0];
+        assert_eq!((web.owner_kind.as_str(), web.owner_name.as_str()), ("Deployment", "web"));
+        assert_eq!(web.replica_count, 3);
+        assert!(!web.consistent);
+        // "0.5" vs "500m" and "1024Mi" vs "1Gi" are the same amounts
+        assert_eq!(web.differences.len(), 1);
+        let difference = &web.differences[0];
+        assert_eq!((difference.kind.as_str(), difference.resource.as_str()), ("limit", "memory"));
+        assert_eq!(difference.values[0].value, "1Gi");
+        assert_eq!(difference.values[0].pods, vec!["web-1", "web-2"]);
+        assert_eq!(difference.values[1].value, "2Gi");
+        assert_eq!(difference.values[1].pods, vec!["web-3"]);
+
+        let db = &response.workloads[1];
+        assert_eq!(db.owner_name, "db");
+        assert!(db.consistent);
+        assert!(response.explanation.starts_with("1 of 2 workloads have replicas with differing requests or limits: Deployment shop/web."));
+
+        // Without the Deployment indexed, ReplicaSets of one rollout still group under the normalized name
+        let mut api_1 = test_pod("api-7d9f8b6c5-x2k4q", "shop", Some("node-a"), "1", "1Gi");
+        let mut api_2 = test_pod("api-5c4d3f8b9-k7wzp", "shop", Some("node-b"), "2", "1Gi");
+        owned_by(&mut api_1, "ReplicaSet", "api-7d9f8b6c5");
+        owned_by(&mut api_2, "ReplicaSet", "api-5c4d3f8b9");
+        let response = ClusterInsights::build_replica_consistency(None, &[api_1, api_2], &OwnerIndex::new(), Lang::En);
+        assert_eq!(response.workloads_checked, 1);
+        assert_eq!((response.workloads[0].owner_name.as_str(), response.workloads[0].replica_count), ("api", 2));
+        assert!(!response.workloads[0].consistent);
     }
-    
-    /// Get node breakdown
-    async fn get_node_breakdown_internal() -> Result<NodeBreakdownResponse, String> {
-        let client = Client::try_default().await
-            .map_err(|e| format!("Failed to create Kubernetes client: {}", e))?;
-        
-        let nodes_api: Api<Node> = Api::all(client.clone());
-        let pods_api: Api<Pod> = Api::all(client.clone());
-        
-        let nodes = nodes_api.list(&Default::default()).await
-            .map_err(|e| format!("Failed to list nodes: {}", e))?;
-        
-        let pods = pods_api.list(&Default::default()).await
-            .map_err(|e| format!("Failed to list pods: {}", e))?;
-        
-        // Build node resource map
-        let mut node_infos = Vec::new();
-        
-        for node in &nodes.items {
-            let name = node.metadata.name.clone().unwrap_or_default();
-            
-            let mut total_cpu_cores = 0.0;
-            let mut total_memory_gb = 0.0;
-            
-            if let Some(status) = &node.status {
-                if let Some(capacity) = &status.capacity {
-                    if let Some(cpu) = capacity.get("cpu") {
-                        total_cpu_cores = quantity_to_cores(cpu);
-                    }
-                    if let Some(memory) = capacity.get("memory") {
-                        total_memory_gb = quantity_to_gb(memory);
-                    }
-                }
-            }
-            
-            let mut allocated_cpu_cores = 0.0;
-            let mut allocated_memory_gb = 0.0;
-            let mut pod_count = 0;
-            
-            for pod in &pods.items {
-                if let Some(spec) = &pod.spec {
-                    if spec.node_name.as_deref() == Some(&name) {
-                        pod_count += 1;
-                        
-                        for container in &spec.containers {
-                            if let Some(resources) = &container.resources {
-                                if let Some(requests) = &resources.requests {
-                                    if let Some(cpu) = requests.get("cpu") {
-                                        allocated_cpu_cores += quantity_to_cores(cpu);
-                                    }
-                                    if let Some(memory) = requests.get("memory") {
-                                        allocated_memory_gb += quantity_to_gb(memory);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            
-            let available_cpu_cores = total_cpu_cores - allocated_cpu_cores;
-            let available_memory_gb = total_memory_gb - allocated_memory_gb;
-            
-            node_infos.push(NodeInfo {
-                name,
-                total_cpu_cores,
-                total_memory_gb,
-                allocated_cpu_cores,
-                allocated_memory_gb,
-                available_cpu_cores,
-                available_memory_gb,
-                pod_count,
-            });
-        }
-        
-        let explanation = format!(
-            "Cluster has {} nodes. Each node shows total capacity, allocated resources (requests), \
-             available resources, and pod count.",
-            node_infos.len()
-        );
-        
-        Ok(NodeBreakdownResponse {
-            total_nodes: node_infos.len(),
-            nodes: node_infos,
-            explanation,
-        })
+
+    #[test]
+    fn test_image_distribution() {
+        let with_image = |name: &str, namespace: &str, image: &str| {
+            let mut pod = test_pod(name, namespace, Some("node-a"), "100m", "128Mi");
+            pod.spec.as_mut().unwrap().containers[0].image = Some(image.to_string());
+            pod
+        };
+        let pods = vec![
+            with_image("web-1", "web", "nginx:1.27"),
+            with_image("web-2", "shop", "nginx:1.27"),
+            with_image("cache-1", "web", "redis:7"),
+        ];
+
+        let response = ClusterInsights::build_image_distribution(None, &pods, Lang::En);
+        assert_eq!(response.total_images, 2);
+        assert_eq!(response.total_pods, 3);
+        assert_eq!(response.images[0].image, "nginx:1.27");
+        assert_eq!(response.images[0].pod_count, 2);
+        assert_eq!(response.images[0].namespaces, vec!["shop", "web"]);
+        assert_eq!(response.images[1].pod_count, 1);
     }
-    
-    /// Get namespace usage
-    async fn get_namespace_usage_internal() -> Result<NamespaceUsageResponse, String> {
-        let client = Client::try_default().await
-            .map_err(|e| format!("Failed to create Kubernetes client: {}", e))?;
-        
-        let namespaces_api: Api<Namespace> = Api::all(client.clone());
-        let pods_api: Api<Pod> = Api::all(client.clone());
-        
-        let namespaces = namespaces_api.list(&Default::default()).await
-            .map_err(|e| format!("Failed to list namespaces: {}", e))?;
-        
-        let pods = pods_api.list(&Default::default()).await
-            .map_err(|e| format!("Failed to list pods: {}", e))?;
-        
-        let mut namespace_usage_map: HashMap<String, NamespaceUsage> = HashMap::new();
-        
-        // Initialize namespace usage
-        for ns in &namespaces.items {
-            let name = ns.metadata.name.clone().unwrap_or_default();
-            namespace_usage_map.insert(name.clone(), NamespaceUsage {
-                namespace: name,
-                cpu_requests_cores: 0.0,
-                memory_requests_gb: 0.0,
-                cpu_limits_cores: 0.0,
-                memory_limits_gb: 0.0,
-                pod_count: 0,
-            });
-        }
-        
-        // Aggregate pod resources by namespace
-        for pod in &pods.items {
-            let ns_name = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
-            
-            let usage = namespace_usage_map.entry(ns_name.clone()).or_insert_with(|| NamespaceUsage {
-                namespace: ns_name.clone(),
-                cpu_requests_cores: 0.0,
-                memory_requests_gb: 0.0,
-                cpu_limits_cores: 0.0,
-                memory_limits_gb: 0.0,
-                pod_count: 0,
-            });
-            
-            usage.pod_count += 1;
-            
-            if let Some(spec) = &pod.spec {
-                for container in &spec.containers {
-                    if let Some(resources) = &container.resources {
-                        if let Some(requests) = &resources.requests {
-                            if let Some(cpu) = requests.get("cpu") {
-                                usage.cpu_requests_cores += quantity_to_cores(cpu);
-                            }
-                            if let Some(memory) = requests.get("memory") {
-                                usage.memory_requests_gb += quantity_to_gb(memory);
-                            }
-                        }
-                        if let Some(limits) = &resources.limits {
-                            if let Some(cpu) = limits.get("cpu") {
-                                usage.cpu_limits_cores += quantity_to_cores(cpu);
-                            }
-                            if let Some(memory) = limits.get("memory") {
-                                usage.memory_limits_gb += quantity_to_gb(memory);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        
-        let mut namespace_usages: Vec<NamespaceUsage> = namespace_usage_map.into_values().collect();
-        namespace_usages.sort_by(|a, b| b.cpu_requests_cores.partial_cmp(&a.cpu_requests_cores).unwrap());
-        
-        let total_namespaces = namespace_usages.len();
-        
-        let explanation = format!(
-            "Cluster has {} namespaces. Resource usage shows CPU/memory requests and limits for each namespace, \
-             sorted by CPU requests (descending).",
-            total_namespaces
+
+    #[test]
+    fn test_pod_resource_stats_sorted_by_cpu_usage() {
+        let pods = vec![
+            test_pod("big-request", "default", Some("node-a"), "2", "1Gi"),
+            test_pod("hot", "default", Some("node-a"), "100m", "1Gi"),
+            test_pod("idle", "default", Some("node-a"), "500m", "1Gi"),
+        ];
+        let usage: PodUsage = HashMap::from([
+            (("default".to_string(), "big-request".to_string()), (0.05, 0.5)),
+            (("default".to_string(), "hot".to_string()), (1.5, 0.75)),
+        ]);
+
+        let response = ClusterInsights::build_pod_resource_stats(&pods, false, false, PodSortBy::CpuUsage, Some(Ok(usage)), Lang::En);
+        let names: Vec<&str> = response.top_pods.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["hot", "big-request", "idle"]);
+        assert_eq!(response.top_pods[0].cpu_usage_millicores, Some(1500));
+        assert_eq!(response.top_pods[0].memory_usage_mb, Some(768));
+        assert_eq!(response.top_pods[2].cpu_usage_millicores, Some(0));
+        assert_eq!(response.sorted_by, "CPU usage (descending)");
+        assert!(response.warning.is_none());
+
+        let fallback = ClusterInsights::build_pod_resource_stats(
+            &pods, false, false, PodSortBy::CpuUsage, Some(Err("metrics API not found".to_string())), Lang::En,
         );
-        
-        Ok(NamespaceUsageResponse {
-            total_namespaces,
-            namespaces: namespace_usages,
-            explanation,
-        })
+        assert_eq!(fallback.top_pods[0].name, "big-request");
+        assert_eq!(fallback.top_pods[0].cpu_usage_millicores, None);
+        assert!(fallback.warning.unwrap().contains("metrics API not found"));
     }
-    
-    /// Get pod resource stats
-    async fn get_pod_resource_stats_internal() -> Result<PodResourceStatsResponse, String> {
-        let client = Client::try_default().await
-            .map_err(|e| format!("Failed to create Kubernetes client: {}", e))?;
-        
-        let pods_api: Api<Pod> = Api::all(client.clone());
-        let pods = pods_api.list(&Default::default()).await
-            .map_err(|e| format!("Failed to list pods: {}", e))?;
-        
-        let mut pod_infos = Vec::new();
-        
-        for pod in &pods.items {
-            let name = pod.metadata.name.clone().unwrap_or_default();
-            let namespace = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
-            let node = pod.spec.as_ref()
-                .and_then(|s| s.node_name.clone())
-                .unwrap_or_else(|| "unscheduled".to_string());
-            
-            let mut cpu_requests_millicores = 0i64;
-            let mut memory_requests_mb = 0i64;
-            let mut cpu_limits_millicores = 0i64;
-            let mut memory_limits_mb = 0i64;
-            
-            if let Some(spec) = &pod.spec {
-                for container in &spec.containers {
-                    if let Some(resources) = &container.resources {
-                        if let Some(requests) = &resources.requests {
-                            if let Some(cpu) = requests.get("cpu") {
-                                cpu_requests_millicores += quantity_to_millicores(cpu);
-                            }
-                            if let Some(memory) = requests.get("memory") {
-                                memory_requests_mb += quantity_to_mb(memory);
-                            }
-                        }
-                        if let Some(limits) = &resources.limits {
-                            if let Some(cpu) = limits.get("cpu") {
-                                cpu_limits_millicores += quantity_to_millicores(cpu);
-                            }
-                            if let Some(memory) = limits.get("memory") {
-                                memory_limits_mb += quantity_to_mb(memory);
-                            }
-                        }
-                    }
-                }
-            }
-            
-            pod_infos.push(PodResourceInfo {
-                name,
-                namespace,
-                cpu_requests_millicores,
-                memory_requests_mb,
-                cpu_limits_millicores,
-                memory_limits_mb,
-                node,
-            });
-        }
-        
-        // Sort by CPU requests (descending)
-        pod_infos.sort_by(|a, b| b.cpu_requests_millicores.cmp(&a.cpu_requests_millicores));
-        
-        let total_pods = pod_infos.len();
-        
-        // Take top 20 pods
-        let top_pods: Vec<PodResourceInfo> = pod_infos.into_iter().take(20).collect();
-        
-        let explanation = format!(
-            "Showing top 20 pods (out of {}) by CPU requests. Each pod shows CPU/memory requests and limits, \
-             along with the node it's scheduled on.",
-            total_pods
-        );
-        
-        Ok(PodResourceStatsResponse {
-            top_pods,
-            total_pods,
-            sorted_by: "CPU requests (descending)".to_string(),
-            explanation,
-        })
+
+    #[test]
+    fn test_structured_sort_matches_ordering() {
+        let pods = vec![
+            test_pod("small", "web", Some("node-a"), "100m", "1Gi"),
+            test_pod("big", "batch", Some("node-a"), "2", "1Gi"),
+            test_pod("mid", "web", Some("node-a"), "500m", "4Gi"),
+        ];
+        let usage: PodUsage = HashMap::from([
+            (("web".to_string(), "small".to_string()), (0.1, 3.0)),
+            (("batch".to_string(), "big".to_string()), (0.2, 0.5)),
+        ]);
+
+        let by_requests = ClusterInsights::build_pod_resource_stats(&pods, false, false, PodSortBy::CpuRequests, None, Lang::En);
+        assert_eq!(by_requests.sort, SortSpec {
+            key: "cpu_requests_millicores".to_string(),
+            order: SortOrder::Descending,
+            basis: ResourceBasis::Requests,
+        });
+        let keys: Vec<i64> = by_requests.top_pods.iter().map(|p| p.cpu_requests_millicores).collect();
+        assert_eq!(keys, vec![2000, 500, 100]);
+
+        let by_memory = ClusterInsights::build_pod_resource_stats(&pods, false, false, PodSortBy::MemoryUsage, Some(Ok(usage.clone())), Lang::En);
+        assert_eq!((by_memory.sort.key.as_str(), by_memory.sort.basis), ("memory_usage_mb", ResourceBasis::Usage));
+        let keys: Vec<Option<i64>> = by_memory.top_pods.iter().map(|p| p.memory_usage_mb).collect();
+        assert_eq!(keys, vec![Some(3072), Some(512), Some(0)]);
+
+        // Falling back to requests reports the applied sort, not the requested one
+        let fallback = ClusterInsights::build_pod_resource_stats(&pods, false, false, PodSortBy::CpuUsage, Some(Err("no metrics".to_string())), Lang::En);
+        assert_eq!(fallback.sort.key, "cpu_requests_millicores");
+
+        let names = vec!["web".to_string(), "batch".to_string()];
+        let namespaces = ClusterInsights::build_namespace_usage(&names, &pods, None, false, Lang::En).unwrap();
+        assert_eq!((namespaces.sort.key.as_str(), namespaces.sort.order), ("cpu_requests_cores", SortOrder::Descending));
+        let keys: Vec<f64> = namespaces.namespaces.iter().map(|n| n.cpu_requests_cores).collect();
+        assert_eq!(keys, vec![2.0, 0.6]);
+    }
+
+    #[test]
+    fn test_terminal_pods_listed_only_with_include_terminal() {
+        let mut finished = test_pod("migrate-1", "jobs", Some("node-a"), "2", "4Gi");
+        finished.status = Some(serde_json::from_value(serde_json::json!({ "phase": "Succeeded" })).unwrap());
+        let pods = vec![finished, test_pod("web-1", "jobs", Some("node-a"), "500m", "1Gi")];
+
+        let default = ClusterInsights::build_pod_resource_stats(&pods, false, false, PodSortBy::CpuRequests, None, Lang::En);
+        assert_eq!(default.total_pods, 1);
+        assert_eq!(default.top_pods[0].name, "web-1");
+        assert_eq!(default.top_pods[0].phase, None);
+
+        let audit = ClusterInsights::build_pod_resource_stats(&pods, false, true, PodSortBy::CpuRequests, None, Lang::En);
+        assert_eq!(audit.total_pods, 2);
+        assert_eq!(audit.top_pods[0].name, "migrate-1");
+        assert_eq!(audit.top_pods[0].phase.as_deref(), Some("Succeeded"));
+
+        // The finished pod is listed but never counted towards the namespace usage
+        let usages = ClusterInsights::build_namespace_usages(&[], &pods);
+        assert_eq!(usages[0].pod_count, 1);
+        assert_eq!(usages[0].cpu_requests_cores, 0.5);
+        let terminal_pods = ClusterInsights::build_terminal_pods(&pods);
+        assert_eq!(terminal_pods.len(), 1);
+        assert_eq!(terminal_pods[0].phase, "Succeeded");
+    }
+
+    #[test]
+    fn test_pod_metrics_usage() {
+        let data = serde_json::json!({
+            "containers": [
+                { "name": "app", "usage": { "cpu": "250000000n", "memory": "512Mi" } },
+                { "name": "sidecar", "usage": { "cpu": "50m", "memory": "256Mi" } }
+            ]
+        });
+        let (cpu, memory) = pod_metrics_usage(&data);
+        assert_eq!(cpu, 0.3);
+        assert_eq!(memory, 0.75);
+    }
+
+    #[test]
+    fn test_find_stuck_pods() {
+        let now = chrono::Utc::now();
+        let terminating = |name: &str, seconds_ago: i64| {
+            let mut pod = test_pod(name, "default", Some("node-a"), "1", "2Gi");
+            pod.metadata.deletion_timestamp = Some(Time(now - chrono::Duration::seconds(seconds_ago)));
+            pod.metadata.finalizers = Some(vec!["example.com/cleanup".to_string()]);
+            pod
+        };
+        let pods = vec![
+            terminating("stuck-for-days", 3 * 24 * 3600),
+            terminating("just-deleted", 10),
+            test_pod("running", "default", Some("node-a"), "2", "4Gi"),
+        ];
+
+        let response = ClusterInsights::build_stuck_pods(&pods, 300, now, Lang::En);
+        assert_eq!(response.total_stuck, 1);
+        assert_eq!(response.pods[0].name, "stuck-for-days");
+        assert_eq!(response.pods[0].terminating_seconds, 3 * 24 * 3600);
+        assert_eq!(response.pods[0].finalizers, vec!["example.com/cleanup"]);
+        assert_eq!(response.held_cpu_cores, 1.0);
+        assert_eq!(response.held_memory_gb, 2.0);
+    }
+
+    #[test]
+    fn test_n_minus_one_overcommits_remaining_nodes() {
+        let nodes = vec![
+            test_node("small-a", "4", "16Gi"),
+            test_node("big", "16", "64Gi"),
+            test_node("small-b", "4", "16Gi"),
+        ];
+        let mut agent = test_pod("agent-big", "logging", Some("big"), "500m", "1Gi");
+        agent.metadata.owner_references = Some(vec![serde_json::from_value(serde_json::json!({
+            "apiVersion": "apps/v1", "kind": "DaemonSet", "name": "agent", "uid": "agent"
+        })).unwrap()]);
+        let pods = vec![
+            test_pod("api-1", "shop", Some("big"), "3", "4Gi"),
+            test_pod("api-2", "shop", Some("big"), "3", "4Gi"),
+            test_pod("worker-1", "shop", Some("big"), "1", "2Gi"),
+            test_pod("web-a", "shop", Some("small-a"), "2", "4Gi"),
+            test_pod("web-b", "shop", Some("small-b"), "2", "4Gi"),
+            agent,
+        ];
+
+        let response = ClusterInsights::build_n_minus_one(&nodes, &pods, 1, Lang::En).unwrap();
+        assert_eq!(response.lost_nodes, vec!["big"]);
+        assert!(!response.survives);
+        assert_eq!(response.remaining_nodes, 2);
+        assert_eq!(response.displaced_pods, 3);
+        // Each small node has 2 cores free: the 3-core pods fit nowhere, the 1-core pod fits
+        assert_eq!(response.unplaceable_pods, vec!["shop/api-1", "shop/api-2"]);
+        assert!((response.requested_cpu_cores - 11.0).abs() < 1e-9);
+        assert!((response.cpu_utilization_percent - 137.5).abs() < 1e-9);
+        assert!(response.explanation.starts_with("The cluster does not survive losing its largest node (big)"));
+
+        let two_lost = ClusterInsights::build_n_minus_one(&nodes, &pods, 2, Lang::En).unwrap();
+        assert_eq!(two_lost.lost_nodes, vec!["big", "small-a"]);
+        assert!(ClusterInsights::build_n_minus_one(&nodes, &pods, 3, Lang::En).is_err());
+    }
+
+    #[test]
+    fn test_overhead_breakdown() {
+        let nodes = vec![test_node("node-a", "10", "40Gi")];
+        let mut agent = test_pod("log-agent-x", "logging", Some("node-a"), "500m", "2Gi");
+        agent.metadata.owner_references = Some(vec![serde_json::from_value(serde_json::json!({
+            "apiVersion": "apps/v1",
+            "kind": "DaemonSet",
+            "name": "log-agent",
+            "uid": "1"
+        })).unwrap()]);
+        let pods = vec![
+            test_pod("coredns-1", "kube-system", Some("node-a"), "1", "2Gi"),
+            agent,
+            test_pod("web-1", "default", Some("node-a"), "2", "8Gi"),
+        ];
+        let system_namespaces = vec!["kube-system".to_string()];
+
+        let response = ClusterInsights::build_overhead_breakdown(&nodes, &pods, &system_namespaces, Lang::En);
+        assert_eq!(response.system.pod_count, 2);
+        assert_eq!(response.system.cpu_cores, 1.5);
+        assert_eq!(response.system.memory_gb, 4.0);
+        assert_eq!(response.system.cpu_percent, 15.0);
+        assert_eq!(response.system.memory_percent, 10.0);
+        assert_eq!(response.workloads.pod_count, 1);
+        assert_eq!(response.workloads.cpu_cores, 2.0);
+        assert_eq!(response.workloads.memory_percent, 20.0);
+
+        // Pending and finished pods hold no node capacity, so the split adds up to the allocated totals
+        let mut finished = test_pod("report-1", "default", Some("node-a"), "3", "4Gi");
+        finished.status = Some(serde_json::from_value(serde_json::json!({ "phase": "Succeeded" })).unwrap());
+        let mut pods = pods;
+        pods.push(finished);
+        pods.push(test_pod("web-2", "default", None, "1", "1Gi"));
+        let response = ClusterInsights::build_overhead_breakdown(&nodes, &pods, &system_namespaces, Lang::En);
+        let capacity = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        assert_eq!(response.workloads.pod_count, 1);
+        assert_eq!(response.system.cpu_cores + response.workloads.cpu_cores, capacity.allocated_cpu_cores);
+        assert_eq!(response.system.memory_gb + response.workloads.memory_gb, capacity.allocated_memory_gb);
+    }
+
+    #[test]
+    fn test_empty_cluster_explanations_drop_percentages() {
+        let capacity = ClusterInsights::compute_cluster_capacity(&[], &[], ResourceBasis::Requests, None);
+        assert!(capacity.explanation.contains("No CPU capacity detected. No memory capacity detected."));
+        assert!(!capacity.explanation.contains('%'));
+
+        let fit = ClusterInsights::build_resource_fit_response(0.0, 0.0, &capacity, config::DEFAULT_FIT_TOLERANCE, Lang::En);
+        assert!(fit.explanation.contains("No CPU capacity detected."));
+        assert!(!fit.explanation.contains('%'));
+
+        let overhead = ClusterInsights::build_overhead_breakdown(&[], &[], &["kube-system".to_string()], Lang::En);
+        assert!(overhead.explanation.contains("No CPU capacity detected."));
+        assert!(!overhead.explanation.contains('%'));
+
+        // Memory-only capacity keeps its percentage
+        let capacity = messages::cluster_capacity(Lang::En, &test_capacity(0.0, 16.0, 0.0, 4.0));
+        assert!(capacity.contains("4.00 GB memory (25.0%)"));
+        assert!(capacity.ends_with("No CPU capacity detected."));
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(vec![]), 0.0);
+        assert_eq!(median(vec![8.0, 4.0, 16.0]), 8.0);
+        assert_eq!(median(vec![4.0, 8.0, 16.0, 32.0]), 12.0);
+    }
+
+    #[test]
+    fn test_pod_without_spec_is_unscheduled_and_requests_nothing() {
+        let specless: Pod = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "partial", "namespace": "shop" }
+        })).unwrap();
+        let pods = vec![test_pod("web-1", "shop", Some("node-a"), "1", "2Gi"), specless.clone()];
+        let nodes = vec![test_node("node-a", "4", "16Gi")];
+
+        let info = ClusterInsights::build_pod_resource_info(&specless, true);
+        assert_eq!(info.node, "unscheduled");
+        assert_eq!((info.cpu_requests_millicores, info.memory_requests_mb), (0, 0));
+
+        let capacity = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        assert_eq!(capacity.allocated_cpu_cores, 1.0);
+        assert_eq!(capacity.allocated_memory_gb, 2.0);
+
+        let node_infos = ClusterInsights::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None);
+        assert_eq!(node_infos[0].pod_count, 1);
+
+        let distribution = ClusterInsights::build_namespace_node_distribution(&pods, Lang::En);
+        let shop = &distribution.namespaces[0];
+        assert_eq!(shop.nodes["unscheduled"].pod_count, 1);
+        assert_eq!(shop.nodes["unscheduled"].cpu_requests_cores, 0.0);
+
+        let by_label = ClusterInsights::build_usage_by_label("team", &pods, Lang::En);
+        assert_eq!(by_label.groups[0].pod_count, 2);
+        assert!((by_label.groups[0].cpu_requests_cores - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pending_pods_are_demand_not_allocation() {
+        let nodes = vec![test_node("node-a", "4", "16Gi")];
+        let mut pending = test_pod("web-2", "shop", None, "2", "4Gi");
+        pending.status = Some(serde_json::from_value(serde_json::json!({ "phase": "Pending" })).unwrap());
+        let pods = vec![test_pod("web-1", "shop", Some("node-a"), "1", "2Gi"), pending];
+
+        let capacity = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        assert_eq!(capacity.allocated_cpu_cores, 1.0);
+        assert_eq!(capacity.allocated_memory_gb, 2.0);
+        assert_eq!(capacity.available_cpu_cores, 3.0);
+        assert_eq!(capacity.pending_cpu_cores, 2.0);
+        assert_eq!(capacity.pending_memory_gb, 4.0);
+        assert_eq!(capacity.pending_pod_count, 1);
+        assert!(capacity.explanation.contains("1 pending pod(s) wait for a node"));
+    }
+
+    #[test]
+    fn test_terminal_pods_hold_no_capacity() {
+        let nodes = vec![test_node("node-a", "4", "16Gi")];
+        let mut finished = test_pod("job-1", "batch", Some("node-a"), "2", "4Gi");
+        finished.status = Some(serde_json::from_value(serde_json::json!({ "phase": "Succeeded" })).unwrap());
+        let pods = vec![test_pod("web-1", "web", Some("node-a"), "1", "2Gi"), finished];
+
+        let capacity = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        assert_eq!((capacity.allocated_cpu_cores, capacity.allocated_memory_gb), (1.0, 2.0));
+        assert_eq!(capacity.pending_pod_count, 0);
+
+        let node_infos = ClusterInsights::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None);
+        assert_eq!((node_infos[0].allocated_cpu_cores, node_infos[0].pod_count), (1.0, 1));
+
+        let mut remaining = pods.clone();
+        let freed = exclude_namespace_pods(&mut remaining, &["batch".to_string()], ResourceBasis::Requests, None);
+        assert_eq!(freed, Resources::default());
+        assert_eq!(ClusterInsights::build_pod_accounting(&pods[1], Lang::En).counted_as, "Ignored");
+    }
+
+    #[test]
+    fn test_node_packing_density() {
+        let mut dense = test_node("node-a", "4", "16Gi");
+        dense.status.as_mut().unwrap().allocatable.as_mut().unwrap().insert("pods".to_string(), Quantity("10".to_string()));
+        let nodes = vec![dense, test_node("node-b", "4", "16Gi"), test_node("node-c", "4", "16Gi")];
+        let mut pods: Vec<Pod> = (0..4).map(|i| test_pod(&format!("web-{}", i), "shop", Some("node-a"), "750m", "2Gi")).collect();
+        pods.push(test_pod("api-1", "shop", Some("node-b"), "2", "8Gi"));
+        pods.push(test_pod("cron-1", "shop", Some("node-c"), "500m", "1Gi"));
+
+        let packing = ClusterInsights::build_node_packing(&nodes, &pods, DEFAULT_OVER_PACKED_PERCENT, DEFAULT_UNDER_PACKED_PERCENT, Lang::En);
+        let node_a = &packing.nodes[0];
+        assert_eq!(node_a.node, "node-a");
+        assert_eq!((node_a.pod_count, node_a.max_pods, node_a.pod_density_percent), (4, Some(10), Some(40.0)));
+        assert_eq!((node_a.avg_pod_cpu_cores, node_a.avg_pod_memory_gb), (0.75, 2.0));
+        assert_eq!((node_a.cpu_utilization_percent, node_a.memory_utilization_percent), (75.0, 50.0));
+        assert_eq!((node_a.packing_efficiency_percent, node_a.imbalance_percent), (62.5, 25.0));
+        assert_eq!(node_a.packing, PackingState::Normal);
+
+        let node_b = &packing.nodes[1];
+        assert_eq!((node_b.max_pods, node_b.pod_density_percent), (None, None));
+        assert_eq!(node_b.packing_efficiency_percent, 50.0);
+        assert_eq!(packing.nodes[2].packing, PackingState::UnderPacked);
+        assert_eq!(packing.under_packed_nodes, vec!["node-c".to_string()]);
+        assert!(packing.over_packed_nodes.is_empty());
+
+        // 5.5 of 12 cores and 17 of 48 GB requested
+        assert!((packing.cluster_packing_efficiency_percent - (5.5 / 12.0 + 17.0 / 48.0) * 50.0).abs() < 1e-9);
+
+        let tight = ClusterInsights::build_node_packing(&nodes, &pods, 70.0, DEFAULT_UNDER_PACKED_PERCENT, Lang::En);
+        assert_eq!(tight.over_packed_nodes, vec!["node-a".to_string()]);
+        assert!(tight.explanation.contains("Over-packed (CPU or memory at 70% or more): node-a"));
+    }
+
+    #[test]
+    fn test_stranded_gpus_on_cpu_saturated_node() {
+        let with_gpus = |mut node: Node, count: &str| {
+            let allocatable = node.status.as_mut().unwrap().allocatable.as_mut().unwrap();
+            allocatable.insert("nvidia.com/gpu".to_string(), Quantity(count.to_string()));
+            node
+        };
+        let requesting = |mut pod: Pod, count: &str| {
+            let resources = pod.spec.as_mut().unwrap().containers[0].resources.as_mut().unwrap();
+            resources.limits = Some(BTreeMap::from([("nvidia.com/gpu".to_string(), Quantity(count.to_string()))]));
+            pod
+        };
+        let nodes = vec![
+            with_gpus(test_node("gpu-1", "8", "64Gi"), "4"),
+            with_gpus(test_node("gpu-2", "16", "64Gi"), "4"),
+            test_node("cpu-1", "8", "32Gi"),
+        ];
+        let pods = vec![
+            requesting(test_pod("train", "ml", Some("gpu-1"), "2", "8Gi"), "1"),
+            test_pod("etl", "data", Some("gpu-1"), "6", "8Gi"),
+            requesting(test_pod("serve", "inference", Some("gpu-2"), "2", "8Gi"), "1"),
+        ];
+
+        let response = ClusterInsights::build_stranded_gpus(&nodes, &pods, None, None, Lang::En);
+        assert_eq!((response.cpu_cores_per_gpu, response.memory_gb_per_gpu), (Some(2.0), Some(8.0)));
+        assert_eq!(response.nodes.len(), 2);
+        let gpu_1 = &response.nodes[0];
+        assert_eq!(gpu_1.node, "gpu-1");
+        assert_eq!((gpu_1.idle_gpus, gpu_1.schedulable_idle_gpus, gpu_1.stranded_gpus), (3, 0, 3));
+        assert_eq!(gpu_1.cpu_utilization_percent, 100.0);
+        assert_eq!(gpu_1.limiting_resource.as_deref(), Some("cpu"));
+        let gpu_2 = &response.nodes[1];
+        assert_eq!((gpu_2.idle_gpus, gpu_2.schedulable_idle_gpus, gpu_2.stranded_gpus), (3, 3, 0));
+        assert_eq!(gpu_2.limiting_resource, None);
+        assert_eq!(response.stranded_nodes, vec!["gpu-1".to_string()]);
+        assert_eq!((response.idle_gpus, response.stranded_gpus), (6, 3));
+        assert!(response.explanation.starts_with("3 of 6 idle GPUs are stranded"));
+
+        // Without GPU pods each GPU needs its node's share: 2 cores on gpu-1, which has room for one
+        let response = ClusterInsights::build_stranded_gpus(&nodes, &pods[1..2], None, None, Lang::En);
+        assert_eq!(response.cpu_cores_per_gpu, None);
+        assert_eq!(response.nodes[0].node, "gpu-1");
+        assert_eq!((response.nodes[0].idle_gpus, response.nodes[0].schedulable_idle_gpus, response.nodes[0].stranded_gpus), (4, 1, 3));
+    }
+
+    #[test]
+    fn test_normalize_workload_name() {
+        assert_eq!(normalize_workload_name("payments-7d9f8b-abcde"), "payments");
+        assert_eq!(normalize_workload_name("payments-worker-6c8d7f9b5-x2k4q"), "payments-worker");
+        assert_eq!(normalize_workload_name("node-exporter-xk2pd"), "node-exporter");
+        assert_eq!(normalize_workload_name("backup-28471234-7kq2m"), "backup");
+        assert_eq!(normalize_workload_name("my-redis"), "my-redis");
+        assert_eq!(normalize_workload_name("db-0"), "db-0");
+        assert_eq!(normalize_workload_name("api-gateway"), "api-gateway");
+
+        let pod = test_pod("payments-worker-6c8d7f9b5-x2k4q", "shop", None, "1", "1Gi");
+        assert!(pod_matches_app(&pod, "payments", false));
+        assert!(!pod_matches_app(&pod, "payments", true));
+        assert!(pod_matches_app(&pod, "payments-worker", true));
     }
 
-    /// Check replica capacity
-    async fn check_replica_capacity_internal(
-        app_name: String,
-        namespace: String,
-        replica_count: i32,
-    ) -> Result<CheckReplicaCapacityResponse, String> {
-        if replica_count <= 0 {
-            return Err("Replica count must be positive".to_string());
-        }
-        
-        let client = Client::try_default().await
-            .map_err(|e| format!("Failed to create Kubernetes client: {}", e))?;
-        
-        let pods_api: Api<Pod> = Api::namespaced(client.clone(), &namespace);
-        let pods = pods_api.list(&Default::default()).await
-            .map_err(|e| format!("Failed to list pods in namespace {}: {}", namespace, e))?;
-        
-        // Find pods matching the app name
-        let matching_pods: Vec<&Pod> = pods.items.iter()
-            .filter(|pod| {
-                pod.metadata.name.as_ref()
-                    .map(|name| name.contains(&app_name))
-                    .unwrap_or(false)
-            })
-            .collect();
-        
-        if matching_pods.is_empty() {
-            return Err(format!(
-                "No pods found matching '{}' in namespace '{}'",
-                app_name, namespace
-            ));
-        }
-        
-        // Use the first matching pod as reference
-        let reference_pod = matching_pods[0];
-        let reference_pod_name = reference_pod.metadata.name.clone().unwrap_or_default();
-        
-        // Calculate resource requirements from the reference pod
-        let mut cpu_per_replica = 0.0;
-        let mut memory_per_replica = 0.0;
-        
-        if let Some(spec) = &reference_pod.spec {
-            for container in &spec.containers {
-                if let Some(resources) = &container.resources {
-                    if let Some(requests) = &resources.requests {
-                        if let Some(cpu) = requests.get("cpu") {
-                            cpu_per_replica += quantity_to_cores(cpu);
-                        }
-                        if let Some(memory) = requests.get("memory") {
-                            memory_per_replica += quantity_to_gb(memory);
-                        }
-                    }
-                }
-            }
-        }
-        
-        // Calculate total resources needed
-        let total_cpu_required = cpu_per_replica * replica_count as f64;
-        let total_memory_required = memory_per_replica * replica_count as f64;
-        
-        // Get cluster capacity
-        let capacity = Self::get_cluster_capacity_internal().await?;
-        
-        // Check if resources fit
-        let fits = capacity.available_cpu_cores >= total_cpu_required 
-                   && capacity.available_memory_gb >= total_memory_required;
-        
-        // Calculate projected utilization
-        let projected_cpu_utilization = if capacity.total_cpu_cores > 0.0 {
-            (capacity.allocated_cpu_cores + total_cpu_required) / capacity.total_cpu_cores * 100.0
-        } else {
-            0.0
-        };
-        
-        let projected_memory_utilization = if capacity.total_memory_gb > 0.0 {
-            (capacity.allocated_memory_gb + total_memory_required) / capacity.total_memory_gb * 100.0
-        } else {
-            0.0
+    #[test]
+    fn test_request_coverage() {
+        let without_requests = |mut pod: Pod, resource: &str| {
+            let resources = pod.spec.as_mut().unwrap().containers[0].resources.as_mut().unwrap();
+            resources.requests.as_mut().unwrap().remove(resource);
+            pod
         };
-        
-        // Build explanation
-        let explanation = if fits {
-            format!(
-                "✓ Capacity CHECK PASSED: You can add {} more replicas of '{}' in namespace '{}'.\n\
-                 \n\
-                 Reference pod: {}\n\
-                 - CPU per replica: {:.3} cores\n\
-                 - Memory per replica: {:.3} GB\n\
-                 \n\
-                 Total required for {} replicas:\n\
-                 - CPU: {:.3} cores\n\
-                 - Memory: {:.3} GB\n\
-                 \n\
-                 Cluster availability:\n\
-                 - Available CPU: {:.3} cores (enough for {:.0} replicas)\n\
-                 - Available Memory: {:.3} GB (enough for {:.0} replicas)\n\
-                 \n\
-                 Projected utilization after adding replicas:\n\
-                 - CPU: {:.1}% (current: {:.1}%)\n\
-                 - Memory: {:.1}% (current: {:.1}%)\n\
-                 \n\
-                 Current pods matching '{}': {}",
-                replica_count, app_name, namespace,
-                reference_pod_name,
-                cpu_per_replica,
-                memory_per_replica,
-                replica_count,
-                total_cpu_required,
-                total_memory_required,
-                capacity.available_cpu_cores,
-                if cpu_per_replica > 0.0 { capacity.available_cpu_cores / cpu_per_replica } else { 0.0 },
-                capacity.available_memory_gb,
-                if memory_per_replica > 0.0 { capacity.available_memory_gb / memory_per_replica } else { 0.0 },
-                projected_cpu_utilization,
-                capacity.allocated_cpu_cores / capacity.total_cpu_cores * 100.0,
-                projected_memory_utilization,
-                capacity.allocated_memory_gb / capacity.total_memory_gb * 100.0,
-                app_name,
-                matching_pods.len()
-            )
-        } else {
-            let mut issues = vec![];
-            
-            if capacity.available_cpu_cores < total_cpu_required {
-                let shortfall = total_cpu_required - capacity.available_cpu_cores;
-                let max_replicas = (capacity.available_cpu_cores / cpu_per_replica).floor() as i32;
-                issues.push(format!(
-                    "CPU shortage: Need {:.3} cores but only {:.3} available (shortfall: {:.3} cores). \
-                     Maximum possible replicas based on CPU: {}",
-                    total_cpu_required, capacity.available_cpu_cores, shortfall, max_replicas
-                ));
-            }
-            
-            if capacity.available_memory_gb < total_memory_required {
-                let shortfall = total_memory_required - capacity.available_memory_gb;
-                let max_replicas = (capacity.available_memory_gb / memory_per_replica).floor() as i32;
-                issues.push(format!(
-                    "Memory shortage: Need {:.3} GB but only {:.3} GB available (shortfall: {:.3} GB). \
-                     Maximum possible replicas based on memory: {}",
-                    total_memory_required, capacity.available_memory_gb, shortfall, max_replicas
-                ));
-            }
-            
-            format!(
-                "✗ Capacity CHECK FAILED: Cannot add {} replicas of '{}' in namespace '{}'.\n\
-                 \n\
-                 Reference pod: {}\n\
-                 - CPU per replica: {:.3} cores\n\
-                 - Memory per replica: {:.3} GB\n\
-                 \n\
-                 Total required for {} replicas:\n\
-                 - CPU: {:.3} cores\n\
-                 - Memory: {:.3} GB\n\
-                 \n\
-                 Issues:\n{}\n\
-                 \n\
-                 Current pods matching '{}': {}",
-                replica_count, app_name, namespace,
-                reference_pod_name,
-                cpu_per_replica,
-                memory_per_replica,
-                replica_count,
-                total_cpu_required,
-                total_memory_required,
-                issues.join("\n"),
-                app_name,
-                matching_pods.len()
-            )
-        };
-        
-        Ok(CheckReplicaCapacityResponse {
-            fits,
-            reference_pod: reference_pod_name,
-            cpu_per_replica_cores: cpu_per_replica,
-            memory_per_replica_gb: memory_per_replica,
-            total_cpu_required_cores: total_cpu_required,
-            total_memory_required_gb: total_memory_required,
-            available_cpu_cores: capacity.available_cpu_cores,
-            available_memory_gb: capacity.available_memory_gb,
-            current_pod_count: matching_pods.len(),
-            projected_cpu_utilization_percent: projected_cpu_utilization,
-            projected_memory_utilization_percent: projected_memory_utilization,
-            explanation,
-        })
+        let mut finished = test_pod("job-1", "batch", Some("node-1"), "1", "1Gi");
+        finished.status = Some(serde_json::from_value(serde_json::json!({ "phase": "Succeeded" })).unwrap());
+        let pods = vec![
+            test_pod("web-1", "shop", Some("node-1"), "1", "1Gi"),
+            test_pod("web-2", "shop", Some("node-1"), "1", "1Gi"),
+            test_pod("web-3", "shop", Some("node-1"), "1", "1Gi"),
+            without_requests(test_pod("cart-1", "shop", Some("node-1"), "1", "1Gi"), "memory"),
+            without_requests(test_pod("worker-1", "batch", Some("node-1"), "1", "1Gi"), "cpu"),
+            finished,
+        ];
+
+        let coverage = ClusterInsights::build_request_coverage(&pods, Lang::En);
+        assert_eq!((coverage.pod_count, coverage.compliant_pod_count, coverage.non_compliant_pod_count), (5, 3, 2));
+        assert_eq!(coverage.coverage_percent, 60.0);
+        assert_eq!((coverage.cpu_coverage_percent, coverage.memory_coverage_percent), (80.0, 80.0));
+        assert_eq!(coverage.namespaces[0].namespace, "batch");
+        assert_eq!(coverage.namespaces[0].coverage_percent, 0.0);
+        assert_eq!(coverage.namespaces[1].coverage_percent, 75.0);
+        assert_eq!(coverage.namespaces[1].non_compliant_pods, vec!["cart-1".to_string()]);
+        assert!(coverage.explanation.starts_with("60.0% of 5 pods declare CPU and memory requests"));
     }
-}
 
-#[tool_router]
-impl ClusterInsights {
-    pub fn new() -> Self {
-        Self {
-            tool_router: Self::tool_router(),
-        }
+    #[test]
+    fn test_request_ratio_mismatch() {
+        let nodes: Vec<Node> = (0..3).map(|i| test_node(&format!("node-{}", i), "8", "32Gi")).collect();
+        let pods: Vec<Pod> = (0..4).map(|i| test_pod(&format!("worker-{}", i), "batch", Some("node-0"), "2", "2Gi")).collect();
+
+        let response = ClusterInsights::build_request_ratio_mismatch(&nodes, &pods, DEFAULT_RATIO_TOLERANCE_PERCENT, Lang::En).unwrap();
+        assert_eq!((response.pod_memory_gb_per_core, response.node_memory_gb_per_core), (1.0, 4.0));
+        assert_eq!(response.mismatch_factor, 0.25);
+        assert!(response.mismatched);
+        assert_eq!(response.stranded_resource.as_deref(), Some("memory"));
+        assert_eq!(response.stranded_percent, 75.0);
+        assert_eq!(response.recommended_node_family, "compute-optimized");
+        assert!(response.recommendation.starts_with("Add compute-optimized nodes (2 GB per core"));
+        assert!(response.explanation.contains("The pods are CPU-heavy for these nodes (4.0x)"));
+
+        let balanced: Vec<Pod> = (0..4).map(|i| test_pod(&format!("web-{}", i), "shop", Some("node-1"), "1", "4500Mi")).collect();
+        let response = ClusterInsights::build_request_ratio_mismatch(&nodes, &balanced, DEFAULT_RATIO_TOLERANCE_PERCENT, Lang::En).unwrap();
+        assert!(!response.mismatched);
+        assert_eq!((response.stranded_resource, response.stranded_percent), (None, 0.0));
+
+        assert!(ClusterInsights::build_request_ratio_mismatch(&nodes, &[], DEFAULT_RATIO_TOLERANCE_PERCENT, Lang::En).is_err());
     }
 
-    /// Get cluster capacity
-    #[tool(description = "Get total cluster capacity, allocated resources (requests), and available resources. \
-                          Returns detailed information about CPU cores and memory in GB across all nodes. \
-                          Example: Returns total 24 CPU cores, 96 GB memory, with 12 cores and 48 GB allocated.")]
-    pub async fn get_cluster_capacity(&self) -> Result<CallToolResult, McpError> {
-        let _timer = RequestTimer::new();
-        increment_requests();
+    #[test]
+    fn test_excluding_namespace_frees_its_requests() {
+        let nodes = vec![test_node("node-a", "8", "32Gi"), test_node("node-b", "8", "32Gi")];
+        let mut pods = vec![
+            test_pod("web-1", "shop", Some("node-a"), "1", "2Gi"),
+            test_pod("etl-1", "batch", Some("node-a"), "4", "8Gi"),
+            test_pod("etl-2", "batch", Some("node-b"), "2", "4Gi"),
+            test_pod("etl-3", "batch", None, "2", "4Gi"),
+        ];
+        let before = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
 
-        match Self::get_cluster_capacity_internal().await {
-            Ok(result) => {
-                match serde_json::to_string_pretty(&result) {
-                    Ok(json_str) => Ok(CallToolResult::success(vec![Content::text(json_str)])),
-                    Err(e) => {
-                        increment_errors();
-                        Ok(CallToolResult::error(vec![Content::text(format!(
-                            "Error serializing response: {}", e
-                        ))]))
-                    }
-                }
-            }
-            Err(e) => {
-                increment_errors();
-                Ok(CallToolResult::error(vec![Content::text(format!(
-                    "Failed to get cluster capacity: {}", e
-                ))]))
-            }
-        }
+        let freed = exclude_namespace_pods(&mut pods, &["batch".to_string()], ResourceBasis::Requests, None);
+        let after = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        assert_eq!(freed.cores_and_gb(), (6.0, 12.0));
+        assert_eq!(after.available_cpu_cores - before.available_cpu_cores, 6.0);
+        assert_eq!(after.available_memory_gb - before.available_memory_gb, 12.0);
+        assert_eq!((before.pending_pod_count, after.pending_pod_count), (1, 0));
+        assert_eq!(pods.len(), 1);
+        assert!(messages::excluded_namespaces(Lang::En, &["batch".to_string()], freed.cores_and_gb())
+            .starts_with("Pods of namespace(s) batch are left out"));
     }
 
-    /// Check if resources fit in cluster
-    #[tool(description = "Check if specified CPU and memory resources can fit in the cluster. \
-                          Parameters: cpu_cores (float), memory_gb (float). \
-                          Returns whether resources fit, available resources, and utilization percentages. \
-                          Example: cpu_cores=4, memory_gb=16 → checks if 4 cores and 16GB available.")]
-    pub async fn check_resource_fit(
-        &self,
-        params: Parameters<CheckResourceFitParams>
-    ) -> Result<CallToolResult, McpError> {
-        let _timer = RequestTimer::new();
-        increment_requests();
+    #[test]
+    fn test_capacity_in_pod_units() {
+        let nodes = vec![test_node("node-a", "4", "16Gi"), test_node("node-b", "8", "8Gi")];
+        let pods = vec![
+            test_pod("api-1", "shop", Some("node-a"), "2", "2Gi"),
+            test_pod("cache-1", "shop", Some("node-b"), "1", "4Gi"),
+        ];
+        let node_infos = ClusterInsights::build_node_infos(&nodes, &pods, ResourceBasis::Requests, None);
 
-        if params.0.cpu_cores < 0.0 {
-            increment_errors();
-            return Ok(CallToolResult::error(vec![Content::text(
-                "CPU cores must be non-negative".to_string()
-            )]));
-        }
+        let units = ClusterInsights::build_capacity_in_pod_units(&node_infos, 1.0, 2.0, Lang::En);
+        let node = |name: &str| units.nodes.iter().find(|n| n.node == name).unwrap();
+        // node-a: 4 cores hold 4 pods, its 16 GB 8; 2 of the 4 cores are requested
+        assert_eq!((node("node-a").total_units, node("node-a").allocated_units, node("node-a").available_units), (4.0, 2.0, 2));
+        assert_eq!(node("node-a").binding_resource, "cpu");
+        // node-b: 8 GB hold 4 pods; 4 of the 8 GB are requested
+        assert_eq!((node("node-b").total_units, node("node-b").allocated_units, node("node-b").available_units), (4.0, 2.0, 2));
+        assert_eq!(node("node-b").binding_resource, "memory");
+        assert_eq!((units.total_units, units.allocated_units, units.available_units), (8.0, 4.0, 4));
+        assert_eq!((units.cpu_bound_nodes, units.memory_bound_nodes), (1, 1));
+        assert!(units.explanation.starts_with("In pods of 1.00 cores and 2.00 GB, the cluster holds 8.0: 4.0 are already placed and 4 more fit"));
 
-        if params.0.memory_gb < 0.0 {
-            increment_errors();
-            return Ok(CallToolResult::error(vec![Content::text(
-                "Memory GB must be non-negative".to_string()
-            )]));
-        }
-
-        match Self::check_resource_fit_internal(params.0.cpu_cores, params.0.memory_gb).await {
-            Ok(result) => {
-                match serde_json::to_string_pretty(&result) {
-                    Ok(json_str) => Ok(CallToolResult::success(vec![Content::text(json_str)])),
-                    Err(e) => {
-                        increment_errors();
-                        Ok(CallToolResult::error(vec![Content::text(format!(
-                            "Error serializing response: {}", e
-                        ))]))
-                    }
-                }
-            }
-            Err(e) => {
-                increment_errors();
-                Ok(CallToolResult::error(vec![Content::text(format!(
-                    "Failed to check resource fit: {}", e
-                ))]))
-            }
-        }
+        // Memory alone: node-a holds 8 pods of 2 GB
+        let memory_only = ClusterInsights::build_capacity_in_pod_units(&node_infos, 0.0, 2.0, Lang::En);
+        assert_eq!(node_infos.len(), memory_only.nodes.len());
+        assert_eq!(memory_only.nodes.iter().find(|n| n.node == "node-a").unwrap().total_units, 8.0);
     }
 
-    /// Get node breakdown
-    #[tool(description = "Get detailed breakdown of each node in the cluster. \
-                          Lists each node with its total capacity, allocated resources (requests), \
-                          available resources, and pod count. \
-                          Example: Returns list of nodes with their CPU/memory capacity and usage.")]
-    pub async fn get_node_breakdown(&self) -> Result<CallToolResult, McpError> {
-        let _timer = RequestTimer::new();
-        increment_requests();
+    #[test]
+    fn test_find_large_pods_by_threshold() {
+        let mut finished = test_pod("report-1", "batch", Some("node-a"), "8", "32Gi");
+        finished.status = Some(serde_json::from_value(serde_json::json!({ "phase": "Failed" })).unwrap());
+        let pods = vec![
+            test_pod("web-1", "shop", Some("node-a"), "500m", "1Gi"),
+            test_pod("db-1", "shop", Some("node-a"), "2", "16Gi"),
+            test_pod("etl-1", "batch", Some("node-b"), "4", "4Gi"),
+            test_pod("etl-2", "batch", None, "6", "8Gi"),
+            test_pod("cache-1", "shop", Some("node-b"), "1", "8Gi"),
+            finished,
+        ];
 
-        match Self::get_node_breakdown_internal().await {
-            Ok(result) => {
-                match serde_json::to_string_pretty(&result) {
-                    Ok(json_str) => Ok(CallToolResult::success(vec![Content::text(json_str)])),
-                    Err(e) => {
-                        increment_errors();
-                        Ok(CallToolResult::error(vec![Content::text(format!(
-                            "Error serializing response: {}", e
-                        ))]))
-                    }
-                }
-            }
-            Err(e) => {
-                increment_errors();
-                Ok(CallToolResult::error(vec![Content::text(format!(
-                    "Failed to get node breakdown: {}", e
-                ))]))
+        let large = ClusterInsights::build_large_pods(&pods, Some(4.0), Some(8.0), Lang::En);
+        let names: Vec<&str> = large.pods.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["etl-2", "etl-1", "db-1", "cache-1"]);
+        assert_eq!(large.pods[0].exceeds, vec!["cpu".to_string(), "memory".to_string()]);
+        assert_eq!(large.pods[2].exceeds, vec!["memory".to_string()]);
+        assert_eq!(large.pods[0].node, UNSCHEDULED_NODE);
+        assert_eq!(large.searched_pod_count, 5);
+        assert!(large.explanation.starts_with("4 of 5 pods request at least 4.00 cores CPU or 8.00 GB memory. By namespace: batch (2), shop (2)."));
+
+        let cpu_only = ClusterInsights::build_large_pods(&pods, Some(4.0), None, Lang::En);
+        assert_eq!(cpu_only.pods.len(), 2);
+        assert!(ClusterInsights::build_large_pods(&pods, Some(16.0), None, Lang::En).pods.is_empty());
+    }
+
+    #[test]
+    fn test_pod_accounting_breakdown() {
+        let pod: Pod = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "web-1", "namespace": "shop" },
+            "spec": {
+                "nodeName": "node-a",
+                "overhead": { "cpu": "50m", "memory": "64Mi" },
+                "initContainers": [
+                    { "name": "proxy", "restartPolicy": "Always", "resources": { "requests": { "cpu": "100m", "memory": "128Mi" } } },
+                    { "name": "migrate", "resources": { "requests": { "cpu": "1500m", "memory": "256Mi" }, "limits": { "cpu": "2" } } }
+                ],
+                "containers": [
+                    { "name": "app", "resources": { "requests": { "cpu": "500m", "memory": "1Gi" }, "limits": { "memory": "2Gi" } } },
+                    { "name": "log", "resources": { "requests": { "cpu": "0.1", "memory": "128Mi" } } }
+                ]
             }
-        }
+        })).unwrap();
+
+        let accounting = ClusterInsights::build_pod_accounting(&pod, Lang::En);
+        let roles: Vec<(&str, ContainerRole)> = accounting.containers.iter().map(|c| (c.name.as_str(), c.role)).collect();
+        assert_eq!(roles, vec![("app", ContainerRole::App), ("log", ContainerRole::App), ("proxy", ContainerRole::Sidecar), ("migrate", ContainerRole::Init)]);
+        assert_eq!(accounting.containers[0].raw_limits.get("memory").map(String::as_str), Some("2Gi"));
+        assert_eq!(accounting.containers[1].cpu_requests_cores, 0.1);
+        assert_eq!((accounting.app_cpu_cores, accounting.app_memory_gb), (0.6, 1.125));
+        assert_eq!((accounting.sidecar_cpu_cores, accounting.sidecar_memory_gb), (0.1, 0.125));
+        // migrate runs next to the proxy sidecar: 1.6 cores and 384 MiB
+        assert_eq!(accounting.containers[3].init_phase_cpu_cores, Some(1.6));
+        assert_eq!((accounting.init_max_cpu_cores, accounting.init_max_memory_gb), (1.6, 0.375));
+        // CPU from the init phase, memory from the running pod, each plus the overhead
+        assert_eq!((accounting.effective_cpu_cores, accounting.effective_memory_gb), (1.65, 1.3125));
+        assert_eq!(
+            (accounting.effective_cpu_cores, accounting.effective_memory_gb),
+            pod_effective_requests(&pod)
+        );
+        assert_eq!(accounting.counted_as, "Allocated");
+        // get_cluster_capacity allocates exactly that effective request
+        let capacity = ClusterInsights::compute_cluster_capacity(&[test_node("node-a", "4", "16Gi")], std::slice::from_ref(&pod), ResourceBasis::Requests, None);
+        assert_eq!((capacity.allocated_cpu_cores, capacity.allocated_memory_gb), (1.65, 1.3125));
+        assert!(accounting.explanation.starts_with("Pod 'shop/web-1' counts 1.65 cores CPU, 1.31 GB memory toward the allocation of node 'node-a'."));
     }
 
-    /// Get namespace resource usage
-    #[tool(description = "Get resource usage per namespace. \
-                          Returns CPU/memory requests and limits for each namespace, along with pod count. \
-                          Results are sorted by CPU requests (descending). \
-                          Example: Returns namespaces with their total CPU/memory consumption.")]
-    pub async fn get_namespace_usage(&self) -> Result<CallToolResult, McpError> {
-        let _timer = RequestTimer::new();
-        increment_requests();
+    #[test]
+    fn test_scoped_quota_counts_selected_pods() {
+        let best_effort: Pod = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "sleeper", "namespace": "batch" },
+            "spec": { "containers": [{ "name": "main" }] }
+        })).unwrap();
+        let mut high = test_pod("train-0", "batch", Some("node-a"), "2", "8Gi");
+        high.spec.as_mut().unwrap().priority_class_name = Some("high".to_string());
+        let pods = vec![best_effort, test_pod("web-1", "batch", Some("node-a"), "1", "2Gi"), high, test_pod("web-1", "shop", Some("node-a"), "1", "2Gi")];
 
-        match Self::get_namespace_usage_internal().await {
-            Ok(result) => {
-                match serde_json::to_string_pretty(&result) {
-                    Ok(json_str) => Ok(CallToolResult::success(vec![Content::text(json_str)])),
-                    Err(e) => {
-                        increment_errors();
-                        Ok(CallToolResult::error(vec![Content::text(format!(
-                            "Error serializing response: {}", e
-                        ))]))
-                    }
-                }
-            }
-            Err(e) => {
-                increment_errors();
-                Ok(CallToolResult::error(vec![Content::text(format!(
-                    "Failed to get namespace usage: {}", e
-                ))]))
-            }
-        }
+        let quota = |name: &str, spec: serde_json::Value| -> ResourceQuota {
+            serde_json::from_value(serde_json::json!({ "metadata": { "name": name, "namespace": "batch" }, "spec": spec })).unwrap()
+        };
+        let quotas = vec![
+            quota("best-effort", serde_json::json!({ "hard": { "pods": "2" }, "scopes": ["BestEffort"] })),
+            quota("compute", serde_json::json!({ "hard": { "requests.cpu": "4", "requests.memory": "20Gi", "pods": "10" } })),
+            quota("high-priority", serde_json::json!({
+                "hard": { "requests.cpu": "4" },
+                "scopeSelector": { "matchExpressions": [{ "scopeName": "PriorityClass", "operator": "In", "values": ["high"] }] }
+            })),
+        ];
+
+        let response = ClusterInsights::build_quota_usage(&quotas, &pods, Lang::En);
+        let by_name = |name: &str| response.quotas.iter().find(|q| q.name == name).unwrap();
+
+        let best_effort = by_name("best-effort");
+        assert_eq!(best_effort.scopes, vec!["BestEffort".to_string()]);
+        assert_eq!((best_effort.matching_pod_count, best_effort.namespace_pod_count), (1, 3));
+        assert_eq!(best_effort.resources[0].resource, "pods");
+        assert_eq!((best_effort.resources[0].used, best_effort.resources[0].remaining), (1.0, 1.0));
+        assert_eq!(best_effort.resources[0].utilization_percent, 50.0);
+
+        // The unscoped quota counts every pod of the namespace, none of other namespaces
+        let compute = by_name("compute");
+        assert_eq!(compute.matching_pod_count, 3);
+        assert_eq!(compute.resources[0].resource, "requests.cpu");
+        assert_eq!(compute.resources[0].used, 3.0);
+        assert_eq!(compute.resources.iter().find(|r| r.resource == "requests.memory").unwrap().used, 10.0);
+
+        let high = by_name("high-priority");
+        assert_eq!(high.scopes, vec!["PriorityClass In (high)".to_string()]);
+        assert_eq!(high.matching_pod_count, 1);
+        assert_eq!(high.resources[0].used, 2.0);
+
+        assert_eq!(response.quotas[0].name, "compute");
+        assert!(response.explanation.contains("'batch/best-effort' (BestEffort) counts 1 of 3 pods"));
     }
 
-    /// Get pod resource statistics
-    #[tool(description = "Get top pods by resource consumption. \
-                          Returns the top 20 pods sorted by CPU requests, showing CPU/memory requests and limits. \
-                          Includes namespace, node assignment, and resource metrics in millicores and MB. \
-                          Example: Returns top resource-consuming pods across the cluster.")]
-    pub async fn get_pod_resource_stats(&self) -> Result<CallToolResult, McpError> {
-        let _timer = RequestTimer::new();
-        increment_requests();
+    #[test]
+    fn test_burst_projection_flags_oversubscribed_node() {
+        let nodes = vec![test_node("node-a", "4", "16Gi"), test_node("node-b", "4", "16Gi")];
+        let mut bursty = test_pod("batch-1", "batch", Some("node-a"), "1", "4Gi");
+        bursty.spec.as_mut().unwrap().containers[0].resources.as_mut().unwrap().limits = Some(BTreeMap::from([
+            ("cpu".to_string(), Quantity("6".to_string())),
+            ("memory".to_string(), Quantity("20Gi".to_string())),
+        ]));
+        let pods = vec![bursty, test_pod("web-1", "shop", Some("node-b"), "1", "2Gi")];
 
-        match Self::get_pod_resource_stats_internal().await {
-            Ok(result) => {
-                match serde_json::to_string_pretty(&result) {
-                    Ok(json_str) => Ok(CallToolResult::success(vec![Content::text(json_str)])),
-                    Err(e) => {
-                        increment_errors();
-                        Ok(CallToolResult::error(vec![Content::text(format!(
-                            "Error serializing response: {}", e
-                        ))]))
-                    }
-                }
-            }
-            Err(e) => {
-                increment_errors();
-                Ok(CallToolResult::error(vec![Content::text(format!(
-                    "Failed to get pod resource stats: {}", e
-                ))]))
-            }
-        }
+        let projection = ClusterInsights::build_burst_projection(&nodes, &pods, Lang::En);
+        assert_eq!(projection.oversubscribed_node_count, 1);
+
+        let node_a = &projection.nodes[0];
+        assert_eq!(node_a.node, "node-a");
+        assert!(node_a.oversubscribed);
+        assert_eq!((node_a.cpu_limits_cores, node_a.memory_limits_gb), (6.0, 20.0));
+        assert_eq!((node_a.cpu_burst_percent, node_a.memory_burst_percent), (150.0, 125.0));
+        assert_eq!((node_a.projected_cpu_cores, node_a.projected_memory_gb), (4.0, 16.0));
+        assert_eq!((node_a.cpu_excess_cores, node_a.memory_excess_gb), (2.0, 4.0));
+        assert_eq!(node_a.unlimited_pod_count, 0);
+
+        // Without limits, web-1 counts at its requests and is flagged as able to burst further
+        let node_b = &projection.nodes[1];
+        assert!(!node_b.oversubscribed);
+        assert_eq!((node_b.cpu_limits_cores, node_b.memory_limits_gb), (1.0, 2.0));
+        assert_eq!(node_b.unlimited_pod_count, 1);
+
+        assert_eq!((projection.projected_cpu_cores, projection.projected_memory_gb), (5.0, 18.0));
+        assert_eq!((projection.total_cpu_excess_cores, projection.total_memory_excess_gb), (2.0, 4.0));
+        assert!(projection.explanation.starts_with("1 of 2 nodes would be oversubscribed"));
     }
 
-    /// Check replica capacity
-    #[tool(description = "Check if cluster has capacity to add more replicas of an application. \
-                          Finds an existing pod matching the app name in the specified namespace, \
-                          calculates its resource requirements, and checks if the cluster can accommodate \
-                          the requested number of additional replicas. \
-                          Parameters: app_name (string) - name or pattern to match pods, \
-                          namespace (string) - Kubernetes namespace, \
-                          replica_count (int) - number of additional replicas needed. \
-                          Returns detailed capacity analysis including per-replica requirements, total needs, \
-                          cluster availability, and projected utilization. \
-                          Example: app_name='my-application', namespace='default', replica_count=10")]
-    pub async fn check_replica_capacity(
-        &self,
-        params: Parameters<CheckReplicaCapacityParams>
-    ) -> Result<CallToolResult, McpError> {
-        let _timer = RequestTimer::new();
-        increment_requests();
+    #[test]
+    fn test_gated_pods_are_reported_not_demand() {
+        let nodes = vec![test_node("node-a", "4", "16Gi")];
+        let mut pending = test_pod("web-2", "shop", None, "2", "4Gi");
+        pending.status = Some(serde_json::from_value(serde_json::json!({ "phase": "Pending" })).unwrap());
+        let mut gated = test_pod("train-0", "batch", None, "8", "32Gi");
+        gated.status = pending.status.clone();
+        gated.spec.as_mut().unwrap().scheduling_gates = Some(vec![
+            serde_json::from_value(serde_json::json!({ "name": "example.com/quota" })).unwrap(),
+        ]);
+        let pods = vec![test_pod("web-1", "shop", Some("node-a"), "1", "2Gi"), pending, gated];
 
-        if params.0.replica_count <= 0 {
-            increment_errors();
-            return Ok(CallToolResult::error(vec![Content::text(
-                "Replica count must be positive".to_string()
-            )]));
-        }
+        let capacity = ClusterInsights::compute_cluster_capacity(&nodes, &pods, ResourceBasis::Requests, None);
+        assert_eq!(capacity.pending_pod_count, 1);
+        assert_eq!(capacity.pending_cpu_cores, 2.0);
+        assert_eq!(capacity.pending_memory_gb, 4.0);
+        assert_eq!(capacity.gated_pod_count, 1);
+        assert!(capacity.explanation.contains("1 pod(s) held back by scheduling gates are not counted"));
 
-        if params.0.app_name.is_empty() {
-            increment_errors();
-            return Ok(CallToolResult::error(vec![Content::text(
-                "Application name cannot be empty".to_string()
-            )]));
-        }
-
-        if params.0.namespace.is_empty() {
-            increment_errors();
-            return Ok(CallToolResult::error(vec![Content::text(
-                "Namespace cannot be empty".to_string()
-            )]));
-        }
-
-        match Self::check_replica_capacity_internal(
-            params.0.app_name,
-            params.0.namespace,
-            params.0.replica_count,
-        ).await {
-            Ok(result) => {
-                match serde_json::to_string_pretty(&result) {
-                    Ok(json_str) => Ok(CallToolResult::success(vec![Content::text(json_str)])),
-                    Err(e) => {
-                        increment_errors();
-                        Ok(CallToolResult::error(vec![Content::text(format!(
-                            "Error serializing response: {}", e
-                        ))]))
+        let report = ClusterInsights::build_gated_pods(&pods, Lang::En);
+        assert_eq!(report.pods.len(), 1);
+        assert_eq!((report.pods[0].namespace.as_str(), report.pods[0].name.as_str()), ("batch", "train-0"));
+        assert_eq!(report.pods[0].gates, vec!["example.com/quota".to_string()]);
+        assert_eq!(report.gates["example.com/quota"], 1);
+        assert_eq!(report.total_cpu_requests_cores, 8.0);
+        assert_eq!(report.total_memory_requests_gb, 32.0);
+        assert!(report.explanation.starts_with("1 pods are held back by scheduling gates: example.com/quota (1)."));
+    }
+
+    #[test]
+    fn test_pod_resource_info_raw_quantities() {
+        let pod: Pod = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "api-1", "namespace": "default" },
+            "spec": {
+                "nodeName": "node-1",
+                "containers": [{
+                    "name": "main",
+                    "resources": {
+                        "requests": { "cpu": "250m", "memory": "300Mi" },
+                        "limits": { "cpu": "1", "memory": "1G" }
                     }
-                }
+                }]
             }
-            Err(e) => {
-                increment_errors();
-                Ok(CallToolResult::error(vec![Content::text(format!(
-                    "Failed to check replica capacity: {}", e
-                ))]))
+        })).unwrap();
+
+        let with_raw = ClusterInsights::build_pod_resource_info(&pod, true);
+        assert_eq!(with_raw.cpu_requests_millicores, 250);
+        assert_eq!(with_raw.raw_cpu_request.as_deref(), Some("250m"));
+        assert_eq!(with_raw.raw_memory_request.as_deref(), Some("300Mi"));
+        assert_eq!(with_raw.raw_cpu_limit.as_deref(), Some("1"));
+        assert_eq!(with_raw.raw_memory_limit.as_deref(), Some("1G"));
+
+        let without_raw = ClusterInsights::build_pod_resource_info(&pod, false);
+        assert_eq!(without_raw.raw_cpu_request, None);
+        assert_eq!(without_raw.raw_memory_limit, None);
+    }
+
+    #[test]
+    fn test_find_unschedulable_by_size() {
+        let nodes = vec![
+            test_node("node-1", "8", "32Gi"),
+            test_node("node-2", "16", "64Gi"),
+        ];
+        let pods = vec![
+            test_pod("small", "default", Some("node-1"), "1", "4Gi"),
+            test_pod("huge-memory", "default", None, "2", "128Gi"),
+        ];
+        let response = ClusterInsights::build_unschedulable_by_size(&nodes, &pods, Lang::En);
+
+        assert_eq!(response.total_pods_checked, 2);
+        assert_eq!(response.largest_node_memory_gb, 64.0);
+        assert_eq!(response.total_oversized, 1);
+        assert_eq!(response.pods[0].name, "huge-memory");
+        assert_eq!(response.pods[0].node, "unscheduled");
+        assert!(response.pods[0].exceeds_memory);
+        assert!(!response.pods[0].exceeds_cpu);
+    }
+
+    #[test]
+    fn test_pod_effective_requests_with_init_and_sidecar() {
+        let pod: Pod = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "app", "namespace": "default" },
+            "spec": {
+                "initContainers": [
+                    { "name": "proxy", "restartPolicy": "Always", "resources": { "requests": { "cpu": "100m", "memory": "128Mi" } } },
+                    { "name": "migrate", "resources": { "requests": { "cpu": "2", "memory": "256Mi" } } }
+                ],
+                "containers": [
+                    { "name": "main", "resources": { "requests": { "cpu": "500m", "memory": "1Gi" } } }
+                ]
             }
-        }
+        })).unwrap();
+
+        let (cpu, memory) = pod_effective_requests(&pod);
+        // CPU: init 2 + sidecar 0.1 beats app 0.5 + sidecar 0.1
+        assert!((cpu - 2.1).abs() < 1e-9);
+        // Memory: app 1Gi + sidecar 128Mi beats init 256Mi + sidecar 128Mi
+        assert!((memory - 1.125).abs() < 1e-9);
     }
-}
 
-#[tool_handler]
-impl ServerHandler for ClusterInsights {
-    fn get_info(&self) -> ServerInfo {
-        // Read basic information from .env file (replaced by sync script during release)
-        let name = "cluster-insights-mcp-rs".to_string();
-        let version = "1.3.2".to_string();
-        let title = "Cluster Insights Engine MCP Server".to_string();
-        let website_url = "https://github.com/alpha-hack-program/cluster-insights-mcp-rs.git".to_string();
+    #[test]
+    fn test_requests_from_in_place_resize_status() {
+        use k8s_openapi::api::core::v1::ContainerStatus;
 
-        ServerInfo {
-            instructions: Some(
-                "Kubernetes Cluster Insights providing resource analysis functions:\
-                 \n\n1. get_cluster_capacity - Get total cluster capacity, allocated resources, and availability\
-                 \n2. check_resource_fit - Check if specified resources can fit in the cluster\
-                 \n3. get_node_breakdown - Get detailed breakdown of each node's resources\
-                 \n4. get_namespace_usage - Get resource usage per namespace\
-                 \n5. get_pod_resource_stats - Get top pods by resource consumption\
-                 \n6. check_replica_capacity - Check if cluster can accommodate additional application replicas\
-                 \n\nAll functions query live Kubernetes cluster data via kubeconfig.".into()
-            ),
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
-            server_info: rmcp::model::Implementation {
-                name: name,
-                version: version, 
-                title: Some(title), 
-                icons: None, 
-                website_url: Some(website_url) 
-            },
+        let nodes = vec![test_node("node-a", "8", "32Gi")];
+        let mut resized = test_pod("resized", "default", Some("node-a"), "500m", "1Gi");
+        let container = &mut resized.spec.as_mut().unwrap().containers[0];
+        container.resources.as_mut().unwrap().limits = Some(BTreeMap::from([
+            ("cpu".to_string(), Quantity("4".to_string())),
+            ("memory".to_string(), Quantity("8Gi".to_string())),
+        ]));
+        let container_name = container.name.clone();
+        resized.status.get_or_insert_with(Default::default).container_statuses = Some(vec![ContainerStatus {
+            name: container_name,
+            resources: Some(ResourceRequirements {
+                requests: Some(BTreeMap::from([
+                    ("cpu".to_string(), Quantity("2".to_string())),
+                    ("memory".to_string(), Quantity("4Gi".to_string())),
+                ])),
+                ..Default::default()
+            }),
             ..Default::default()
-        }
+        }]);
+        let untouched = test_pod("untouched", "default", Some("node-a"), "500m", "1Gi");
+
+        // The resized-up status wins over the spec; a pod without status resources keeps its spec
+        assert_eq!(pod_requests(&resized), (2.0, 4.0));
+        assert_eq!(pod_effective_requests(&resized), (2.0, 4.0));
+        assert_eq!(pod_requests(&untouched), (0.5, 1.0));
+        // The status only reports requests, so the limits still come from the spec
+        assert_eq!(pod_limits(&resized), (4.0, 8.0));
+        assert!(pod_has_cpu_and_memory_limits(&resized));
+
+        let capacity = ClusterInsights::compute_cluster_capacity(&nodes, &[resized, untouched], ResourceBasis::Requests, None);
+        assert_eq!((capacity.allocated_cpu_cores, capacity.allocated_memory_gb), (2.5, 5.0));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[tokio::test]
+    async fn test_disabled_tools() {
+        use rmcp::ServiceExt;
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 
-    #[test]
-    fn test_quantity_to_cores() {
-        assert_eq!(quantity_to_cores(&Quantity("2".to_string())), 2.0);
-        assert_eq!(quantity_to_cores(&Quantity("500m".to_string())), 0.5);
-        assert_eq!(quantity_to_cores(&Quantity("100m".to_string())), 0.1);
+        let config = ServerConfig { disabled_tools: vec!["check_replica_capacity".to_string()], ..Default::default() };
+        let cluster_insights = ClusterInsights::builder().config(config).build().unwrap();
+        let capabilities = cluster_insights.list_capabilities_internal(Lang::En);
+        assert!(capabilities.tools.iter().all(|t| t.name != "check_replica_capacity"));
+        assert!(capabilities.tools.iter().any(|t| t.name == "check_resource_fit"));
+        assert!(cluster_insights.get_info().instructions.unwrap().ends_with("Disabled on this server: check_replica_capacity."));
+
+        // Speak JSON-RPC to the server over an in-memory pipe, as a client would
+        let (client, server) = tokio::io::duplex(1 << 20);
+        tokio::spawn(async move {
+            if let Ok(service) = cluster_insights.serve(server).await {
+                let _ = service.waiting().await;
+            }
+        });
+        let (read, mut write) = tokio::io::split(client);
+        let mut lines = BufReader::new(read).lines();
+        let mut request = async |message: serde_json::Value| -> serde_json::Value {
+            write.write_all(format!("{}\n", message).as_bytes()).await.unwrap();
+            if message.get("id").is_none() {
+                return serde_json::Value::Null;
+            }
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap()
+        };
+
+        request(serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {
+            "protocolVersion": "2024-11-05", "capabilities": {}, "clientInfo": {"name": "test", "version": "0"},
+        }})).await;
+        request(serde_json::json!({"jsonrpc": "2.0", "method": "notifications/initialized"})).await;
+
+        let listed = request(serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list", "params": {}})).await;
+        let names: Vec<&str> = listed["result"]["tools"].as_array().unwrap().iter()
+            .filter_map(|tool| tool["name"].as_str())
+            .collect();
+        assert!(names.contains(&"get_cluster_capacity"));
+        assert!(!names.contains(&"check_replica_capacity"));
+
+        let called = request(serde_json::json!({"jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": {
+            "name": "check_replica_capacity", "arguments": {"app_name": "web", "replica_count": 1},
+        }})).await;
+        assert!(called["result"].is_null());
+        assert_eq!(called["error"]["message"], "tool 'check_replica_capacity' is disabled on this server (DISABLED_TOOLS)");
     }
 
     #[test]
-    fn test_quantity_to_gb() {
-        assert_eq!(quantity_to_gb(&Quantity("1Gi".to_string())), 1.0);
-        assert_eq!(quantity_to_gb(&Quantity("512Mi".to_string())), 0.5);
+    fn test_list_capabilities() {
+        let cluster_insights = ClusterInsights::new();
+        let response = cluster_insights.list_capabilities_internal(Lang::En);
+
+        for name in [
+            "get_cluster_capacity",
+            "check_resource_fit",
+            "get_node_breakdown",
+            "get_namespace_usage",
+            "get_pod_resource_stats",
+            "check_replica_capacity",
+        ] {
+            let tool = response.tools.iter().find(|t| t.name == name)
+                .unwrap_or_else(|| panic!("tool {} not listed", name));
+            assert!(!tool.description.is_empty());
+            assert!(tool.input_schema.as_object().map(|o| !o.is_empty()).unwrap_or(false));
+        }
+        assert_eq!(response.total_tools, response.tools.len());
+
+        // The flattened cache parameters show up as a plain fresh property
+        let capacity = response.tools.iter().find(|t| t.name == "get_cluster_capacity").unwrap();
+        assert!(capacity.input_schema["properties"]["fresh"].is_object());
+
+        let spanish = cluster_insights.list_capabilities_internal(Lang::Es);
+        assert!(spanish.explanation.starts_with(&format!("El servidor expone {} herramientas.", spanish.total_tools)));
     }
 
     // Test the engine to get the cluster capacity
     #[tokio::test]
     async fn test_get_cluster_capacity() {
         let cluster_insights = ClusterInsights::new();
-        let result = cluster_insights.get_cluster_capacity().await;
+        let result = cluster_insights.get_cluster_capacity(Parameters(ClusterCapacityParams::default())).await;
         match result {
             Ok(call_result) => {
                 println!("Cluster capacity: {:?}", call_result);
@@ -1145,7 +14561,11 @@ mod tests {
     #[tokio::test]
     async fn test_check_resource_fit() {
         let cluster_insights = ClusterInsights::new();
-        let result = cluster_insights.check_resource_fit(Parameters(CheckResourceFitParams { cpu_cores: 1.0, memory_gb: 1.0 })).await;
+        let result = cluster_insights.check_resource_fit(Parameters(CheckResourceFitParams {
+            cpu_cores: 1.0,
+            memory_gb: 1.0,
+            ..Default::default()
+        })).await;
         match result {
             Ok(call_result) => {
                 println!("Check resource fit: {:?}", call_result);
@@ -1158,7 +14578,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_node_breakdown() {
         let cluster_insights = ClusterInsights::new();
-        let result = cluster_insights.get_node_breakdown().await;
+        let result = cluster_insights.get_node_breakdown(Parameters(NodeBreakdownParams::default())).await;
         match result {
             Ok(call_result) => {
                 println!("Node breakdown: {:?}", call_result);
@@ -1175,6 +14595,7 @@ mod tests {
             app_name: "test".to_string(),
             namespace: "default".to_string(),
             replica_count: 10,
+            ..Default::default()
         })).await;
         match result {
             Ok(call_result) => {
@@ -0,0 +1,256 @@
+//! Server tunables, loaded once at startup from an optional YAML config file (CONFIG_PATH).
+//! Environment variables override the values of the file.
+
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+
+/// Default upper bound for replica_count
+pub const DEFAULT_MAX_REPLICA_COUNT: i32 = 100_000;
+
+/// Default time-to-live of cached list results (0 disables the cache)
+pub const DEFAULT_CACHE_TTL_SECONDS: u64 = 0;
+
+/// Default cap on the items of each list in a tool response (0 means no cap)
+pub const DEFAULT_MAX_RESPONSE_ITEMS: usize = 0;
+
+/// Default cap on the size of a tool response in bytes (0 means no cap)
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 0;
+
+/// Default number of kubelet stats summaries get_eviction_risk requests at once
+pub const DEFAULT_KUBELET_STATS_CONCURRENCY: usize = 16;
+
+/// Default slack, in cores and GB, when comparing what a fit check requires with what is available
+pub const DEFAULT_FIT_TOLERANCE: f64 = 1e-6;
+
+/// Configuration loaded by init(), or on first use when init() was not called (tests)
+static CONFIG: OnceCell<ServerConfig> = OnceCell::new();
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// Address the HTTP transports listen on (BIND_ADDRESS), None for the transport's default
+    #[allow(dead_code)] // Only read by the HTTP transports
+    pub bind_address: Option<String>,
+    /// Serve nodes and pods from watch-based caches (WATCH_MODE)
+    pub watch_mode: bool,
+    /// Time-to-live of cached list results in seconds, 0 disables the cache (CACHE_TTL_SECONDS)
+    pub cache_ttl_seconds: u64,
+    /// Namespace used when namespaced tools get a blank namespace (DEFAULT_NAMESPACE)
+    pub default_namespace: Option<String>,
+    /// Upper bound for replica_count in check_replica_capacity (MAX_REPLICA_COUNT)
+    pub max_replica_count: i32,
+    /// Tools removed from the tool router, so clients can neither list nor call them (DISABLED_TOOLS, comma-separated)
+    pub disabled_tools: Vec<String>,
+    /// Slack in cores and GB by which a fit check may fall short and still fit, absorbing floating-point
+    /// rounding at the boundary (FIT_TOLERANCE)
+    pub fit_tolerance: f64,
+    /// CPU in cores each node keeps for the kubelet and OS (kube-reserved plus system-reserved), added on top
+    /// of the allocatable recommended by recommend_node_size (NODE_RESERVED_CPU_CORES)
+    pub node_reserved_cpu_cores: f64,
+    /// Memory in GB each node keeps for the kubelet and OS, including the eviction threshold
+    /// (NODE_RESERVED_MEMORY_GB)
+    pub node_reserved_memory_gb: f64,
+    /// Most items kept in each list of a tool response, 0 for no cap (MAX_RESPONSE_ITEMS)
+    pub max_response_items: usize,
+    /// Size in bytes a tool response is cut down to by halving its longest lists, 0 for no cap (MAX_RESPONSE_BYTES)
+    pub max_response_bytes: usize,
+    /// Kubelet stats summaries get_eviction_risk requests from the node proxy at once (KUBELET_STATS_CONCURRENCY)
+    pub kubelet_stats_concurrency: usize,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: None,
+            watch_mode: false,
+            cache_ttl_seconds: DEFAULT_CACHE_TTL_SECONDS,
+            default_namespace: None,
+            max_replica_count: DEFAULT_MAX_REPLICA_COUNT,
+            disabled_tools: Vec::new(),
+            fit_tolerance: DEFAULT_FIT_TOLERANCE,
+            node_reserved_cpu_cores: 0.0,
+            node_reserved_memory_gb: 0.0,
+            max_response_items: DEFAULT_MAX_RESPONSE_ITEMS,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            kubelet_stats_concurrency: DEFAULT_KUBELET_STATS_CONCURRENCY,
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Read the file named by CONFIG_PATH (if set), apply environment overrides and validate
+    pub fn load() -> Result<Self, String> {
+        let config = match std::env::var("CONFIG_PATH") {
+            Ok(path) => Self::from_file(&path)?,
+            Err(_) => Self::default(),
+        };
+
+        config.with_env(|name| std::env::var(name).ok())?.validated()
+    }
+
+    /// Parse a YAML config file; missing fields keep their defaults
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read config file '{}': {}", path, e))?;
+
+        serde_yaml::from_str(&contents)
+            .map_err(|e| format!("Invalid config file '{}': {}", path, e))
+    }
+
+    /// Override fields with the environment variables returned by `var`
+    fn with_env(mut self, var: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        if let Some(value) = var("BIND_ADDRESS") {
+            self.bind_address = Some(value);
+        }
+        if let Some(value) = var("WATCH_MODE") {
+            self.watch_mode = value.eq_ignore_ascii_case("true");
+        }
+        if let Some(value) = var("CACHE_TTL_SECONDS") {
+            self.cache_ttl_seconds = parse_env("CACHE_TTL_SECONDS", &value)?;
+        }
+        if let Some(value) = var("DEFAULT_NAMESPACE") {
+            self.default_namespace = Some(value);
+        }
+        if let Some(value) = var("MAX_REPLICA_COUNT") {
+            self.max_replica_count = parse_env("MAX_REPLICA_COUNT", &value)?;
+        }
+        if let Some(value) = var("FIT_TOLERANCE") {
+            self.fit_tolerance = parse_env("FIT_TOLERANCE", &value)?;
+        }
+        if let Some(value) = var("NODE_RESERVED_CPU_CORES") {
+            self.node_reserved_cpu_cores = parse_env("NODE_RESERVED_CPU_CORES", &value)?;
+        }
+        if let Some(value) = var("NODE_RESERVED_MEMORY_GB") {
+            self.node_reserved_memory_gb = parse_env("NODE_RESERVED_MEMORY_GB", &value)?;
+        }
+        if let Some(value) = var("MAX_RESPONSE_ITEMS") {
+            self.max_response_items = parse_env("MAX_RESPONSE_ITEMS", &value)?;
+        }
+        if let Some(value) = var("MAX_RESPONSE_BYTES") {
+            self.max_response_bytes = parse_env("MAX_RESPONSE_BYTES", &value)?;
+        }
+        if let Some(value) = var("KUBELET_STATS_CONCURRENCY") {
+            self.kubelet_stats_concurrency = parse_env("KUBELET_STATS_CONCURRENCY", &value)?;
+        }
+        if let Some(value) = var("DISABLED_TOOLS") {
+            self.disabled_tools = value.split(',').map(|name| name.to_string()).collect();
+        }
+
+        Ok(self)
+    }
+
+    /// Reject invalid values and drop a blank default namespace and blank tool names
+    pub(crate) fn validated(mut self) -> Result<Self, String> {
+        if self.max_replica_count <= 0 {
+            return Err(format!("max_replica_count must be positive, got {}", self.max_replica_count));
+        }
+
+        if self.kubelet_stats_concurrency == 0 {
+            return Err("kubelet_stats_concurrency must be positive, got 0".to_string());
+        }
+
+        if !self.fit_tolerance.is_finite() || self.fit_tolerance < 0.0 {
+            return Err(format!("fit_tolerance must be non-negative, got {}", self.fit_tolerance));
+        }
+
+        for (name, value) in [("node_reserved_cpu_cores", self.node_reserved_cpu_cores), ("node_reserved_memory_gb", self.node_reserved_memory_gb)] {
+            if !value.is_finite() || value < 0.0 {
+                return Err(format!("{} must be non-negative, got {}", name, value));
+            }
+        }
+
+        if let Some(address) = &self.bind_address {
+            let port = address.rsplit_once(':').map(|(_, port)| port.parse::<u16>());
+            if !matches!(port, Some(Ok(_))) {
+                return Err(format!("bind_address '{}' must be host:port", address));
+            }
+        }
+
+        self.default_namespace = self.default_namespace
+            .map(|namespace| namespace.trim().to_string())
+            .filter(|namespace| !namespace.is_empty());
+        self.disabled_tools = self.disabled_tools.iter()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect();
+
+        Ok(self)
+    }
+}
+
+/// Parse the value of an environment variable, naming the variable on error
+fn parse_env<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value.trim().parse().map_err(|_| format!("Invalid value '{}' for {}", value, name))
+}
+
+/// Load and validate the configuration. Call once at startup, before serving.
+pub fn init() -> Result<&'static ServerConfig, String> {
+    let config = ServerConfig::load()?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// Active configuration
+pub fn get() -> &'static ServerConfig {
+    CONFIG.get_or_init(|| ServerConfig::load().unwrap_or_else(|e| {
+        tracing::warn!("Invalid configuration, using defaults: {}", e);
+        ServerConfig::default()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config.example.yaml");
+
+    #[test]
+    fn test_load_example_config() {
+        let config = ServerConfig::from_file(EXAMPLE_CONFIG).unwrap().validated().unwrap();
+
+        assert_eq!(config.bind_address.as_deref(), Some("127.0.0.1:8001"));
+        assert!(!config.watch_mode);
+        assert_eq!(config.cache_ttl_seconds, 30);
+        assert_eq!(config.default_namespace.as_deref(), Some("default"));
+        assert_eq!(config.max_replica_count, 500);
+        assert!(config.disabled_tools.is_empty());
+        assert_eq!(config.fit_tolerance, DEFAULT_FIT_TOLERANCE);
+        assert_eq!(config.node_reserved_cpu_cores, 0.1);
+        assert_eq!(config.node_reserved_memory_gb, 1.0);
+        assert_eq!(config.max_response_items, 500);
+        assert_eq!(config.max_response_bytes, DEFAULT_MAX_RESPONSE_BYTES);
+        assert_eq!(config.kubelet_stats_concurrency, 8);
+    }
+
+    #[test]
+    fn test_env_overrides_and_validation() {
+        let env = |name: &str| match name {
+            "CACHE_TTL_SECONDS" => Some("5".to_string()),
+            "DEFAULT_NAMESPACE" => Some("  ".to_string()),
+            "DISABLED_TOOLS" => Some(" check_replica_capacity, ,get_gpu_report".to_string()),
+            "NODE_RESERVED_MEMORY_GB" => Some("1.5".to_string()),
+            "MAX_RESPONSE_BYTES" => Some("200000".to_string()),
+            "KUBELET_STATS_CONCURRENCY" => Some("4".to_string()),
+            _ => None,
+        };
+        let config = ServerConfig::default().with_env(env).unwrap().validated().unwrap();
+        assert_eq!(config.cache_ttl_seconds, 5);
+        assert_eq!(config.default_namespace, None);
+        assert_eq!(config.disabled_tools, ["check_replica_capacity", "get_gpu_report"]);
+        assert_eq!(config.node_reserved_memory_gb, 1.5);
+        assert_eq!(config.max_response_bytes, 200_000);
+        assert_eq!(config.kubelet_stats_concurrency, 4);
+
+        let error = ServerConfig::default().with_env(|_| Some("many".to_string())).unwrap_err();
+        assert!(error.contains("CACHE_TTL_SECONDS"));
+
+        let negative = ServerConfig { max_replica_count: -1, ..Default::default() };
+        assert!(negative.validated().is_err());
+        let negative = ServerConfig { fit_tolerance: -0.1, ..Default::default() };
+        assert!(negative.validated().is_err());
+        let negative = ServerConfig { node_reserved_cpu_cores: -1.0, ..Default::default() };
+        assert!(negative.validated().is_err());
+        let zero = ServerConfig { kubelet_stats_concurrency: 0, ..Default::default() };
+        assert!(zero.validated().is_err());
+        assert!(serde_yaml::from_str::<ServerConfig>("cache_ttl: 5").is_err());
+    }
+}
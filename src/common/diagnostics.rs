@@ -0,0 +1,65 @@
+//! Structured diagnostics for tool results.
+//!
+//! Aggregation code reports the data it skips or degrades on (unparseable quantities, unavailable
+//! metrics, unreachable kubelets) here instead of only logging it. Everything reported while a tool
+//! call runs ends up in the `diagnostics` field of its result envelope, so agents can tell how far
+//! to trust the numbers.
+
+use std::cell::RefCell;
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+
+/// How much a diagnostic affects the numbers of a response
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticLevel {
+    /// Some detail is missing, the numbers still hold
+    Info,
+    /// Data was skipped or replaced by a fallback, the numbers may be off
+    Warning,
+}
+
+/// One skipped or degraded input of a tool call
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub level: DiagnosticLevel,
+    /// Stable snake_case identifier of the condition, e.g. `invalid_quantity`
+    pub code: String,
+    pub message: String,
+}
+
+tokio::task_local! {
+    static DIAGNOSTICS: RefCell<Vec<Diagnostic>>;
+}
+
+/// Run a tool call, collecting the diagnostics reported while it runs
+pub async fn scope<F: Future>(call: F) -> F::Output {
+    DIAGNOSTICS.scope(RefCell::new(Vec::new()), call).await
+}
+
+/// Log a diagnostic and record it for the current tool call (once per code and message).
+/// Outside a tool call it is only logged.
+pub fn report(level: DiagnosticLevel, code: &str, message: String) {
+    match level {
+        DiagnosticLevel::Info => tracing::debug!("{}", message),
+        DiagnosticLevel::Warning => tracing::warn!("{}", message),
+    }
+
+    let _ = DIAGNOSTICS.try_with(|diagnostics| {
+        let mut diagnostics = diagnostics.borrow_mut();
+        if !diagnostics.iter().any(|d| d.code == code && d.message == message) {
+            diagnostics.push(Diagnostic { level, code: code.to_string(), message });
+        }
+    });
+}
+
+/// Report a warning: data was skipped or replaced by a fallback
+pub fn warn(code: &str, message: String) {
+    report(DiagnosticLevel::Warning, code, message);
+}
+
+/// Take the diagnostics recorded so far in the current tool call (none outside one)
+pub fn take() -> Vec<Diagnostic> {
+    DIAGNOSTICS.try_with(|diagnostics| diagnostics.take()).unwrap_or_default()
+}
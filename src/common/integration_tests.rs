@@ -0,0 +1,188 @@
+//! Integration tests against a real kind or k3d cluster.
+//!
+//! Run with `INTEGRATION_CLUSTER=kind make test-integration` (or `k3d`), which creates or reuses a
+//! throwaway cluster and switches to its context. Without INTEGRATION_CLUSTER the tests fail rather
+//! than pass silently, and never touch whatever cluster the current kubeconfig context points at.
+
+use std::process::Command;
+use std::time::Duration;
+
+use k8s_openapi::api::core::v1::{Node, Pod};
+use kube::api::ListParams;
+use kube::{Api, Client};
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::CallToolResult;
+
+use super::cluster_insights::{CacheParams, CheckReplicaCapacityParams, ClusterCapacityParams, ClusterInsights};
+
+/// Name of the cluster created when INTEGRATION_CLUSTER is set
+const CLUSTER_NAME: &str = "cluster-insights-it";
+const NAMESPACE: &str = "cluster-insights-it";
+const APP: &str = "insights-fixture";
+const FIXTURE_NODE_LABEL: &str = "cluster-insights/fixture";
+const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/integration");
+const READY_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Requests of one fixture replica (see deployment.yaml)
+const REPLICA_CPU_CORES: f64 = 0.1;
+const REPLICA_MEMORY_GB: f64 = 0.0625;
+
+/// Run a command and panic with its stderr if it fails
+fn run(program: &str, args: &[&str]) {
+    let output = Command::new(program).args(args).output()
+        .unwrap_or_else(|e| panic!("Failed to run {}: {}", program, e));
+    assert!(
+        output.status.success(),
+        "{} {} failed: {}",
+        program, args.join(" "), String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Whether a command exits successfully
+fn succeeds(program: &str, args: &[&str]) -> bool {
+    Command::new(program).args(args).output().is_ok_and(|o| o.status.success())
+}
+
+/// Create the kind/k3d cluster requested by INTEGRATION_CLUSTER (if it does not exist yet) and
+/// switch to it. Panics when INTEGRATION_CLUSTER is unset, so the `integration` feature never passes
+/// without running the tests.
+fn ensure_cluster() {
+    match std::env::var("INTEGRATION_CLUSTER").as_deref() {
+        Ok("kind") => {
+            if !succeeds("kind", &["get", "kubeconfig", "--name", CLUSTER_NAME]) {
+                run("kind", &["create", "cluster", "--name", CLUSTER_NAME, "--wait", "120s"]);
+            }
+            run("kubectl", &["config", "use-context", &format!("kind-{}", CLUSTER_NAME)]);
+        }
+        Ok("k3d") => {
+            if !succeeds("k3d", &["cluster", "get", CLUSTER_NAME]) {
+                run("k3d", &["cluster", "create", CLUSTER_NAME, "--wait"]);
+            }
+            run("kubectl", &["config", "use-context", &format!("k3d-{}", CLUSTER_NAME)]);
+        }
+        Ok(other) => panic!("Unsupported INTEGRATION_CLUSTER '{}', expected 'kind' or 'k3d'", other),
+        Err(_) => panic!(
+            "The integration tests need INTEGRATION_CLUSTER=kind or INTEGRATION_CLUSTER=k3d; \
+             they never run against the current kubeconfig context"
+        ),
+    }
+}
+
+/// Removes the fixture namespace and node label when dropped, so a failing test cleans up too
+struct FixtureGuard {
+    node: String,
+}
+
+impl Drop for FixtureGuard {
+    fn drop(&mut self) {
+        // Best effort: panicking here while a failed test unwinds would abort the run
+        succeeds("kubectl", &["delete", "-f", &format!("{}/namespace.yaml", FIXTURES_DIR), "--wait=false", "--ignore-not-found"]);
+        succeeds("kubectl", &["label", "node", &self.node, &format!("{}-", FIXTURE_NODE_LABEL)]);
+    }
+}
+
+/// Label the first node for the fixture and apply the fixture manifests
+async fn apply_fixture(client: &Client) -> FixtureGuard {
+    let nodes = Api::<Node>::all(client.clone()).list(&ListParams::default()).await
+        .expect("list nodes");
+    let node = nodes.items.first().and_then(|n| n.metadata.name.clone())
+        .expect("cluster has at least one node");
+
+    let guard = FixtureGuard { node: node.clone() };
+    run("kubectl", &["label", "node", &node, &format!("{}=true", FIXTURE_NODE_LABEL), "--overwrite"]);
+    run("kubectl", &["apply", "-f", &format!("{}/namespace.yaml", FIXTURES_DIR)]);
+    run("kubectl", &["apply", "-f", &format!("{}/deployment.yaml", FIXTURES_DIR)]);
+    guard
+}
+
+fn is_pod_ready(pod: &Pod) -> bool {
+    pod.status.as_ref()
+        .and_then(|s| s.conditions.as_ref())
+        .is_some_and(|conditions| conditions.iter().any(|c| c.type_ == "Ready" && c.status == "True"))
+}
+
+/// Wait until exactly `expected` pods match `selector` in `namespace` and all of them are Ready
+async fn wait_for_pods_ready(client: &Client, namespace: &str, selector: &str, expected: usize) -> Result<(), String> {
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let deadline = tokio::time::Instant::now() + READY_TIMEOUT;
+
+    loop {
+        let list = pods.list(&ListParams::default().labels(selector)).await
+            .map_err(|e| format!("Failed to list pods: {}", e))?;
+        let live: Vec<&Pod> = list.items.iter().filter(|p| p.metadata.deletion_timestamp.is_none()).collect();
+        let ready = live.iter().filter(|p| is_pod_ready(p)).count();
+
+        if live.len() == expected && ready == expected {
+            return Ok(());
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(format!(
+                "Timed out waiting for {} ready pods with {} in {} ({} of {} ready)",
+                expected, selector, namespace, ready, live.len()
+            ));
+        }
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+}
+
+/// Scale the fixture deployment and wait for the new replica count
+async fn scale_fixture(client: &Client, replicas: usize) {
+    run("kubectl", &["scale", "deployment", APP, "-n", NAMESPACE, &format!("--replicas={}", replicas)]);
+    wait_for_pods_ready(client, NAMESPACE, &format!("app={}", APP), replicas).await.unwrap();
+}
+
+/// The `data` of a successful tool result
+fn tool_data(result: &CallToolResult) -> serde_json::Value {
+    let text = &result.content[0].as_text().expect("text content").text;
+    let envelope: serde_json::Value = serde_json::from_str(text).expect("tool output is an envelope");
+    assert_eq!(envelope["status"], "ok", "tool failed: {}", envelope["error"]);
+    envelope["data"].clone()
+}
+
+/// Numeric field of a tool response
+fn number(data: &serde_json::Value, field: &str) -> f64 {
+    data[field].as_f64().unwrap_or_else(|| panic!("{} is not a number in {}", field, data))
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-6, "expected {}, got {}", expected, actual);
+}
+
+/// Cluster capacity straight from the API server
+async fn fresh_capacity(cluster_insights: &ClusterInsights) -> serde_json::Value {
+    let params = ClusterCapacityParams { cache: CacheParams { fresh: Some(true) }, ..Default::default() };
+    tool_data(&cluster_insights.get_cluster_capacity(Parameters(params)).await.unwrap())
+}
+
+#[tokio::test]
+async fn test_fixture_capacity_and_replicas() {
+    ensure_cluster();
+    let client = Client::try_default().await.expect("kubeconfig for the test cluster");
+    let _fixture = apply_fixture(&client).await;
+    scale_fixture(&client, 2).await;
+
+    let cluster_insights = ClusterInsights::new();
+
+    // Two more replicas add exactly their requests to the allocated totals
+    let before = fresh_capacity(&cluster_insights).await;
+    scale_fixture(&client, 4).await;
+    let after = fresh_capacity(&cluster_insights).await;
+
+    assert_eq!(after["total_cpu_cores"], before["total_cpu_cores"]);
+    assert_close(number(&after, "allocated_cpu_cores") - number(&before, "allocated_cpu_cores"), 2.0 * REPLICA_CPU_CORES);
+    assert_close(number(&after, "allocated_memory_gb") - number(&before, "allocated_memory_gb"), 2.0 * REPLICA_MEMORY_GB);
+
+    let replicas = tool_data(&cluster_insights.check_replica_capacity(Parameters(CheckReplicaCapacityParams {
+        app_name: APP.to_string(),
+        namespace: NAMESPACE.to_string(),
+        replica_count: 3,
+        cache: CacheParams { fresh: Some(true) },
+        ..Default::default()
+    })).await.unwrap());
+
+    assert_eq!(replicas["current_pod_count"], 4);
+    assert_close(number(&replicas, "cpu_per_replica_cores"), REPLICA_CPU_CORES);
+    assert_close(number(&replicas, "memory_per_replica_gb"), REPLICA_MEMORY_GB);
+    assert_close(number(&replicas, "total_cpu_required_cores"), 3.0 * REPLICA_CPU_CORES);
+    assert_close(number(&replicas, "total_memory_required_gb"), 3.0 * REPLICA_MEMORY_GB);
+}
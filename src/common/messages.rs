@@ -0,0 +1,2518 @@
+// Message catalog for the explanation strings returned by the tools.
+// Numeric fields of the responses are never localized, only the human-readable explanations.
+
+use std::collections::BTreeMap;
+
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+
+use super::cluster_insights::{
+    format_cpu, format_memory_gb, BurstRiskResponse, CheckReplicaCapacityResponse, CheckResourceFitResponse, ClusterCapacityResponse, ExtraResourceFit,
+    ForecastCapacityResponse, ImageDistributionResponse, ListCapabilitiesResponse, NMinusOneResponse, NamespacesWithoutLimitsResponse, NodeAcceptanceMatrixResponse, MaxPodsOfSizeResponse, LargestPodPerNodeResponse, StrandedCapacityResponse, OverpackedNodesResponse, BalanceReportResponse, PodChurnResponse, GpuReportResponse, OverheadBreakdownResponse, NamespaceNodeDistributionResponse, NamespaceUsage, NamespaceUsageResponse,
+    NodeBreakdownResponse, PodResourceStatsResponse, PodSortBy, PodsOnNodeResponse, PreemptibleCapacity, ResourceBasis, ScaleUpRecommendation,
+    StuckPodsResponse, UnschedulableBySizeResponse, UsageByLabelResponse, UsageForSelectorResponse, UsageByWorkloadKindResponse, ReplicaConsistencyResponse, DeploymentScaleResponse, EvictionRiskResponse, NamespaceShareResponse, RequestOutliersResponse, CapacityByNodeStateResponse, NamespaceHeadroomResponse, RecommendNodeSizeResponse, OrphanedPodsResponse, CronJobCapacityResponse, NamespaceDownsizeResponse,
+    FullReportResponse, HealthSignals, GatedPodsResponse,
+    BurstProjectionResponse, QuotaUsageResponse, NodePackingResponse, StrandedGpusResponse,
+    RequestCoverageResponse, RequestRatioMismatchResponse, CapacityInPodUnitsResponse,
+    FindLargePodsResponse, PodAccountingResponse, ContainerAccounting, ContainerRole, UsageSource,
+    NamespaceLimitGap, PodSize, NodeLargestPods, NodeStrandedCapacity, OverpackedNode, StrandedGpuNode,
+};
+
+/// Language of the explanation strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    En,
+    Es,
+}
+
+impl Lang {
+    /// Parse an optional language code (`en`, `es`), defaulting to English
+    pub fn parse(code: Option<&str>) -> Result<Self, String> {
+        match code.map(|c| c.trim().to_lowercase()).as_deref() {
+            None | Some("") | Some("en") => Ok(Lang::En),
+            Some("es") => Ok(Lang::Es),
+            Some(other) => Err(format!("Unsupported language '{}' (supported: en, es)", other)),
+        }
+    }
+}
+
+/// Explanation of the cluster capacity
+pub fn cluster_capacity(lang: Lang, capacity: &ClusterCapacityResponse) -> String {
+    let cpu_percent = percent_suffix(capacity.allocated_cpu_cores, capacity.total_cpu_cores);
+    let memory_percent = percent_suffix(capacity.allocated_memory_gb, capacity.total_memory_gb);
+
+    let mut summary = match lang {
+        Lang::En => format!(
+            "Cluster has {} nodes. Total capacity: {:.2} CPU cores, {:.2} GB memory. \
+             Allocated ({}): {:.2} CPU cores{}, {:.2} GB memory{}. \
+             Available: {:.2} CPU cores, {:.2} GB memory.",
+            capacity.node_count,
+            capacity.total_cpu_cores, capacity.total_memory_gb,
+            basis_label(lang, capacity.basis),
+            capacity.allocated_cpu_cores, cpu_percent,
+            capacity.allocated_memory_gb, memory_percent,
+            capacity.available_cpu_cores, capacity.available_memory_gb
+        ),
+        Lang::Es => format!(
+            "El clúster tiene {} nodos. Capacidad total: {:.2} núcleos de CPU, {:.2} GB de memoria. \
+             Asignado ({}): {:.2} núcleos de CPU{}, {:.2} GB de memoria{}. \
+             Disponible: {:.2} núcleos de CPU, {:.2} GB de memoria.",
+            capacity.node_count,
+            capacity.total_cpu_cores, capacity.total_memory_gb,
+            basis_label(lang, capacity.basis),
+            capacity.allocated_cpu_cores, cpu_percent,
+            capacity.allocated_memory_gb, memory_percent,
+            capacity.available_cpu_cores, capacity.available_memory_gb
+        ),
+    };
+    if let Some(note) = no_capacity_note(lang, capacity.total_cpu_cores, capacity.total_memory_gb) {
+        summary = format!("{} {}", summary, note);
+    }
+
+    if capacity.pending_pod_count > 0 {
+        summary = match lang {
+            Lang::En => format!(
+                "{} Another {} pending pod(s) wait for a node, requesting {:.2} CPU cores and {:.2} GB memory.",
+                summary, capacity.pending_pod_count, capacity.pending_cpu_cores, capacity.pending_memory_gb
+            ),
+            Lang::Es => format!(
+                "{} Otros {} pod(s) pendientes esperan un nodo y solicitan {:.2} núcleos de CPU y {:.2} GB de memoria.",
+                summary, capacity.pending_pod_count, capacity.pending_cpu_cores, capacity.pending_memory_gb
+            ),
+        };
+    }
+    if capacity.gated_pod_count > 0 {
+        summary = match lang {
+            Lang::En => format!(
+                "{} {} pod(s) held back by scheduling gates are not counted as demand; see find_gated_pods.",
+                summary, capacity.gated_pod_count
+            ),
+            Lang::Es => format!(
+                "{} {} pod(s) retenidos por scheduling gates no cuentan como demanda; consulte find_gated_pods.",
+                summary, capacity.gated_pod_count
+            ),
+        };
+    }
+    summary
+}
+
+/// Change in allocation since the session's previous get_cluster_capacity call, or a note that there is none
+pub fn capacity_delta(lang: Lang, delta: Option<(f64, f64)>) -> String {
+    match (delta, lang) {
+        (Some((cpu_cores, memory_gb)), Lang::En) => format!(
+            "Allocation changed by {:+.2} CPU cores and {:+.2} GB memory since the previous call.",
+            cpu_cores, memory_gb
+        ),
+        (Some((cpu_cores, memory_gb)), Lang::Es) => format!(
+            "La asignación cambió en {:+.2} núcleos de CPU y {:+.2} GB de memoria desde la llamada anterior.",
+            cpu_cores, memory_gb
+        ),
+        (None, Lang::En) => "No previous call in this session to compare with.".to_string(),
+        (None, Lang::Es) => "No hay una llamada anterior en esta sesión con la que comparar.".to_string(),
+    }
+}
+
+/// Explanation of a resource fit check
+pub fn resource_fit(
+    lang: Lang,
+    cpu_cores: f64,
+    memory_gb: f64,
+    capacity: &ClusterCapacityResponse,
+    cpu_utilization_percent: f64,
+    memory_utilization_percent: f64,
+) -> String {
+    let cpu_fits = capacity.available_cpu_cores >= cpu_cores;
+    let memory_fits = capacity.available_memory_gb >= memory_gb;
+
+    if cpu_fits && memory_fits {
+        // Only resources with capacity get a utilization percentage
+        let mut utilization = vec![];
+        if capacity.total_cpu_cores > 0.0 {
+            utilization.push(match lang {
+                Lang::En => format!("{:.1}% CPU", cpu_utilization_percent),
+                Lang::Es => format!("al {:.1}% de CPU", cpu_utilization_percent),
+            });
+        }
+        if capacity.total_memory_gb > 0.0 {
+            utilization.push(match lang {
+                Lang::En => format!("{:.1}% memory", memory_utilization_percent),
+                Lang::Es => format!("al {:.1}% de memoria", memory_utilization_percent),
+            });
+        }
+        let mut after = match (lang, utilization.is_empty()) {
+            (_, true) => String::new(),
+            (Lang::En, false) => format!(" After allocation, cluster would be at {} utilization.", utilization.join(" and ")),
+            (Lang::Es, false) => format!(" Tras la asignación, el clúster estaría {}.", utilization.join(" y ")),
+        };
+        if let Some(note) = no_capacity_note(lang, capacity.total_cpu_cores, capacity.total_memory_gb) {
+            after = format!("{} {}", after, note);
+        }
+
+        return match lang {
+            Lang::En => format!(
+                "Resources FIT in cluster. Requested: {:.2} CPU cores, {:.2} GB memory. \
+                 Available: {:.2} CPU cores, {:.2} GB memory.{}",
+                cpu_cores, memory_gb,
+                capacity.available_cpu_cores, capacity.available_memory_gb,
+                after
+            ),
+            Lang::Es => format!(
+                "Los recursos CABEN en el clúster. Solicitado: {:.2} núcleos de CPU, {:.2} GB de memoria. \
+                 Disponible: {:.2} núcleos de CPU, {:.2} GB de memoria.{}",
+                cpu_cores, memory_gb,
+                capacity.available_cpu_cores, capacity.available_memory_gb,
+                after
+            ),
+        };
+    }
+
+    let cpu_shortage = if !cpu_fits {
+        match lang {
+            Lang::En => format!("CPU shortage: {:.2} cores needed but only {:.2} available. ",
+                cpu_cores - capacity.available_cpu_cores, capacity.available_cpu_cores),
+            Lang::Es => format!("Falta de CPU: se necesitan {:.2} núcleos más pero solo hay {:.2} disponibles. ",
+                cpu_cores - capacity.available_cpu_cores, capacity.available_cpu_cores),
+        }
+    } else {
+        String::new()
+    };
+    let memory_shortage = if !memory_fits {
+        match lang {
+            Lang::En => format!("Memory shortage: {:.2} GB needed but only {:.2} GB available.",
+                memory_gb - capacity.available_memory_gb, capacity.available_memory_gb),
+            Lang::Es => format!("Falta de memoria: se necesitan {:.2} GB más pero solo hay {:.2} GB disponibles.",
+                memory_gb - capacity.available_memory_gb, capacity.available_memory_gb),
+        }
+    } else {
+        String::new()
+    };
+
+    match lang {
+        Lang::En => format!(
+            "Resources DO NOT FIT in cluster. Requested: {:.2} CPU cores, {:.2} GB memory. \
+             Available: {:.2} CPU cores, {:.2} GB memory. {}{}",
+            cpu_cores, memory_gb,
+            capacity.available_cpu_cores, capacity.available_memory_gb,
+            cpu_shortage, memory_shortage
+        ),
+        Lang::Es => format!(
+            "Los recursos NO CABEN en el clúster. Solicitado: {:.2} núcleos de CPU, {:.2} GB de memoria. \
+             Disponible: {:.2} núcleos de CPU, {:.2} GB de memoria. {}{}",
+            cpu_cores, memory_gb,
+            capacity.available_cpu_cores, capacity.available_memory_gb,
+            cpu_shortage, memory_shortage
+        ),
+    }
+}
+
+/// Explanation of the node breakdown
+pub fn node_breakdown(lang: Lang, node_count: usize, basis: ResourceBasis) -> String {
+    match lang {
+        Lang::En => format!(
+            "Cluster has {} nodes. Each node shows total capacity, allocatable, allocated resources ({}), \
+             available resources, and pod count.",
+            node_count, basis_label(lang, basis)
+        ),
+        Lang::Es => format!(
+            "El clúster tiene {} nodos. Cada nodo muestra la capacidad total, lo asignable, los recursos asignados \
+             ({}), los recursos disponibles y el número de pods.",
+            node_count, basis_label(lang, basis)
+        ),
+    }
+}
+
+/// Note on a node breakdown that collapses identical nodes into groups
+pub fn node_groups(lang: Lang, group_count: usize, node_count: usize) -> String {
+    match lang {
+        Lang::En => format!(
+            "The {} nodes collapse into {} groups of the same instance type, allocatable and utilization band, \
+             each with min/max/avg availability.",
+            node_count, group_count
+        ),
+        Lang::Es => format!(
+            "Los {} nodos se agrupan en {} grupos con el mismo tipo de instancia, capacidad asignable y franja de utilización, \
+             cada uno con la disponibilidad mínima, máxima y media.",
+            node_count, group_count
+        ),
+    }
+}
+
+/// Note on a node breakdown that returns one page of the nodes
+pub fn node_page(lang: Lang, offset: usize, shown: usize, total: usize) -> String {
+    if shown == 0 {
+        return match lang {
+            Lang::En => format!("No nodes left after offset {} of {}.", offset, total),
+            Lang::Es => format!("No quedan nodos tras el desplazamiento {} de {}.", offset, total),
+        };
+    }
+    match lang {
+        Lang::En => format!("Showing nodes {}-{} of {}.", offset + 1, offset + shown, total),
+        Lang::Es => format!("Mostrando los nodos {}-{} de {}.", offset + 1, offset + shown, total),
+    }
+}
+
+/// Note on a node breakdown that leaves out pods bound to missing nodes
+pub fn orphaned_pods(lang: Lang, count: usize) -> String {
+    match lang {
+        Lang::En => format!("{} pod(s) bound to nodes that no longer exist are left out; see find_orphaned_pods.", count),
+        Lang::Es => format!("Se omiten {} pod(s) asignados a nodos que ya no existen; consulte find_orphaned_pods.", count),
+    }
+}
+
+/// Explanation of the namespace usage
+pub fn namespace_usage(lang: Lang, namespace_count: usize) -> String {
+    match lang {
+        Lang::En => format!(
+            "Cluster has {} namespaces. Resource usage shows CPU/memory requests and limits for each namespace, \
+             sorted by CPU requests (descending).",
+            namespace_count
+        ),
+        Lang::Es => format!(
+            "El clúster tiene {} namespaces. El uso de recursos muestra las requests y limits de CPU/memoria de cada \
+             namespace, ordenados por requests de CPU (descendente).",
+            namespace_count
+        ),
+    }
+}
+
+/// Sentence on the combined usage of the namespaces selected in get_namespace_usage
+pub fn combined_namespace_usage(lang: Lang, combined: &NamespaceUsage) -> String {
+    match lang {
+        Lang::En => format!(
+            "Together, {} have {} pods requesting {} CPU and {} memory.",
+            combined.namespace, combined.pod_count, format_cpu(combined.cpu_requests_cores), format_memory_gb(combined.memory_requests_gb)
+        ),
+        Lang::Es => format!(
+            "En conjunto, {} tienen {} pods que solicitan {} de CPU y {} de memoria.",
+            combined.namespace, combined.pod_count, format_cpu(combined.cpu_requests_cores), format_memory_gb(combined.memory_requests_gb)
+        ),
+    }
+}
+
+/// Explanation of the pod resource stats
+pub fn pod_resource_stats(lang: Lang, total_pods: usize, sort_by: PodSortBy) -> String {
+    match lang {
+        Lang::En => format!(
+            "Showing top 20 pods (out of {}) by {}. Each pod shows CPU/memory requests and limits, \
+             along with the node it's scheduled on.",
+            total_pods, sort_label(lang, sort_by)
+        ),
+        Lang::Es => format!(
+            "Mostrando los 20 pods principales (de {}) por {}. Cada pod muestra las requests y limits \
+             de CPU/memoria, junto con el nodo en el que está planificado.",
+            total_pods, sort_label(lang, sort_by)
+        ),
+    }
+}
+
+/// Name of a pod ranking criterion
+pub fn sort_label(lang: Lang, sort_by: PodSortBy) -> &'static str {
+    match (lang, sort_by) {
+        (Lang::En, PodSortBy::CpuRequests) => "CPU requests",
+        (Lang::En, PodSortBy::CpuUsage) => "CPU usage",
+        (Lang::En, PodSortBy::MemoryUsage) => "memory usage",
+        (Lang::Es, PodSortBy::CpuRequests) => "requests de CPU",
+        (Lang::Es, PodSortBy::CpuUsage) => "uso de CPU",
+        (Lang::Es, PodSortBy::MemoryUsage) => "uso de memoria",
+    }
+}
+
+/// Warning for a replica capacity check whose reference pod requests no CPU or memory
+pub fn best_effort_reference(lang: Lang, reference_pod: &str, app_name: &str) -> String {
+    match lang {
+        Lang::En => format!(
+            "Reference pod '{}' of '{}' has no CPU or memory requests (BestEffort QoS), so replica capacity \
+             cannot be estimated from requests: the scheduler admits any number of such pods and they compete \
+             for whatever is left on their nodes. Set requests on the workload for a meaningful check.",
+            reference_pod, app_name
+        ),
+        Lang::Es => format!(
+            "El pod de referencia '{}' de '{}' no tiene requests de CPU ni de memoria (QoS BestEffort), por lo que \
+             la capacidad para réplicas no se puede estimar a partir de los requests: el planificador admite cualquier \
+             número de estos pods y compiten por lo que quede libre en sus nodos. Defina requests en la carga de trabajo \
+             para obtener una comprobación útil.",
+            reference_pod, app_name
+        ),
+    }
+}
+
+/// Note for a replica capacity check capped by the reference pod's required pod anti-affinity
+pub fn anti_affinity_limit(lang: Lang, limit: i32, replica_count: i32) -> String {
+    match lang {
+        Lang::En => format!(
+            "Required pod anti-affinity allows at most {} more replicas (one per free topology domain), fewer than the {} requested; \
+             the rest would stay Pending regardless of free resources.",
+            limit, replica_count
+        ),
+        Lang::Es => format!(
+            "La anti-afinidad de pods obligatoria permite como máximo {} réplicas más (una por dominio de topología libre), \
+             menos que las {} solicitadas; el resto quedaría en Pending aunque haya recursos libres.",
+            limit, replica_count
+        ),
+    }
+}
+
+/// Explanation of a replica capacity check
+pub fn replica_capacity(
+    lang: Lang,
+    response: &CheckReplicaCapacityResponse,
+    app_name: &str,
+    namespace: &str,
+    replica_count: i32,
+    capacity: &ClusterCapacityResponse,
+) -> String {
+    let r = response;
+
+    if let Some(warning) = &r.warning {
+        return match lang {
+            Lang::En => format!(
+                "⚠ Capacity for {} more replicas of '{}' in namespace '{}' cannot be estimated.\n\n{}\n\nCurrent pods matching '{}': {}",
+                replica_count, app_name, namespace, warning, app_name, r.current_pod_count
+            ),
+            Lang::Es => format!(
+                "⚠ No se puede estimar la capacidad para {} réplicas más de '{}' en el namespace '{}'.\n\n{}\n\nPods actuales que coinciden con '{}': {}",
+                replica_count, app_name, namespace, warning, app_name, r.current_pod_count
+            ),
+        };
+    }
+
+    if r.fits {
+        let cpu_replicas = if r.cpu_per_replica_cores > 0.0 { capacity.available_cpu_cores / r.cpu_per_replica_cores } else { 0.0 };
+        let memory_replicas = if r.memory_per_replica_gb > 0.0 { capacity.available_memory_gb / r.memory_per_replica_gb } else { 0.0 };
+        // Without capacity there is no utilization to project
+        let projection = |projected: f64, allocated: f64, total: f64, resource: &str| {
+            if total <= 0.0 {
+                return match lang {
+                    Lang::En => format!("no {} capacity detected", resource),
+                    Lang::Es => format!("no se detectó capacidad de {}", resource),
+                };
+            }
+            let current = allocated / total * 100.0;
+            match lang {
+                Lang::En => format!("{:.1}% (current: {:.1}%)", projected, current),
+                Lang::Es => format!("{:.1}% (actual: {:.1}%)", projected, current),
+            }
+        };
+        let memory_name = match lang {
+            Lang::En => "memory",
+            Lang::Es => "memoria",
+        };
+        let cpu_projection = projection(r.projected_cpu_utilization_percent, capacity.allocated_cpu_cores, capacity.total_cpu_cores, "CPU");
+        let memory_projection = projection(r.projected_memory_utilization_percent, capacity.allocated_memory_gb, capacity.total_memory_gb, memory_name);
+
+        return match lang {
+            Lang::En => format!(
+                "✓ Capacity CHECK PASSED: You can add {} more replicas of '{}' in namespace '{}'.\n\
+                 \n\
+                 Reference pod: {}\n\
+                 - CPU per replica: {}\n\
+                 - Memory per replica: {}\n\
+                 \n\
+                 Total required for {} replicas:\n\
+                 - CPU: {}\n\
+                 - Memory: {}\n\
+                 \n\
+                 Cluster availability:\n\
+                 - Available CPU: {} (enough for {:.0} replicas)\n\
+                 - Available Memory: {} (enough for {:.0} replicas)\n\
+                 \n\
+                 Projected utilization after adding replicas:\n\
+                 - CPU: {}\n\
+                 - Memory: {}\n\
+                 \n\
+                 Current pods matching '{}': {}",
+                replica_count, app_name, namespace,
+                r.reference_pod,
+                format_cpu(r.cpu_per_replica_cores),
+                format_memory_gb(r.memory_per_replica_gb),
+                replica_count,
+                format_cpu(r.total_cpu_required_cores),
+                format_memory_gb(r.total_memory_required_gb),
+                format_cpu(capacity.available_cpu_cores), cpu_replicas,
+                format_memory_gb(capacity.available_memory_gb), memory_replicas,
+                cpu_projection, memory_projection,
+                app_name, r.current_pod_count
+            ),
+            Lang::Es => format!(
+                "✓ Comprobación de capacidad SUPERADA: puede añadir {} réplicas más de '{}' en el namespace '{}'.\n\
+                 \n\
+                 Pod de referencia: {}\n\
+                 - CPU por réplica: {}\n\
+                 - Memoria por réplica: {}\n\
+                 \n\
+                 Total necesario para {} réplicas:\n\
+                 - CPU: {}\n\
+                 - Memoria: {}\n\
+                 \n\
+                 Disponibilidad del clúster:\n\
+                 - CPU disponible: {} (suficiente para {:.0} réplicas)\n\
+                 - Memoria disponible: {} (suficiente para {:.0} réplicas)\n\
+                 \n\
+                 Utilización proyectada tras añadir las réplicas:\n\
+                 - CPU: {}\n\
+                 - Memoria: {}\n\
+                 \n\
+                 Pods actuales que coinciden con '{}': {}",
+                replica_count, app_name, namespace,
+                r.reference_pod,
+                format_cpu(r.cpu_per_replica_cores),
+                format_memory_gb(r.memory_per_replica_gb),
+                replica_count,
+                format_cpu(r.total_cpu_required_cores),
+                format_memory_gb(r.total_memory_required_gb),
+                format_cpu(capacity.available_cpu_cores), cpu_replicas,
+                format_memory_gb(capacity.available_memory_gb), memory_replicas,
+                cpu_projection, memory_projection,
+                app_name, r.current_pod_count
+            ),
+        };
+    }
+
+    let mut issues = vec![];
+
+    if r.cpu_per_replica_cores > 0.0 && capacity.available_cpu_cores < r.total_cpu_required_cores {
+        let shortfall = r.total_cpu_required_cores - capacity.available_cpu_cores;
+        let max_replicas = (capacity.available_cpu_cores / r.cpu_per_replica_cores).floor() as i32;
+        issues.push(match lang {
+            Lang::En => format!(
+                "CPU shortage: Need {} but only {} available (shortfall: {}). \
+                 Maximum possible replicas based on CPU: {}",
+                format_cpu(r.total_cpu_required_cores),
+                format_cpu(capacity.available_cpu_cores),
+                format_cpu(shortfall),
+                max_replicas
+            ),
+            Lang::Es => format!(
+                "Falta de CPU: se necesitan {} pero solo hay {} disponibles (déficit: {}). \
+                 Máximo de réplicas posible según la CPU: {}",
+                format_cpu(r.total_cpu_required_cores),
+                format_cpu(capacity.available_cpu_cores),
+                format_cpu(shortfall),
+                max_replicas
+            ),
+        });
+    }
+
+    if r.memory_per_replica_gb > 0.0 && capacity.available_memory_gb < r.total_memory_required_gb {
+        let shortfall = r.total_memory_required_gb - capacity.available_memory_gb;
+        let max_replicas = (capacity.available_memory_gb / r.memory_per_replica_gb).floor() as i32;
+        issues.push(match lang {
+            Lang::En => format!(
+                "Memory shortage: Need {} but only {} available (shortfall: {}). \
+                 Maximum possible replicas based on memory: {}",
+                format_memory_gb(r.total_memory_required_gb),
+                format_memory_gb(capacity.available_memory_gb),
+                format_memory_gb(shortfall),
+                max_replicas
+            ),
+            Lang::Es => format!(
+                "Falta de memoria: se necesitan {} pero solo hay {} disponibles (déficit: {}). \
+                 Máximo de réplicas posible según la memoria: {}",
+                format_memory_gb(r.total_memory_required_gb),
+                format_memory_gb(capacity.available_memory_gb),
+                format_memory_gb(shortfall),
+                max_replicas
+            ),
+        });
+    }
+
+    match lang {
+        Lang::En => format!(
+            "✗ Capacity CHECK FAILED: Cannot add {} replicas of '{}' in namespace '{}'.\n\
+             \n\
+             Reference pod: {}\n\
+             - CPU per replica: {}\n\
+             - Memory per replica: {}\n\
+             \n\
+             Total required for {} replicas:\n\
+             - CPU: {}\n\
+             - Memory: {}\n\
+             \n\
+             Issues:\n{}\n\
+             \n\
+             Current pods matching '{}': {}",
+            replica_count, app_name, namespace,
+            r.reference_pod,
+            format_cpu(r.cpu_per_replica_cores),
+            format_memory_gb(r.memory_per_replica_gb),
+            replica_count,
+            format_cpu(r.total_cpu_required_cores),
+            format_memory_gb(r.total_memory_required_gb),
+            issues.join("\n"),
+            app_name, r.current_pod_count
+        ),
+        Lang::Es => format!(
+            "✗ Comprobación de capacidad FALLIDA: no se pueden añadir {} réplicas de '{}' en el namespace '{}'.\n\
+             \n\
+             Pod de referencia: {}\n\
+             - CPU por réplica: {}\n\
+             - Memoria por réplica: {}\n\
+             \n\
+             Total necesario para {} réplicas:\n\
+             - CPU: {}\n\
+             - Memoria: {}\n\
+             \n\
+             Problemas:\n{}\n\
+             \n\
+             Pods actuales que coinciden con '{}': {}",
+            replica_count, app_name, namespace,
+            r.reference_pod,
+            format_cpu(r.cpu_per_replica_cores),
+            format_memory_gb(r.memory_per_replica_gb),
+            replica_count,
+            format_cpu(r.total_cpu_required_cores),
+            format_memory_gb(r.total_memory_required_gb),
+            issues.join("\n"),
+            app_name, r.current_pod_count
+        ),
+    }
+}
+
+/// Explanation of a scale-up recommendation
+pub fn scale_up(lang: Lang, recommendation: &ScaleUpRecommendation) -> String {
+    let r = recommendation;
+    let usable_cpu_cores = r.node_cpu_cores - r.daemonset_cpu_overhead_cores;
+    let usable_memory_gb = r.node_memory_gb - r.daemonset_memory_overhead_gb;
+
+    match (lang, r.nodes_needed) {
+        (Lang::En, Some(count)) => format!(
+            "Scale-up: add {} node(s) of {} / {} ({}), each keeping {} / {} usable after DaemonSet overhead.",
+            count,
+            format_cpu(r.node_cpu_cores), format_memory_gb(r.node_memory_gb),
+            r.node_size_source,
+            format_cpu(usable_cpu_cores), format_memory_gb(usable_memory_gb)
+        ),
+        (Lang::En, None) => format!(
+            "Scale-up: nodes of {} / {} ({}) cannot cover the shortfall once DaemonSet overhead \
+             ({} / {}) is reserved; a larger node size is required.",
+            format_cpu(r.node_cpu_cores), format_memory_gb(r.node_memory_gb),
+            r.node_size_source,
+            format_cpu(r.daemonset_cpu_overhead_cores), format_memory_gb(r.daemonset_memory_overhead_gb)
+        ),
+        (Lang::Es, Some(count)) => format!(
+            "Ampliación: añadir {} nodo(s) de {} / {} ({}), cada uno con {} / {} utilizables tras la sobrecarga de DaemonSets.",
+            count,
+            format_cpu(r.node_cpu_cores), format_memory_gb(r.node_memory_gb),
+            node_size_source_es(&r.node_size_source),
+            format_cpu(usable_cpu_cores), format_memory_gb(usable_memory_gb)
+        ),
+        (Lang::Es, None) => format!(
+            "Ampliación: los nodos de {} / {} ({}) no pueden cubrir el déficit una vez reservada la sobrecarga \
+             de DaemonSets ({} / {}); se necesita un tamaño de nodo mayor.",
+            format_cpu(r.node_cpu_cores), format_memory_gb(r.node_memory_gb),
+            node_size_source_es(&r.node_size_source),
+            format_cpu(r.daemonset_cpu_overhead_cores), format_memory_gb(r.daemonset_memory_overhead_gb)
+        ),
+    }
+}
+
+/// Amount of an extra resource for explanations, rounded to three decimals
+fn format_amount(value: f64) -> String {
+    ((value * 1000.0).round() / 1000.0).to_string()
+}
+
+/// Explanation of one extra resource in a fit check
+pub fn extra_resource_fit(lang: Lang, fit: &ExtraResourceFit) -> String {
+    let (requested, available) = (format_amount(fit.requested), format_amount(fit.available));
+    match (lang, fit.fits) {
+        (Lang::En, true) => format!("{} fits: {} requested, {} available.", fit.resource, requested, available),
+        (Lang::En, false) => format!(
+            "{} does NOT fit: {} requested but only {} available.", fit.resource, requested, available
+        ),
+        (Lang::Es, true) => format!("{} cabe: {} solicitado, {} disponible.", fit.resource, requested, available),
+        (Lang::Es, false) => format!(
+            "{} NO cabe: {} solicitado pero solo hay {} disponible.", fit.resource, requested, available
+        ),
+    }
+}
+
+/// Note on a resource fit computed from caller-supplied totals
+pub fn offline_estimate(lang: Lang) -> &'static str {
+    match lang {
+        Lang::En => "Estimated from the supplied cluster totals, not the live cluster.",
+        Lang::Es => "Estimación a partir de los totales de clúster indicados, no del clúster en vivo.",
+    }
+}
+
+/// Note appended to fit checks that count the demand of Pending pods as taken
+pub fn pending_demand(lang: Lang, capacity: &ClusterCapacityResponse) -> String {
+    let cpu = format_cpu(capacity.pending_cpu_cores);
+    let memory = format_memory_gb(capacity.pending_memory_gb);
+    match lang {
+        Lang::En => format!(
+            "Available capacity is net of the {} CPU and {} memory requested by {} Pending pods.",
+            cpu, memory, capacity.pending_pod_count
+        ),
+        Lang::Es => format!(
+            "La capacidad disponible descuenta los {} de CPU y {} de memoria solicitados por {} pods en Pending.",
+            cpu, memory, capacity.pending_pod_count
+        ),
+    }
+}
+
+/// Note appended to fit checks whose available capacity is capped at a target utilization
+pub fn utilization_cap(lang: Lang, target_percent: f64, capacity: &ClusterCapacityResponse) -> String {
+    let reserve = 1.0 - target_percent / 100.0;
+    let cpu = format_cpu(capacity.total_cpu_cores * reserve);
+    let memory = format_memory_gb(capacity.total_memory_gb * reserve);
+    match lang {
+        Lang::En => format!(
+            "Available capacity is capped at {}% allocation, keeping {} CPU and {} memory in reserve.",
+            target_percent, cpu, memory
+        ),
+        Lang::Es => format!(
+            "La capacidad disponible se limita a un {}% de asignación, reservando {} de CPU y {} de memoria.",
+            target_percent, cpu, memory
+        ),
+    }
+}
+
+/// Note on capacity computed as if some namespaces' pods did not exist
+pub fn excluded_namespaces(lang: Lang, namespaces: &[String], freed: (f64, f64)) -> String {
+    let names = namespaces.join(", ");
+    let cpu = format_cpu(freed.0);
+    let memory = format_memory_gb(freed.1);
+    match lang {
+        Lang::En => format!(
+            "Pods of namespace(s) {} are left out as if evacuated, freeing {} CPU and {} memory.",
+            names, cpu, memory
+        ),
+        Lang::Es => format!(
+            "Se omiten los pods de los namespaces {} como si se hubieran evacuado, liberando {} de CPU y {} de memoria.",
+            names, cpu, memory
+        ),
+    }
+}
+
+/// Explanation of the health section of the full report
+pub fn health_signals(lang: Lang, health: &HealthSignals) -> String {
+    let h = health;
+    let issues = h.pending_pod_count + h.orphaned_pod_count + h.stuck_pod_count
+        + h.overpacked_node_count + h.cordoned_node_count + h.not_ready_node_count;
+    match (lang, issues) {
+        (Lang::En, 0) => "Health: no pending, orphaned or stuck pods, and no overpacked, cordoned or NotReady nodes.".to_string(),
+        (Lang::En, _) => format!(
+            "Health: {} pending, {} orphaned and {} stuck pods; {} overpacked, {} cordoned and {} NotReady nodes.",
+            h.pending_pod_count, h.orphaned_pod_count, h.stuck_pod_count,
+            h.overpacked_node_count, h.cordoned_node_count, h.not_ready_node_count
+        ),
+        (Lang::Es, 0) => "Salud: ningún pod pendiente, huérfano ni atascado, y ningún nodo sobrecargado, acordonado ni NotReady.".to_string(),
+        (Lang::Es, _) => format!(
+            "Salud: {} pods pendientes, {} huérfanos y {} atascados; {} nodos sobrecargados, {} acordonados y {} NotReady.",
+            h.pending_pod_count, h.orphaned_pod_count, h.stuck_pod_count,
+            h.overpacked_node_count, h.cordoned_node_count, h.not_ready_node_count
+        ),
+    }
+}
+
+/// Note appended to fit checks run at a PriorityClass
+pub fn preemption(lang: Lang, preemption: &PreemptibleCapacity) -> String {
+    let p = preemption;
+    let cpu = format_cpu(p.reclaimable_cpu_cores);
+    let memory = format_memory_gb(p.reclaimable_memory_gb);
+    match (lang, p.preempts) {
+        (Lang::En, true) => format!(
+            "At PriorityClass '{}' (priority {}), the {} CPU and {} memory held by {} lower-priority pods count as available, \
+             since the scheduler can preempt them.",
+            p.priority_class, p.priority, cpu, memory, p.preemptible_pod_count
+        ),
+        (Lang::En, false) => format!(
+            "PriorityClass '{}' (priority {}) has preemptionPolicy Never, so the {} CPU and {} memory held by {} lower-priority pods stay taken.",
+            p.priority_class, p.priority, cpu, memory, p.preemptible_pod_count
+        ),
+        (Lang::Es, true) => format!(
+            "Con la PriorityClass '{}' (prioridad {}), los {} de CPU y {} de memoria de {} pods de menor prioridad cuentan como disponibles, \
+             ya que el planificador puede desalojarlos.",
+            p.priority_class, p.priority, cpu, memory, p.preemptible_pod_count
+        ),
+        (Lang::Es, false) => format!(
+            "La PriorityClass '{}' (prioridad {}) tiene preemptionPolicy Never, así que los {} de CPU y {} de memoria de {} pods \
+             de menor prioridad siguen ocupados.",
+            p.priority_class, p.priority, cpu, memory, p.preemptible_pod_count
+        ),
+    }
+}
+
+/// Note appended to explanations that count existing pods by something other than requests
+pub fn resource_basis(lang: Lang, basis: ResourceBasis) -> Option<&'static str> {
+    match (basis, lang) {
+        (ResourceBasis::Requests, _) => None,
+        (ResourceBasis::Limits, Lang::En) => Some("Existing pods are counted by their limits (requests where unset), so the answer assumes no overcommit."),
+        (ResourceBasis::Limits, Lang::Es) => Some("Los pods existentes se cuentan por sus límites (las solicitudes donde no hay límite), así que la respuesta no asume sobrecompromiso."),
+        (ResourceBasis::Usage, Lang::En) => Some("Existing pods are counted by their live usage, which the scheduler ignores: free capacity here may still be reserved by requests."),
+        (ResourceBasis::Usage, Lang::Es) => Some("Los pods existentes se cuentan por su uso real, que el planificador ignora: la capacidad libre aquí puede seguir reservada por solicitudes."),
+    }
+}
+
+/// Share of `part` in `total` as " (12.5%)", or nothing when the total is zero and any percentage would be meaningless
+pub fn percent_suffix(part: f64, total: f64) -> String {
+    if total > 0.0 {
+        format!(" ({:.1}%)", part / total * 100.0)
+    } else {
+        String::new()
+    }
+}
+
+/// Note replacing the dropped percentages of a cluster without CPU or memory capacity (e.g. no nodes at all)
+pub fn no_capacity_note(lang: Lang, total_cpu_cores: f64, total_memory_gb: f64) -> Option<String> {
+    let mut notes = vec![];
+    if total_cpu_cores <= 0.0 {
+        notes.push(match lang {
+            Lang::En => "No CPU capacity detected.",
+            Lang::Es => "No se detectó capacidad de CPU.",
+        });
+    }
+    if total_memory_gb <= 0.0 {
+        notes.push(match lang {
+            Lang::En => "No memory capacity detected.",
+            Lang::Es => "No se detectó capacidad de memoria.",
+        });
+    }
+    (!notes.is_empty()).then(|| notes.join(" "))
+}
+
+/// Name of a resource basis inside an explanation
+fn basis_label(lang: Lang, basis: ResourceBasis) -> &'static str {
+    match (basis, lang) {
+        (ResourceBasis::Requests, _) => "requests",
+        (ResourceBasis::Limits, _) => "limits",
+        (ResourceBasis::Usage, Lang::En) => "usage",
+        (ResourceBasis::Usage, Lang::Es) => "uso",
+    }
+}
+
+/// Explanation of the autoscaler verdict
+pub fn autoscale(lang: Lang, nodes_needed: Option<u32>) -> String {
+    match (lang, nodes_needed) {
+        (Lang::En, Some(count)) => format!(
+            "Fits after autoscaling: the node groups can add the {} node(s) needed.", count
+        ),
+        (Lang::En, None) => "Does not fit after autoscaling: the node groups reach their max size first.".to_string(),
+        (Lang::Es, Some(count)) => format!(
+            "Cabe tras el autoescalado: los grupos de nodos pueden añadir los {} nodo(s) necesarios.", count
+        ),
+        (Lang::Es, None) => "No cabe tras el autoescalado: los grupos de nodos alcanzan antes su tamaño máximo.".to_string(),
+    }
+}
+
+fn node_size_source_es(source: &str) -> &str {
+    match source {
+        "parameter" => "parámetro",
+        "median of existing nodes" => "mediana de los nodos existentes",
+        other => other,
+    }
+}
+
+/// Explanation of the burst risk check; `highest` is the namespace with the highest limit/request ratio
+pub fn burst_risk(lang: Lang, flagged: usize, checked: usize, ratio_threshold: f64, highest: Option<(&str, f64)>) -> String {
+    match (lang, highest) {
+        (Lang::En, None) => format!(
+            "None of {} namespaces has limits above {:.1}x its requests.", checked, ratio_threshold
+        ),
+        (Lang::En, Some((namespace, ratio))) => format!(
+            "{} of {} namespaces have CPU or memory limits above {:.1}x their requests, \
+             so their pods can burst well beyond what the scheduler reserved and destabilize nodes. \
+             Highest: '{}' at {:.1}x.",
+            flagged, checked, ratio_threshold, namespace, ratio
+        ),
+        (Lang::Es, None) => format!(
+            "Ninguno de los {} namespaces tiene límites por encima de {:.1}x sus solicitudes.", checked, ratio_threshold
+        ),
+        (Lang::Es, Some((namespace, ratio))) => format!(
+            "{} de {} namespaces tienen límites de CPU o memoria por encima de {:.1}x sus solicitudes, \
+             así que sus pods pueden superar con creces lo que reservó el planificador y desestabilizar los nodos. \
+             Mayor: '{}' con {:.1}x.",
+            flagged, checked, ratio_threshold, namespace, ratio
+        ),
+    }
+}
+
+/// Explanation of the namespaces whose pods lack memory limits
+pub fn namespaces_without_limits(
+    lang: Lang,
+    flagged: usize,
+    checked: usize,
+    min_unlimited_fraction: f64,
+    worst: Option<&NamespaceLimitGap>,
+) -> String {
+    let percent = min_unlimited_fraction * 100.0;
+    match (lang, worst) {
+        (Lang::En, None) => format!(
+            "None of {} namespaces has {:.0}% or more of its pods without a memory limit.", checked, percent
+        ),
+        (Lang::En, Some(worst)) => format!(
+            "{} of {} namespaces have {:.0}% or more of their pods without a memory limit. \
+             Those pods can grow until their node runs out of memory and are the first to be evicted; \
+             a LimitRange with default limits closes the gap. Worst: '{}' with {} of {} pods unlimited.",
+            flagged, checked, percent, worst.namespace, worst.pods_without_memory_limit, worst.pod_count
+        ),
+        (Lang::Es, None) => format!(
+            "Ninguno de los {} namespaces tiene el {:.0}% o más de sus pods sin límite de memoria.", checked, percent
+        ),
+        (Lang::Es, Some(worst)) => format!(
+            "{} de {} namespaces tienen el {:.0}% o más de sus pods sin límite de memoria. \
+             Esos pods pueden crecer hasta agotar la memoria de su nodo y son los primeros en ser desalojados; \
+             un LimitRange con límites por defecto cierra el hueco. Peor: '{}' con {} de {} pods sin límite.",
+            flagged, checked, percent, worst.namespace, worst.pods_without_memory_limit, worst.pod_count
+        ),
+    }
+}
+
+/// Explanation of the node acceptance matrix
+pub fn node_acceptance_matrix(lang: Lang, node_count: usize, sizes: &[PodSize], accepting_nodes: &[usize], full_nodes: &[&str]) -> String {
+    let per_size = sizes.iter().zip(accepting_nodes)
+        .map(|(size, count)| match lang {
+            Lang::En => format!("'{}' fits on {}", size.name, count),
+            Lang::Es => format!("'{}' cabe en {}", size.name, count),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut explanation = match lang {
+        Lang::En => format!("Of {} nodes, {}.", node_count, per_size),
+        Lang::Es => format!("De {} nodos, {}.", node_count, per_size),
+    };
+    if !full_nodes.is_empty() {
+        explanation.push_str(&match lang {
+            Lang::En => format!(" {} node(s) accept none of the sizes: {}.", full_nodes.len(), full_nodes.join(", ")),
+            Lang::Es => format!(" {} nodo(s) no aceptan ninguno de los tamaños: {}.", full_nodes.len(), full_nodes.join(", ")),
+        });
+    }
+    explanation
+}
+
+/// Explanation of the largest pods per node; `top` is the node holding the biggest CPU consumer
+pub fn largest_pod_per_node(lang: Lang, node_count: usize, top: Option<&NodeLargestPods>) -> String {
+    let Some(top) = top else {
+        return match lang {
+            Lang::En => "No pods are running on any node.".to_string(),
+            Lang::Es => "No hay pods en ejecución en ningún nodo.".to_string(),
+        };
+    };
+    let pod = &top.largest_by_cpu;
+    match lang {
+        Lang::En => format!(
+            "Found the largest pods on {} nodes. The biggest CPU consumer is {}/{} on {} with {} CPU and {} memory; \
+             moving the largest pod frees the most capacity when draining or rebalancing a node.",
+            node_count, pod.namespace, pod.name, top.node,
+            format_cpu(pod.cpu_requests_cores), format_memory_gb(pod.memory_requests_gb)
+        ),
+        Lang::Es => format!(
+            "Se encontraron los pods más grandes en {} nodos. El mayor consumidor de CPU es {}/{} en {} con {} de CPU y {} de memoria; \
+             mover el pod más grande libera más capacidad al drenar o reequilibrar un nodo.",
+            node_count, pod.namespace, pod.name, top.node,
+            format_cpu(pod.cpu_requests_cores), format_memory_gb(pod.memory_requests_gb)
+        ),
+    }
+}
+
+/// Explanation of the stranded capacity; `stranded` is the total stranded CPU (cores) and memory (GB)
+pub fn stranded_capacity(
+    lang: Lang,
+    node_count: usize,
+    memory_gb_per_core: f64,
+    stranded: (f64, f64),
+    worst: Option<&NodeStrandedCapacity>,
+) -> String {
+    match (lang, worst) {
+        (Lang::En, None) => format!(
+            "No capacity is stranded: the free CPU and memory of every node are balanced for pods with {:.2} GB per core.",
+            memory_gb_per_core
+        ),
+        (Lang::En, Some(worst)) => format!(
+            "{} CPU and {} memory are stranded across {} nodes for pods with {:.2} GB per core: \
+             free on one resource but unusable without the other. Worst: '{}' with {:.1}% of its allocatable stranded \
+             ({} CPU, {} memory). Pods with a matching shape, or rebalancing, recover it.",
+            format_cpu(stranded.0), format_memory_gb(stranded.1), node_count, memory_gb_per_core,
+            worst.node, worst.stranded_percent,
+            format_cpu(worst.stranded_cpu_cores), format_memory_gb(worst.stranded_memory_gb)
+        ),
+        (Lang::Es, None) => format!(
+            "No hay capacidad varada: la CPU y la memoria libres de cada nodo están equilibradas para pods con {:.2} GB por núcleo.",
+            memory_gb_per_core
+        ),
+        (Lang::Es, Some(worst)) => format!(
+            "{} de CPU y {} de memoria están varados en {} nodos para pods con {:.2} GB por núcleo: \
+             libres en un recurso pero inutilizables sin el otro. Peor: '{}' con el {:.1}% de su capacidad asignable varada \
+             ({} de CPU, {} de memoria). Pods con una forma adecuada, o reequilibrar, la recuperan.",
+            format_cpu(stranded.0), format_memory_gb(stranded.1), node_count, memory_gb_per_core,
+            worst.node, worst.stranded_percent,
+            format_cpu(worst.stranded_cpu_cores), format_memory_gb(worst.stranded_memory_gb)
+        ),
+    }
+}
+
+/// Explanation of the nodes requesting more than their allocatable
+pub fn overpacked_nodes(lang: Lang, overpacked: usize, node_count: usize, worst: Option<&OverpackedNode>) -> String {
+    match (lang, worst) {
+        (Lang::En, None) => format!("None of {} nodes has more requested than allocatable.", node_count),
+        (Lang::En, Some(worst)) => format!(
+            "{} of {} nodes have more requested than allocatable, which the scheduler never allows on its own: \
+             check for pods bound manually (spec.nodeName) or allocatable that shrank after the pods started. \
+             Worst: '{}' with {} CPU and {} memory over its allocatable.",
+            overpacked, node_count, worst.node,
+            format_cpu(worst.cpu_overage_cores), format_memory_gb(worst.memory_overage_gb)
+        ),
+        (Lang::Es, None) => format!("Ninguno de los {} nodos tiene más solicitado que asignable.", node_count),
+        (Lang::Es, Some(worst)) => format!(
+            "{} de {} nodos tienen más solicitado que asignable, algo que el planificador nunca permite por sí solo: \
+             busque pods asignados a mano (spec.nodeName) o capacidad asignable que se redujo después de arrancar los pods. \
+             Peor: '{}' con {} de CPU y {} de memoria por encima de su capacidad asignable.",
+            overpacked, node_count, worst.node,
+            format_cpu(worst.cpu_overage_cores), format_memory_gb(worst.memory_overage_gb)
+        ),
+    }
+}
+
+/// Explanation of the utilization spread across nodes
+pub fn balance_report(lang: Lang, report: &BalanceReportResponse, cv_threshold: f64) -> String {
+    let (cpu, memory) = (&report.cpu, &report.memory);
+    let summary = match lang {
+        Lang::En => format!(
+            "CPU utilization averages {:.1}% (std dev {:.1} points, CV {:.2}) and memory {:.1}% (std dev {:.1} points, CV {:.2}) across {} nodes.",
+            cpu.mean_percent, cpu.std_dev_percent, cpu.coefficient_of_variation,
+            memory.mean_percent, memory.std_dev_percent, memory.coefficient_of_variation, report.nodes.len()
+        ),
+        Lang::Es => format!(
+            "El uso de CPU promedia {:.1}% (desviación típica {:.1} puntos, CV {:.2}) y el de memoria {:.1}% (desviación típica {:.1} puntos, CV {:.2}) en {} nodos.",
+            cpu.mean_percent, cpu.std_dev_percent, cpu.coefficient_of_variation,
+            memory.mean_percent, memory.std_dev_percent, memory.coefficient_of_variation, report.nodes.len()
+        ),
+    };
+    match (&report.most_utilized, &report.least_utilized, lang) {
+        (Some(most), Some(least), Lang::En) if report.imbalanced => format!(
+            "The cluster is imbalanced (coefficient of variation above {:.2}). {} Most utilized: '{}' ({:.1}% CPU, {:.1}% memory); \
+             least utilized: '{}' ({:.1}% CPU, {:.1}% memory). Consider rebalancing: run the descheduler, or add pod \
+             anti-affinity or topology spread constraints so new pods land on the idle nodes.",
+            cv_threshold, summary,
+            most.node, most.cpu_utilization_percent, most.memory_utilization_percent,
+            least.node, least.cpu_utilization_percent, least.memory_utilization_percent
+        ),
+        (Some(most), Some(least), Lang::Es) if report.imbalanced => format!(
+            "El clúster está desequilibrado (coeficiente de variación por encima de {:.2}). {} Más utilizado: '{}' ({:.1}% de CPU, {:.1}% de memoria); \
+             menos utilizado: '{}' ({:.1}% de CPU, {:.1}% de memoria). Considere reequilibrar: ejecute el descheduler, o añada \
+             anti-afinidad de pods o restricciones de distribución topológica para que los pods nuevos vayan a los nodos ociosos.",
+            cv_threshold, summary,
+            most.node, most.cpu_utilization_percent, most.memory_utilization_percent,
+            least.node, least.cpu_utilization_percent, least.memory_utilization_percent
+        ),
+        (_, _, Lang::En) => format!("The cluster is balanced (coefficient of variation at most {:.2}). {}", cv_threshold, summary),
+        (_, _, Lang::Es) => format!("El clúster está equilibrado (coeficiente de variación como mucho {:.2}). {}", cv_threshold, summary),
+    }
+}
+
+/// Explanation of the pod churn; `churn_per_hour` is the cluster-wide rate
+pub fn pod_churn(lang: Lang, churn: &PodChurnResponse, churn_per_hour: f64) -> String {
+    let mut explanation = match (churn.namespaces.first(), lang) {
+        (None, Lang::En) => format!("No pods were created or deleted in the last {} minutes.", churn.window_minutes),
+        (None, Lang::Es) => format!("No se crearon ni eliminaron pods en los últimos {} minutos.", churn.window_minutes),
+        (Some(top), Lang::En) => format!(
+            "In the last {} minutes {} pods were created and {} deleted ({:.1} per hour). \
+             Highest churn: namespace '{}' with {:.1} pods per hour; sustained churn points at CrashLoopBackOff, \
+             evictions or scaling storms, and makes capacity figures move.",
+            churn.window_minutes, churn.created_pods, churn.deleted_pods, churn_per_hour,
+            top.namespace, top.churn_per_hour
+        ),
+        (Some(top), Lang::Es) => format!(
+            "En los últimos {} minutos se crearon {} pods y se eliminaron {} ({:.1} por hora). \
+             Mayor rotación: namespace '{}' con {:.1} pods por hora; una rotación sostenida apunta a CrashLoopBackOff, \
+             desalojos o tormentas de escalado, y hace variar las cifras de capacidad.",
+            churn.window_minutes, churn.created_pods, churn.deleted_pods, churn_per_hour,
+            top.namespace, top.churn_per_hour
+        ),
+    };
+    if !churn.events_available {
+        explanation.push_str(match lang {
+            Lang::En => " Events could not be listed, so deletions are not counted.",
+            Lang::Es => " No se pudieron listar los eventos, así que no se cuentan las eliminaciones.",
+        });
+    }
+    explanation
+}
+
+/// Explanation of the GPU report; `pending_pod_count` pods wait for a node
+pub fn gpu_report(lang: Lang, report: &GpuReportResponse, pending_pod_count: usize) -> String {
+    let mut explanation = match (report.total_gpus, lang) {
+        (0, Lang::En) => "No node advertises GPUs (nvidia.com/gpu or amd.com/gpu).".to_string(),
+        (0, Lang::Es) => "Ningún nodo anuncia GPUs (nvidia.com/gpu o amd.com/gpu).".to_string(),
+        (_, Lang::En) => format!(
+            "{} of {} GPUs on {} nodes are requested and {} are idle.",
+            report.requested_gpus, report.total_gpus, report.nodes.len(), report.idle_gpus
+        ),
+        (_, Lang::Es) => format!(
+            "{} de {} GPUs en {} nodos están solicitadas y {} están ociosas.",
+            report.requested_gpus, report.total_gpus, report.nodes.len(), report.idle_gpus
+        ),
+    };
+    if let Some(top) = report.namespaces.first() {
+        explanation.push_str(&match lang {
+            Lang::En => format!(" Largest consumer: namespace '{}' with {} GPUs.", top.namespace, top.requested_gpus),
+            Lang::Es => format!(" Mayor consumidor: namespace '{}' con {} GPUs.", top.namespace, top.requested_gpus),
+        });
+    }
+    if report.pending_gpus > 0 {
+        explanation.push_str(&match lang {
+            Lang::En => format!(" {} pods waiting for a node request {} more GPUs.", pending_pod_count, report.pending_gpus),
+            Lang::Es => format!(" {} pods que esperan un nodo solicitan {} GPUs más.", pending_pod_count, report.pending_gpus),
+        });
+    }
+    explanation
+}
+
+/// Name of a resource ("cpu", "memory") in the explanation language
+fn resource_name(lang: Lang, resource: &str) -> &str {
+    match (lang, resource) {
+        (Lang::Es, "memory") => "memoria",
+        (Lang::Es, "cpu") => "CPU",
+        (_, other) => other,
+    }
+}
+
+/// Explanation of how many pods of one size still fit
+pub fn max_pods_of_size(lang: Lang, result: &MaxPodsOfSizeResponse) -> String {
+    let mut explanation = match lang {
+        Lang::En => format!(
+            "{} pods of {} CPU and {} memory still fit on {} nodes, limited by {}.",
+            result.max_pods, format_cpu(result.cpu_cores), format_memory_gb(result.memory_gb), result.nodes.len(), result.limiting_resource
+        ),
+        Lang::Es => format!(
+            "Aún caben {} pods de {} de CPU y {} de memoria en {} nodos, limitados por {}.",
+            result.max_pods, format_cpu(result.cpu_cores), format_memory_gb(result.memory_gb), result.nodes.len(),
+            resource_name(lang, &result.limiting_resource)
+        ),
+    };
+    if result.naive_max_pods > result.max_pods {
+        let lost = result.naive_max_pods - result.max_pods;
+        explanation.push_str(&match lang {
+            Lang::En => format!(
+                " Dividing the cluster-wide free resources would suggest {}, but {} of those do not fit \
+                 because the free capacity is fragmented across nodes.",
+                result.naive_max_pods, lost
+            ),
+            Lang::Es => format!(
+                " Dividir los recursos libres de todo el clúster sugeriría {}, pero {} de ellos no caben \
+                 porque la capacidad libre está fragmentada entre los nodos.",
+                result.naive_max_pods, lost
+            ),
+        });
+    }
+    explanation
+}
+
+/// Explanation of the usage grouped by a label
+pub fn usage_by_label(lang: Lang, group_count: usize, label_key: &str) -> String {
+    match lang {
+        Lang::En => format!(
+            "Pods grouped into {} values of label '{}'. Each value shows CPU/memory requests and limits and pod count, \
+             sorted by CPU requests (descending); pods without the label are counted as 'unlabeled'.",
+            group_count, label_key
+        ),
+        Lang::Es => format!(
+            "Pods agrupados en {} valores de la etiqueta '{}'. Cada valor muestra solicitudes y límites de CPU/memoria y el número de pods, \
+             ordenados por solicitudes de CPU (descendente); los pods sin la etiqueta cuentan como 'unlabeled'.",
+            group_count, label_key
+        ),
+    }
+}
+
+/// Explanation of the usage of the pods matching a label selector
+pub fn usage_for_selector(lang: Lang, usage: &UsageForSelectorResponse) -> String {
+    match (usage.pod_count, lang) {
+        (0, Lang::En) => format!("No running pods match '{}' in any namespace.", usage.label_selector),
+        (0, Lang::Es) => format!("Ningún pod en ejecución coincide con '{}' en ningún namespace.", usage.label_selector),
+        (_, Lang::En) => format!(
+            "{} pods match '{}' across {} namespaces ({}), requesting {} CPU and {} memory with limits of {} CPU and {} memory.",
+            usage.pod_count, usage.label_selector, usage.namespaces.len(), usage.namespaces.join(", "),
+            format_cpu(usage.cpu_requests_cores), format_memory_gb(usage.memory_requests_gb),
+            format_cpu(usage.cpu_limits_cores), format_memory_gb(usage.memory_limits_gb)
+        ),
+        (_, Lang::Es) => format!(
+            "{} pods coinciden con '{}' en {} namespaces ({}), solicitan {} de CPU y {} de memoria con límites de {} de CPU y {} de memoria.",
+            usage.pod_count, usage.label_selector, usage.namespaces.len(), usage.namespaces.join(", "),
+            format_cpu(usage.cpu_requests_cores), format_memory_gb(usage.memory_requests_gb),
+            format_cpu(usage.cpu_limits_cores), format_memory_gb(usage.memory_limits_gb)
+        ),
+    }
+}
+
+/// Explanation of the usage grouped by top-level workload kind
+pub fn usage_by_workload_kind(lang: Lang, pod_count: usize, kind_count: usize) -> String {
+    match lang {
+        Lang::En => format!(
+            "{} pods grouped into {} top-level workload kinds. Each kind shows CPU/memory requests and limits and pod count, \
+             sorted by CPU requests (descending); ReplicaSet pods count toward their Deployment, Job pods toward their CronJob, \
+             and pods without an owner are counted as 'Pod'.",
+            pod_count, kind_count
+        ),
+        Lang::Es => format!(
+            "{} pods agrupados en {} tipos de carga de nivel superior. Cada tipo muestra solicitudes y límites de CPU/memoria y el número de pods, \
+             ordenados por solicitudes de CPU (descendente); los pods de un ReplicaSet cuentan para su Deployment, los de un Job para su CronJob, \
+             y los pods sin propietario cuentan como 'Pod'.",
+            pod_count, kind_count
+        ),
+    }
+}
+
+/// Explanation of the replica consistency check; `inconsistent` names the workloads whose replicas differ
+pub fn replica_consistency(lang: Lang, workloads_checked: usize, inconsistent: &[String]) -> String {
+    match (inconsistent.is_empty(), lang) {
+        (true, Lang::En) => format!(
+            "All {} workloads with several replicas have identical requests and limits on every replica.",
+            workloads_checked
+        ),
+        (true, Lang::Es) => format!(
+            "Las {} cargas con varias réplicas tienen solicitudes y límites idénticos en todas las réplicas.",
+            workloads_checked
+        ),
+        (false, Lang::En) => format!(
+            "{} of {} workloads have replicas with differing requests or limits: {}. \
+             This usually means a rollout in progress or stuck, or pods edited by hand; \
+             each difference lists the values and the replicas using them, most common first.",
+            inconsistent.len(), workloads_checked, inconsistent.join(", ")
+        ),
+        (false, Lang::Es) => format!(
+            "{} de {} cargas tienen réplicas con solicitudes o límites distintos: {}. \
+             Suele indicar un despliegue en curso o atascado, o pods editados a mano; \
+             cada diferencia lista los valores y las réplicas que los usan, primero el más común.",
+            inconsistent.len(), workloads_checked, inconsistent.join(", ")
+        ),
+    }
+}
+
+/// Explanation of how container images are distributed across pods
+pub fn image_distribution(lang: Lang, pod_count: usize, total_images: usize, shared_images: usize) -> String {
+    match lang {
+        Lang::En => format!(
+            "{} pods use {} distinct images; {} images are shared by more than one pod. \
+             Images are sorted by pod count (descending); widely shared images are pulled onto many nodes.",
+            pod_count, total_images, shared_images
+        ),
+        Lang::Es => format!(
+            "{} pods usan {} imágenes distintas; {} imágenes las comparte más de un pod. \
+             Las imágenes se ordenan por número de pods (descendente); las más compartidas se descargan en muchos nodos.",
+            pod_count, total_images, shared_images
+        ),
+    }
+}
+
+/// Explanation of the split between system overhead and application workloads
+pub fn overhead_breakdown(lang: Lang, breakdown: &OverheadBreakdownResponse) -> String {
+    let cpu_percent = |cores: f64| percent_suffix(cores, breakdown.total_cpu_cores);
+    let memory_percent = |gb: f64| percent_suffix(gb, breakdown.total_memory_gb);
+    let (system, workloads) = (&breakdown.system, &breakdown.workloads);
+    let mut explanation = match lang {
+        Lang::En => format!(
+            "System overhead reserves {} CPU{} and {} memory{}; application workloads reserve {} CPU{} \
+             and {} memory{} of {} CPU and {} memory. Overhead is every pod in {} plus DaemonSet pods in any namespace.",
+            format_cpu(system.cpu_cores), cpu_percent(system.cpu_cores),
+            format_memory_gb(system.memory_gb), memory_percent(system.memory_gb),
+            format_cpu(workloads.cpu_cores), cpu_percent(workloads.cpu_cores),
+            format_memory_gb(workloads.memory_gb), memory_percent(workloads.memory_gb),
+            format_cpu(breakdown.total_cpu_cores), format_memory_gb(breakdown.total_memory_gb),
+            breakdown.system_namespaces.join(", ")
+        ),
+        Lang::Es => format!(
+            "La sobrecarga del sistema reserva {} de CPU{} y {} de memoria{}; las cargas de aplicación reservan {} de CPU{} \
+             y {} de memoria{} de {} de CPU y {} de memoria. La sobrecarga son todos los pods de {} más los pods de DaemonSet de cualquier namespace.",
+            format_cpu(system.cpu_cores), cpu_percent(system.cpu_cores),
+            format_memory_gb(system.memory_gb), memory_percent(system.memory_gb),
+            format_cpu(workloads.cpu_cores), cpu_percent(workloads.cpu_cores),
+            format_memory_gb(workloads.memory_gb), memory_percent(workloads.memory_gb),
+            format_cpu(breakdown.total_cpu_cores), format_memory_gb(breakdown.total_memory_gb),
+            breakdown.system_namespaces.join(", ")
+        ),
+    };
+    if let Some(note) = no_capacity_note(lang, breakdown.total_cpu_cores, breakdown.total_memory_gb) {
+        explanation = format!("{} {}", explanation, note);
+    }
+    explanation
+}
+
+/// Explanation of the pods stuck in Terminating
+pub fn stuck_pods(lang: Lang, stuck: &StuckPodsResponse) -> String {
+    match (stuck.pods.is_empty(), lang) {
+        (true, Lang::En) => format!("No pods have been terminating for more than {} seconds.", stuck.grace_period_seconds),
+        (true, Lang::Es) => format!("Ningún pod lleva terminando más de {} segundos.", stuck.grace_period_seconds),
+        (false, Lang::En) => format!(
+            "{} pods have been terminating for more than {} seconds and still hold {} CPU and {} memory of requests \
+             on their nodes. Check their finalizers and the kubelet on those nodes; force deletion releases the capacity.",
+            stuck.pods.len(), stuck.grace_period_seconds, format_cpu(stuck.held_cpu_cores), format_memory_gb(stuck.held_memory_gb)
+        ),
+        (false, Lang::Es) => format!(
+            "{} pods llevan terminando más de {} segundos y aún retienen {} de CPU y {} de memoria solicitadas \
+             en sus nodos. Revise sus finalizers y el kubelet de esos nodos; borrarlos a la fuerza libera la capacidad.",
+            stuck.pods.len(), stuck.grace_period_seconds, format_cpu(stuck.held_cpu_cores), format_memory_gb(stuck.held_memory_gb)
+        ),
+    }
+}
+
+/// Explanation of whether the cluster survives losing its largest nodes
+pub fn n_minus_one(lang: Lang, result: &NMinusOneResponse) -> String {
+    let losing = match (result.nodes_lost == 1, lang) {
+        (true, Lang::En) => format!("its largest node ({})", result.lost_nodes[0]),
+        (true, Lang::Es) => format!("su nodo más grande ({})", result.lost_nodes[0]),
+        (false, Lang::En) => format!("its {} largest nodes ({})", result.nodes_lost, result.lost_nodes.join(", ")),
+        (false, Lang::Es) => format!("sus {} nodos más grandes ({})", result.nodes_lost, result.lost_nodes.join(", ")),
+    };
+    match (result.survives, lang) {
+        (true, Lang::En) => format!(
+            "The cluster survives losing {}: all {} displaced pods fit on the remaining {} nodes. \
+             Requests would use {:.1}% of the remaining CPU and {:.1}% of the remaining memory.",
+            losing, result.displaced_pods, result.remaining_nodes, result.cpu_utilization_percent, result.memory_utilization_percent
+        ),
+        (true, Lang::Es) => format!(
+            "El clúster sobrevive a perder {}: los {} pods desplazados caben en los {} nodos restantes. \
+             Las solicitudes usarían el {:.1}% de la CPU restante y el {:.1}% de la memoria restante.",
+            losing, result.displaced_pods, result.remaining_nodes, result.cpu_utilization_percent, result.memory_utilization_percent
+        ),
+        (false, Lang::En) => format!(
+            "The cluster does not survive losing {}: {} of {} displaced pods fit on no remaining node. \
+             Requests would need {:.1}% of the remaining CPU and {:.1}% of the remaining memory; \
+             add nodes or reduce requests to tolerate the loss.",
+            losing, result.unplaceable_pods.len(), result.displaced_pods, result.cpu_utilization_percent, result.memory_utilization_percent
+        ),
+        (false, Lang::Es) => format!(
+            "El clúster no sobrevive a perder {}: {} de {} pods desplazados no caben en ningún nodo restante. \
+             Las solicitudes necesitarían el {:.1}% de la CPU restante y el {:.1}% de la memoria restante; \
+             añada nodos o reduzca las solicitudes para tolerar la pérdida.",
+            losing, result.unplaceable_pods.len(), result.displaced_pods, result.cpu_utilization_percent, result.memory_utilization_percent
+        ),
+    }
+}
+
+/// Explanation of whether a Deployment can scale to its target; `node_count` is every node considered
+pub fn deployment_scale(lang: Lang, scale: &DeploymentScaleResponse, eligible_nodes: usize, node_count: usize) -> String {
+    let each = match lang {
+        Lang::En => format!("{} CPU and {} memory each", format_cpu(scale.cpu_per_replica_cores), format_memory_gb(scale.memory_per_replica_gb)),
+        Lang::Es => format!("{} de CPU y {} de memoria cada una", format_cpu(scale.cpu_per_replica_cores), format_memory_gb(scale.memory_per_replica_gb)),
+    };
+    let on = match lang {
+        Lang::En => "on",
+        Lang::Es => "en",
+    };
+    let spread = scale.placements.iter()
+        .map(|p| format!("{} {} {}", p.replicas, on, p.node))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut explanation = match (scale.additional_replicas == 0, scale.fits, lang) {
+        (true, _, Lang::En) => format!(
+            "Deployment {}/{} already has {} replicas, at or above the target of {}; nothing to schedule.",
+            scale.namespace, scale.deployment, scale.current_replicas, scale.target_replicas
+        ),
+        (true, _, Lang::Es) => format!(
+            "El Deployment {}/{} ya tiene {} réplicas, igual o por encima del objetivo de {}; no hay nada que programar.",
+            scale.namespace, scale.deployment, scale.current_replicas, scale.target_replicas
+        ),
+        (false, true, Lang::En) => format!(
+            "Deployment {}/{} can scale from {} to {} replicas: all {} additional replicas ({}) fit on the {} eligible nodes ({}).",
+            scale.namespace, scale.deployment, scale.current_replicas, scale.target_replicas, scale.additional_replicas, each,
+            eligible_nodes, spread
+        ),
+        (false, true, Lang::Es) => format!(
+            "El Deployment {}/{} puede escalar de {} a {} réplicas: las {} réplicas adicionales ({}) caben en los {} nodos elegibles ({}).",
+            scale.namespace, scale.deployment, scale.current_replicas, scale.target_replicas, scale.additional_replicas, each,
+            eligible_nodes, spread
+        ),
+        (false, false, Lang::En) => format!(
+            "Deployment {}/{} cannot fully scale from {} to {} replicas: only {} of {} additional replicas ({}) fit, {} would stay Pending. \
+             {} of {} nodes are eligible for the pod template; {} are excluded by cordons, nodeSelector, node affinity or taints.",
+            scale.namespace, scale.deployment, scale.current_replicas, scale.target_replicas, scale.schedulable_replicas,
+            scale.additional_replicas, each, scale.unschedulable_replicas, eligible_nodes, node_count, scale.excluded_nodes.len()
+        ),
+        (false, false, Lang::Es) => format!(
+            "El Deployment {}/{} no puede escalar del todo de {} a {} réplicas: solo caben {} de {} réplicas adicionales ({}), {} quedarían Pending. \
+             {} de {} nodos son elegibles para la plantilla del pod; {} quedan excluidos por cordons, nodeSelector, afinidad de nodo o taints.",
+            scale.namespace, scale.deployment, scale.current_replicas, scale.target_replicas, scale.schedulable_replicas,
+            scale.additional_replicas, each, scale.unschedulable_replicas, eligible_nodes, node_count, scale.excluded_nodes.len()
+        ),
+    };
+    if let Some(limit) = scale.anti_affinity_limit.filter(|limit| *limit < scale.additional_replicas) {
+        explanation.push_str(&match lang {
+            Lang::En => format!(" Required pod anti-affinity allows at most {} more replicas, one per free topology domain.", limit),
+            Lang::Es => format!(" La antiafinidad de pods obligatoria permite como mucho {} réplicas más, una por dominio de topología libre.", limit),
+        });
+    }
+    explanation
+}
+
+/// Explanation of whether the peak of concurrent CronJob runs fits; `node_count` is every node considered
+pub fn cronjob_capacity(lang: Lang, capacity: &CronJobCapacityResponse, eligible_nodes: usize, node_count: usize) -> String {
+    let peak = match lang {
+        Lang::En => format!(
+            "{} concurrent Jobs of CronJob {}/{} run {} pods ({} per Job, {} CPU and {} memory each), {} CPU and {} memory in total",
+            capacity.concurrency, capacity.namespace, capacity.cronjob, capacity.pods_needed, capacity.pods_per_job,
+            format_cpu(capacity.cpu_per_pod_cores), format_memory_gb(capacity.memory_per_pod_gb),
+            format_cpu(capacity.total_cpu_cores), format_memory_gb(capacity.total_memory_gb)
+        ),
+        Lang::Es => format!(
+            "{} Jobs simultáneos del CronJob {}/{} ejecutan {} pods ({} por Job, {} de CPU y {} de memoria cada uno), {} de CPU y {} de memoria en total",
+            capacity.concurrency, capacity.namespace, capacity.cronjob, capacity.pods_needed, capacity.pods_per_job,
+            format_cpu(capacity.cpu_per_pod_cores), format_memory_gb(capacity.memory_per_pod_gb),
+            format_cpu(capacity.total_cpu_cores), format_memory_gb(capacity.total_memory_gb)
+        ),
+    };
+    let mut explanation = match (capacity.fits, lang) {
+        (true, Lang::En) => format!("{}: all fit on the {} eligible nodes alongside the current allocation.", peak, eligible_nodes),
+        (true, Lang::Es) => format!("{}: todos caben en los {} nodos elegibles junto a la asignación actual.", peak, eligible_nodes),
+        (false, Lang::En) => format!(
+            "{}: only {} fit, {} would stay Pending. {} of {} nodes are eligible for the job template; \
+             {} are excluded by cordons, nodeSelector, node affinity or taints.",
+            peak, capacity.schedulable_pods, capacity.unschedulable_pods, eligible_nodes, node_count, capacity.excluded_nodes.len()
+        ),
+        (false, Lang::Es) => format!(
+            "{}: solo caben {}, {} quedarían Pending. {} de {} nodos son elegibles para la plantilla del job; \
+             {} quedan excluidos por cordons, nodeSelector, afinidad de nodo o taints.",
+            peak, capacity.schedulable_pods, capacity.unschedulable_pods, eligible_nodes, node_count, capacity.excluded_nodes.len()
+        ),
+    };
+    if let Some(limit) = capacity.anti_affinity_limit.filter(|limit| *limit < capacity.pods_needed) {
+        explanation.push_str(&match lang {
+            Lang::En => format!(" Required pod anti-affinity allows at most {} of these pods, one per free topology domain.", limit),
+            Lang::Es => format!(" La antiafinidad de pods obligatoria permite como mucho {} de estos pods, uno por dominio de topología libre.", limit),
+        });
+    }
+    if capacity.concurrency > 1 && capacity.concurrency_policy != "Allow" {
+        explanation.push_str(&match lang {
+            Lang::En => format!(
+                " Note: concurrencyPolicy {} keeps at most one Job running, so this peak cannot happen unless the policy changes.",
+                capacity.concurrency_policy
+            ),
+            Lang::Es => format!(
+                " Nota: concurrencyPolicy {} mantiene como mucho un Job en ejecución, así que este pico no puede darse salvo que cambie la política.",
+                capacity.concurrency_policy
+            ),
+        });
+    }
+    explanation
+}
+
+/// Explanation of how far the namespaces' requests could shrink
+pub fn namespace_downsize(lang: Lang, downsize: &NamespaceDownsizeResponse) -> String {
+    if downsize.namespaces.is_empty() {
+        return match lang {
+            Lang::En => "No namespace holds running pods, so there is nothing to downsize.".to_string(),
+            Lang::Es => "Ningún namespace tiene pods en ejecución, así que no hay nada que reducir.".to_string(),
+        };
+    }
+    if downsize.usage_source == UsageSource::Limits {
+        return match lang {
+            Lang::En => format!(
+                "Pod metrics are unavailable, so nothing can be reported as reducible in the {} namespaces: without metrics-server \
+                 usage is only bounded by limits (requests where no limit is set), and limits are never below requests.",
+                downsize.namespaces.len()
+            ),
+            Lang::Es => format!(
+                "Las métricas de pods no están disponibles, así que no puede indicarse nada reducible en los {} namespaces: sin \
+                 metrics-server el uso solo está acotado por los límites (las solicitudes donde no hay límite), y los límites nunca \
+                 están por debajo de las solicitudes.",
+                downsize.namespaces.len()
+            ),
+        };
+    }
+    let (cpu, memory) = (format_cpu(downsize.total_cpu_reducible_cores), format_memory_gb(downsize.total_memory_reducible_gb));
+    let mut explanation = match lang {
+        Lang::En => format!(
+            "Across {} namespaces, keeping {}% headroom over observed usage, requests could shrink by {} CPU and {} memory.",
+            downsize.namespaces.len(), downsize.headroom_percent, cpu, memory
+        ),
+        Lang::Es => format!(
+            "En {} namespaces, manteniendo un {}% de margen sobre el uso observado, las solicitudes podrían reducirse en {} de CPU y {} de memoria.",
+            downsize.namespaces.len(), downsize.headroom_percent, cpu, memory
+        ),
+    };
+    if let Some(top) = downsize.namespaces.first().filter(|n| n.cpu_reducible_cores > 0.0 || n.memory_reducible_gb > 0.0) {
+        let cpu = (format_cpu(top.cpu_requests_cores), format_cpu(top.recommended_cpu_requests_cores), format_cpu(top.cpu_usage_cores));
+        let memory = (
+            format_memory_gb(top.memory_requests_gb), format_memory_gb(top.recommended_memory_requests_gb), format_memory_gb(top.memory_usage_gb),
+        );
+        explanation.push_str(&match lang {
+            Lang::En => format!(
+                " {} frees the most: CPU requests {} → {} (usage {}), memory requests {} → {} (usage {}).",
+                top.namespace, cpu.0, cpu.1, cpu.2, memory.0, memory.1, memory.2
+            ),
+            Lang::Es => format!(
+                " {} libera más: solicitudes de CPU {} → {} (uso {}), solicitudes de memoria {} → {} (uso {}).",
+                top.namespace, cpu.0, cpu.1, cpu.2, memory.0, memory.1, memory.2
+            ),
+        });
+    }
+    explanation.push_str(match lang {
+        Lang::En => " Usage is a metrics-server snapshot, not a peak; check it against the workloads' busiest hours before resizing.",
+        Lang::Es => " El uso es una instantánea de metrics-server, no un pico; contrástelo con las horas de más carga antes de redimensionar.",
+    });
+    explanation
+}
+
+/// Explanation of the pods held back by scheduling gates
+pub fn gated_pods(lang: Lang, gated: &GatedPodsResponse) -> String {
+    let by_gate: Vec<String> = gated.gates.iter().map(|(gate, count)| format!("{} ({})", gate, count)).collect();
+    let (cpu, memory) = (format_cpu(gated.total_cpu_requests_cores), format_memory_gb(gated.total_memory_requests_gb));
+    match (gated.pods.is_empty(), lang) {
+        (true, Lang::En) => "No pending pod is held back by scheduling gates.".to_string(),
+        (true, Lang::Es) => "Ningún pod pendiente está retenido por scheduling gates.".to_string(),
+        (false, Lang::En) => format!(
+            "{} pods are held back by scheduling gates: {}. They request {} CPU and {} memory, which the scheduler \
+             ignores until the controller that added each gate removes it, so get_cluster_capacity leaves them out \
+             of the pending demand.",
+            gated.pods.len(), by_gate.join(", "), cpu, memory
+        ),
+        (false, Lang::Es) => format!(
+            "{} pods están retenidos por scheduling gates: {}. Solicitan {} de CPU y {} de memoria, que el planificador \
+             ignora hasta que el controlador que añadió cada gate lo retire, así que get_cluster_capacity los deja fuera \
+             de la demanda pendiente.",
+            gated.pods.len(), by_gate.join(", "), cpu, memory
+        ),
+    }
+}
+
+/// Explanation of the nodes that would be oversubscribed if every pod used its full limits
+pub fn burst_projection(lang: Lang, projection: &BurstProjectionResponse) -> String {
+    let node_count = projection.nodes.len();
+    let mut explanation = match projection.nodes.iter().find(|n| n.oversubscribed) {
+        None => match lang {
+            Lang::En => format!("None of {} nodes would exceed its allocatable CPU or memory if every pod used its full limits.", node_count),
+            Lang::Es => format!("Ninguno de los {} nodos superaría su CPU o memoria asignables si cada pod usara sus límites completos.", node_count),
+        },
+        Some(worst) => {
+            let (cpu, memory) = (format_cpu(projection.total_cpu_excess_cores), format_memory_gb(projection.total_memory_excess_gb));
+            match lang {
+                Lang::En => format!(
+                    "{} of {} nodes would be oversubscribed if every pod used its full limits, by {} CPU and {} memory in total. \
+                     Worst: '{}' with limits at {:.0}% of allocatable CPU and {:.0}% of allocatable memory. \
+                     Memory overruns end in OOM kills or evictions, CPU overruns in throttling.",
+                    projection.oversubscribed_node_count, node_count, cpu, memory,
+                    worst.node, worst.cpu_burst_percent, worst.memory_burst_percent
+                ),
+                Lang::Es => format!(
+                    "{} de {} nodos quedarían sobresuscritos si cada pod usara sus límites completos, en {} de CPU y {} de memoria en total. \
+                     Peor: '{}' con límites al {:.0}% de la CPU asignable y al {:.0}% de la memoria asignable. \
+                     Exceder la memoria acaba en OOM kills o desalojos, exceder la CPU en throttling.",
+                    projection.oversubscribed_node_count, node_count, cpu, memory,
+                    worst.node, worst.cpu_burst_percent, worst.memory_burst_percent
+                ),
+            }
+        }
+    };
+    let unlimited_pod_count: usize = projection.nodes.iter().map(|n| n.unlimited_pod_count).sum();
+    if unlimited_pod_count > 0 {
+        explanation.push_str(&match lang {
+            Lang::En => format!(
+                " {} pods have a container without a CPU or memory limit; they are counted at their requests but can burst further.",
+                unlimited_pod_count
+            ),
+            Lang::Es => format!(
+                " {} pods tienen un contenedor sin límite de CPU o memoria; se cuentan por sus solicitudes pero pueden consumir más.",
+                unlimited_pod_count
+            ),
+        });
+    }
+    explanation
+}
+
+/// Explanation of the ResourceQuota usage
+pub fn quota_usage(lang: Lang, usage: &QuotaUsageResponse) -> String {
+    let quotas = &usage.quotas;
+    let Some(top) = quotas.first().filter(|q| !q.resources.is_empty()) else {
+        return match (quotas.is_empty(), lang) {
+            (true, Lang::En) => "No ResourceQuota found.".to_string(),
+            (true, Lang::Es) => "No se encontró ninguna ResourceQuota.".to_string(),
+            (false, Lang::En) => format!("{} ResourceQuotas found, none limiting CPU, memory or pods.", quotas.len()),
+            (false, Lang::Es) => format!("Se encontraron {} ResourceQuotas, ninguna limita CPU, memoria ni pods.", quotas.len()),
+        };
+    };
+    let mut explanation = match lang {
+        Lang::En => format!(
+            "{} ResourceQuotas found. Highest: '{}/{}' at {:.0}% of {}.",
+            quotas.len(), top.namespace, top.name, top.resources[0].utilization_percent, top.resources[0].resource
+        ),
+        Lang::Es => format!(
+            "Se encontraron {} ResourceQuotas. La más alta: '{}/{}' al {:.0}% de {}.",
+            quotas.len(), top.namespace, top.name, top.resources[0].utilization_percent, top.resources[0].resource
+        ),
+    };
+    let scoped: Vec<String> = quotas.iter()
+        .filter(|q| !q.scopes.is_empty())
+        .map(|q| match lang {
+            Lang::En => format!("'{}/{}' ({}) counts {} of {} pods", q.namespace, q.name, q.scopes.join(", "), q.matching_pod_count, q.namespace_pod_count),
+            Lang::Es => format!("'{}/{}' ({}) cuenta {} de {} pods", q.namespace, q.name, q.scopes.join(", "), q.matching_pod_count, q.namespace_pod_count),
+        })
+        .collect();
+    if !scoped.is_empty() {
+        explanation.push_str(&match lang {
+            Lang::En => format!(" Scoped quotas only count the pods their scopes select: {}.", scoped.join("; ")),
+            Lang::Es => format!(" Las cuotas con scopes solo cuentan los pods que seleccionan: {}.", scoped.join("; ")),
+        });
+    }
+    explanation
+}
+
+/// Explanation of how tightly the nodes are packed
+pub fn node_packing(lang: Lang, packing: &NodePackingResponse) -> String {
+    let mut explanation = match lang {
+        Lang::En => format!(
+            "{} nodes are packed at {:.0}% efficiency overall (requests over allocatable, averaged across CPU and memory).",
+            packing.nodes.len(), packing.cluster_packing_efficiency_percent
+        ),
+        Lang::Es => format!(
+            "{} nodos están empaquetados con un {:.0}% de eficiencia global (solicitudes sobre asignable, promediando CPU y memoria).",
+            packing.nodes.len(), packing.cluster_packing_efficiency_percent
+        ),
+    };
+    if !packing.over_packed_nodes.is_empty() {
+        explanation.push_str(&match lang {
+            Lang::En => format!(
+                " Over-packed (CPU or memory at {:.0}% or more): {}; move pods off them before they run out of room.",
+                packing.over_packed_percent, packing.over_packed_nodes.join(", ")
+            ),
+            Lang::Es => format!(
+                " Sobreempaquetados (CPU o memoria al {:.0}% o más): {}; mueva pods fuera de ellos antes de que se queden sin espacio.",
+                packing.over_packed_percent, packing.over_packed_nodes.join(", ")
+            ),
+        });
+    }
+    if !packing.under_packed_nodes.is_empty() {
+        explanation.push_str(&match lang {
+            Lang::En => format!(
+                " Under-packed (CPU and memory below {:.0}%): {}; candidates to drain, or a sign the nodes are larger than the workloads need.",
+                packing.under_packed_percent, packing.under_packed_nodes.join(", ")
+            ),
+            Lang::Es => format!(
+                " Infraempaquetados (CPU y memoria por debajo del {:.0}%): {}; candidatos a vaciar, o señal de que los nodos son mayores de lo que necesitan las cargas.",
+                packing.under_packed_percent, packing.under_packed_nodes.join(", ")
+            ),
+        });
+    }
+    if packing.over_packed_nodes.is_empty() && packing.under_packed_nodes.is_empty() {
+        explanation.push_str(&match lang {
+            Lang::En => format!(" Every node is between {:.0}% and {:.0}%.", packing.under_packed_percent, packing.over_packed_percent),
+            Lang::Es => format!(" Todos los nodos están entre el {:.0}% y el {:.0}%.", packing.under_packed_percent, packing.over_packed_percent),
+        });
+    }
+    explanation
+}
+
+/// Explanation of the idle GPUs stranded by a lack of CPU or memory on their node
+pub fn stranded_gpus(lang: Lang, gpus: &StrandedGpusResponse) -> String {
+    let stranded: Vec<&StrandedGpuNode> = gpus.nodes.iter().filter(|n| n.stranded_gpus > 0).collect();
+    let mut explanation = if gpus.nodes.is_empty() {
+        match lang {
+            Lang::En => "No node advertises GPUs (nvidia.com/gpu or amd.com/gpu).".to_string(),
+            Lang::Es => "Ningún nodo anuncia GPUs (nvidia.com/gpu o amd.com/gpu).".to_string(),
+        }
+    } else if stranded.is_empty() {
+        match lang {
+            Lang::En => format!(
+                "None of the {} idle GPUs on {} GPU nodes is stranded: every node has the CPU and memory to feed them.",
+                gpus.idle_gpus, gpus.nodes.len()
+            ),
+            Lang::Es => format!(
+                "Ninguna de las {} GPUs ociosas en {} nodos con GPU está varada: todos los nodos tienen CPU y memoria para alimentarlas.",
+                gpus.idle_gpus, gpus.nodes.len()
+            ),
+        }
+    } else {
+        let details: Vec<String> = stranded.iter()
+            .map(|n| {
+                let short_of = resource_name(lang, n.limiting_resource.as_deref().unwrap_or_default());
+                match lang {
+                    Lang::En => format!(
+                        "{} ({} of {} idle GPUs, CPU {:.0}%, memory {:.0}%, short of {})",
+                        n.node, n.stranded_gpus, n.idle_gpus, n.cpu_utilization_percent, n.memory_utilization_percent, short_of
+                    ),
+                    Lang::Es => format!(
+                        "{} ({} de {} GPUs ociosas, CPU {:.0}%, memoria {:.0}%, falta {})",
+                        n.node, n.stranded_gpus, n.idle_gpus, n.cpu_utilization_percent, n.memory_utilization_percent, short_of
+                    ),
+                }
+            })
+            .collect();
+        match lang {
+            Lang::En => format!(
+                "{} of {} idle GPUs are stranded because their node ran out of CPU or memory: {}. \
+                 Move CPU-only pods off these nodes or keep them off with a taint.",
+                gpus.stranded_gpus, gpus.idle_gpus, details.join(", ")
+            ),
+            Lang::Es => format!(
+                "{} de {} GPUs ociosas están varadas porque su nodo se quedó sin CPU o memoria: {}. \
+                 Saque de estos nodos los pods que solo usan CPU o manténgalos fuera con un taint.",
+                gpus.stranded_gpus, gpus.idle_gpus, details.join(", ")
+            ),
+        }
+    };
+    if !gpus.nodes.is_empty() {
+        explanation.push_str(&match (gpus.cpu_cores_per_gpu, gpus.memory_gb_per_gpu, lang) {
+            (Some(cpu), Some(memory), Lang::En) => format!(
+                " A GPU pod is assumed to need {} and {} per GPU.", format_cpu(cpu), format_memory_gb(memory)
+            ),
+            (Some(cpu), Some(memory), Lang::Es) => format!(
+                " Se supone que un pod con GPU necesita {} y {} por GPU.", format_cpu(cpu), format_memory_gb(memory)
+            ),
+            (_, _, Lang::En) => " Without running GPU pods, each GPU is assumed to need its node's share of CPU and memory.".to_string(),
+            (_, _, Lang::Es) => " Sin pods con GPU en ejecución, se supone que cada GPU necesita su parte de la CPU y la memoria del nodo.".to_string(),
+        });
+    }
+    explanation
+}
+
+/// Explanation of how many pods declare CPU and memory requests
+pub fn request_coverage(lang: Lang, coverage: &RequestCoverageResponse) -> String {
+    let mut explanation = match lang {
+        Lang::En => format!(
+            "{:.1}% of {} pods declare CPU and memory requests ({:.1}% CPU, {:.1}% memory).",
+            coverage.coverage_percent, coverage.pod_count, coverage.cpu_coverage_percent, coverage.memory_coverage_percent
+        ),
+        Lang::Es => format!(
+            "El {:.1}% de {} pods declara solicitudes de CPU y memoria ({:.1}% CPU, {:.1}% memoria).",
+            coverage.coverage_percent, coverage.pod_count, coverage.cpu_coverage_percent, coverage.memory_coverage_percent
+        ),
+    };
+    let lagging: Vec<String> = coverage.namespaces.iter()
+        .filter(|ns| ns.non_compliant_pod_count > 0)
+        .take(5)
+        .map(|ns| format!("{} ({:.0}%, {} pods)", ns.namespace, ns.coverage_percent, ns.non_compliant_pod_count))
+        .collect();
+    explanation.push_str(&match (lagging.is_empty(), lang) {
+        (true, Lang::En) => " Every pod is covered.".to_string(),
+        (true, Lang::Es) => " Todos los pods están cubiertos.".to_string(),
+        (false, Lang::En) => format!(
+            " {} pods miss a request; least covered namespaces: {}. The scheduler places such pods as if they needed nothing.",
+            coverage.non_compliant_pod_count, lagging.join(", ")
+        ),
+        (false, Lang::Es) => format!(
+            " A {} pods les falta una solicitud; namespaces menos cubiertos: {}. El planificador coloca esos pods como si no necesitaran nada.",
+            coverage.non_compliant_pod_count, lagging.join(", ")
+        ),
+    });
+    explanation
+}
+
+/// The (heavy, light) resources of a request ratio mismatch that strands `stranded`
+fn heavy_and_light(lang: Lang, stranded: &str) -> (&'static str, &'static str) {
+    match (stranded, lang) {
+        ("cpu", Lang::En) => ("memory", "CPU"),
+        ("cpu", Lang::Es) => ("memoria", "CPU"),
+        (_, Lang::En) => ("CPU", "memory"),
+        (_, Lang::Es) => ("CPU", "memoria"),
+    }
+}
+
+/// Recommendation for a request ratio mismatch; `same_family` is set when the nodes already are the closest family
+pub fn ratio_recommendation(lang: Lang, mismatch: &RequestRatioMismatchResponse, family_ratio: f64, same_family: bool) -> String {
+    let Some(stranded) = mismatch.stranded_resource.as_deref() else {
+        return match lang {
+            Lang::En => "No change needed: the node shape matches the workloads.".to_string(),
+            Lang::Es => "No hace falta ningún cambio: la forma de los nodos encaja con las cargas.".to_string(),
+        };
+    };
+    let (heavy, light) = heavy_and_light(lang, stranded);
+    let family = &mismatch.recommended_node_family;
+    match (same_family, lang) {
+        (true, Lang::En) => format!(
+            "Review the {} requests: pods asking {:.2} GB per core on {:.0} GB-per-core nodes often over-request {}; \
+             rightsizing them toward the node shape recovers the stranded {}.",
+            heavy, mismatch.pod_memory_gb_per_core, mismatch.node_memory_gb_per_core, heavy, light
+        ),
+        (true, Lang::Es) => format!(
+            "Revise las solicitudes de {}: pods que piden {:.2} GB por núcleo en nodos de {:.0} GB por núcleo suelen sobredimensionar {}; \
+             ajustarlas a la forma del nodo recupera la {} varada.",
+            heavy, mismatch.pod_memory_gb_per_core, mismatch.node_memory_gb_per_core, heavy, light
+        ),
+        (false, Lang::En) => format!(
+            "Add {} nodes ({:.0} GB per core, e.g. 8 cores with {:.0} GB) for new capacity, or review whether the pods over-request {}.",
+            family, family_ratio, family_ratio * 8.0, heavy
+        ),
+        (false, Lang::Es) => format!(
+            "Añada nodos {} ({:.0} GB por núcleo, p. ej. 8 núcleos con {:.0} GB) para nueva capacidad, o revise si los pods sobredimensionan {}.",
+            family, family_ratio, family_ratio * 8.0, heavy
+        ),
+    }
+}
+
+/// Explanation of how the pods' memory-per-core ratio compares to the nodes'
+pub fn request_ratio_mismatch(lang: Lang, mismatch: &RequestRatioMismatchResponse, tolerance_percent: f64) -> String {
+    let requested = (format_cpu(mismatch.requested_cpu_cores), format_memory_gb(mismatch.requested_memory_gb));
+    let allocatable = (format_cpu(mismatch.allocatable_cpu_cores), format_memory_gb(mismatch.allocatable_memory_gb));
+    let mut explanation = match lang {
+        Lang::En => format!(
+            "Pods request {:.2} GB per core ({} and {}); the nodes offer {:.2} GB per core ({} and {}).",
+            mismatch.pod_memory_gb_per_core, requested.0, requested.1, mismatch.node_memory_gb_per_core, allocatable.0, allocatable.1
+        ),
+        Lang::Es => format!(
+            "Los pods solicitan {:.2} GB por núcleo ({} y {}); los nodos ofrecen {:.2} GB por núcleo ({} y {}).",
+            mismatch.pod_memory_gb_per_core, requested.0, requested.1, mismatch.node_memory_gb_per_core, allocatable.0, allocatable.1
+        ),
+    };
+    match mismatch.stranded_resource.as_deref() {
+        None => explanation.push_str(&match lang {
+            Lang::En => format!(" The shapes match within {:.0}%, so neither resource is stranded systemically.", tolerance_percent),
+            Lang::Es => format!(" Las formas coinciden con un margen del {:.0}%, así que ningún recurso queda varado de forma sistemática.", tolerance_percent),
+        }),
+        Some(stranded) => {
+            let (heavy, light) = heavy_and_light(lang, stranded);
+            let factor = if mismatch.mismatch_factor > 1.0 { mismatch.mismatch_factor } else { 1.0 / mismatch.mismatch_factor };
+            explanation.push_str(&match lang {
+                Lang::En => format!(
+                    " The pods are {}-heavy for these nodes ({:.1}x): once they fill the {}, {:.0}% of the {} is stranded.",
+                    heavy, factor, heavy, mismatch.stranded_percent, light
+                ),
+                Lang::Es => format!(
+                    " Los pods cargan demasiado la {} de estos nodos ({:.1}x): cuando la llenen, el {:.0}% de la {} queda varado.",
+                    heavy, factor, mismatch.stranded_percent, light
+                ),
+            });
+        }
+    }
+    format!("{} {}", explanation, mismatch.recommendation)
+}
+
+/// Explanation of the cluster capacity counted in pods of one size
+pub fn capacity_in_pod_units(lang: Lang, units: &CapacityInPodUnitsResponse) -> String {
+    let (cpu, memory) = (format_cpu(units.cpu_cores), format_memory_gb(units.memory_gb));
+    match lang {
+        Lang::En => format!(
+            "In pods of {} and {}, the cluster holds {:.1}: {:.1} are already placed and {} more fit across {} nodes. \
+             CPU limits the fit on {} nodes and memory on {}.",
+            cpu, memory, units.total_units, units.allocated_units, units.available_units, units.nodes.len(),
+            units.cpu_bound_nodes, units.memory_bound_nodes
+        ),
+        Lang::Es => format!(
+            "En pods de {} y {}, el clúster admite {:.1}: {:.1} ya están colocados y caben {} más en {} nodos. \
+             La CPU limita el encaje en {} nodos y la memoria en {}.",
+            cpu, memory, units.total_units, units.allocated_units, units.available_units, units.nodes.len(),
+            units.cpu_bound_nodes, units.memory_bound_nodes
+        ),
+    }
+}
+
+/// Explanation of the pods whose requests reach the thresholds
+pub fn large_pods(lang: Lang, found: &FindLargePodsResponse) -> String {
+    let (cpu, memory, or) = match lang {
+        Lang::En => ("CPU", "memory", " or "),
+        Lang::Es => ("de CPU", "de memoria", " o "),
+    };
+    let thresholds = found.min_cpu_cores.map(|min| format!("{} {}", format_cpu(min), cpu)).into_iter()
+        .chain(found.min_memory_gb.map(|min| format!("{} {}", format_memory_gb(min), memory)))
+        .collect::<Vec<_>>()
+        .join(or);
+    let Some(largest) = found.pods.first() else {
+        return match lang {
+            Lang::En => format!("None of {} pods requests at least {}.", found.searched_pod_count, thresholds),
+            Lang::Es => format!("Ninguno de {} pods solicita al menos {}.", found.searched_pod_count, thresholds),
+        };
+    };
+    let mut by_namespace: BTreeMap<&str, usize> = BTreeMap::new();
+    for pod in &found.pods {
+        *by_namespace.entry(pod.namespace.as_str()).or_default() += 1;
+    }
+    let namespaces: Vec<String> = by_namespace.iter().map(|(namespace, count)| format!("{} ({})", namespace, count)).collect();
+    let size = (format_cpu(largest.cpu_requests_cores), format_memory_gb(largest.memory_requests_gb));
+    match lang {
+        Lang::En => format!(
+            "{} of {} pods request at least {}. By namespace: {}. Largest: '{}/{}' with {} CPU and {} memory.",
+            found.pods.len(), found.searched_pod_count, thresholds, namespaces.join(", "),
+            largest.namespace, largest.name, size.0, size.1
+        ),
+        Lang::Es => format!(
+            "{} de {} pods solicitan al menos {}. Por namespace: {}. El mayor: '{}/{}' con {} de CPU y {} de memoria.",
+            found.pods.len(), found.searched_pod_count, thresholds, namespaces.join(", "),
+            largest.namespace, largest.name, size.0, size.1
+        ),
+    }
+}
+
+/// CPU and memory of one step of the pod accounting
+fn accounting_amount(lang: Lang, cpu_cores: f64, memory_gb: f64) -> String {
+    match lang {
+        Lang::En => format!("{} CPU, {} memory", format_cpu(cpu_cores), format_memory_gb(memory_gb)),
+        Lang::Es => format!("{} de CPU, {} de memoria", format_cpu(cpu_cores), format_memory_gb(memory_gb)),
+    }
+}
+
+/// Steps of the pod accounting, one sentence per container and one per total
+pub fn pod_accounting_steps(lang: Lang, pod: &PodAccountingResponse) -> Vec<String> {
+    let show = |cpu: f64, memory: f64| accounting_amount(lang, cpu, memory);
+    let mut steps = Vec::new();
+    let (app, init): (Vec<&ContainerAccounting>, Vec<&ContainerAccounting>) =
+        pod.containers.iter().partition(|c| c.role == ContainerRole::App);
+    for container in app {
+        let requests = show(container.cpu_requests_cores, container.memory_requests_gb);
+        steps.push(match lang {
+            Lang::En => format!("App container '{}' requests {}.", container.name, requests),
+            Lang::Es => format!("El contenedor de aplicación '{}' solicita {}.", container.name, requests),
+        });
+    }
+    let app = show(pod.app_cpu_cores, pod.app_memory_gb);
+    steps.push(match lang {
+        Lang::En => format!("App containers sum to {}.", app),
+        Lang::Es => format!("Los contenedores de aplicación suman {}.", app),
+    });
+    for container in init {
+        let requests = show(container.cpu_requests_cores, container.memory_requests_gb);
+        let phase = show(container.init_phase_cpu_cores.unwrap_or_default(), container.init_phase_memory_gb.unwrap_or_default());
+        steps.push(match (container.role, lang) {
+            (ContainerRole::Sidecar, Lang::En) => format!(
+                "Native sidecar '{}' requests {}; it keeps running, so it adds to the app containers and later init containers.",
+                container.name, requests
+            ),
+            (ContainerRole::Sidecar, Lang::Es) => format!(
+                "El sidecar nativo '{}' solicita {}; sigue en ejecución, así que se suma a los contenedores de aplicación y a los init posteriores.",
+                container.name, requests
+            ),
+            (_, Lang::En) => format!(
+                "Init container '{}' requests {}; with the sidecars started before it the pod needs {} while it runs.",
+                container.name, requests, phase
+            ),
+            (_, Lang::Es) => format!(
+                "El contenedor init '{}' solicita {}; con los sidecars arrancados antes el pod necesita {} mientras se ejecuta.",
+                container.name, requests, phase
+            ),
+        });
+    }
+    let running = show(pod.app_cpu_cores + pod.sidecar_cpu_cores, pod.app_memory_gb + pod.sidecar_memory_gb);
+    let init = show(pod.init_max_cpu_cores, pod.init_max_memory_gb);
+    let overhead = show(pod.overhead_cpu_cores, pod.overhead_memory_gb);
+    let effective = show(pod.effective_cpu_cores, pod.effective_memory_gb);
+    match lang {
+        Lang::En => steps.extend([
+            format!("While running the pod needs app containers plus sidecars: {}.", running),
+            format!("The largest init phase needs {}.", init),
+            format!("Pod overhead (spec.overhead) adds {}.", overhead),
+            format!("Effective request, the per-resource maximum of the two plus the overhead: {}.", effective),
+        ]),
+        Lang::Es => steps.extend([
+            format!("En ejecución el pod necesita los contenedores de aplicación más los sidecars: {}.", running),
+            format!("La mayor fase init necesita {}.", init),
+            format!("La sobrecarga del pod (spec.overhead) añade {}.", overhead),
+            format!("Solicitud efectiva, el máximo por recurso de ambos más la sobrecarga: {}.", effective),
+        ]),
+    }
+    steps
+}
+
+/// Explanation of what a pod counts toward, followed by its accounting steps
+pub fn pod_accounting(lang: Lang, pod: &PodAccountingResponse) -> String {
+    let effective = accounting_amount(lang, pod.effective_cpu_cores, pod.effective_memory_gb);
+    let toward = match (pod.counted_as.as_str(), lang) {
+        ("Allocated", Lang::En) => format!("the allocation of node '{}'", pod.node),
+        ("Allocated", Lang::Es) => format!("la asignación del nodo '{}'", pod.node),
+        ("Pending", Lang::En) => "the pending demand".to_string(),
+        ("Pending", Lang::Es) => "la demanda pendiente".to_string(),
+        (_, Lang::En) => "nothing (it is gated or finished)".to_string(),
+        (_, Lang::Es) => "nada (está retenido o terminado)".to_string(),
+    };
+    match lang {
+        Lang::En => format!("Pod '{}/{}' counts {} toward {}. {}", pod.namespace, pod.name, effective, toward, pod.steps.join(" ")),
+        Lang::Es => format!("El pod '{}/{}' cuenta {} para {}. {}", pod.namespace, pod.name, effective, toward, pod.steps.join(" ")),
+    }
+}
+
+/// Explanation of the nodes close to kubelet eviction
+pub fn eviction_risk(lang: Lang, risk: &EvictionRiskResponse) -> String {
+    let flagged: Vec<String> = risk.nodes.iter()
+        .filter(|node| node.at_risk)
+        .map(|node| {
+            let mut reasons: Vec<String> = Vec::new();
+            if let Some(p) = node.memory_usage_percent.filter(|_| node.resources_at_risk.iter().any(|r| r == "memory")) {
+                reasons.push(format!("{} {:.1}%", resource_name(lang, "memory"), p));
+            }
+            if let Some(p) = node.storage_usage_percent.filter(|_| node.resources_at_risk.iter().any(|r| r == "ephemeral-storage")) {
+                reasons.push(format!("ephemeral-storage {:.1}%", p));
+            }
+            reasons.extend(node.pressure_conditions.iter().cloned());
+            format!("{} ({})", node.node, reasons.join(", "))
+        })
+        .collect();
+    let mut explanation = match (flagged.is_empty(), lang) {
+        (true, Lang::En) => format!(
+            "No node is within {}% of its allocatable memory or ephemeral storage, and none reports memory or disk pressure.",
+            risk.margin_percent
+        ),
+        (true, Lang::Es) => format!(
+            "Ningún nodo está a menos del {}% de su memoria o almacenamiento efímero asignables, y ninguno informa presión de memoria o disco.",
+            risk.margin_percent
+        ),
+        (false, Lang::En) => format!(
+            "{} of {} nodes are within {}% of allocatable memory or ephemeral storage or report pressure: {}. \
+             The kubelet evicts pods once a node crosses its eviction thresholds; free memory or disk, \
+             or move pods off these nodes, before that happens.",
+            flagged.len(), risk.nodes.len(), risk.margin_percent, flagged.join(", ")
+        ),
+        (false, Lang::Es) => format!(
+            "{} de {} nodos están a menos del {}% de su memoria o almacenamiento efímero asignables o informan presión: {}. \
+             El kubelet desaloja pods cuando un nodo cruza sus umbrales de desalojo; libere memoria o disco, \
+             o mueva pods fuera de estos nodos, antes de que ocurra.",
+            flagged.len(), risk.nodes.len(), risk.margin_percent, flagged.join(", ")
+        ),
+    };
+    if !risk.warnings.is_empty() {
+        explanation.push_str(match lang {
+            Lang::En => " Usage data is partly unavailable (see warnings), so some nodes are judged on their pressure conditions only.",
+            Lang::Es => " Parte de los datos de uso no está disponible (ver warnings), así que algunos nodos se juzgan solo por sus condiciones de presión.",
+        });
+    }
+    explanation
+}
+
+/// Explanation of each namespace's share of the cluster's requests
+pub fn namespace_share(lang: Lang, share: &NamespaceShareResponse) -> String {
+    let Some(top) = share.namespaces.first() else {
+        return match lang {
+            Lang::En => "No pods request resources, so no namespace holds a share of the cluster.".to_string(),
+            Lang::Es => "Ningún pod solicita recursos, así que ningún namespace tiene una parte del clúster.".to_string(),
+        };
+    };
+    let leaders: Vec<String> = share.namespaces.iter()
+        .take(3)
+        .map(|s| format!("{} {:.1}%", s.namespace, s.resource_share_percent))
+        .collect();
+    let (cpu, memory) = (format_cpu(share.total_cpu_requests_cores), format_memory_gb(share.total_memory_requests_gb));
+    match lang {
+        Lang::En => format!(
+            "{} namespaces share {} CPU and {} memory requests; {} holds the largest share at {:.1}% \
+             (CPU {:.1}%, memory {:.1}%). Top shares: {}. The blended share averages the CPU and memory shares, \
+             a relative chargeback proxy without pricing.",
+            share.namespaces.len(), cpu, memory, top.namespace, top.resource_share_percent, top.cpu_share_percent,
+            top.memory_share_percent, leaders.join(", ")
+        ),
+        Lang::Es => format!(
+            "{} namespaces se reparten {} de CPU y {} de memoria solicitadas; {} tiene la mayor parte con un {:.1}% \
+             (CPU {:.1}%, memoria {:.1}%). Mayores partes: {}. La parte combinada promedia las de CPU y memoria, \
+             una aproximación relativa de imputación de costes sin precios.",
+            share.namespaces.len(), cpu, memory, top.namespace, top.resource_share_percent, top.cpu_share_percent,
+            top.memory_share_percent, leaders.join(", ")
+        ),
+    }
+}
+
+/// Explanation of the pods whose effective requests stand out
+pub fn request_outliers(lang: Lang, outliers: &RequestOutliersResponse) -> String {
+    let median = (format_cpu(outliers.median_cpu_cores), format_memory_gb(outliers.median_memory_gb));
+    if outliers.pods.is_empty() {
+        return match lang {
+            Lang::En => format!(
+                "None of {} pods requests {}x the median pod ({} CPU, {} memory) or more, \
+                 and no init container sets a pod's effective request.",
+                outliers.total_pods_checked, outliers.median_multiple, median.0, median.1
+            ),
+            Lang::Es => format!(
+                "Ninguno de {} pods solicita {}x el pod mediano ({} de CPU, {} de memoria) o más, \
+                 y ningún contenedor init fija la solicitud efectiva de un pod.",
+                outliers.total_pods_checked, outliers.median_multiple, median.0, median.1
+            ),
+        };
+    }
+    let flagged: Vec<String> = outliers.pods.iter()
+        .map(|o| format!("{}/{} ({})", o.namespace, o.name, accounting_amount(lang, o.effective_cpu_cores, o.effective_memory_gb)))
+        .collect();
+    match lang {
+        Lang::En => format!(
+            "{} of {} pods have surprising effective requests: {}. The median pod requests {} CPU and {} memory; \
+             check the per-container breakdown for containers that each request a whole node's worth \
+             or init containers larger than the app.",
+            outliers.pods.len(), outliers.total_pods_checked, flagged.join(", "), median.0, median.1
+        ),
+        Lang::Es => format!(
+            "{} de {} pods tienen solicitudes efectivas sorprendentes: {}. El pod mediano solicita {} de CPU y {} de memoria; \
+             revise el desglose por contenedor en busca de contenedores que pidan cada uno un nodo entero \
+             o contenedores init mayores que la aplicación.",
+            outliers.pods.len(), outliers.total_pods_checked, flagged.join(", "), median.0, median.1
+        ),
+    }
+}
+
+/// Explanation of the capacity on Ready, cordoned and not-ready nodes
+pub fn capacity_by_node_state(lang: Lang, capacity: &CapacityByNodeStateResponse) -> String {
+    let node_count: usize = capacity.states.iter().map(|s| s.node_count).sum();
+    let summary: Vec<String> = capacity.states.iter()
+        .map(|s| format!("{} {} ({})", s.node_count, s.state, accounting_amount(lang, s.cpu_cores, s.memory_gb)))
+        .collect();
+    let mut explanation = match lang {
+        Lang::En => format!("{} nodes: {}.", node_count, summary.join(", ")),
+        Lang::Es => format!("{} nodos: {}.", node_count, summary.join(", ")),
+    };
+    if capacity.stuck_cpu_cores > 0.0 || capacity.stuck_memory_gb > 0.0 {
+        let cpu = (format_cpu(capacity.stuck_cpu_cores), percent_of(capacity.stuck_cpu_cores, capacity.total_cpu_cores));
+        let memory = (format_memory_gb(capacity.stuck_memory_gb), percent_of(capacity.stuck_memory_gb, capacity.total_memory_gb));
+        explanation.push_str(&match lang {
+            Lang::En => format!(
+                " {} CPU ({:.1}%) and {} memory ({:.1}%) sit on cordoned or not-ready nodes and take no new pods; \
+                 uncordon or repair those nodes to make it usable.",
+                cpu.0, cpu.1, memory.0, memory.1
+            ),
+            Lang::Es => format!(
+                " {} de CPU ({:.1}%) y {} de memoria ({:.1}%) están en nodos acordonados o no listos y no aceptan pods nuevos; \
+                 quite el cordon o repare esos nodos para poder usarla.",
+                cpu.0, cpu.1, memory.0, memory.1
+            ),
+        });
+    } else {
+        explanation.push_str(match lang {
+            Lang::En => " All capacity is on Ready, schedulable nodes.",
+            Lang::Es => " Toda la capacidad está en nodos Ready y planificables.",
+        });
+    }
+    explanation
+}
+
+/// Percentage of `part` in `total`, 0 when the total is empty
+fn percent_of(part: f64, total: f64) -> f64 {
+    if total > 0.0 { part / total * 100.0 } else { 0.0 }
+}
+
+/// Explanation of each namespace's headroom below its weighted fair share
+pub fn namespace_headroom(lang: Lang, headroom: &NamespaceHeadroomResponse) -> String {
+    let over: Vec<&str> = headroom.namespaces.iter()
+        .filter(|h| h.over_fair_share)
+        .map(|h| h.namespace.as_str())
+        .collect();
+    let (cpu, memory) = (format_cpu(headroom.total_cpu_cores), format_memory_gb(headroom.total_memory_gb));
+    let mut explanation = match (headroom.namespaces.is_empty(), lang) {
+        (true, Lang::En) => "No namespace holds pods, so there is nothing to share.".to_string(),
+        (true, Lang::Es) => "Ningún namespace tiene pods, así que no hay nada que repartir.".to_string(),
+        (false, Lang::En) => format!(
+            "{} namespaces share {} CPU and {} memory by weight (total weight {}).",
+            headroom.namespaces.len(), cpu, memory, headroom.total_weight
+        ),
+        (false, Lang::Es) => format!(
+            "{} namespaces se reparten {} de CPU y {} de memoria por peso (peso total {}).",
+            headroom.namespaces.len(), cpu, memory, headroom.total_weight
+        ),
+    };
+    if let Some(tightest) = headroom.namespaces.first().filter(|_| over.is_empty()) {
+        let left = (format_cpu(tightest.cpu_headroom_cores), format_memory_gb(tightest.memory_headroom_gb));
+        explanation.push_str(&match lang {
+            Lang::En => format!(
+                " Every namespace is within its fair share; {} has the least headroom left: {} CPU and {} memory.",
+                tightest.namespace, left.0, left.1
+            ),
+            Lang::Es => format!(
+                " Todos los namespaces están dentro de su parte justa; a {} le queda el menor margen: {} de CPU y {} de memoria.",
+                tightest.namespace, left.0, left.1
+            ),
+        });
+    } else if !over.is_empty() {
+        explanation.push_str(&match lang {
+            Lang::En => format!(
+                " {} namespace(s) request more than their fair share: {}. They can only grow by taking capacity \
+                 other namespaces are entitled to.",
+                over.len(), over.join(", ")
+            ),
+            Lang::Es => format!(
+                " {} namespace(s) solicitan más que su parte justa: {}. Solo pueden crecer tomando capacidad \
+                 que corresponde a otros namespaces.",
+                over.len(), over.join(", ")
+            ),
+        });
+    }
+    explanation
+}
+
+/// Explanation of the smallest node that hosts a workload; `node_count` is every existing node
+pub fn node_size_recommendation(lang: Lang, size: &RecommendNodeSizeResponse, node_count: usize) -> String {
+    let allocatable = (format_cpu(size.min_allocatable_cpu_cores), format_memory_gb(size.min_allocatable_memory_gb));
+    let workload = (format_cpu(size.workload_cpu_cores), format_memory_gb(size.workload_memory_gb));
+    let daemonsets = (format_cpu(size.daemonset_cpu_overhead_cores), format_memory_gb(size.daemonset_memory_overhead_gb));
+    let capacity = (format_cpu(size.min_capacity_cpu_cores), format_memory_gb(size.min_capacity_memory_gb));
+    let reserved = (format_cpu(size.reserved_cpu_cores), format_memory_gb(size.reserved_memory_gb));
+    let mut explanation = match lang {
+        Lang::En => format!(
+            "A node needs at least {} CPU and {} memory allocatable: {} CPU and {} memory for the workload plus {} CPU \
+             and {} memory of DaemonSet overhead. Provision at least {} CPU and {} memory of capacity to leave {} CPU \
+             and {} memory for the kubelet and OS.",
+            allocatable.0, allocatable.1, workload.0, workload.1, daemonsets.0, daemonsets.1,
+            capacity.0, capacity.1, reserved.0, reserved.1
+        ),
+        Lang::Es => format!(
+            "Un nodo necesita al menos {} de CPU y {} de memoria asignables: {} de CPU y {} de memoria para la carga más {} de CPU \
+             y {} de memoria de sobrecarga de DaemonSets. Aprovisione al menos {} de CPU y {} de memoria de capacidad para dejar {} de CPU \
+             y {} de memoria al kubelet y al sistema operativo.",
+            allocatable.0, allocatable.1, workload.0, workload.1, daemonsets.0, daemonsets.1,
+            capacity.0, capacity.1, reserved.0, reserved.1
+        ),
+    };
+    explanation.push_str(&match (size.large_enough_nodes.is_empty(), lang) {
+        (true, Lang::En) => " No existing node is large enough.".to_string(),
+        (true, Lang::Es) => " Ningún nodo existente es lo bastante grande.".to_string(),
+        (false, Lang::En) => format!(" {} of {} existing nodes are large enough.", size.large_enough_nodes.len(), node_count),
+        (false, Lang::Es) => format!(" {} de {} nodos existentes son lo bastante grandes.", size.large_enough_nodes.len(), node_count),
+    });
+    explanation.push_str(match lang {
+        Lang::En => " The overhead assumes every DaemonSet runs on the new node; node selectors may exclude some.",
+        Lang::Es => " La sobrecarga supone que todos los DaemonSets se ejecutan en el nodo nuevo; los node selectors pueden excluir alguno.",
+    });
+    explanation
+}
+
+/// Explanation of the pods bound to nodes that no longer exist
+pub fn orphaned_pods_report(lang: Lang, orphans: &OrphanedPodsResponse) -> String {
+    let (cpu, memory) = (format_cpu(orphans.total_cpu_requests_cores), format_memory_gb(orphans.total_memory_requests_gb));
+    match (orphans.pods.is_empty(), lang) {
+        (true, Lang::En) => "Every scheduled pod is bound to an existing node.".to_string(),
+        (true, Lang::Es) => "Todos los pods planificados están asignados a un nodo existente.".to_string(),
+        (false, Lang::En) => format!(
+            "{} pods are bound to {} node(s) that no longer exist: {}. They request {} CPU and {} memory that no node \
+             provides, so get_node_breakdown leaves them out. The pod garbage collector normally deletes them; \
+             otherwise delete them so their controllers recreate them on live nodes.",
+            orphans.pods.len(), orphans.missing_nodes.len(), orphans.missing_nodes.join(", "), cpu, memory
+        ),
+        (false, Lang::Es) => format!(
+            "{} pods están asignados a {} nodo(s) que ya no existen: {}. Solicitan {} de CPU y {} de memoria que ningún nodo \
+             aporta, así que get_node_breakdown los omite. El recolector de basura de pods suele borrarlos; \
+             si no, bórrelos para que sus controladores los recreen en nodos vivos.",
+            orphans.pods.len(), orphans.missing_nodes.len(), orphans.missing_nodes.join(", "), cpu, memory
+        ),
+    }
+}
+
+/// Explanation of how each namespace's pods are distributed across nodes
+pub fn namespace_node_distribution(lang: Lang, namespace_count: usize) -> String {
+    match lang {
+        Lang::En => format!(
+            "Pods of {} namespaces distributed across nodes. Each namespace shows pod count and CPU/memory requests \
+             per node, and the share of its pods running on its busiest node (100% means all pods on one node).",
+            namespace_count
+        ),
+        Lang::Es => format!(
+            "Pods de {} namespaces repartidos entre los nodos. Cada namespace muestra el número de pods y las solicitudes de CPU/memoria \
+             por nodo, y la parte de sus pods que corre en su nodo más cargado (100% significa todos los pods en un nodo).",
+            namespace_count
+        ),
+    }
+}
+
+/// Explanation of the pods too large for any single node
+pub fn unschedulable_by_size(lang: Lang, result: &UnschedulableBySizeResponse) -> String {
+    let largest = accounting_amount(lang, result.largest_node_cpu_cores, result.largest_node_memory_gb);
+    match (result.pods.is_empty(), lang) {
+        (true, Lang::En) => format!(
+            "All {} pods fit on at least one node. Largest node allocatable: {}.",
+            result.total_pods_checked, largest
+        ),
+        (true, Lang::Es) => format!(
+            "Los {} pods caben en al menos un nodo. Asignable del nodo más grande: {}.",
+            result.total_pods_checked, largest
+        ),
+        (false, Lang::En) => format!(
+            "{} of {} pods request more than any single node can allocate and will never schedule \
+             until their requests shrink or a larger node is added. Largest node allocatable: {}.",
+            result.total_oversized, result.total_pods_checked, largest
+        ),
+        (false, Lang::Es) => format!(
+            "{} de {} pods solicitan más de lo que puede asignar cualquier nodo y nunca se planificarán \
+             hasta que reduzcan sus solicitudes o se añada un nodo mayor. Asignable del nodo más grande: {}.",
+            result.total_oversized, result.total_pods_checked, largest
+        ),
+    }
+}
+
+/// Explanation of the pods on a node and, when checked, whether they fit elsewhere
+pub fn pods_on_node(lang: Lang, node: &PodsOnNodeResponse) -> String {
+    let total_cpu: f64 = node.pods.iter().map(|p| p.cpu_requests_cores).sum();
+    let total_memory: f64 = node.pods.iter().map(|p| p.memory_requests_gb).sum();
+    let (cpu, memory) = (format_cpu(total_cpu), format_memory_gb(total_memory));
+    let mut explanation = match (node.cordoned, lang) {
+        (false, Lang::En) => format!("Node {} runs {} pods requesting {} CPU and {} memory.", node.node, node.total_pods, cpu, memory),
+        (true, Lang::En) => format!("Node {} (cordoned) runs {} pods requesting {} CPU and {} memory.", node.node, node.total_pods, cpu, memory),
+        (false, Lang::Es) => format!("El nodo {} ejecuta {} pods que solicitan {} de CPU y {} de memoria.", node.node, node.total_pods, cpu, memory),
+        (true, Lang::Es) => format!(
+            "El nodo {} (acordonado) ejecuta {} pods que solicitan {} de CPU y {} de memoria.", node.node, node.total_pods, cpu, memory
+        ),
+    };
+    if let Some(failed) = node.unreschedulable_pods {
+        let movable = node.pods.iter().filter(|p| !p.daemonset).count();
+        explanation.push_str(&match (failed, lang) {
+            (0, Lang::En) => format!(" All {} non-DaemonSet pods fit on the other schedulable nodes.", movable),
+            (0, Lang::Es) => format!(" Los {} pods que no son de DaemonSet caben en los demás nodos planificables.", movable),
+            (_, Lang::En) => format!(
+                " {} of {} non-DaemonSet pods fit on no other schedulable node; \
+                 free capacity or add nodes before draining.",
+                failed, movable
+            ),
+            (_, Lang::Es) => format!(
+                " {} de {} pods que no son de DaemonSet no caben en ningún otro nodo planificable; \
+                 libere capacidad o añada nodos antes de vaciarlo.",
+                failed, movable
+            ),
+        });
+    }
+    explanation
+}
+
+/// Explanation of the capacity forecast
+pub fn capacity_forecast(lang: Lang, forecast: &ForecastCapacityResponse) -> String {
+    let growth = (format_cpu(forecast.cpu_growth_cores_per_day), format_memory_gb(forecast.memory_growth_gb_per_day));
+    match (forecast.days_until_full, forecast.limiting_resource.as_deref(), lang) {
+        (Some(days), Some(resource), Lang::En) => format!(
+            "Based on {} snapshots, allocations grow by {} and {} per day. \
+             The cluster reaches {:.0}% {} utilization in about {:.1} days.",
+            forecast.snapshots_used, growth.0, growth.1, forecast.threshold_percent, resource, days
+        ),
+        (Some(days), Some(resource), Lang::Es) => format!(
+            "Según {} instantáneas, las asignaciones crecen {} y {} al día. \
+             El clúster alcanza el {:.0}% de uso de {} en unos {:.1} días.",
+            forecast.snapshots_used, growth.0, growth.1, forecast.threshold_percent, resource_name(lang, resource), days
+        ),
+        (_, _, Lang::En) => format!(
+            "Based on {} snapshots, allocations are not growing ({} and {} per day), \
+             so the cluster is not projected to reach {:.0}% utilization.",
+            forecast.snapshots_used, growth.0, growth.1, forecast.threshold_percent
+        ),
+        (_, _, Lang::Es) => format!(
+            "Según {} instantáneas, las asignaciones no crecen ({} y {} al día), \
+             así que no se prevé que el clúster alcance el {:.0}% de uso.",
+            forecast.snapshots_used, growth.0, growth.1, forecast.threshold_percent
+        ),
+    }
+}
+
+/// Explanation of the capabilities listing
+pub fn capabilities(lang: Lang, total_tools: usize) -> String {
+    match lang {
+        Lang::En => format!(
+            "Server exposes {} tools. Each tool shows its name, description and input JSON schema.",
+            total_tools
+        ),
+        Lang::Es => format!(
+            "El servidor expone {} herramientas. Cada herramienta muestra su nombre, su descripción y el esquema JSON de su entrada.",
+            total_tools
+        ),
+    }
+}
+
+/// How much explanation text the tools return
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub enum ExplanationDetail {
+    /// No explanation (empty string)
+    #[serde(alias = "none")]
+    None,
+    /// One-line summary
+    #[serde(alias = "brief")]
+    Brief,
+    /// Complete explanation
+    #[default]
+    #[serde(alias = "full")]
+    Full,
+}
+
+/// Responses carrying an explanation that can be shortened or dropped
+pub trait Explained {
+    fn explanation(&self) -> &str;
+
+    fn explanation_mut(&mut self) -> &mut String;
+
+    /// Explanations nested in the response (e.g. a scale-up recommendation)
+    fn nested_explanations(&mut self) -> Vec<&mut String> {
+        Vec::new()
+    }
+
+    /// One-line summary of the response, by default the explanation's first sentence
+    fn brief(&self, _lang: Lang) -> String {
+        first_sentence(self.explanation())
+    }
+}
+
+/// First line of `text`, cut after its first sentence
+fn first_sentence(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default();
+    match line.find(". ") {
+        Some(end) => line[..=end].to_string(),
+        None => line.to_string(),
+    }
+}
+
+/// Apply the requested explanation detail to a response
+pub fn with_detail<T: Explained>(mut response: T, detail: ExplanationDetail, lang: Lang) -> T {
+    let explanation = match detail {
+        ExplanationDetail::Full => return response,
+        ExplanationDetail::Brief => response.brief(lang),
+        ExplanationDetail::None => String::new(),
+    };
+    *response.explanation_mut() = explanation;
+    for nested in response.nested_explanations() {
+        nested.clear();
+    }
+    response
+}
+
+/// Implement Explained for responses keeping their explanation in an `explanation` field. A response
+/// may follow with a block overriding `nested_explanations` or `brief`.
+macro_rules! impl_explained {
+    ($($response:ident $({ $($overrides:tt)* })?),* $(,)?) => {
+        $(
+            impl Explained for $response {
+                fn explanation(&self) -> &str {
+                    &self.explanation
+                }
+
+                fn explanation_mut(&mut self) -> &mut String {
+                    &mut self.explanation
+                }
+
+                $($($overrides)*)?
+            }
+        )*
+    };
+}
+
+impl_explained! {
+    ClusterCapacityResponse {
+        fn brief(&self, lang: Lang) -> String {
+            match lang {
+                Lang::En => format!(
+                    "{} nodes: {} and {} available.",
+                    self.node_count, format_cpu(self.available_cpu_cores), format_memory_gb(self.available_memory_gb)
+                ),
+                Lang::Es => format!(
+                    "{} nodos: {} y {} disponibles.",
+                    self.node_count, format_cpu(self.available_cpu_cores), format_memory_gb(self.available_memory_gb)
+                ),
+            }
+        }
+    },
+    CheckResourceFitResponse {
+        fn nested_explanations(&mut self) -> Vec<&mut String> {
+            self.scale_up.iter_mut().map(|s| &mut s.explanation).collect()
+        }
+
+        fn brief(&self, lang: Lang) -> String {
+            let cpu = format_cpu(self.available_cpu_cores);
+            let memory = format_memory_gb(self.available_memory_gb);
+            let nodes_needed = self.scale_up.as_ref().and_then(|s| s.nodes_needed);
+
+            match (lang, self.fits, nodes_needed) {
+                (Lang::En, true, _) => format!("Fits: {}, {} available.", cpu, memory),
+                (Lang::En, false, Some(count)) => format!(
+                    "Does not fit: {}, {} available; add {} node(s).", cpu, memory, count
+                ),
+                (Lang::En, false, None) => format!("Does not fit: {}, {} available.", cpu, memory),
+                (Lang::Es, true, _) => format!("Cabe: {}, {} disponibles.", cpu, memory),
+                (Lang::Es, false, Some(count)) => format!(
+                    "No cabe: {}, {} disponibles; añadir {} nodo(s).", cpu, memory, count
+                ),
+                (Lang::Es, false, None) => format!("No cabe: {}, {} disponibles.", cpu, memory),
+            }
+        }
+    },
+    CheckReplicaCapacityResponse {
+        fn nested_explanations(&mut self) -> Vec<&mut String> {
+            self.scale_up.iter_mut().map(|s| &mut s.explanation).collect()
+        }
+
+        fn brief(&self, _lang: Lang) -> String {
+            self.explanation.lines().next().unwrap_or_default().to_string()
+        }
+    },
+    NodeBreakdownResponse,
+    NamespaceUsageResponse,
+    PodResourceStatsResponse,
+    NamespaceNodeDistributionResponse,
+    UnschedulableBySizeResponse,
+    PodsOnNodeResponse,
+    ForecastCapacityResponse {
+        fn brief(&self, lang: Lang) -> String {
+            match (self.days_until_full, &self.limiting_resource, lang) {
+                (Some(days), Some(resource), Lang::En) => format!(
+                    "{:.0}% {} utilization in about {:.1} days.", self.threshold_percent, resource, days
+                ),
+                (Some(days), Some(resource), Lang::Es) => format!(
+                    "{:.0}% de uso de {} en unos {:.1} días.", self.threshold_percent, resource_name(lang, resource), days
+                ),
+                (_, _, Lang::En) => format!("Not projected to reach {:.0}% utilization.", self.threshold_percent),
+                (_, _, Lang::Es) => format!("No se prevé alcanzar el {:.0}% de uso.", self.threshold_percent),
+            }
+        }
+    },
+    UsageByLabelResponse,
+    UsageByWorkloadKindResponse,
+    ImageDistributionResponse,
+    OverheadBreakdownResponse {
+        fn brief(&self, lang: Lang) -> String {
+            let system = (format_cpu(self.system.cpu_cores), percent_suffix(self.system.cpu_cores, self.total_cpu_cores));
+            let workloads = (format_cpu(self.workloads.cpu_cores), percent_suffix(self.workloads.cpu_cores, self.total_cpu_cores));
+            match lang {
+                Lang::En => format!("Overhead {} CPU{}, workloads {} CPU{}.", system.0, system.1, workloads.0, workloads.1),
+                Lang::Es => format!("Sobrecarga {} de CPU{}, cargas {} de CPU{}.", system.0, system.1, workloads.0, workloads.1),
+            }
+        }
+    },
+    NMinusOneResponse,
+    StuckPodsResponse {
+        fn brief(&self, lang: Lang) -> String {
+            match lang {
+                Lang::En => format!(
+                    "{} stuck pods holding {} CPU, {} memory.",
+                    self.total_stuck, format_cpu(self.held_cpu_cores), format_memory_gb(self.held_memory_gb)
+                ),
+                Lang::Es => format!(
+                    "{} pods atascados retienen {} de CPU, {} de memoria.",
+                    self.total_stuck, format_cpu(self.held_cpu_cores), format_memory_gb(self.held_memory_gb)
+                ),
+            }
+        }
+    },
+    BurstRiskResponse {
+        fn brief(&self, lang: Lang) -> String {
+            let Some(top) = self.namespaces.first() else {
+                return first_sentence(&self.explanation);
+            };
+            let ratio = top.cpu_limit_ratio.unwrap_or(0.0).max(top.memory_limit_ratio.unwrap_or(0.0));
+            match lang {
+                Lang::En => format!(
+                    "{} namespaces above {:.1}x limits/requests; highest '{}' at {:.1}x.",
+                    self.namespaces.len(), self.ratio_threshold, top.namespace, ratio
+                ),
+                Lang::Es => format!(
+                    "{} namespaces por encima de {:.1}x límites/solicitudes; el mayor '{}' con {:.1}x.",
+                    self.namespaces.len(), self.ratio_threshold, top.namespace, ratio
+                ),
+            }
+        }
+    },
+    NamespacesWithoutLimitsResponse,
+    NodeAcceptanceMatrixResponse,
+    MaxPodsOfSizeResponse,
+    LargestPodPerNodeResponse,
+    StrandedCapacityResponse,
+    OverpackedNodesResponse,
+    BalanceReportResponse,
+    PodChurnResponse,
+    GpuReportResponse,
+    UsageForSelectorResponse,
+    ReplicaConsistencyResponse,
+    DeploymentScaleResponse,
+    EvictionRiskResponse,
+    NamespaceShareResponse,
+    RequestOutliersResponse,
+    CapacityByNodeStateResponse,
+    NamespaceHeadroomResponse,
+    RecommendNodeSizeResponse,
+    OrphanedPodsResponse,
+    CronJobCapacityResponse,
+    NamespaceDownsizeResponse,
+    FullReportResponse {
+        fn nested_explanations(&mut self) -> Vec<&mut String> {
+            let mut nested = Vec::new();
+            nested.extend(self.capacity.as_mut().map(|c| &mut c.explanation));
+            nested.extend(self.nodes.as_mut().map(|n| &mut n.explanation));
+            nested.extend(self.namespaces.as_mut().map(|n| &mut n.explanation));
+            nested.extend(self.top_pods.as_mut().map(|p| &mut p.explanation));
+            nested.extend(self.health.as_mut().map(|h| &mut h.explanation));
+            nested
+        }
+
+        fn brief(&self, _lang: Lang) -> String {
+            self.explanation.lines().collect::<Vec<_>>().join(" ")
+        }
+    },
+    GatedPodsResponse,
+    BurstProjectionResponse,
+    QuotaUsageResponse,
+    NodePackingResponse,
+    StrandedGpusResponse,
+    RequestCoverageResponse,
+    RequestRatioMismatchResponse,
+    CapacityInPodUnitsResponse,
+    FindLargePodsResponse,
+    PodAccountingResponse,
+    ListCapabilitiesResponse,
+}
@@ -1,2 +1,10 @@
 pub mod cluster_insights;
+pub mod config;
+pub mod diagnostics;
+pub mod messages;
 pub mod metrics;
+pub mod progress;
+pub mod watch;
+
+#[cfg(all(test, feature = "integration"))]
+mod integration_tests;
@@ -0,0 +1,46 @@
+//! Progress notifications for long-running tool calls.
+
+use rmcp::model::{Meta, ProgressNotificationParam, ProgressToken};
+use rmcp::{Peer, RoleServer};
+use tokio::sync::mpsc;
+
+/// Reports the progress of one tool call. Only exists when the client sent a progress token.
+pub struct Progress {
+    token: ProgressToken,
+    sender: mpsc::UnboundedSender<ProgressNotificationParam>,
+}
+
+impl Progress {
+    /// Reporter forwarding notifications to `peer`, or None when the request has no progress token
+    pub fn for_request(meta: &Meta, peer: Peer<RoleServer>) -> Option<Self> {
+        let (progress, mut notifications) = Self::channel(meta.get_progress_token()?);
+
+        tokio::spawn(async move {
+            while let Some(notification) = notifications.recv().await {
+                if let Err(e) = peer.notify_progress(notification).await {
+                    tracing::warn!("Failed to send progress notification: {}", e);
+                    break;
+                }
+            }
+        });
+
+        Some(progress)
+    }
+
+    /// Reporter and the receiving end of its notifications
+    pub fn channel(token: ProgressToken) -> (Self, mpsc::UnboundedReceiver<ProgressNotificationParam>) {
+        let (sender, notifications) = mpsc::unbounded_channel();
+        (Self { token, sender }, notifications)
+    }
+
+    /// Report `progress` items done out of `total` (when known)
+    pub fn report(&self, progress: usize, total: Option<usize>, message: String) {
+        // Sending only fails once the forwarding task stopped; the tool keeps working regardless
+        let _ = self.sender.send(ProgressNotificationParam {
+            progress_token: self.token.clone(),
+            progress: progress as f64,
+            total: total.map(|total| total as f64),
+            message: Some(message),
+        });
+    }
+}
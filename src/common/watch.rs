@@ -0,0 +1,58 @@
+use futures::StreamExt;
+use k8s_openapi::api::core::v1::{Node, Pod};
+use kube::runtime::reflector::{self, Store};
+use kube::runtime::{watcher, WatchStreamExt};
+use kube::{Api, Client};
+
+/// Node and Pod reflector stores, kept up to date by watches when WATCH_MODE is enabled
+#[derive(Debug, Clone)]
+pub struct WatchStores {
+    nodes: Store<Node>,
+    pods: Store<Pod>,
+}
+
+impl WatchStores {
+    /// Serve reads from the given stores
+    pub fn new(nodes: Store<Node>, pods: Store<Pod>) -> Self {
+        WatchStores { nodes, pods }
+    }
+
+    /// Current cached nodes
+    pub fn nodes(&self) -> Vec<Node> {
+        self.nodes.state().iter().map(|n| n.as_ref().clone()).collect()
+    }
+
+    /// Current cached pods
+    pub fn pods(&self) -> Vec<Pod> {
+        self.pods.state().iter().map(|p| p.as_ref().clone()).collect()
+    }
+}
+
+/// Start Node and Pod reflectors on `client` and wait for their initial sync
+pub async fn start_watch_mode(client: Client) -> Result<WatchStores, String> {
+    let (nodes, node_writer) = reflector::store::<Node>();
+    let (pods, pod_writer) = reflector::store::<Pod>();
+
+    let node_stream = reflector::reflector(node_writer, watcher(Api::<Node>::all(client.clone()), watcher::Config::default()));
+    let pod_stream = reflector::reflector(pod_writer, watcher(Api::<Pod>::all(client), watcher::Config::default()));
+
+    tokio::spawn(node_stream.default_backoff().for_each(|event| async move {
+        if let Err(e) = event {
+            tracing::warn!("Node watch error: {}", e);
+        }
+    }));
+    tokio::spawn(pod_stream.default_backoff().for_each(|event| async move {
+        if let Err(e) = event {
+            tracing::warn!("Pod watch error: {}", e);
+        }
+    }));
+
+    nodes.wait_until_ready().await
+        .map_err(|e| format!("Node watch stopped before initial sync: {}", e))?;
+    pods.wait_until_ready().await
+        .map_err(|e| format!("Pod watch stopped before initial sync: {}", e))?;
+
+    tracing::info!("Watch mode enabled: serving nodes and pods from reflector caches");
+
+    Ok(WatchStores::new(nodes, pods))
+}
@@ -7,7 +7,7 @@ use tracing_subscriber::{
     {self},
 };
 mod common;
-use common::{cluster_insights::ClusterInsights, metrics};
+use common::{cluster_insights::{self, ClusterInsights}, config, metrics};
 use axum::{response::IntoResponse, http::StatusCode};
 
 const BIND_ADDRESS: &str = "127.0.0.1:8001";
@@ -22,11 +22,21 @@ async fn main() -> anyhow::Result<()> {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    // Use environment variable or the static value
-    let bind_address = std::env::var("BIND_ADDRESS").unwrap_or_else(|_| BIND_ADDRESS.to_string());
+    // Fail fast on an invalid config file or environment variable
+    let config = config::init().map_err(anyhow::Error::msg)?;
+
+    // Use the configured address (BIND_ADDRESS or bind_address) or the static value
+    let bind_address = config.bind_address.clone().unwrap_or_else(|| BIND_ADDRESS.to_string());
     tracing::info!("Starting streamable-http Compatibility Engine MCP server on {}", bind_address);
+    // Start without a cluster too; tools then explain how to configure one
+    cluster_insights::detect_cluster_config().await;
+
+    // Optionally serve nodes and pods from watch-based caches (WATCH_MODE=true)
+    // Every session builds its own ClusterInsights; they share the reflector stores
+    let builder = ClusterInsights::builder().start_watch_mode().await.map_err(anyhow::Error::msg)?;
+
     let service = StreamableHttpService::new(
-        || Ok(ClusterInsights::new()),
+        move || builder.clone().build().map_err(std::io::Error::other),
         LocalSessionManager::default().into(),
         Default::default(),
     );
@@ -4,7 +4,7 @@ use rmcp::{ServiceExt, transport::stdio};
 use tracing_subscriber::{self, EnvFilter};
 
 mod common;
-use common::cluster_insights::ClusterInsights;
+use common::{cluster_insights::{self, ClusterInsights}, config};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -17,8 +17,17 @@ async fn main() -> Result<()> {
 
     tracing::info!("Starting Cluster Insights MCP server using stdio transport");
 
+    // Fail fast on an invalid config file or environment variable
+    config::init().map_err(anyhow::Error::msg)?;
+
+    // Start without a cluster too; tools then explain how to configure one
+    cluster_insights::detect_cluster_config().await;
+
+    // Optionally serve nodes and pods from watch-based caches (WATCH_MODE=true)
+    let builder = ClusterInsights::builder().start_watch_mode().await.map_err(anyhow::Error::msg)?;
+
     // Create an instance of our cluster-insights router
-    let service = ClusterInsights::new().serve(stdio()).await.inspect_err(|e| {
+    let service = builder.build().map_err(anyhow::Error::msg)?.serve(stdio()).await.inspect_err(|e| {
         tracing::error!("serving error: {:?}", e);
     })?;
 